@@ -0,0 +1,20 @@
+//! 无障碍：为只有图标的控件提供屏幕阅读器读出的名称，打开对话框时把键盘焦点移入对话框
+
+use eframe::egui;
+
+/// 设置控件在屏幕阅读器中读出的名称，用于只显示图标的按钮和标记
+pub fn name(response: &egui::Response, typ: egui::WidgetType, label: &str) {
+    response.widget_info(|| egui::WidgetInfo::labeled(typ, response.enabled(), label));
+}
+
+/// 焦点不在对话框内时移到 default 上，使键盘和屏幕阅读器用户打开对话框后可以直接操作
+pub fn focus_dialog(ui: &egui::Ui, default: &egui::Response) {
+    let ctx = ui.ctx();
+    let focused_here = ctx
+        .memory(|m| m.focused())
+        .and_then(|id| ctx.read_response(id))
+        .is_some_and(|response| response.layer_id == ui.layer_id());
+    if !focused_here {
+        default.request_focus();
+    }
+}
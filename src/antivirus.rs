@@ -0,0 +1,77 @@
+//! 杀毒软件干扰检测：Microsoft Defender 等实时扫描会在每个文件写入后扫描它，使复制几 GB 的语音文件慢数倍，
+//! 还会短暂占用或隔离刚写入的文件；复制时统计这些迹象，建议把游戏目录和备份目录加入排除项后重试
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use tracing::info;
+
+use crate::i18n::t;
+
+const ERROR_VIRUS_INFECTED: i32 = 225;
+const ERROR_VIRUS_DELETED: i32 = 226;
+
+/// 按大小估算复制时间时假设的最低速度（字节/秒），慢于这个速度的磁盘很少见
+const MIN_RATE: f64 = 20.0 * 1024.0 * 1024.0;
+/// 估算之外允许的额外时间，超过时视为异常缓慢
+const SLOW_OVERHEAD: Duration = Duration::from_secs(2);
+/// 异常缓慢的文件达到这个数量才提示，避免偶尔的磁盘繁忙误报
+const SLOW_FILES_THRESHOLD: usize = 3;
+
+/// 复制被杀毒软件拦截或文件已被隔离
+pub fn is_quarantine(e: &io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(ERROR_VIRUS_INFECTED | ERROR_VIRUS_DELETED))
+}
+
+/// 复制 size 字节的文件用时 elapsed 是否明显超出正常范围
+pub fn is_slow(size: u64, elapsed: Duration) -> bool {
+    elapsed > SLOW_OVERHEAD + Duration::from_secs_f64(size as f64 / MIN_RATE)
+}
+
+/// 一次任务中观察到的杀毒软件干扰迹象
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Interference {
+    /// 复制用时异常的文件数
+    pub slow_files: usize,
+    /// 复制时遇到共享冲突、需要重试的文件数
+    pub locked_files: usize,
+    /// 被拦截或隔离的文件数
+    pub quarantined: usize,
+}
+
+impl Interference {
+    /// 迹象足够明显，值得提示用户
+    pub fn is_suspicious(&self) -> bool {
+        self.quarantined > 0 || self.locked_files > 0 || self.slow_files >= SLOW_FILES_THRESHOLD
+    }
+
+    /// 每种迹象一行说明
+    pub fn reasons(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+        if self.quarantined > 0 {
+            reasons.push(t!("antivirus.quarantined", self.quarantined));
+        }
+        if self.locked_files > 0 {
+            reasons.push(t!("antivirus.locked", self.locked_files));
+        }
+        if self.slow_files > 0 {
+            reasons.push(t!("antivirus.slow", self.slow_files));
+        }
+        reasons
+    }
+}
+
+/// 把 paths 加入 Defender 排除项的 PowerShell 命令，需要在管理员 PowerShell 中运行
+pub fn exclusion_command(paths: &[PathBuf]) -> String {
+    let quoted: Vec<String> = paths.iter().map(|p| format!("'{}'", p.display().to_string().replace('\'', "''"))).collect();
+    format!("Add-MpPreference -ExclusionPath {}", quoted.join(","))
+}
+
+/// 打开 Windows 安全中心的“病毒和威胁防护”设置，可以在其中手动添加排除项
+pub fn open_settings() -> io::Result<()> {
+    Command::new("explorer.exe").arg("windowsdefender://threatsettings").spawn()?;
+    info!("opened windows security threat settings");
+    Ok(())
+}
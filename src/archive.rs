@@ -0,0 +1,176 @@
+//! 压缩备份：把没有链接到游戏目录的备份打包为 zip 节省空间，恢复前解压回备份目录
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::backup_info::BackupInfo;
+use crate::copy::Progress;
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+
+/// 备份目录中的压缩包
+pub const ARCHIVE_FILE: &str = "backup.zip";
+/// 解压时使用的临时目录，完成后内容移到备份目录
+const UNPACK_DIR: &str = ".unpack";
+
+pub fn archive_path(backup_path: &Path) -> PathBuf {
+    backup_path.join(ARCHIVE_FILE)
+}
+
+/// 压缩包中文件解压后的总字节数
+pub fn unpacked_size(backup_path: &Path) -> u64 {
+    File::open(archive_path(backup_path))
+        .ok()
+        .and_then(|file| ZipArchive::new(file).ok())
+        .map(|mut zip| (0..zip.len()).filter_map(|i| zip.by_index(i).ok().map(|f| f.size())).sum())
+        .unwrap_or(0)
+}
+
+/// 压缩所需的信息，在界面线程中收集后交给后台线程执行
+pub struct CompressJob {
+    pub backup_path: PathBuf,
+    pub info: BackupInfo,
+    pub lang_name: String,
+    /// 备份中的语音文件夹和 .toc 文件，压缩后记录在备份信息中
+    pub voice_folders: Vec<PathBuf>,
+    pub toc_files: Vec<PathBuf>,
+    /// deflate 压缩级别（1-9）
+    pub level: i64,
+}
+
+impl CompressJob {
+    pub fn total_size(&self) -> u64 {
+        self.voice_folders.iter().chain(&self.toc_files).map(|rel_path| disk::dir_size(&self.backup_path.join(rel_path))).sum()
+    }
+
+    pub fn run(mut self, progress: &Progress) -> Result<String, SwitcherError> {
+        let before = self.total_size();
+        let archive = archive_path(&self.backup_path);
+        let temp = self.backup_path.join(format!("{}.tmp", ARCHIVE_FILE));
+        if let Err(e) = self.write_archive(&temp, progress) {
+            let _ = fs::remove_file(&temp);
+            return Err(e);
+        }
+        fs::rename(&temp, &archive)?;
+
+        // 压缩包写入完成后才删除原文件
+        for rel_path in self.voice_folders.iter().chain(&self.toc_files) {
+            let path = self.backup_path.join(rel_path);
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        }
+        self.info.folders = self.voice_folders;
+        self.info.toc_files = self.toc_files;
+        self.info.compressed = true;
+        self.info.save(&self.backup_path)?;
+
+        let after = fs::metadata(&archive).map(|m| m.len()).unwrap_or(0);
+        info!("compressed {} ({} -> {} bytes)", self.backup_path.display(), before, after);
+        Ok(t!("status.compressed", self.lang_name, disk::format_size(before), disk::format_size(after)))
+    }
+
+    fn write_archive(&self, dest: &Path, progress: &Progress) -> Result<(), SwitcherError> {
+        let mut zip = ZipWriter::new(File::create(dest)?);
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .compression_level(Some(self.level))
+            .large_file(true);
+        let mut files = Vec::new();
+        for rel_path in self.voice_folders.iter().chain(&self.toc_files) {
+            list_files(&self.backup_path, &self.backup_path.join(rel_path), &mut files);
+        }
+        let mut buf = vec![0u8; 1 << 20];
+        for rel_path in files {
+            progress.update(|s| s.current_file = rel_path.display().to_string());
+            zip.start_file(entry_name(&rel_path), options)?;
+            let mut file = File::open(self.backup_path.join(&rel_path))?;
+            loop {
+                let n = file.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                zip.write_all(&buf[..n])?;
+                progress.update(|s| s.done_bytes += n as u64);
+            }
+        }
+        zip.finish()?;
+        Ok(())
+    }
+}
+
+/// 将压缩的备份解压回备份目录，并更新备份信息
+pub fn decompress(backup_path: &Path, info: &mut BackupInfo, progress: &Progress) -> Result<(), SwitcherError> {
+    let archive = archive_path(backup_path);
+    let unpack = backup_path.join(UNPACK_DIR);
+    if unpack.exists() {
+        fs::remove_dir_all(&unpack).map_err(SwitcherError::CleanStaging)?;
+    }
+    if let Err(e) = extract(&archive, &unpack, progress) {
+        let _ = fs::remove_dir_all(&unpack);
+        return Err(e);
+    }
+    for entry in fs::read_dir(&unpack)?.flatten() {
+        fs::rename(entry.path(), backup_path.join(entry.file_name()))?;
+    }
+    fs::remove_dir_all(&unpack)?;
+    fs::remove_file(&archive)?;
+    info.compressed = false;
+    info.save(backup_path)?;
+    info!("decompressed {}", backup_path.display());
+    Ok(())
+}
+
+/// 解压 zip 到 dest，忽略指向目录外的条目
+pub fn extract(archive: &Path, dest: &Path, progress: &Progress) -> Result<(), SwitcherError> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    let total: u64 = (0..zip.len()).filter_map(|i| zip.by_index(i).ok().map(|f| f.size())).sum();
+    progress.update(|s| s.total_bytes += total);
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        let Some(rel_path) = entry.enclosed_name() else {
+            continue;
+        };
+        let path = dest.join(&rel_path);
+        if entry.is_dir() {
+            fs::create_dir_all(&path).map_err(SwitcherError::CreateDir)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(SwitcherError::CreateDir)?;
+        }
+        progress.update(|s| s.current_file = rel_path.display().to_string());
+        let size = entry.size();
+        io::copy(&mut entry, &mut File::create(&path)?)?;
+        progress.update(|s| s.done_bytes += size);
+    }
+    Ok(())
+}
+
+/// 递归列出 path 下的所有文件，返回相对 base 的路径
+pub fn list_files(base: &Path, path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        if let Ok(rel_path) = path.strip_prefix(base) {
+            files.push(rel_path.to_path_buf());
+        }
+        return;
+    }
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            list_files(base, &entry.path(), files);
+        }
+    }
+}
+
+/// zip 中的条目名使用 / 分隔
+pub fn entry_name(rel_path: &Path) -> String {
+    rel_path.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
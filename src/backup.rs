@@ -0,0 +1,328 @@
+//! 备份任务：把语音文件复制（或移动）到临时目录，成功后替换正式备份
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+
+use crate::backup_info::{BackupInfo, RestoreRecord};
+use crate::copy::{self, CopyLog, CopyOptions, Progress, COPY_LOG_FILE};
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::hash;
+use crate::i18n::t;
+use crate::journal::{EntryKind, Journal};
+use crate::link::{self, RestoreMode};
+use crate::logging;
+use crate::plan::{Action, Operation, Plan};
+use crate::retry;
+use crate::settings::CopyEngine;
+use crate::toc;
+
+/// 正在写入的备份所在的临时目录前缀
+pub const STAGING_PREFIX: &str = ".tmp-";
+/// 移动模式下记录语音文件来源的文件名
+pub const MOVE_SOURCE_FILE: &str = "move_source.txt";
+
+/// 首次删除前自动保存的原始语音文件快照所在的子目录，不出现在备份列表中
+pub const ORIGINALS_DIR: &str = "originals";
+
+/// 移动模式替换备份时，旧备份暂时改名为临时目录名加这个后缀，链接全部创建后才删除
+pub const OLD_SUFFIX: &str = ".old";
+
+/// 移动模式下被替换的旧备份暂存的目录
+pub fn previous_dir(backup_dir: &Path, lang_code: &str) -> PathBuf {
+    backup_dir.join(format!("{}{}{}", STAGING_PREFIX, lang_code, OLD_SUFFIX))
+}
+
+/// 语言 lang_code 的备份写入时使用的临时目录
+pub fn staging_dir(backup_dir: &Path, lang_code: &str) -> PathBuf {
+    backup_dir.join(format!("{}{}", STAGING_PREFIX, lang_code))
+}
+
+/// 临时目录是否是中断的复制备份，可以继续复制
+pub fn is_resumable(staging: &Path) -> bool {
+    staging.join(COPY_LOG_FILE).is_file() && !staging.join(MOVE_SOURCE_FILE).exists()
+}
+
+/// 备份所需的全部信息，在界面线程中收集后交给后台线程执行
+pub struct BackupJob {
+    pub source: PathBuf,
+    pub backup_dir: PathBuf,
+    pub lang_code: String,
+    pub lang_name: String,
+    pub build_id: String,
+    pub voice_folders: Vec<PathBuf>,
+    pub toc_files: Vec<PathBuf>,
+    pub move_mode: bool,
+    /// 并行复制的线程数
+    pub workers: usize,
+    /// 该语言已有的备份目录，未变化的文件从这里创建硬链接，只复制变化或新增的文件
+    pub reuse_from: Option<PathBuf>,
+    /// 复制语音文件夹使用的引擎
+    pub engine: CopyEngine,
+}
+
+impl BackupJob {
+    /// 列出备份将执行的动作
+    pub fn plan(&self) -> Plan {
+        let mut plan = Plan::new(Operation::Backup);
+        let target = self.backup_dir.join(&self.lang_code);
+        if target.exists() {
+            plan.push(Action::Delete, target.clone(), disk::dir_size(&target));
+        }
+        let action = if self.move_mode { Action::Move } else { Action::Copy };
+        for rel_path in &self.voice_folders {
+            plan.push(action, target.join(rel_path), disk::dir_size(&self.source.join(rel_path)));
+            if self.move_mode {
+                plan.push(Action::Link(RestoreMode::Junction), self.source.join(rel_path), 0);
+            }
+        }
+        for rel_path in &self.toc_files {
+            plan.push(Action::Copy, target.join(rel_path), disk::dir_size(&self.source.join(rel_path)));
+        }
+        if self.engine == CopyEngine::Robocopy && self.reuse_from.is_some() {
+            plan.notes.push(t!("plan.robocopy_no_reuse").to_string());
+        }
+        plan
+    }
+
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        let source = &self.source;
+        let target = self.backup_dir.join(&self.lang_code);
+
+        // 先写入临时目录，全部成功后再重命名为正式备份，避免留下不完整的备份
+        // 复制模式下上次中断的临时目录会保留，已复制完成的文件不再重复复制
+        let staging = staging_dir(&self.backup_dir, &self.lang_code);
+        if staging.exists() && (self.move_mode || !is_resumable(&staging)) {
+            fs::remove_dir_all(&staging).map_err(SwitcherError::CleanStaging)?;
+        }
+        fs::create_dir_all(&staging).map_err(SwitcherError::CreateDir)?;
+        // 移动模式下记录来源，程序中断时启动后可以把语音文件移回游戏目录
+        if self.move_mode {
+            let _ = fs::write(staging.join(MOVE_SOURCE_FILE), source.to_string_lossy().as_bytes());
+        }
+
+        // 复制（或移动）文件夹和 .toc 文件，保存备份信息，并用临时目录替换旧备份
+        let mut moved_folders = Vec::new();
+        let staged = if self.move_mode {
+            self.stage(&staging, &mut moved_folders, &CopyOptions::new(self.workers), progress)
+        } else {
+            let log = CopyLog::open(&staging)?;
+            if log.len() > 0 {
+                info!("resuming backup of {}, {} files already copied", self.lang_code, log.len());
+            }
+            let options = CopyOptions {
+                workers: self.workers,
+                log: Some(&log),
+                reuse: self.reuse_from.as_deref().map(|old| (staging.as_path(), old)),
+                engine: self.engine,
+            };
+            self.stage(&staging, &mut moved_folders, &options, progress)
+        };
+        let saved = staged
+            .and_then(|()| {
+                // .toc 引用的语音文件必须都在备份中，否则恢复后游戏无法加载
+                let missing = toc::missing_references(&staging, &self.toc_files, &self.voice_folders);
+                if missing.is_empty() { Ok(()) } else { Err(SwitcherError::BackupMissingFiles(missing)) }
+            })
+            .and_then(|()| {
+                // 复制日志只在写入过程中使用，不保留在正式备份中
+                let _ = fs::remove_file(staging.join(COPY_LOG_FILE));
+                // 更新已有备份时保留用户设置的名称和备注
+                let previous = BackupInfo::load(&target, &self.lang_code);
+                let info = BackupInfo {
+                    lang_code: self.lang_code.clone(),
+                    build_id: self.build_id.clone(),
+                    folders: self.voice_folders.clone(),
+                    toc_files: self.toc_files.clone(),
+                    toc_hashes: self
+                        .toc_files
+                        .iter()
+                        .filter_map(|rel_path| hash::file_hash(&staging.join(rel_path)).ok().map(|h| (rel_path.clone(), h)))
+                        .collect(),
+                    restore: self.move_mode.then(|| RestoreRecord {
+                        mode: RestoreMode::Junction,
+                        target: source.clone(),
+                        folders: moved_folders.clone(),
+                    }),
+                    compressed: false,
+                    label: previous.label,
+                    note: previous.note,
+                    created: logging::local_time(),
+                    dir: String::new(),
+                };
+                let keep_old = self.move_mode.then(|| previous_dir(&self.backup_dir, &self.lang_code));
+                replace_backup(&info, &staging, &target, keep_old.as_deref()).map_err(|e| SwitcherError::SaveBackup(Box::new(e)))
+            });
+        let stored = if saved.is_ok() { &target } else { &staging };
+
+        if !self.move_mode {
+            // 失败时保留临时目录和复制日志，下次备份时继续
+            saved?;
+        } else {
+            // 移动模式：立即在游戏目录中创建指向备份的 Junction，任何一步失败都回滚
+            let journal = Journal::new(&self.backup_dir);
+            let old = previous_dir(&self.backup_dir, &self.lang_code);
+            let mut linked = Vec::new();
+            let result = saved.and_then(|()| {
+                for rel_path in &moved_folders {
+                    link::create_junction(&target.join(rel_path), &source.join(rel_path)).map_err(|e| {
+                        SwitcherError::LinkRolledBack { path: rel_path.clone(), source: Box::new(e) }
+                    })?;
+                    journal.record(EntryKind::Junction, &source.join(rel_path));
+                    linked.push(rel_path.clone());
+                }
+                Ok(())
+            });
+            if result.is_ok() {
+                // 链接全部创建后才移除来源记录和旧备份，之前中断时启动后可以据此移回语音文件并恢复旧备份
+                let _ = fs::remove_file(target.join(MOVE_SOURCE_FILE));
+                if old.exists() {
+                    if let Err(e) = fs::remove_dir_all(&old) {
+                        warn!("failed to remove previous backup {}: {}", old.display(), e);
+                    }
+                }
+            }
+            if let Err(mut error) = result {
+                for rel_path in &linked {
+                    let _ = link::remove_junction(&source.join(rel_path));
+                    journal.record(EntryKind::Removed, &source.join(rel_path));
+                }
+                let mut rolled_back = true;
+                for rel_path in &moved_folders {
+                    if let Err(e) = link::move_dir(&stored.join(rel_path), &source.join(rel_path), self.workers, progress) {
+                        rolled_back = false;
+                        error = SwitcherError::RollbackFailed { path: stored.join(rel_path), source: Box::new(e) };
+                    }
+                }
+                if rolled_back {
+                    let _ = fs::remove_dir_all(stored);
+                    // 放回被替换的旧备份
+                    if !target.exists() && old.exists() {
+                        if let Err(e) = fs::rename(&old, &target) {
+                            warn!("failed to put back previous backup {}: {}", old.display(), e);
+                        }
+                    }
+                }
+                return Err(error);
+            }
+        }
+
+        let action = if self.move_mode { t!("status.moved_and_linked") } else { t!("status.backup_done") };
+        let mut summary = t!("status.backup_summary",
+            self.lang_name, action, self.voice_folders.len(), self.toc_files.len(), self.build_id);
+        let state = progress.snapshot();
+        if state.reused_files > 0 {
+            summary.push(' ');
+            summary.push_str(&t!("status.backup_reused", state.reused_files, disk::format_size(state.reused_bytes)));
+        }
+        Ok(summary)
+    }
+
+    /// 将语音文件夹和 .toc 文件复制（或移动）到临时目录，moved 记录已移动的文件夹以便回滚
+    fn stage(
+        &self,
+        staging: &Path,
+        moved: &mut Vec<PathBuf>,
+        options: &CopyOptions,
+        progress: &Progress,
+    ) -> Result<(), SwitcherError> {
+        for rel_path in &self.voice_folders {
+            let src_folder = self.source.join(rel_path);
+            let dst_folder = staging.join(rel_path);
+            let result = if self.move_mode {
+                link::move_dir(&src_folder, &dst_folder, self.workers, progress)
+            } else {
+                copy::copy_dir(&src_folder, &dst_folder, options, progress).map_err(Into::into)
+            };
+            result.map_err(|e| SwitcherError::BackupItem { path: rel_path.clone(), source: Box::new(e) })?;
+            info!("{} {} -> {}", if self.move_mode { "moved" } else { "copied" }, rel_path.display(), staging.display());
+            if self.move_mode {
+                moved.push(rel_path.clone());
+            }
+        }
+
+        for rel_path in &self.toc_files {
+            let dst_file = staging.join(rel_path);
+            if let Some(parent) = dst_file.parent() {
+                fs::create_dir_all(parent).map_err(SwitcherError::CreateDir)?;
+            }
+            copy::copy_files(&[(self.source.join(rel_path), dst_file)], options, progress)
+                .map_err(|e| SwitcherError::BackupItem { path: rel_path.clone(), source: Box::new(e.into()) })?;
+            info!("copied {} -> {}", rel_path.display(), staging.display());
+        }
+        Ok(())
+    }
+}
+
+/// 写入备份信息，并用临时目录替换旧备份；keep_old 不为 None 时旧备份改名到该目录而不删除，由调用方确认新备份可用后删除
+fn replace_backup(info: &BackupInfo, staging: &Path, target: &Path, keep_old: Option<&Path>) -> Result<(), SwitcherError> {
+    info.save(staging)?;
+    if target.exists() {
+        match keep_old {
+            Some(old) => {
+                if old.exists() {
+                    retry::retry("remove", &[old], || fs::remove_dir_all(old)).map_err(SwitcherError::RemoveOldBackup)?;
+                }
+                retry::retry("move", &[target], || fs::rename(target, old)).map_err(SwitcherError::RemoveOldBackup)?;
+            }
+            None => retry::retry("remove", &[target], || fs::remove_dir_all(target)).map_err(SwitcherError::RemoveOldBackup)?,
+        }
+    }
+    retry::retry("move", &[staging], || fs::rename(staging, target))?;
+    Ok(())
+}
+
+/// 依次备份多个语言，进度按所有语言的总字节数计算
+pub struct BatchBackupJob {
+    pub jobs: Vec<BackupJob>,
+    /// 无法备份而跳过的语言：(语言名称, 原因)
+    pub skipped: Vec<(String, String)>,
+}
+
+impl BatchBackupJob {
+    pub fn plan(&self) -> Plan {
+        let mut plan = Plan::new(Operation::BackupAll);
+        for job in &self.jobs {
+            plan.items.extend(job.plan().items);
+        }
+        plan.notes.extend(self.skipped.iter().map(|(name, reason)| t!("status.batch_item_failed", name, reason)));
+        plan
+    }
+
+    /// 一个语言失败不影响其余语言，全部失败时返回错误
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        let total = self.jobs.len() + self.skipped.len();
+        let mut lines: Vec<String> = self.skipped.iter().map(|(name, reason)| t!("status.batch_item_failed", name, reason)).collect();
+        let mut succeeded = 0;
+        for job in self.jobs {
+            let name = job.lang_name.clone();
+            if progress.is_cancelled() {
+                lines.push(t!("status.batch_item_failed", name, t!("error.cancelled")));
+                continue;
+            }
+            // 每个语言的摘要只统计自己复用的文件
+            progress.update(|s| {
+                s.reused_files = 0;
+                s.reused_bytes = 0;
+            });
+            match job.run(progress) {
+                Ok(summary) => {
+                    succeeded += 1;
+                    lines.push(summary);
+                }
+                Err(e) => {
+                    warn!("batch backup of {} failed: {}", name, e);
+                    lines.push(t!("status.batch_item_failed", name, e));
+                }
+            }
+        }
+        lines.insert(0, t!("status.backup_all_summary", succeeded, total));
+        let summary = lines.join("\n");
+        if succeeded == 0 {
+            return Err(SwitcherError::BackupAllFailed(summary));
+        }
+        Ok(summary)
+    }
+}
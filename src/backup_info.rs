@@ -0,0 +1,119 @@
+//! 备份元数据（backup.json）的读写
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::SwitcherError;
+use crate::link::RestoreMode;
+
+pub const BACKUP_META_FILE: &str = "backup.json";
+/// 复制的备份所在文件夹名称为 "{语言代码}~{序号}"
+pub const CLONE_SEPARATOR: char = '~';
+/// 旧版本写入的 key=value 格式元数据
+const LEGACY_META_FILE: &str = "backup_info.txt";
+
+/// 最近一次恢复到游戏目录的记录，删除游戏语音时据此撤销
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RestoreRecord {
+    pub mode: RestoreMode,
+    pub target: PathBuf,
+    pub folders: Vec<PathBuf>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub lang_code: String,
+    pub build_id: String,
+    #[serde(default)]
+    pub folders: Vec<PathBuf>,
+    #[serde(default)]
+    pub toc_files: Vec<PathBuf>,
+    /// 备份时各 .toc 文件的 SHA-256，恢复前据此发现损坏的文件；旧版本的备份为空
+    #[serde(default)]
+    pub toc_hashes: BTreeMap<PathBuf, String>,
+    #[serde(default)]
+    pub restore: Option<RestoreRecord>,
+    /// 文件夹和 .toc 文件已打包为 backup.zip，恢复前需要解压
+    #[serde(default)]
+    pub compressed: bool,
+    /// 用户设置的显示名称，为空时显示语言名称
+    #[serde(default)]
+    pub label: String,
+    /// 用户备注，例如测试分支或正式版
+    #[serde(default)]
+    pub note: String,
+    /// 创建备份的本地时间，旧版本的备份为空
+    #[serde(default)]
+    pub created: String,
+    /// 备份所在的文件夹名称，不写入 backup.json；原始备份与语言代码相同
+    #[serde(skip)]
+    pub dir: String,
+}
+
+/// 备份文件夹名称对应的语言代码
+pub fn base_code(dir_name: &str) -> &str {
+    dir_name.split_once(CLONE_SEPARATOR).map_or(dir_name, |(code, _)| code)
+}
+
+impl BackupInfo {
+    /// 读取备份目录中的元数据，兼容旧版 backup_info.txt
+    pub fn load(dir: &Path, lang_code: &str) -> Self {
+        let dir_name = dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if let Ok(content) = fs::read_to_string(dir.join(BACKUP_META_FILE)) {
+            if let Ok(info) = serde_json::from_str::<BackupInfo>(&content) {
+                return BackupInfo { dir: dir_name, ..info };
+            }
+        }
+
+        let content = fs::read_to_string(dir.join(LEGACY_META_FILE)).unwrap_or_default();
+        let value = |key: &str| {
+            content
+                .lines()
+                .find_map(|l| l.strip_prefix(key).and_then(|l| l.strip_prefix('=')))
+                .unwrap_or_default()
+                .to_string()
+        };
+        let paths = |key: &str| {
+            value(key)
+                .split(';')
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from)
+                .collect()
+        };
+
+        BackupInfo {
+            lang_code: lang_code.to_string(),
+            build_id: value("build_id"),
+            folders: paths("folders"),
+            toc_files: paths("toc_files"),
+            toc_hashes: BTreeMap::new(),
+            restore: None,
+            compressed: false,
+            label: String::new(),
+            note: String::new(),
+            created: String::new(),
+            dir: dir_name,
+        }
+    }
+
+    /// 备份所在的文件夹名称，用于在备份列表中区分同一语言的副本
+    pub fn dir_name(&self) -> &str {
+        if self.dir.is_empty() { &self.lang_code } else { &self.dir }
+    }
+
+    /// 备份在 backup_dir 中的路径
+    pub fn path(&self, backup_dir: &Path) -> PathBuf {
+        backup_dir.join(self.dir_name())
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), SwitcherError> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(dir.join(BACKUP_META_FILE), content)?;
+        // 已迁移到 backup.json，旧文件不再需要
+        let _ = fs::remove_file(dir.join(LEGACY_META_FILE));
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! 二进制 VDF（如 shortcuts.vdf）的读取与写入，不认识的定长值按原始字节原样保留
+
+use crate::error::SwitcherError;
+
+const TYPE_MAP: u8 = 0;
+const TYPE_STRING: u8 = 1;
+const TYPE_INT: u8 = 2;
+const TYPE_END: u8 = 8;
+
+pub enum Value {
+    Map(Vec<(String, Value)>),
+    Str(String),
+    Int(u32),
+    /// 浮点数、颜色和 64 位整数等定长值：(类型, 原始字节)
+    Raw(u8, Vec<u8>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+/// 按键名（不区分大小写）查找子项
+pub fn get<'a>(entries: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+    entries.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], SwitcherError> {
+        let slice = self.bytes.get(self.pos..self.pos + len).ok_or(SwitcherError::VdfInvalid)?;
+        self.pos += len;
+        Ok(slice)
+    }
+
+    fn string(&mut self) -> Result<String, SwitcherError> {
+        let len = self.bytes[self.pos..].iter().position(|&b| b == 0).ok_or(SwitcherError::VdfUnterminated)?;
+        let text = String::from_utf8_lossy(&self.bytes[self.pos..self.pos + len]).into_owned();
+        self.pos += len + 1;
+        Ok(text)
+    }
+
+    /// 读取到结束标记或文件末尾为止的所有子项
+    fn map(&mut self) -> Result<Vec<(String, Value)>, SwitcherError> {
+        let mut entries = Vec::new();
+        while self.pos < self.bytes.len() {
+            let kind = self.take(1)?[0];
+            if kind == TYPE_END {
+                return Ok(entries);
+            }
+            let key = self.string()?;
+            let value = match kind {
+                TYPE_MAP => Value::Map(self.map()?),
+                TYPE_STRING => Value::Str(self.string()?),
+                TYPE_INT => Value::Int(u32::from_le_bytes(self.take(4)?.try_into().map_err(|_| SwitcherError::VdfInvalid)?)),
+                3 | 4 | 6 => Value::Raw(kind, self.take(4)?.to_vec()),
+                7 | 10 => Value::Raw(kind, self.take(8)?.to_vec()),
+                _ => return Err(SwitcherError::VdfInvalid),
+            };
+            entries.push((key, value));
+        }
+        Ok(entries)
+    }
+}
+
+pub fn parse(bytes: &[u8]) -> Result<Vec<(String, Value)>, SwitcherError> {
+    Reader { bytes, pos: 0 }.map()
+}
+
+fn write_map(entries: &[(String, Value)], out: &mut Vec<u8>) {
+    for (key, value) in entries {
+        let kind = match value {
+            Value::Map(_) => TYPE_MAP,
+            Value::Str(_) => TYPE_STRING,
+            Value::Int(_) => TYPE_INT,
+            Value::Raw(kind, _) => *kind,
+        };
+        out.push(kind);
+        out.extend_from_slice(key.as_bytes());
+        out.push(0);
+        match value {
+            Value::Map(children) => write_map(children, out),
+            Value::Str(text) => {
+                out.extend_from_slice(text.as_bytes());
+                out.push(0);
+            }
+            Value::Int(n) => out.extend_from_slice(&n.to_le_bytes()),
+            Value::Raw(_, bytes) => out.extend_from_slice(bytes),
+        }
+    }
+    out.push(TYPE_END);
+}
+
+pub fn write(entries: &[(String, Value)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_map(entries, &mut out);
+    out
+}
@@ -0,0 +1,108 @@
+//! 删除其他语音语言：只保留所选语言，删除游戏目录中其他语言由游戏自身安装的语音文件夹和 .toc 文件以释放空间；
+//! 本工具创建的链接和恢复的文件夹不在此处理
+
+use std::path::PathBuf;
+
+use tracing::{info, warn};
+
+use crate::copy::Progress;
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+use crate::plan::{Action, Operation, Plan};
+use crate::recycle::{self, Removed};
+
+/// 一个语言将删除的文件
+pub struct LangCleanup {
+    pub lang_name: String,
+    /// 游戏目录中的绝对路径
+    pub folders: Vec<PathBuf>,
+    pub toc_files: Vec<PathBuf>,
+    /// 每个路径的大小，与 folders 和 toc_files 依次对应
+    pub sizes: Vec<u64>,
+    /// 有该语言的备份，删除后仍可恢复
+    pub backed_up: bool,
+}
+
+impl LangCleanup {
+    pub fn size(&self) -> u64 {
+        self.sizes.iter().sum()
+    }
+
+    fn paths(&self) -> impl Iterator<Item = (&PathBuf, u64)> {
+        self.folders.iter().chain(&self.toc_files).zip(self.sizes.iter().copied())
+    }
+}
+
+pub struct CleanupJob {
+    pub langs: Vec<LangCleanup>,
+    /// 不删除的语言：(语言名称, 原因)
+    pub skipped: Vec<(String, String)>,
+    pub permanent: bool,
+}
+
+impl CleanupJob {
+    pub fn total_size(&self) -> u64 {
+        self.langs.iter().map(LangCleanup::size).sum()
+    }
+
+    pub fn plan(&self) -> Plan {
+        let mut plan = Plan::new(Operation::DeleteOthers);
+        let action = if self.permanent { Action::Delete } else { Action::Recycle };
+        for lang in &self.langs {
+            for (path, size) in lang.paths() {
+                plan.push(action, path.clone(), size);
+            }
+        }
+        let unbacked: Vec<&str> = self.langs.iter().filter(|l| !l.backed_up).map(|l| l.lang_name.as_str()).collect();
+        if !unbacked.is_empty() {
+            plan.notes.push(t!("plan.cleanup_unbacked", unbacked.join(", ")));
+        }
+        plan.notes.extend(self.skipped.iter().map(|(name, reason)| t!("status.cleanup_skipped", name, reason)));
+        plan
+    }
+
+    /// 一个语言失败不影响其余语言，全部失败时返回错误
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        let total = self.langs.len();
+        let mut lines: Vec<String> = self.skipped.iter().map(|(name, reason)| t!("status.cleanup_skipped", name, reason)).collect();
+        let mut succeeded = 0;
+        let mut freed = 0;
+        let mut not_recycled = Vec::new();
+        for lang in &self.langs {
+            progress.wait_if_paused();
+            if progress.is_cancelled() {
+                lines.push(t!("status.batch_item_failed", lang.lang_name, t!("error.cancelled")));
+                continue;
+            }
+            let result: Result<(), SwitcherError> = lang.paths().try_for_each(|(path, size)| {
+                progress.update(|s| s.current_file = path.display().to_string());
+                let removed = recycle::remove(path, self.permanent).map_err(|e| SwitcherError::DeleteItem { path: path.clone(), source: e })?;
+                if removed == Removed::Permanently {
+                    not_recycled.push(path.clone());
+                }
+                info!("deleted {}", path.display());
+                progress.update(|s| s.done_bytes += size);
+                freed += size;
+                Ok(())
+            });
+            match result {
+                Ok(()) => {
+                    succeeded += 1;
+                    lines.push(t!("status.cleanup_item", lang.lang_name, lang.folders.len(), lang.toc_files.len(), disk::format_size(lang.size())));
+                }
+                Err(e) => {
+                    warn!("failed to remove {} voice files: {}", lang.lang_name, e);
+                    lines.push(t!("status.batch_item_failed", lang.lang_name, e));
+                }
+            }
+        }
+        lines.insert(0, t!("status.cleanup_summary", succeeded, total, disk::format_size(freed)));
+        lines.extend(recycle::note(&not_recycled));
+        let summary = lines.join("\n");
+        if succeeded == 0 {
+            return Err(SwitcherError::CleanupFailed(summary));
+        }
+        Ok(summary)
+    }
+}
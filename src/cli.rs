@@ -0,0 +1,125 @@
+//! 命令行模式：--switch、--launch、--repair 和 --status，不打开窗口，结果输出到启动本程序的控制台；
+//! 加上 --json 时以 JSON 输出结果
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+use crate::disk;
+use crate::report::StatusReport;
+
+/// 应用方案：恢复方案的语音并写入启动参数
+pub const ARG_SWITCH: &str = "--switch";
+/// 启动器模式：应用方案后启动游戏，后面的方案名称可以省略
+pub const ARG_LAUNCH: &str = "--launch";
+/// 重新创建被游戏更新移除的语音文件夹，计划任务使用
+pub const ARG_REPAIR: &str = "--repair";
+/// 输出当前状态
+pub const ARG_STATUS: &str = "--status";
+/// 以 JSON 输出结果，可与所有命令一起使用
+pub const ARG_JSON: &str = "--json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Command {
+    Switch(String),
+    /// 省略方案名称时使用上次应用的方案
+    Launch(Option<String>),
+    Repair,
+    Status,
+}
+
+impl Command {
+    /// JSON 输出中的操作名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Switch(_) => "switch",
+            Command::Launch(_) => "launch",
+            Command::Repair => "repair",
+            Command::Status => "status",
+        }
+    }
+
+    /// 从命令行参数中找出命令，没有命令时打开窗口
+    pub fn parse(args: &[String]) -> Option<Command> {
+        let value = |idx: usize| args.get(idx + 1).filter(|arg| !arg.starts_with("--")).cloned();
+        args.iter().enumerate().find_map(|(idx, arg)| match arg.as_str() {
+            ARG_SWITCH => Some(Command::Switch(value(idx).unwrap_or_default())),
+            ARG_LAUNCH => Some(Command::Launch(value(idx))),
+            ARG_REPAIR => Some(Command::Repair),
+            ARG_STATUS => Some(Command::Status),
+            _ => None,
+        })
+    }
+}
+
+/// 本程序没有自己的控制台，从命令行运行时附加到父进程的控制台以便输出结果
+pub fn attach_console() {
+    // SAFETY: 没有父控制台（例如由计划任务或快捷方式启动）时调用失败，无副作用
+    unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+}
+
+/// 命令成功时的结果
+#[derive(Default, Serialize, Deserialize)]
+pub struct Outcome {
+    pub message: String,
+    /// 恢复或修复的文件夹
+    pub paths: Vec<PathBuf>,
+    /// --status 的状态报告
+    #[serde(default)]
+    pub status: Option<Box<StatusReport>>,
+}
+
+#[derive(Serialize)]
+struct AffectedPath<'a> {
+    path: &'a PathBuf,
+    size: u64,
+}
+
+/// --json 输出的结果
+#[derive(Serialize)]
+struct Report<'a> {
+    operation: &'static str,
+    success: bool,
+    exit_code: i32,
+    message: Option<&'a str>,
+    error: Option<&'a str>,
+    paths: Vec<AffectedPath<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<&'a StatusReport>,
+}
+
+/// 执行过程中的进度，JSON 模式下输出到 stderr，保持 stdout 只有结果
+pub fn progress(json: bool, line: &str) {
+    if json {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// 输出命令的结果，返回进程的退出码
+pub fn print(command: &Command, result: &Result<Outcome, String>, json: bool) -> i32 {
+    let exit_code = if result.is_ok() { 0 } else { 1 };
+    if json {
+        let outcome = result.as_ref().ok();
+        let report = Report {
+            operation: command.name(),
+            success: result.is_ok(),
+            exit_code,
+            message: outcome.map(|o| o.message.as_str()),
+            error: result.as_ref().err().map(String::as_str),
+            paths: outcome
+                .map(|o| o.paths.iter().map(|path| AffectedPath { path, size: disk::dir_size(path) }).collect())
+                .unwrap_or_default(),
+            status: outcome.and_then(|o| o.status.as_deref()),
+        };
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    } else {
+        match result {
+            Ok(outcome) => println!("{}", outcome.message),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    exit_code
+}
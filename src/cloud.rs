@@ -0,0 +1,142 @@
+//! 云同步目录检测：备份目录位于 OneDrive、Dropbox 或 iCloud 中时，同步会上传大量数据，
+//! 按需下载的占位文件还会让 Junction 目标无法直接读取；可以把备份目录设为始终保留在本机或排除同步
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+use windows_sys::Win32::Storage::FileSystem::{
+    GetFileAttributesW, SetFileAttributesW, FILE_ATTRIBUTE_OFFLINE, FILE_ATTRIBUTE_PINNED, FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS,
+    FILE_ATTRIBUTE_RECALL_ON_OPEN, FILE_ATTRIBUTE_UNPINNED, INVALID_FILE_ATTRIBUTES,
+};
+
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+use crate::link;
+
+/// Dropbox 通过这个备用数据流标记不同步的文件夹
+const DROPBOX_IGNORED_STREAM: &str = ":com.dropbox.ignored";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Provider {
+    OneDrive,
+    Dropbox,
+    ICloud,
+}
+
+impl Provider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::OneDrive => "OneDrive",
+            Provider::Dropbox => "Dropbox",
+            Provider::ICloud => "iCloud Drive",
+        }
+    }
+
+    /// OneDrive 和 iCloud 使用 Windows 云文件占位符，可以固定在本机；Dropbox 只能排除同步
+    pub fn supports_pinning(&self) -> bool {
+        matches!(self, Provider::OneDrive | Provider::ICloud)
+    }
+}
+
+/// 备份目录所在的同步目录
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyncRoot {
+    pub provider: Provider,
+    pub root: PathBuf,
+}
+
+/// 已知同步客户端的同步目录
+fn sync_roots() -> Vec<SyncRoot> {
+    let mut roots = Vec::new();
+    for var in ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"] {
+        if let Some(path) = env::var_os(var) {
+            roots.push(SyncRoot { provider: Provider::OneDrive, root: PathBuf::from(path) });
+        }
+    }
+    // Dropbox 在 info.json 中记录个人和团队账户的同步目录
+    for var in ["APPDATA", "LOCALAPPDATA"] {
+        let Some(dir) = env::var_os(var) else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(PathBuf::from(dir).join("Dropbox").join("info.json")) else {
+            continue;
+        };
+        let Ok(serde_json::Value::Object(accounts)) = serde_json::from_str(&content) else {
+            continue;
+        };
+        for account in accounts.values() {
+            if let Some(path) = account.get("path").and_then(|p| p.as_str()) {
+                roots.push(SyncRoot { provider: Provider::Dropbox, root: PathBuf::from(path) });
+            }
+        }
+    }
+    if let Some(profile) = env::var_os("USERPROFILE") {
+        let path = PathBuf::from(profile).join("iCloudDrive");
+        if path.is_dir() {
+            roots.push(SyncRoot { provider: Provider::ICloud, root: path });
+        }
+    }
+    roots
+}
+
+/// dir 所在的同步目录
+pub fn sync_root(dir: &Path) -> Option<SyncRoot> {
+    let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf());
+    sync_roots().into_iter().find(|root| !root.root.as_os_str().is_empty() && link::points_into(&dir, &root.root))
+}
+
+fn attributes(path: &Path) -> Option<u32> {
+    let wide = disk::wide_path(path);
+    // SAFETY: wide 为以 0 结尾的路径，在调用期间有效
+    let attributes = unsafe { GetFileAttributesW(wide.as_ptr()) };
+    (attributes != INVALID_FILE_ATTRIBUTES).then_some(attributes)
+}
+
+/// 把 path 及其中的文件设为始终保留在本机，placeholders 累计仍为占位符（尚未下载）的文件数
+fn pin(path: &Path, placeholders: &mut usize) -> io::Result<()> {
+    if let Some(current) = attributes(path) {
+        if current & (FILE_ATTRIBUTE_OFFLINE | FILE_ATTRIBUTE_RECALL_ON_OPEN | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS) != 0 {
+            *placeholders += 1;
+        }
+        let pinned = (current & !FILE_ATTRIBUTE_UNPINNED) | FILE_ATTRIBUTE_PINNED;
+        if pinned != current {
+            let wide = disk::wide_path(path);
+            // SAFETY: wide 为以 0 结尾的路径，在调用期间有效
+            if unsafe { SetFileAttributesW(wide.as_ptr(), pinned) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+    }
+    if path.is_dir() && !link::is_junction(path) {
+        for entry in fs::read_dir(path)?.flatten() {
+            pin(&entry.path(), placeholders)?;
+        }
+    }
+    Ok(())
+}
+
+/// 按同步客户端保护备份目录：OneDrive 和 iCloud 固定在本机，Dropbox 排除同步
+pub fn protect(root: &SyncRoot, dir: &Path) -> Result<String, SwitcherError> {
+    let name = root.provider.name();
+    if root.provider.supports_pinning() {
+        let mut placeholders = 0;
+        pin(dir, &mut placeholders).map_err(|e| SwitcherError::CloudProtect(name, e))?;
+        info!("pinned {} in {} ({} placeholders)", dir.display(), name, placeholders);
+        let mut message = t!("status.cloud_pinned", dir.display(), name);
+        if placeholders > 0 {
+            message.push('\n');
+            message.push_str(&t!("status.cloud_placeholders", placeholders));
+        }
+        Ok(message)
+    } else {
+        let mut stream = dir.as_os_str().to_owned();
+        stream.push(DROPBOX_IGNORED_STREAM);
+        fs::write(PathBuf::from(stream), "1").map_err(|e| SwitcherError::CloudProtect(name, e))?;
+        info!("excluded {} from {}", dir.display(), name);
+        Ok(t!("status.cloud_excluded", dir.display(), name))
+    }
+}
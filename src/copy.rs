@@ -0,0 +1,421 @@
+//! 带进度的文件复制：通过 CopyFileExW 的回调逐字节报告进度，多个文件可并行复制
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use tracing::{info, warn};
+use windows_sys::Win32::Foundation::HANDLE;
+
+use crate::antivirus::{self, Interference};
+use crate::disk;
+use crate::hash;
+use crate::i18n::t;
+use crate::link;
+use crate::priority;
+use crate::retry;
+use crate::robocopy;
+use crate::settings::CopyEngine;
+use windows_sys::Win32::Storage::FileSystem::{CopyFileExW, PROGRESS_CANCEL, PROGRESS_CONTINUE};
+
+/// 复制选项
+#[derive(Clone, Copy)]
+pub struct CopyOptions<'a> {
+    /// 并行复制的线程数
+    pub workers: usize,
+    /// 跳过日志中已复制完成的文件，并记录新复制的文件
+    pub log: Option<&'a CopyLog>,
+    /// (目标根目录, 旧备份根目录)：旧备份中未变化的文件直接创建硬链接，不再复制
+    pub reuse: Option<(&'a Path, &'a Path)>,
+    /// 复制文件夹使用的引擎，robocopy 不使用复制日志和旧备份
+    pub engine: CopyEngine,
+}
+
+impl CopyOptions<'_> {
+    pub fn new(workers: usize) -> Self {
+        CopyOptions { workers, log: None, reuse: None, engine: CopyEngine::Native }
+    }
+}
+
+/// 复制日志的文件名，位于正在写入的目录中
+pub const COPY_LOG_FILE: &str = "copied_files.txt";
+
+/// 后台任务的进度，由工作线程更新，界面线程读取
+#[derive(Default)]
+pub struct Progress {
+    state: Mutex<ProgressState>,
+    /// 界面线程请求取消，复制在下一个文件或下一段数据前停止
+    cancelled: AtomicBool,
+    /// 界面线程请求暂停，复制在当前文件完成后等待，直到继续或取消
+    paused: AtomicBool,
+}
+
+/// 暂停时检查是否继续的间隔
+const PAUSE_POLL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Default)]
+pub struct ProgressState {
+    /// 最近开始复制的文件
+    pub current_file: String,
+    /// 所有线程已复制的字节数
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+    /// 从旧备份创建硬链接、未重新复制的文件数和字节数
+    pub reused_files: usize,
+    pub reused_bytes: u64,
+    /// 暂停开始的时间，未暂停时为 None
+    pub paused_since: Option<Instant>,
+    /// 已结束的暂停累计的时间，不计入速度
+    pub paused_total: Duration,
+    /// 杀毒软件实时扫描可能造成的缓慢、占用和隔离
+    pub interference: Interference,
+    pub started: Option<Instant>,
+}
+
+impl Progress {
+    pub fn new(total_bytes: u64) -> Self {
+        Progress {
+            state: Mutex::new(ProgressState {
+                total_bytes,
+                started: Some(Instant::now()),
+                ..Default::default()
+            }),
+            cancelled: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+        self.update(|s| {
+            s.paused_since.get_or_insert_with(Instant::now);
+        });
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+        self.update(|s| {
+            if let Some(since) = s.paused_since.take() {
+                s.paused_total += since.elapsed();
+            }
+        });
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// 暂停时等待继续，取消时立即返回；在两个文件之间调用，已完成的文件和复制日志保持不变
+    pub fn wait_if_paused(&self) {
+        while self.is_paused() && !self.is_cancelled() {
+            thread::sleep(PAUSE_POLL);
+        }
+    }
+
+    /// 已请求取消时返回错误
+    pub fn check_cancelled(&self) -> io::Result<()> {
+        if self.is_cancelled() {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, t!("error.cancelled")));
+        }
+        Ok(())
+    }
+
+    pub fn snapshot(&self) -> ProgressState {
+        self.state.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    pub fn update(&self, f: impl FnOnce(&mut ProgressState)) {
+        if let Ok(mut state) = self.state.lock() {
+            f(&mut state);
+        }
+    }
+}
+
+impl ProgressState {
+    pub fn fraction(&self) -> f32 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            (self.done_bytes as f64 / self.total_bytes as f64).min(1.0) as f32
+        }
+    }
+
+    /// 平均速度（字节/秒），不计暂停的时间
+    pub fn throughput(&self) -> f64 {
+        let paused = self.paused_total + self.paused_since.map(|s| s.elapsed()).unwrap_or_default();
+        let elapsed = self.started.map(|s| s.elapsed().saturating_sub(paused).as_secs_f64()).unwrap_or(0.0);
+        if elapsed < 0.5 {
+            0.0
+        } else {
+            self.done_bytes as f64 / elapsed
+        }
+    }
+
+    /// 按平均速度估算的剩余时间
+    pub fn eta(&self) -> Option<Duration> {
+        let speed = self.throughput();
+        (speed > 0.0).then(|| {
+            Duration::from_secs_f64(self.total_bytes.saturating_sub(self.done_bytes) as f64 / speed)
+        })
+    }
+}
+
+/// 复制日志：逐行记录已复制完成的文件，中断后再次复制时跳过这些文件
+pub struct CopyLog {
+    base: PathBuf,
+    /// 相对路径 -> 复制时源文件的 (大小, 修改时间)
+    done: HashMap<PathBuf, (u64, u64)>,
+    file: Mutex<File>,
+}
+
+impl CopyLog {
+    /// 打开 base 目录下的复制日志，读入已有记录
+    pub fn open(base: &Path) -> io::Result<Self> {
+        let path = base.join(COPY_LOG_FILE);
+        let done = fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let size = parts.next()?.parse().ok()?;
+                let modified = parts.next()?.parse().ok()?;
+                Some((PathBuf::from(parts.next()?), (size, modified)))
+            })
+            .collect();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(CopyLog { base: base.to_path_buf(), done, file: Mutex::new(file) })
+    }
+
+    /// 已记录的文件数
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    /// 目标文件已完整复制且源文件未变化时返回文件大小
+    fn completed(&self, src: &Path, dst: &Path) -> Option<u64> {
+        let rel = dst.strip_prefix(&self.base).ok()?;
+        let stamp = file_stamp(src)?;
+        let dst_size = fs::metadata(dst).ok()?.len();
+        (self.done.get(rel) == Some(&stamp) && dst_size == stamp.0).then_some(stamp.0)
+    }
+
+    fn record(&self, src: &Path, dst: &Path) {
+        let (Ok(rel), Some((size, modified))) = (dst.strip_prefix(&self.base), file_stamp(src)) else {
+            return;
+        };
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{}\t{}\t{}", size, modified, rel.display());
+        }
+    }
+}
+
+/// 文件大小和修改时间（秒）
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let modified = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some((meta.len(), modified))
+}
+
+/// 单个文件的复制上下文，记录已计入总进度的字节数
+struct FileCopy<'a> {
+    progress: &'a Progress,
+    reported: Cell<u64>,
+}
+
+impl FileCopy<'_> {
+    fn report(&self, transferred: u64) {
+        let delta = transferred.saturating_sub(self.reported.replace(transferred));
+        self.progress.update(|s| s.done_bytes += delta);
+    }
+}
+
+unsafe extern "system" fn progress_routine(
+    _total_file_size: i64,
+    total_bytes_transferred: i64,
+    _stream_size: i64,
+    _stream_bytes_transferred: i64,
+    _stream_number: u32,
+    _callback_reason: u32,
+    _source_file: HANDLE,
+    _destination_file: HANDLE,
+    data: *const c_void,
+) -> u32 {
+    // SAFETY: data 是 copy_file 传入的 &FileCopy，复制期间一直有效，回调在调用线程中执行
+    let copy = unsafe { &*(data as *const FileCopy) };
+    copy.report(total_bytes_transferred as u64);
+    if copy.progress.is_cancelled() {
+        PROGRESS_CANCEL
+    } else {
+        PROGRESS_CONTINUE
+    }
+}
+
+/// 复制单个文件（目标已存在时覆盖）
+pub fn copy_file(src: &Path, dst: &Path, progress: &Progress) -> io::Result<()> {
+    let name = src.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    progress.update(|s| s.current_file = name);
+
+    let copy = FileCopy { progress, reported: Cell::new(0) };
+    let (src_w, dst_w) = (disk::wide_path(src), disk::wide_path(dst));
+    let started = Instant::now();
+    let locked = Cell::new(false);
+    let result = retry::retry("copy", &[src, dst], || {
+        // SAFETY: 路径以 0 结尾，copy 在调用期间有效
+        let ok = unsafe {
+            CopyFileExW(
+                src_w.as_ptr(),
+                dst_w.as_ptr(),
+                Some(progress_routine),
+                &copy as *const FileCopy as *const c_void,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if ok == 0 {
+            // 失败的这次已计入的字节不算进度，重试时从头复制
+            let error = io::Error::last_os_error();
+            let prev = copy.reported.replace(0);
+            progress.update(|s| s.done_bytes = s.done_bytes.saturating_sub(prev));
+            locked.set(locked.get() || retry::is_sharing_violation(&error));
+            return Err(error);
+        }
+        Ok(())
+    });
+    if locked.get() {
+        progress.update(|s| s.interference.locked_files += 1);
+    }
+    if result.as_ref().is_err_and(antivirus::is_quarantine) {
+        progress.update(|s| s.interference.quarantined += 1);
+    }
+    // 取消时 CopyFileExW 返回的错误没有说明原因
+    if result.is_err() {
+        progress.check_cancelled()?;
+    }
+    result?;
+
+    // 回调不一定覆盖最后一段（例如空文件），以实际大小为准
+    let size = fs::metadata(dst).map(|m| m.len()).unwrap_or(0);
+    copy.report(size);
+    if antivirus::is_slow(size, started.elapsed()) {
+        progress.update(|s| s.interference.slow_files += 1);
+    }
+    Ok(())
+}
+
+/// 递归复制文件夹，dst 为新文件夹的完整路径
+pub fn copy_dir(src: &Path, dst: &Path, options: &CopyOptions, progress: &Progress) -> io::Result<()> {
+    if options.engine == CopyEngine::Robocopy {
+        // robocopy 无法从旧备份创建硬链接，也不写复制日志，界面的说明和预览中已提示增量备份不可用
+        if options.reuse.is_some() || options.log.is_some() {
+            info!("robocopy engine: incremental reuse and copy log disabled for {}", src.display());
+        }
+        return robocopy::copy_dir(src, dst, options.workers, progress);
+    }
+    let mut files = Vec::new();
+    collect_files(src, dst, &mut files)?;
+    copy_files(&files, options, progress)
+}
+
+/// 在目标位置创建目录结构，并收集需要复制的 (源文件, 目标文件)；Junction 和符号链接跳过，不复制链接指向的内容
+fn collect_files(src: &Path, dst: &Path, files: &mut Vec<(PathBuf, PathBuf)>) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = dst.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() || link::is_junction(&path) {
+            warn!("skipped link {} while copying", path.display());
+        } else if file_type.is_dir() {
+            collect_files(&path, &target, files)?;
+        } else {
+            files.push((path, target));
+        }
+    }
+    Ok(())
+}
+
+/// 旧备份中与 src 相比未变化的文件
+fn reusable(src: &Path, dst: &Path, reuse: Option<(&Path, &Path)>) -> Option<PathBuf> {
+    let (root, old_root) = reuse?;
+    let old = old_root.join(dst.strip_prefix(root).ok()?);
+    hash::unchanged(src, &old).then_some(old)
+}
+
+/// 用多个线程复制文件，任一文件失败后其余线程不再领取新文件，返回第一个错误
+pub fn copy_files(files: &[(PathBuf, PathBuf)], options: &CopyOptions, progress: &Progress) -> io::Result<()> {
+    let copy_one = |src: &Path, dst: &Path| -> io::Result<()> {
+        progress.wait_if_paused();
+        progress.check_cancelled()?;
+        if let Some(size) = options.log.and_then(|log| log.completed(src, dst)) {
+            progress.update(|s| s.done_bytes += size);
+            return Ok(());
+        }
+        let linked = reusable(src, dst, options.reuse).is_some_and(|old| {
+            let _ = fs::remove_file(dst);
+            fs::hard_link(&old, dst).is_ok()
+        });
+        if linked {
+            let size = fs::metadata(dst).map(|m| m.len()).unwrap_or(0);
+            progress.update(|s| {
+                s.done_bytes += size;
+                s.reused_files += 1;
+                s.reused_bytes += size;
+            });
+        } else {
+            copy_file(src, dst, progress)?;
+        }
+        if let Some(log) = options.log {
+            log.record(src, dst);
+        }
+        Ok(())
+    };
+
+    let workers = options.workers;
+    if workers <= 1 || files.len() <= 1 {
+        // 可能在界面线程等非任务线程中调用，复制结束后恢复原来的优先级
+        let _background = priority::apply_to_current_thread();
+        return files.iter().try_for_each(|(src, dst)| copy_one(src, dst));
+    }
+
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let first_error = Mutex::new(None);
+    thread::scope(|scope| {
+        for _ in 0..workers.min(files.len()) {
+            scope.spawn(|| {
+                let _background = priority::apply_to_current_thread();
+                while !failed.load(Ordering::Relaxed) {
+                    let Some((src, dst)) = files.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                        break;
+                    };
+                    if let Err(e) = copy_one(src, dst) {
+                        failed.store(true, Ordering::Relaxed);
+                        if let Ok(mut first) = first_error.lock() {
+                            first.get_or_insert(e);
+                        }
+                    }
+                }
+            });
+        }
+    });
+    match first_error.into_inner() {
+        Ok(Some(e)) => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,57 @@
+//! 崩溃报告：程序 panic 时把错误信息、调用栈和当前状态写入程序所在目录的 crash_report.txt，并弹窗提示
+
+use std::backtrace::Backtrace;
+use std::fs;
+use std::iter;
+use std::panic;
+use std::path::Path;
+use std::sync::Mutex;
+use std::thread;
+
+use tracing::error;
+use windows_sys::Win32::UI::WindowsAndMessaging::{MessageBoxW, MB_ICONERROR, MB_OK};
+
+use crate::i18n::t;
+use crate::logging;
+
+pub const CRASH_REPORT_FILE: &str = "crash_report.txt";
+
+/// 界面线程最近记录的状态摘要
+static STATE: Mutex<String> = Mutex::new(String::new());
+
+/// 更新写入崩溃报告的状态摘要
+pub fn set_state(summary: String) {
+    if let Ok(mut state) = STATE.lock() {
+        *state = summary;
+    }
+}
+
+/// 安装 panic 钩子，报告写入 dir
+pub fn install(dir: &Path) {
+    let path = dir.join(CRASH_REPORT_FILE);
+    panic::set_hook(Box::new(move |info| {
+        let backtrace = Backtrace::force_capture();
+        // panic 可能发生在持有锁的时候，取不到锁时不写状态，避免死锁
+        let state = STATE.try_lock().map(|s| s.clone()).unwrap_or_default();
+        let report = format!(
+            "time: {}\nversion: {}\nthread: {}\n\n{}\n\n[state]\n{}\n\n[backtrace]\n{}\n",
+            logging::local_time(),
+            env!("CARGO_PKG_VERSION"),
+            thread::current().name().unwrap_or("<unnamed>"),
+            info,
+            state,
+            backtrace,
+        );
+        error!("panic: {}", info);
+        let written = fs::write(&path, report).is_ok();
+        show_message(&path, written);
+    }));
+}
+
+fn show_message(path: &Path, written: bool) {
+    let text = if written { t!("error.crashed", path.display()) } else { t!("error.crashed_no_report").to_string() };
+    let wide = |s: &str| -> Vec<u16> { s.encode_utf16().chain(iter::once(0)).collect() };
+    let (text, title) = (wide(&text), wide(t!("ui.title")));
+    // SAFETY: 字符串以 0 结尾且在调用期间有效，没有所属窗口
+    unsafe { MessageBoxW(std::ptr::null_mut(), text.as_ptr(), title.as_ptr(), MB_OK | MB_ICONERROR) };
+}
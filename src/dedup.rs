@@ -0,0 +1,148 @@
+//! 备份去重：在备份目录中查找内容相同的文件，用 NTFS 硬链接替换重复的副本
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::os::windows::io::AsRawHandle;
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+use windows_sys::Win32::Storage::FileSystem::{GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION};
+
+use crate::backup::{MOVE_SOURCE_FILE, STAGING_PREFIX};
+use crate::backup_info::BACKUP_META_FILE;
+use crate::copy::{Progress, COPY_LOG_FILE};
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::hash;
+use crate::i18n::t;
+use crate::journal::JOURNAL_FILE;
+use crate::link;
+
+/// 替换重复文件时使用的临时文件后缀
+const TEMP_SUFFIX: &str = ".dedup";
+
+/// 去重所需的信息，交给后台线程执行
+pub struct DedupJob {
+    /// 游戏的备份目录，包含所有语言的备份和原始文件快照
+    pub root: PathBuf,
+    /// 以硬链接恢复到游戏目录的备份：替换其中的文件会断开与游戏文件的硬链接，因此跳过
+    pub skip: Vec<PathBuf>,
+}
+
+impl DedupJob {
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        collect_files(&self.root, &self.skip, &mut by_size);
+        for path in &self.skip {
+            warn!("skipped {} restored as hardlinks", path.display());
+        }
+
+        // 同一大小的文件按文件 ID 分组，已经是硬链接的文件只计算一次哈希；
+        // 硬链接不能跨卷，备份中可能有指向其他磁盘的 Junction，因此再按卷序列号分开
+        let mut candidates = Vec::new();
+        for (size, paths) in by_size {
+            let mut by_volume: HashMap<u32, HashMap<u64, Vec<PathBuf>>> = HashMap::new();
+            for path in paths {
+                if let Ok((volume, index)) = file_id(&path) {
+                    by_volume.entry(volume).or_default().entry(index).or_default().push(path);
+                }
+            }
+            for by_id in by_volume.into_values().filter(|by_id| by_id.len() > 1) {
+                candidates.push((size, by_id.into_values().collect::<Vec<_>>()));
+            }
+        }
+        let total: u64 = candidates.iter().map(|(size, ids)| size * ids.len() as u64).sum();
+        progress.update(|s| s.total_bytes = total);
+
+        let mut merged_files = 0;
+        let mut saved_bytes = 0;
+        for (size, ids) in candidates {
+            let mut by_hash: HashMap<String, Vec<Vec<PathBuf>>> = HashMap::new();
+            for links in ids {
+                progress.update(|s| s.current_file = links[0].display().to_string());
+                if let Ok(digest) = hash::file_hash(&links[0]) {
+                    by_hash.entry(digest).or_default().push(links);
+                }
+                progress.update(|s| s.done_bytes += size);
+            }
+            for (_, mut groups) in by_hash.into_iter().filter(|(_, groups)| groups.len() > 1) {
+                let keep = groups.remove(0).remove(0);
+                for links in groups {
+                    let mut replaced = true;
+                    for path in &links {
+                        if let Err(e) = replace_with_link(&keep, path) {
+                            warn!("failed to deduplicate {}: {}", path.display(), e);
+                            replaced = false;
+                        } else {
+                            merged_files += 1;
+                        }
+                    }
+                    // 只有该文件的所有硬链接都被替换后才释放空间
+                    if replaced {
+                        saved_bytes += size;
+                    }
+                }
+            }
+        }
+
+        info!("deduplicated {} files in {}, saved {} bytes", merged_files, self.root.display(), saved_bytes);
+        let mut summary = if merged_files == 0 {
+            t!("status.dedup_none").to_string()
+        } else {
+            t!("status.dedup_done", merged_files, disk::format_size(saved_bytes))
+        };
+        if !self.skip.is_empty() {
+            summary.push('\n');
+            summary.push_str(&t!("status.dedup_skipped_hardlink", self.skip.len()));
+        }
+        Ok(summary)
+    }
+}
+
+/// 按大小收集目录中的文件，跳过 Junction、临时目录、skip 中的目录、元数据和空文件
+fn collect_files(dir: &Path, skip: &[PathBuf], by_size: &mut HashMap<u64, Vec<PathBuf>>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if !link::is_junction(&path) && !name.starts_with(STAGING_PREFIX) && !skip.contains(&path) {
+                collect_files(&path, skip, by_size);
+            }
+        } else if metadata.is_file()
+            && metadata.len() > 0
+            && ![BACKUP_META_FILE, COPY_LOG_FILE, JOURNAL_FILE, MOVE_SOURCE_FILE].contains(&name.as_str())
+        {
+            by_size.entry(metadata.len()).or_default().push(path);
+        }
+    }
+}
+
+/// 文件所在卷的序列号和文件索引，相同则为同一文件的硬链接
+pub fn file_id(path: &Path) -> io::Result<(u32, u64)> {
+    let file = File::open(path)?;
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    // SAFETY: 句柄在 file 存活期间有效，info 为可写的输出结构
+    if unsafe { GetFileInformationByHandle(file.as_raw_handle(), &mut info) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok((info.dwVolumeSerialNumber, ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64))
+}
+
+/// 先在临时路径创建指向 keep 的硬链接，再替换 path，失败时 path 保持不变
+fn replace_with_link(keep: &Path, path: &Path) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(TEMP_SUFFIX);
+    let temp = PathBuf::from(temp);
+    let _ = fs::remove_file(&temp);
+    fs::hard_link(keep, &temp)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
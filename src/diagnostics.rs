@@ -0,0 +1,137 @@
+//! 诊断信息导出：把日志、备份元数据、Steam/游戏路径和语音目录列表打包为一个 zip 文件
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::backup_info::BACKUP_META_FILE;
+use crate::error::SwitcherError;
+use crate::link;
+use crate::logging::LOG_FILE;
+
+/// appmanifest 中与问题排查相关的键
+const MANIFEST_KEYS: [&str; 8] = [
+    "appid", "name", "StateFlags", "installdir", "LastUpdated", "buildid", "SizeOnDisk", "language",
+];
+/// 目录列表的最大行数，避免文件过大
+const MAX_LISTING_LINES: usize = 20000;
+
+pub struct DiagnosticInfo<'a> {
+    /// 程序版本、当前游戏、检测到的路径等概要信息
+    pub summary: String,
+    pub exe_dir: &'a Path,
+    pub backup_root: &'a Path,
+    pub manifest: Option<&'a Path>,
+    pub data_dir: Option<&'a Path>,
+}
+
+/// 将诊断信息写入 dest
+pub fn export(dest: &Path, info: &DiagnosticInfo) -> Result<(), SwitcherError> {
+    let mut zip = ZipWriter::new(File::create(dest)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("summary.txt", options)?;
+    zip.write_all(info.summary.as_bytes())?;
+
+    for name in [
+        LOG_FILE.to_string(),
+        format!("{}.1", LOG_FILE),
+        crate::settings::SETTINGS_FILE.to_string(),
+        crate::crash::CRASH_REPORT_FILE.to_string(),
+    ] {
+        if let Ok(content) = fs::read(info.exe_dir.join(&name)) {
+            zip.start_file(name, options)?;
+            zip.write_all(&content)?;
+        }
+    }
+
+    for (name, content) in backup_metadata(info.backup_root) {
+        zip.start_file(format!("backups/{}", name), options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+
+    if let Some(manifest) = info.manifest {
+        let excerpt = manifest_excerpt(manifest);
+        zip.start_file("appmanifest.txt", options)?;
+        zip.write_all(excerpt.as_bytes())?;
+    }
+
+    if let Some(data_dir) = info.data_dir {
+        let mut listing = Vec::new();
+        list_dir(data_dir, data_dir, &mut listing);
+        if listing.len() >= MAX_LISTING_LINES {
+            listing.push("...".to_string());
+        }
+        zip.start_file("data_listing.txt", options)?;
+        zip.write_all(format!("{}\n{}", data_dir.display(), listing.join("\n")).as_bytes())?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// 收集备份根目录及各游戏子目录中的 backup.json，返回 (zip 内路径, 内容)
+fn backup_metadata(root: &Path) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut dirs = vec![(root.to_path_buf(), String::new(), 0)];
+    while let Some((dir, prefix, depth)) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+            if let Ok(content) = fs::read_to_string(path.join(BACKUP_META_FILE)) {
+                result.push((format!("{}/{}", name, BACKUP_META_FILE), content));
+            } else if depth == 0 {
+                dirs.push((path, format!("{}/", name), depth + 1));
+            }
+        }
+    }
+    result
+}
+
+fn manifest_excerpt(path: &Path) -> String {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            let parts: Vec<&str> = line.split('"').collect();
+            parts.len() >= 4 && MANIFEST_KEYS.iter().any(|k| k.eq_ignore_ascii_case(parts[1]))
+        })
+        .map(str::trim)
+        .collect();
+    format!("{}\n{}", path.display(), lines.join("\n"))
+}
+
+/// 递归列出目录内容，Junction 只记录目标路径，不进入
+fn list_dir(root: &Path, dir: &Path, out: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        if out.len() >= MAX_LISTING_LINES {
+            return;
+        }
+        let path = entry.path();
+        let rel = path.strip_prefix(root).unwrap_or(&path).display().to_string();
+        if link::is_junction(&path) {
+            let target = link::read_junction_target(&path).map(|t| t.display().to_string()).unwrap_or_default();
+            out.push(format!("{}\\ -> {}", rel, target));
+        } else if path.is_dir() {
+            out.push(format!("{}\\", rel));
+            list_dir(root, &path, out);
+        } else {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            out.push(format!("{}\t{}", rel, size));
+        }
+    }
+}
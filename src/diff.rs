@@ -0,0 +1,133 @@
+//! 比较备份与游戏目录：列出游戏更新后新增、删除和修改的语音文件
+//!
+//! 判断文件是否修改与增量备份复用旧文件时相同（hash::unchanged），修改和新增的文件就是更新备份时需要复制的文件。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+
+use crate::archive;
+use crate::hash;
+use crate::i18n::t;
+use crate::link;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Change {
+    /// 只在游戏目录中
+    Added,
+    /// 只在备份中
+    Removed,
+    Modified,
+}
+
+impl Change {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Change::Added => t!("diff.added"),
+            Change::Removed => t!("diff.removed"),
+            Change::Modified => t!("diff.modified"),
+        }
+    }
+}
+
+pub struct DiffEntry {
+    /// 相对游戏目录（也即备份目录）的路径
+    pub rel_path: PathBuf,
+    pub change: Change,
+    /// 游戏目录中的大小，已删除的文件为备份中的大小
+    pub size: u64,
+}
+
+#[derive(Default)]
+pub struct Diff {
+    pub entries: Vec<DiffEntry>,
+    /// 比较过的文件数
+    pub compared: usize,
+    /// 游戏目录中的文件夹是指向该备份的链接，内容必然相同
+    pub linked: usize,
+}
+
+impl Diff {
+    /// 更新备份时需要复制的文件数和字节数
+    pub fn update_size(&self) -> (usize, u64) {
+        let copied = self.entries.iter().filter(|e| e.change != Change::Removed);
+        copied.fold((0, 0), |(count, size), e| (count + 1, size + e.size))
+    }
+
+    /// 按所在目录分组，用于按树形显示
+    pub fn grouped(&self) -> BTreeMap<&Path, Vec<&DiffEntry>> {
+        let mut groups: BTreeMap<&Path, Vec<&DiffEntry>> = BTreeMap::new();
+        for entry in &self.entries {
+            groups.entry(entry.rel_path.parent().unwrap_or(&entry.rel_path)).or_default().push(entry);
+        }
+        groups
+    }
+}
+
+fn files_in(root: &Path, rel_path: &Path) -> BTreeSet<PathBuf> {
+    let mut files = Vec::new();
+    archive::list_files(root, &root.join(rel_path), &mut files);
+    files.into_iter().collect()
+}
+
+fn size(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |m| m.len())
+}
+
+/// 比较备份 backup_path 与游戏目录 source 中的 folders 和 toc_files
+pub fn compare(backup_path: &Path, source: &Path, folders: &[PathBuf], toc_files: &[PathBuf]) -> Diff {
+    let mut diff = Diff::default();
+    let mut backup_files = BTreeSet::new();
+    let mut game_files = BTreeSet::new();
+    for rel_path in folders {
+        let game_folder = source.join(rel_path);
+        if link::read_junction_target(&game_folder).is_some_and(|target| link::points_into(&target, backup_path)) {
+            diff.linked += 1;
+            continue;
+        }
+        backup_files.extend(files_in(backup_path, rel_path));
+        game_files.extend(files_in(source, rel_path));
+    }
+    for rel_path in toc_files {
+        backup_files.extend(files_in(backup_path, rel_path));
+        game_files.extend(files_in(source, rel_path));
+    }
+
+    for rel_path in game_files.union(&backup_files) {
+        let (in_game, in_backup) = (game_files.contains(rel_path), backup_files.contains(rel_path));
+        let (game_path, backup_file) = (source.join(rel_path), backup_path.join(rel_path));
+        let change = match (in_game, in_backup) {
+            (true, false) => Some(Change::Added),
+            (false, true) => Some(Change::Removed),
+            _ => (!hash::unchanged(&game_path, &backup_file)).then_some(Change::Modified),
+        };
+        diff.compared += 1;
+        if let Some(change) = change {
+            let size = if change == Change::Removed { size(&backup_file) } else { size(&game_path) };
+            diff.entries.push(DiffEntry { rel_path: rel_path.clone(), change, size });
+        }
+    }
+    diff
+}
+
+/// 在后台线程中比较，界面线程轮询结果
+pub struct DiffScan {
+    /// 显示用的备份名称
+    pub name: String,
+    handle: JoinHandle<Diff>,
+}
+
+impl DiffScan {
+    pub fn spawn(name: String, backup_path: PathBuf, source: PathBuf, folders: Vec<PathBuf>, toc_files: Vec<PathBuf>) -> Self {
+        let handle = thread::spawn(move || compare(&backup_path, &source, &folders, &toc_files));
+        DiffScan { name, handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn join(self) -> Diff {
+        self.handle.join().unwrap_or_default()
+    }
+}
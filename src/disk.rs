@@ -0,0 +1,272 @@
+//! 磁盘空间相关：目录大小统计与剩余空间查询
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Component, Path, PathBuf, Prefix};
+use std::thread::{self, JoinHandle};
+
+use windows_sys::Win32::Storage::FileSystem::{
+    GetDiskFreeSpaceExW, GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW, GetVolumePathNameW,
+};
+
+/// 递归统计目录（或单个文件）的总字节数，不进入 Junction
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.is_file() {
+        return metadata.len();
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            if crate::link::is_junction(&path) {
+                0
+            } else {
+                dir_size(&path)
+            }
+        })
+        .sum()
+}
+
+/// 在后台线程中统计多组路径的总大小，界面线程轮询结果
+pub struct SizeScan {
+    handle: JoinHandle<HashMap<String, u64>>,
+}
+
+impl SizeScan {
+    /// groups 为 (名称, 路径列表)，结果为每个名称对应路径的总字节数
+    pub fn spawn(groups: Vec<(String, Vec<PathBuf>)>) -> Self {
+        let handle = thread::spawn(move || {
+            groups
+                .into_iter()
+                .map(|(name, paths)| (name, paths.iter().map(|p| dir_size(p)).sum()))
+                .collect()
+        });
+        SizeScan { handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn join(self) -> HashMap<String, u64> {
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+/// 转换为以 0 结尾的 UTF-16 路径供 Win32 API 使用，超过 MAX_PATH 的绝对路径加上 \\?\ 前缀
+pub fn wide_path(path: &Path) -> Vec<u16> {
+    const MAX_PATH: usize = 260;
+
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let plain: Vec<u16> = absolute.as_os_str().encode_wide().collect();
+    let text = absolute.to_string_lossy();
+    let prefix = if plain.len() < MAX_PATH || text.starts_with(r"\\?\") {
+        ""
+    } else if text.starts_with(r"\\") {
+        r"\\?\UNC"
+    } else {
+        r"\\?\"
+    };
+    // UNC 路径 \\server\share 变为 \\?\UNC\server\share
+    let skip = if prefix.ends_with("UNC") { 1 } else { 0 };
+    prefix.encode_utf16().chain(plain.into_iter().skip(skip)).chain(std::iter::once(0)).collect()
+}
+
+/// 查询路径所在分区对当前用户可用的剩余空间，路径不存在时向上查找已存在的父目录
+pub fn free_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let wide = wide_path(existing);
+    let mut free: u64 = 0;
+    // SAFETY: wide 是以 0 结尾的 UTF-16 字符串，其余输出参数允许为空
+    let ok = unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut free, std::ptr::null_mut(), std::ptr::null_mut()) };
+    (ok != 0).then_some(free)
+}
+
+/// 分区的文件系统信息
+pub struct VolumeInfo {
+    /// 文件系统名称，如 NTFS、exFAT
+    pub filesystem: String,
+    /// GetVolumeInformation 返回的文件系统特性标志
+    pub flags: u32,
+    /// 是否为网络驱动器
+    pub remote: bool,
+}
+
+impl VolumeInfo {
+    const FILE_SUPPORTS_REPARSE_POINTS: u32 = 0x80;
+    const FILE_SUPPORTS_HARD_LINKS: u32 = 0x0040_0000;
+
+    /// 是否支持重解析点（Junction 和符号链接）
+    pub fn supports_reparse_points(&self) -> bool {
+        self.flags & Self::FILE_SUPPORTS_REPARSE_POINTS != 0
+    }
+
+    pub fn supports_hard_links(&self) -> bool {
+        self.flags & Self::FILE_SUPPORTS_HARD_LINKS != 0
+    }
+}
+
+/// 查询路径所在分区的文件系统，路径不存在时向上查找已存在的父目录
+pub fn volume_info(path: &Path) -> Option<VolumeInfo> {
+    const DRIVE_REMOTE: u32 = 4;
+    const BUFFER_LEN: usize = 261;
+
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let wide = wide_path(existing);
+    let mut root = [0u16; BUFFER_LEN];
+    let mut name = [0u16; BUFFER_LEN];
+    let mut flags: u32 = 0;
+    // SAFETY: wide 以 0 结尾，root 和 name 的长度与传入的缓冲区大小一致，其余输出参数允许为空
+    let (ok, drive_type) = unsafe {
+        if GetVolumePathNameW(wide.as_ptr(), root.as_mut_ptr(), BUFFER_LEN as u32) == 0 {
+            return None;
+        }
+        let ok = GetVolumeInformationW(
+            root.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut flags,
+            name.as_mut_ptr(),
+            BUFFER_LEN as u32,
+        );
+        (ok, GetDriveTypeW(root.as_ptr()))
+    };
+    if ok == 0 {
+        return None;
+    }
+    let len = name.iter().position(|&c| c == 0).unwrap_or(BUFFER_LEN);
+    Some(VolumeInfo {
+        filesystem: String::from_utf16_lossy(&name[..len]),
+        flags,
+        remote: drive_type == DRIVE_REMOTE,
+    })
+}
+
+/// 路径是否位于网络共享中：UNC 路径或映射的网络驱动器
+pub fn is_network(path: &Path) -> bool {
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) if matches!(prefix.kind(), Prefix::UNC(..) | Prefix::VerbatimUNC(..)) => true,
+        _ => volume_info(path).is_some_and(|volume| volume.remote),
+    }
+}
+
+/// 访问网络共享时的凭据或连接错误
+pub fn is_network_error(e: &io::Error) -> bool {
+    const ERROR_BAD_NETPATH: i32 = 53;
+    const ERROR_NETNAME_DELETED: i32 = 64;
+    const ERROR_BAD_NET_NAME: i32 = 67;
+    const ERROR_INVALID_PASSWORD: i32 = 86;
+    const ERROR_SESSION_CREDENTIAL_CONFLICT: i32 = 1219;
+    const ERROR_NETWORK_UNREACHABLE: i32 = 1231;
+    const ERROR_NOT_AUTHENTICATED: i32 = 1244;
+    const ERROR_LOGON_FAILURE: i32 = 1326;
+    matches!(
+        e.raw_os_error(),
+        Some(
+            ERROR_BAD_NETPATH
+                | ERROR_NETNAME_DELETED
+                | ERROR_BAD_NET_NAME
+                | ERROR_INVALID_PASSWORD
+                | ERROR_SESSION_CREDENTIAL_CONFLICT
+                | ERROR_NETWORK_UNREACHABLE
+                | ERROR_NOT_AUTHENTICATED
+                | ERROR_LOGON_FAILURE
+        )
+    )
+}
+
+/// 所有本地固定磁盘的根目录，如 C:\
+pub fn fixed_drives() -> Vec<PathBuf> {
+    const DRIVE_FIXED: u32 = 3;
+
+    // SAFETY: 无参数，返回盘符位掩码
+    let mask = unsafe { GetLogicalDrives() };
+    (0..26u8)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| format!("{}:\\", (b'A' + bit) as char))
+        .filter(|root| {
+            let wide: Vec<u16> = root.encode_utf16().chain(std::iter::once(0)).collect();
+            // SAFETY: wide 是以 0 结尾的 UTF-16 字符串
+            unsafe { GetDriveTypeW(wide.as_ptr()) == DRIVE_FIXED }
+        })
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// 两个路径是否位于同一盘符（用于判断移动是否只需重命名）
+pub fn same_volume(a: &Path, b: &Path) -> bool {
+    let prefix = |p: &Path| match p.components().next() {
+        Some(Component::Prefix(prefix)) => Some(prefix.as_os_str().to_ascii_uppercase()),
+        _ => None,
+    };
+    prefix(a).is_some() && prefix(a) == prefix(b)
+}
+
+/// 将字节数格式化为易读的大小
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.2} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide_text(path: &str) -> String {
+        let wide = wide_path(Path::new(path));
+        assert_eq!(wide.last(), Some(&0));
+        String::from_utf16(&wide[..wide.len() - 1]).unwrap()
+    }
+
+    #[test]
+    fn short_path_unchanged() {
+        assert_eq!(wide_text(r"C:\Games\Battlefield 6"), r"C:\Games\Battlefield 6");
+    }
+
+    #[test]
+    fn long_drive_path_prefixed() {
+        let path = format!(r"C:\Games\{}\Data", "a".repeat(300));
+        assert_eq!(wide_text(&path), format!(r"\\?\{}", path));
+    }
+
+    #[test]
+    fn long_unc_path_prefixed() {
+        let rest = format!(r"server\share\{}\Data", "a".repeat(300));
+        assert_eq!(wide_text(&format!(r"\\{}", rest)), format!(r"\\?\UNC\{}", rest));
+    }
+
+    #[test]
+    fn prefixed_path_unchanged() {
+        let path = format!(r"\\?\C:\Games\{}", "a".repeat(300));
+        assert_eq!(wide_text(&path), path);
+    }
+
+    #[test]
+    fn non_ascii_path_round_trips() {
+        let short = r"D:\游戏\战地风云\语音";
+        assert_eq!(wide_text(short), short);
+        // 长度按 UTF-16 单元计算，300 个字符超过 MAX_PATH
+        let long = format!(r"D:\游戏\{}", "语音ボイス".repeat(60));
+        assert_eq!(wide_text(&long), format!(r"\\?\{}", long));
+    }
+}
@@ -0,0 +1,70 @@
+//! 最后的检测手段：在所有本地磁盘的常见位置和浅层目录中查找 Steam 库（包含 steamapps 的文件夹）
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::disk;
+
+/// 每个盘符下优先检查的位置
+const COMMON_LOCATIONS: [&str; 6] = [
+    "Steam",
+    "SteamLibrary",
+    "Games\\Steam",
+    "Games\\SteamLibrary",
+    "Program Files (x86)\\Steam",
+    "Program Files\\Steam",
+];
+
+/// 在盘符根目录下查找 steamapps 的最大深度
+const MAX_DEPTH: usize = 2;
+
+/// 扫描时跳过的系统文件夹
+const SKIP_DIRS: [&str; 3] = ["windows", "system volume information", "$recycle.bin"];
+
+fn is_library(path: &Path) -> bool {
+    path.join("steamapps").is_dir()
+}
+
+/// 查找所有本地磁盘上的 Steam 库，按盘符顺序返回，常见位置在前
+pub fn find_libraries() -> Vec<PathBuf> {
+    let mut libraries = Vec::new();
+    for drive in disk::fixed_drives() {
+        for location in COMMON_LOCATIONS {
+            let path = drive.join(location);
+            if is_library(&path) && !libraries.contains(&path) {
+                libraries.push(path);
+            }
+        }
+        scan_dir(&drive, MAX_DEPTH, &mut libraries);
+    }
+    info!("drive scan found {} steam libraries", libraries.len());
+    libraries
+}
+
+fn scan_dir(dir: &Path, depth: usize, libraries: &mut Vec<PathBuf>) {
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        if SKIP_DIRS.contains(&name.as_str()) || name == "steamapps" {
+            continue;
+        }
+        let path = entry.path();
+        if is_library(&path) {
+            if !libraries.contains(&path) {
+                libraries.push(path);
+            }
+        } else {
+            scan_dir(&path, depth - 1, libraries);
+        }
+    }
+}
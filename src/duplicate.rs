@@ -0,0 +1,57 @@
+//! 复制备份：在备份目录中创建所选备份的独立副本或硬链接副本，副本在备份列表中单独显示
+
+use std::fs;
+use std::path::PathBuf;
+
+use tracing::info;
+
+use crate::backup_info::BackupInfo;
+use crate::copy::Progress;
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+use crate::link::{self, RestoreMode};
+use crate::logging;
+
+/// 复制所需的全部信息，在界面线程中收集后交给后台线程执行
+pub struct DuplicateJob {
+    pub source: PathBuf,
+    /// 副本的文件夹，先写入 staging，完成后再重命名
+    pub target: PathBuf,
+    pub staging: PathBuf,
+    pub info: BackupInfo,
+    pub label: String,
+    /// 用硬链接代替复制，不占用额外空间，但修改文件时原备份也会改变
+    pub hardlink: bool,
+    pub workers: usize,
+}
+
+impl DuplicateJob {
+    /// 需要复制的总字节数
+    pub fn total_size(&self) -> u64 {
+        if self.hardlink { 0 } else { disk::dir_size(&self.source) }
+    }
+
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        if self.staging.exists() {
+            fs::remove_dir_all(&self.staging).map_err(SwitcherError::CleanStaging)?;
+        }
+        let mode = if self.hardlink { RestoreMode::Hardlink } else { RestoreMode::Copy };
+        if let Err(e) = link::restore_folder(mode, &self.source, &self.staging, self.workers, progress) {
+            let _ = fs::remove_dir_all(&self.staging);
+            return Err(e);
+        }
+        // 副本没有恢复到游戏目录，不继承恢复记录
+        let info = BackupInfo {
+            restore: None,
+            label: self.label.clone(),
+            created: logging::local_time(),
+            dir: String::new(),
+            ..self.info
+        };
+        info.save(&self.staging)?;
+        fs::rename(&self.staging, &self.target)?;
+        info!("duplicated {} to {} ({})", self.source.display(), self.target.display(), mode.label());
+        Ok(t!("status.backup_duplicated", self.label))
+    }
+}
@@ -0,0 +1,65 @@
+//! EA App 安装：在各磁盘的默认安装位置查找游戏，读取安装信息中的版本
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::disk;
+
+/// 各盘符下 EA App（以及旧版 Origin）的默认游戏目录
+const LIBRARY_DIRS: [&str; 3] = ["Program Files\\EA Games", "EA Games", "Program Files (x86)\\Origin Games"];
+
+/// 安装信息文件，位于游戏目录中
+const INSTALLER_DATA: &str = "__Installer\\installerdata.xml";
+
+/// installerdata.xml 中可能记录版本的元素，按顺序尝试
+const VERSION_TAGS: [&str; 3] = ["gameVersion", "buildID", "version"];
+
+pub struct EaInstall {
+    pub game_path: PathBuf,
+    /// 安装信息中的游戏版本，读取不到时为空
+    pub version: String,
+    pub installer_data: PathBuf,
+}
+
+/// 查找 EA App 安装的游戏，exe_name 不为空时要求目录中存在该主程序
+pub fn find_installs(install_dir: &str, exe_name: &str) -> Vec<EaInstall> {
+    if install_dir.is_empty() {
+        return Vec::new();
+    }
+    let mut installs = Vec::new();
+    for drive in disk::fixed_drives() {
+        for library in LIBRARY_DIRS {
+            let game_path = drive.join(library).join(install_dir);
+            if !game_path.is_dir() || (!exe_name.is_empty() && !game_path.join(exe_name).exists()) {
+                continue;
+            }
+            let installer_data = game_path.join(INSTALLER_DATA);
+            let version = read_version(&installer_data).unwrap_or_default();
+            info!("found ea app install at {} (version {})", game_path.display(), version);
+            installs.push(EaInstall { game_path, version, installer_data });
+        }
+    }
+    installs
+}
+
+fn read_version(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    VERSION_TAGS.iter().find_map(|tag| element_text(&content, tag))
+}
+
+/// 元素 <tag>text</tag> 中的文本，忽略标签上的属性
+fn element_text(content: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}", tag);
+    let start = content.match_indices(&open).find_map(|(idx, _)| {
+        let rest = &content[idx + open.len()..];
+        if !rest.starts_with(['>', ' ']) {
+            return None;
+        }
+        Some(idx + open.len() + rest.find('>')? + 1)
+    })?;
+    let end = content[start..].find(&format!("</{}>", tag))?;
+    let text = content[start..start + end].trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
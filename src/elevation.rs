@@ -0,0 +1,58 @@
+//! 管理员权限：游戏安装在 Program Files 等受保护目录时需要以管理员身份运行，检测后可一键重新启动
+
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::iter;
+use std::path::Path;
+
+use tracing::info;
+use windows_sys::Win32::UI::Shell::{IsUserAnAdmin, ShellExecuteW};
+use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+/// 检测写入权限时创建的临时文件
+const PROBE_FILE: &str = ".bf6vs-write-test";
+
+/// 当前进程是否以管理员身份运行
+pub fn is_elevated() -> bool {
+    // SAFETY: 无参数
+    unsafe { IsUserAnAdmin() != 0 }
+}
+
+/// 目录是否因权限不足无法写入：尝试创建并删除临时文件，目录不存在或其他错误不算权限问题
+pub fn write_denied(dir: &Path) -> bool {
+    if !dir.is_dir() {
+        return false;
+    }
+    let probe = dir.join(PROBE_FILE);
+    match OpenOptions::new().write(true).create_new(true).open(&probe) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            false
+        }
+        Err(e) => e.kind() == io::ErrorKind::PermissionDenied,
+    }
+}
+
+/// 以管理员身份重新启动本程序，args 为传给新进程的命令行参数
+pub fn relaunch_as_admin(args: &[String]) -> io::Result<()> {
+    let exe = std::env::current_exe()?;
+    let params: Vec<String> = args.iter().map(|arg| quote(arg)).collect();
+    let wide = |s: &str| -> Vec<u16> { s.encode_utf16().chain(iter::once(0)).collect() };
+    let (verb, file, params) = (wide("runas"), wide(&exe.to_string_lossy()), wide(&params.join(" ")));
+    // SAFETY: 字符串以 0 结尾且在调用期间有效，窗口句柄和工作目录允许为空
+    let result = unsafe {
+        ShellExecuteW(std::ptr::null_mut(), verb.as_ptr(), file.as_ptr(), params.as_ptr(), std::ptr::null(), SW_SHOWNORMAL)
+    };
+    // 返回值大于 32 表示成功，用户在 UAC 提示中取消时返回错误
+    if result as usize <= 32 {
+        return Err(io::Error::last_os_error());
+    }
+    info!("relaunched as administrator");
+    Ok(())
+}
+
+/// 按 Windows 命令行规则为参数加上引号，结尾的反斜杠需要加倍以免转义引号
+fn quote(arg: &str) -> String {
+    let trailing = arg.len() - arg.trim_end_matches('\\').len();
+    format!("\"{}{}\"", arg.replace('"', "\\\""), "\\".repeat(trailing))
+}
@@ -0,0 +1,236 @@
+//! 统一的错误类型，界面显示时按当前界面语言格式化
+
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::i18n::t;
+use crate::link::RestoreMode;
+
+#[derive(Debug, Error)]
+pub enum SwitcherError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::ser::Error),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
+    // 游戏与 Steam
+    #[error("{}", t!("error.no_steam_launch_option"))]
+    SteamNotFound,
+    #[error("{}", t!("error.steam_running"))]
+    SteamRunning,
+    #[error("{}", t!("error.game_running", .0))]
+    GameRunning(String),
+    #[error("{}", t!("error.localconfig_not_found"))]
+    LocalConfigNotFound,
+    #[error("{}", t!("error.localconfig_backup", .0))]
+    LocalConfigBackup(io::Error),
+    #[error("{}", t!("error.user_cfg", .0))]
+    UserCfg(io::Error),
+    #[error("{}", t!("error.user_cfg_not_found"))]
+    UserCfgNotFound,
+    #[error("{}", t!("error.launch_option_failed", .0))]
+    LaunchOption(Box<SwitcherError>),
+    #[error("{}", t!("error.launch_failed", .0))]
+    Launch(Box<SwitcherError>),
+    #[error("{}", t!("error.version_mismatch", .backup, .current))]
+    VersionMismatch { backup: String, current: String },
+
+    // VDF 解析
+    #[error("{}", t!("error.vdf_unterminated"))]
+    VdfUnterminated,
+    #[error("{}", t!("error.vdf_invalid"))]
+    VdfInvalid,
+    #[error("{}", t!("error.vdf_braces"))]
+    VdfBraces,
+    #[error("{}", t!("error.vdf_missing_node", .0))]
+    VdfMissingNode(String),
+    #[error("{}", t!("error.vdf_not_value", .0))]
+    VdfNotValue(String),
+    #[error("{}", t!("error.vdf_write"))]
+    VdfWrite,
+
+    // 语音文件与备份
+    #[error("{}", t!("error.select_folder_first"))]
+    NoFolderSelected,
+    #[error("{}", t!("error.select_game_folder_first"))]
+    NoGameFolderSelected,
+    #[error("{}", t!("error.folder_not_found"))]
+    FolderNotFound,
+    #[error("{}", t!("error.voice_not_found", .0, .0))]
+    VoiceNotFound(String),
+    #[error("{}", t!("error.backup_incomplete", .0))]
+    BackupIncomplete(String),
+    #[error("{}", t!("error.move_junction", .0.display()))]
+    MoveJunction(PathBuf),
+    #[error("{}", t!("error.disk_space", crate::disk::format_size(*.required), crate::disk::format_size(*.available)))]
+    DiskSpace { required: u64, available: u64 },
+    #[error("{}", t!("error.clean_staging", .0))]
+    CleanStaging(io::Error),
+    #[error("{}", t!("error.create_dir", .0))]
+    CreateDir(io::Error),
+    #[error("{}", t!("error.no_installed_languages"))]
+    NoInstalledLanguages,
+    #[error("{}", t!("error.backup_all_failed", .0))]
+    BackupAllFailed(String),
+    #[error("{}", t!("error.no_other_voices"))]
+    NoOtherVoices,
+    #[error("{}", t!("error.cleanup_failed", .0))]
+    CleanupFailed(String),
+    #[error("{}", t!("error.backup_missing_files", .0.len(), path_list(.0)))]
+    BackupMissingFiles(Vec<PathBuf>),
+    #[error("{}", t!("error.backup_damaged", .missing.len(), .corrupted.len(), path_list(&[.missing.as_slice(), .corrupted.as_slice()].concat())))]
+    BackupDamaged { missing: Vec<PathBuf>, corrupted: Vec<PathBuf> },
+    #[error("{}", t!("error.restore_unverified", .0.len(), path_list(.0)))]
+    RestoreUnverified(Vec<PathBuf>),
+    #[error("{}", t!("error.backup_item", .path.display(), .source))]
+    BackupItem { path: PathBuf, source: Box<SwitcherError> },
+    #[error("{}", t!("error.remove_old_backup", .0))]
+    RemoveOldBackup(io::Error),
+    #[error("{}", t!("error.save_backup", .0))]
+    SaveBackup(Box<SwitcherError>),
+    #[error("{}", t!("error.link_rolled_back", .path.display(), .source))]
+    LinkRolledBack { path: PathBuf, source: Box<SwitcherError> },
+    #[error("{}", t!("error.rollback_failed", .path.display(), .source))]
+    RollbackFailed { path: PathBuf, source: Box<SwitcherError> },
+    #[error("{}", t!("error.orphaned_move", .0.display()))]
+    OrphanedMove(PathBuf),
+    #[error("{}", t!("error.no_backups"))]
+    NoBackups,
+    #[error("{}", t!("error.backup_missing"))]
+    BackupMissing,
+    #[error("{}", t!("error.backup_empty"))]
+    BackupEmpty,
+    #[error("{}", t!("error.target_exists", .0.display()))]
+    TargetExists(PathBuf),
+    #[error("{}", t!("error.junction_failed", .0))]
+    JunctionFailed(String),
+    #[error("{}", t!("error.restore_item_mode", .path.display(), .mode.label(), .source))]
+    RestoreItem { path: PathBuf, mode: RestoreMode, source: Box<SwitcherError> },
+    #[error("{}", t!("error.restore_item", .path.display(), .source))]
+    RestoreToc { path: PathBuf, source: io::Error },
+    #[error("{}", t!("error.delete_item", .path.display(), .source))]
+    DeleteItem { path: PathBuf, source: io::Error },
+    #[error("{}", t!("error.no_backup_to_delete"))]
+    NoBackupToDelete,
+    #[error("{}", t!("error.delete_backup", .0))]
+    DeleteBackup(io::Error),
+    #[error("{}", t!("error.backup_in_use", .0))]
+    BackupInUse(usize),
+    #[error("{}", t!("error.link_target_missing", .0.display()))]
+    LinkTargetMissing(PathBuf),
+    #[error("{}", t!("error.materialize", .path.display(), .source))]
+    Materialize { path: PathBuf, source: Box<SwitcherError> },
+    #[error("{}", t!("error.backup_exists", .0))]
+    BackupExists(String),
+    #[error("{}", t!("error.import_invalid"))]
+    ImportInvalid,
+    #[error("{}", t!("error.import_wrong_game", .0))]
+    ImportWrongGame(String),
+    #[error("{}", t!("error.import_version", .backup, .current))]
+    ImportVersionMismatch { backup: String, current: String },
+    #[error("{}", t!("error.import_file_mismatch", .0))]
+    ImportFileMismatch(String),
+    #[error("{}", t!("error.import_unknown_language", .0))]
+    ImportUnknownLanguage(String),
+    #[error("{}", t!("error.import_bad_path", .0.display()))]
+    ImportBadPath(PathBuf),
+    #[error("{}", t!("error.verify_missing", .0.display()))]
+    VerifyMissing(PathBuf),
+    #[error("{}", t!("error.verify_failed", .path.display(), .source))]
+    VerifyFailed { path: PathBuf, source: io::Error },
+    #[error("{}", t!("error.elevate", .0))]
+    Elevate(io::Error),
+    #[error("{}", t!("error.update_download", .0))]
+    UpdateDownload(io::Error),
+    #[error("{}", t!("error.update_no_checksum"))]
+    UpdateNoChecksum,
+    #[error("{}", t!("error.update_hash_mismatch"))]
+    UpdateHashMismatch,
+    #[error("{}", t!("error.update_install", .0))]
+    UpdateInstall(io::Error),
+    #[error("{}", t!("error.launch_unsupported"))]
+    LaunchUnsupported,
+    #[error("{}", t!("error.launch_option_ea"))]
+    LaunchOptionEaApp,
+    #[error("{}", t!("error.launch_option_xbox"))]
+    LaunchOptionXbox,
+    #[error("{}", t!("error.schedule_task", .0))]
+    ScheduleTask(String),
+    #[error("{}", t!("error.shortcut", .0))]
+    Shortcut(String),
+    #[error("{}", t!("error.repair_incomplete", .0))]
+    RepairIncomplete(usize),
+    #[error("{}", t!("error.network_access", .0.display(), .1))]
+    NetworkAccess(PathBuf, io::Error),
+    #[error("{}", t!("error.cloud_protect", .0, .1))]
+    CloudProtect(&'static str, io::Error),
+    #[error("{}", t!("error.security_settings", .0))]
+    SecuritySettings(io::Error),
+    #[error("{}", t!("error.backup_inside_game", .0.display()))]
+    BackupInsideGame(PathBuf),
+    #[error("{}", t!("error.relocate_inside", .0.display()))]
+    RelocateInside(PathBuf),
+    #[error("{}", t!("error.relocate_not_empty", .0.display()))]
+    RelocateNotEmpty(PathBuf),
+    #[error("{}", t!("error.relocate_network_links", .0))]
+    RelocateNetworkLinks(usize),
+    #[error("{}", t!("error.compare_compressed"))]
+    CompareCompressed,
+    #[error("{}", t!("error.empty_backup_label"))]
+    EmptyBackupLabel,
+    #[error("{}", t!("error.retention_disabled"))]
+    RetentionDisabled,
+    #[error("{}", t!("error.not_a_directory", .0.display()))]
+    NotADirectory(PathBuf),
+    #[error("{}", t!("error.task_failed"))]
+    TaskFailed,
+    #[error("{}", t!("error.task_running"))]
+    TaskRunning,
+
+    // 配置文件
+    #[error("{}", t!("error.save_settings", .0))]
+    SaveSettings(Box<SwitcherError>),
+    #[error("{}", t!("error.games_file_parse", .0))]
+    GamesFileParse(String),
+    #[error("{}", t!("error.game_def_id", .0))]
+    GameDefId(String),
+    #[error("{}", t!("error.game_def_field", .id, .field))]
+    GameDefField { id: String, field: &'static str },
+    #[error("{}", t!("error.game_def_duplicate", .0))]
+    GameDefDuplicate(String),
+    #[error("{}", t!("error.custom_lang_code", .0))]
+    CustomLangCode(String),
+    #[error("{}", t!("error.custom_lang_miles", .0))]
+    CustomLangMiles(String),
+    #[error("{}", t!("error.profile_name_empty"))]
+    ProfileNameEmpty,
+    #[error("{}", t!("error.no_profile_selected"))]
+    NoProfileSelected,
+    #[error("{}", t!("error.profile_not_found", .0))]
+    ProfileNotFound(String),
+    #[error("{}", t!("error.unknown_language", .0))]
+    UnknownLanguage(String),
+    #[error("{}", t!("error.switch_in_use", .name, .reasons))]
+    LanguageInUse { name: String, reasons: String },
+    #[error("{}", t!("error.invalid_hotkey", .0))]
+    InvalidHotkey(String),
+    #[error("{}", t!("error.hotkey_unavailable", .0))]
+    HotkeyUnavailable(String),
+}
+
+/// 错误信息中最多列出的路径数
+const MAX_LISTED_PATHS: usize = 10;
+
+fn path_list(paths: &[PathBuf]) -> String {
+    let mut list: Vec<String> = paths.iter().take(MAX_LISTED_PATHS).map(|p| p.display().to_string()).collect();
+    if paths.len() > MAX_LISTED_PATHS {
+        list.push("...".to_string());
+    }
+    list.join("\n")
+}
@@ -0,0 +1,20 @@
+//! 在资源管理器中打开文件夹
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use tracing::info;
+
+/// 用 explorer.exe 打开 path，路径作为单独的参数传递，包含空格和非 ASCII 字符时也能正确打开
+pub fn open(path: &Path) -> io::Result<()> {
+    if !path.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, path.display().to_string()));
+    }
+    // explorer.exe 不接受 / 分隔的路径，先转换为完整的 Windows 路径
+    let path = std::path::absolute(path)?;
+    // explorer.exe 成功时也可能返回非 0，只检查能否启动
+    Command::new("explorer.exe").arg(&path).spawn()?;
+    info!("opened {} in explorer", path.display());
+    Ok(())
+}
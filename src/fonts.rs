@@ -0,0 +1,42 @@
+//! 界面字体：内嵌 CJK 字体子集，系统字体作为补充
+
+use eframe::egui;
+
+/// 霞鹜文楷子集，覆盖界面中的中日韩文字（详见 assets/fonts/README.md）
+const EMBEDDED_FONT: &[u8] = include_bytes!("../assets/fonts/LXGWWenKai-Subset.ttf");
+
+/// 子集未覆盖的字符依次从这些系统字体中查找（存在时才加载）
+const SYSTEM_FALLBACKS: [(&str, &str); 3] = [
+    ("msyh", "C:\\Windows\\Fonts\\msyh.ttc"),
+    ("yugothic", "C:\\Windows\\Fonts\\YuGothM.ttc"),
+    ("malgun", "C:\\Windows\\Fonts\\malgun.ttf"),
+];
+
+pub fn setup_fonts(ctx: &egui::Context) {
+    let mut fonts = egui::FontDefinitions::default();
+
+    fonts.font_data.insert(
+        "lxgw_wenkai".to_owned(),
+        egui::FontData::from_static(EMBEDDED_FONT).into(),
+    );
+    let mut family_fonts = vec!["lxgw_wenkai".to_owned()];
+
+    for (name, path) in SYSTEM_FALLBACKS {
+        if let Ok(font_data) = std::fs::read(path) {
+            fonts.font_data.insert(
+                name.to_owned(),
+                egui::FontData::from_owned(font_data).into(),
+            );
+            family_fonts.push(name.to_owned());
+        }
+    }
+
+    for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+        let entry = fonts.families.entry(family).or_default();
+        // 内嵌字体优先，系统字体排在 egui 默认字体之后作为补充
+        entry.insert(0, family_fonts[0].clone());
+        entry.extend(family_fonts[1..].iter().cloned());
+    }
+
+    ctx.set_fonts(fonts);
+}
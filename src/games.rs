@@ -0,0 +1,305 @@
+//! 支持的游戏定义：Steam App ID、语音文件所在目录、启动参数和可用语言
+//!
+//! 内置定义可以被程序所在目录的 games.toml 覆盖或扩展。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::error::SwitcherError;
+
+pub const GAMES_FILE: &str = "games.toml";
+
+/// Frostbite 游戏默认使用的语音语言启动参数
+pub const DEFAULT_LAUNCH_PARAM: &str = "+miles_language";
+
+/// 匹配模式中代表语言文件夹代码的占位符
+const CODE_PLACEHOLDER: &str = "{code}";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct GameDef {
+    /// 唯一标识，同时用作备份子目录名
+    pub id: String,
+    pub name: String,
+    pub app_id: String,
+    /// 游戏主程序文件名，用于检查游戏是否正在运行，为空时不检查
+    #[serde(default)]
+    pub exe_name: String,
+    /// EA App 安装时的游戏文件夹名，为空时不查找 EA App 安装
+    #[serde(default)]
+    pub ea_install_dir: String,
+    /// Xbox 安装时 XboxGames 中的游戏文件夹名，为空时不查找 Xbox 安装
+    #[serde(default)]
+    pub xbox_install_dir: String,
+    /// 语音文件所在目录，相对于游戏安装目录
+    #[serde(default = "default_data_subpath")]
+    pub data_subpath: String,
+    /// 语音语言启动参数名，参数值为语言的 miles_lang
+    #[serde(default = "default_launch_param")]
+    pub launch_param: String,
+    /// 支持的语言代码，为空时显示全部语言
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// 语音文件夹名称模式，{code} 替换为语言的文件夹代码，支持 * 和 ? 通配符（如 vo*{code}*）
+    #[serde(default = "default_folder_patterns")]
+    pub folder_patterns: Vec<String>,
+    /// .toc 文件名称模式，{code} 替换为语言的文件夹代码，支持 * 和 ? 通配符
+    #[serde(default = "default_toc_patterns")]
+    pub toc_patterns: Vec<String>,
+    /// 扫描时跳过的文件夹名称模式，支持 * 和 ? 通配符（如 mods）
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>,
+    /// 备份子目录名，None 时使用 id，空字符串表示直接使用备份根目录
+    pub backup_subdir: Option<String>,
+}
+
+fn default_data_subpath() -> String {
+    "Data\\Win32".to_string()
+}
+
+fn default_launch_param() -> String {
+    DEFAULT_LAUNCH_PARAM.to_string()
+}
+
+fn default_folder_patterns() -> Vec<String> {
+    vec!["{code}".to_string(), "vo{code}".to_string()]
+}
+
+fn default_toc_patterns() -> Vec<String> {
+    vec!["{code}.toc".to_string(), "vo{code}.toc".to_string()]
+}
+
+/// 语音文件夹的检查结果
+#[derive(Debug, PartialEq, Eq)]
+pub enum DataFolderCheck {
+    /// 包含 Frostbite 的数据文件
+    Valid,
+    /// 选择的是游戏根目录或上级目录，应改为其中的语音文件目录
+    Corrected(PathBuf),
+    /// 包含数据文件，但所在的游戏目录中没有该游戏的主程序
+    WrongGame,
+    /// 不包含数据文件
+    Unexpected,
+}
+
+/// Frostbite 数据目录中的文件类型
+const DATA_EXTENSIONS: [&str; 3] = ["toc", "sb", "cas"];
+
+/// 文件夹或其下一级子文件夹中是否有 .toc、.sb 或 .cas 文件
+fn has_data_files(path: &Path) -> bool {
+    let is_data_file = |path: &Path| {
+        path.extension().is_some_and(|ext| DATA_EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+    };
+    let Ok(entries) = fs::read_dir(path) else {
+        return false;
+    };
+    let mut dirs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_data_file(&path) {
+            return true;
+        }
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            dirs.push(path);
+        }
+    }
+    dirs.iter().any(|dir| fs::read_dir(dir).is_ok_and(|entries| entries.flatten().any(|entry| is_data_file(&entry.path()))))
+}
+
+#[derive(Deserialize)]
+struct GamesFile {
+    #[serde(default)]
+    games: Vec<GameDef>,
+}
+
+impl GameDef {
+    fn frostbite(id: &str, name: &str, app_id: &str, exe_name: &str) -> Self {
+        GameDef {
+            id: id.to_string(),
+            name: name.to_string(),
+            app_id: app_id.to_string(),
+            exe_name: exe_name.to_string(),
+            ea_install_dir: name.to_string(),
+            xbox_install_dir: name.to_string(),
+            data_subpath: default_data_subpath(),
+            launch_param: default_launch_param(),
+            languages: Vec::new(),
+            folder_patterns: default_folder_patterns(),
+            toc_patterns: default_toc_patterns(),
+            exclude_patterns: Vec::new(),
+            backup_subdir: None,
+        }
+    }
+
+    /// 游戏安装目录下的语音文件目录
+    pub fn data_path(&self, game_path: &Path) -> PathBuf {
+        self.data_subpath
+            .split(['\\', '/'])
+            .filter(|part| !part.is_empty())
+            .fold(game_path.to_path_buf(), |path, part| path.join(part))
+    }
+
+    /// 检查所选文件夹是否为该游戏的语音文件目录，选择了游戏根目录或中间目录时找出其中的语音文件目录
+    pub fn check_data_folder(&self, path: &Path) -> DataFolderCheck {
+        if has_data_files(path) {
+            let game_root = self.game_root(path);
+            if !self.exe_name.is_empty() && game_root.is_some_and(|root| !root.join(&self.exe_name).exists()) {
+                return DataFolderCheck::WrongGame;
+            }
+            return DataFolderCheck::Valid;
+        }
+        // 依次尝试 Data\Win32、Win32 等语音文件目录的后缀
+        let parts = self.data_subpath_parts();
+        (0..parts.len())
+            .map(|skip| parts[skip..].iter().fold(path.to_path_buf(), |path, part| path.join(part)))
+            .find(|candidate| has_data_files(candidate))
+            .map_or(DataFolderCheck::Unexpected, DataFolderCheck::Corrected)
+    }
+
+    fn data_subpath_parts(&self) -> Vec<&str> {
+        self.data_subpath.split(['\\', '/']).filter(|part| !part.is_empty()).collect()
+    }
+
+    /// 由语音文件目录推出游戏安装目录，目录层级与 data_subpath 不符时返回 None
+    pub fn game_root(&self, data_path: &Path) -> Option<PathBuf> {
+        let parts = self.data_subpath_parts();
+        let mut root = data_path;
+        for part in parts.iter().rev() {
+            if !root.file_name()?.to_string_lossy().eq_ignore_ascii_case(part) {
+                return None;
+            }
+            root = root.parent()?;
+        }
+        Some(root.to_path_buf())
+    }
+
+    /// 直接启动游戏主程序（EA App 和 Xbox 安装），由游戏自行唤起所属的平台
+    pub fn launch_exe(&self, game_path: &Path, args: &str) -> Result<(), SwitcherError> {
+        if self.exe_name.is_empty() {
+            return Err(SwitcherError::LaunchUnsupported);
+        }
+        Command::new(game_path.join(&self.exe_name))
+            .args(args.split_whitespace())
+            .current_dir(game_path)
+            .spawn()?;
+        Ok(())
+    }
+
+    /// 该游戏的备份目录
+    pub fn backup_dir(&self, root: &Path) -> PathBuf {
+        match self.backup_subdir.as_deref().unwrap_or(&self.id) {
+            "" => root.to_path_buf(),
+            subdir => root.join(subdir),
+        }
+    }
+
+    pub fn supports_language(&self, code: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|c| c == code)
+    }
+
+    /// 按模式生成某个文件夹代码对应的语音文件夹名
+    pub fn folder_names(&self, code: &str) -> Vec<String> {
+        self.folder_patterns.iter().map(|p| p.replace(CODE_PLACEHOLDER, code)).collect()
+    }
+
+    /// 按模式生成某个文件夹代码对应的 .toc 文件名
+    pub fn toc_names(&self, code: &str) -> Vec<String> {
+        self.toc_patterns.iter().map(|p| p.replace(CODE_PLACEHOLDER, code)).collect()
+    }
+
+    fn invalid_field(&self, field: &'static str) -> SwitcherError {
+        SwitcherError::GameDefField { id: self.id.clone(), field }
+    }
+
+    fn validate(&self) -> Result<(), SwitcherError> {
+        let valid_id = !self.id.is_empty()
+            && self.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_id {
+            return Err(SwitcherError::GameDefId(self.id.clone()));
+        }
+        if self.name.trim().is_empty() {
+            return Err(self.invalid_field("name"));
+        }
+        if self.app_id.is_empty() || !self.app_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(self.invalid_field("app_id"));
+        }
+        let data_path = Path::new(&self.data_subpath);
+        if data_path.is_absolute() || self.data_subpath.split(['\\', '/']).any(|part| part == "..") {
+            return Err(self.invalid_field("data_subpath"));
+        }
+        if self.launch_param.is_empty() || self.launch_param.contains(char::is_whitespace) {
+            return Err(self.invalid_field("launch_param"));
+        }
+        let valid_patterns = |patterns: &[String]| {
+            !patterns.is_empty() && patterns.iter().all(|p| p.contains(CODE_PLACEHOLDER) && !p.contains(['\\', '/']))
+        };
+        if !valid_patterns(&self.folder_patterns) {
+            return Err(self.invalid_field("folder_patterns"));
+        }
+        if !valid_patterns(&self.toc_patterns) {
+            return Err(self.invalid_field("toc_patterns"));
+        }
+        if self.exclude_patterns.iter().any(|p| p.is_empty() || p.contains(['\\', '/'])) {
+            return Err(self.invalid_field("exclude_patterns"));
+        }
+        Ok(())
+    }
+}
+
+/// 读取游戏列表：内置定义加上 games.toml 中的定义（id 相同时覆盖内置定义）
+///
+/// 文件不存在时只使用内置定义；无效的条目会被跳过，并返回对应的错误信息。
+pub fn load_games(path: &Path) -> (Vec<GameDef>, Vec<SwitcherError>) {
+    let mut games = builtin_games();
+    let mut errors = Vec::new();
+
+    let Ok(content) = fs::read_to_string(path) else {
+        return (games, errors);
+    };
+    let file: GamesFile = match toml::from_str(&content) {
+        Ok(file) => file,
+        Err(e) => {
+            errors.push(SwitcherError::GamesFileParse(e.message().to_string()));
+            return (games, errors);
+        }
+    };
+
+    let mut seen = Vec::new();
+    for game in file.games {
+        if let Err(e) = game.validate() {
+            errors.push(e);
+            continue;
+        }
+        if seen.contains(&game.id) {
+            errors.push(SwitcherError::GameDefDuplicate(game.id));
+            continue;
+        }
+        seen.push(game.id.clone());
+        match games.iter_mut().find(|g| g.id == game.id) {
+            Some(existing) => {
+                // 未指定备份目录时沿用内置定义，避免找不到已有的备份
+                let backup_subdir = game.backup_subdir.clone().or(existing.backup_subdir.take());
+                *existing = GameDef { backup_subdir, ..game };
+            }
+            None => games.push(game),
+        }
+    }
+    (games, errors)
+}
+
+/// 内置的游戏列表，第一个为默认游戏
+fn builtin_games() -> Vec<GameDef> {
+    // 战地6 的备份直接放在备份根目录，与早期版本保持兼容
+    let mut bf6 = GameDef::frostbite("bf6", "Battlefield 6", "2807960", "bf6.exe");
+    bf6.backup_subdir = Some(String::new());
+
+    vec![
+        bf6,
+        GameDef::frostbite("bf2042", "Battlefield 2042", "1517290", "BF2042.exe"),
+        GameDef::frostbite("bfv", "Battlefield V", "1238810", "bfv.exe"),
+        GameDef::frostbite("bf1", "Battlefield 1", "1238840", "bf1.exe"),
+        GameDef::frostbite("bf4", "Battlefield 4", "1238860", "bf4.exe"),
+    ]
+}
@@ -0,0 +1,47 @@
+//! 文件内容哈希（SHA-256），用于判断文件是否变化
+
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+/// 计算文件的 SHA-256，返回十六进制字符串
+pub fn file_hash(path: &Path) -> io::Result<String> {
+    hash_reader(&mut File::open(path)?, |_| Ok(()))
+}
+
+/// 读取全部内容并计算 SHA-256，读到的数据同时交给 sink（用于边复制边计算）
+pub fn hash_reader(reader: &mut (impl Read + ?Sized), mut sink: impl FnMut(&[u8]) -> io::Result<()>) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        sink(&buf[..n])?;
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// 文件未变化：大小和修改时间相同时直接视为未变化，否则比较哈希
+pub fn unchanged(a: &Path, b: &Path) -> bool {
+    let (Ok(x), Ok(y)) = (fs::metadata(a), fs::metadata(b)) else {
+        return false;
+    };
+    if x.len() != y.len() {
+        return false;
+    }
+    matches!((x.modified(), y.modified()), (Ok(x), Ok(y)) if x == y) || same_content(a, b)
+}
+
+/// 两个文件大小和内容都相同
+pub fn same_content(a: &Path, b: &Path) -> bool {
+    let size = |p: &Path| fs::metadata(p).map(|m| m.len()).ok();
+    match (size(a), size(b)) {
+        (Some(x), Some(y)) if x == y => matches!((file_hash(a), file_hash(b)), (Ok(x), Ok(y)) if x == y),
+        _ => false,
+    }
+}
@@ -0,0 +1,137 @@
+//! 启动检查：启动时快速检查游戏目录、版本号、备份目录、恢复的链接、启动选项和剩余空间，
+//! 以清单显示结果，能直接处理的问题附带修复按钮
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::disk;
+use crate::games::DataFolderCheck;
+use crate::i18n::t;
+use crate::monitor::Problem;
+
+/// 备份所在磁盘的剩余空间低于这个值时提醒，一种语言的语音文件通常有几 GB
+pub const LOW_SPACE: u64 = 10 * 1024 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// 可以一键完成的修复
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fix {
+    /// 重新检测游戏安装
+    DetectGame,
+    /// 创建备份目录
+    CreateBackupDir,
+    /// 重新创建被移除的链接
+    RepairLinks,
+    /// 把启动选项改为所选语言
+    WriteLaunchOption,
+}
+
+impl Fix {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Fix::DetectGame => t!("health.fix_detect"),
+            Fix::CreateBackupDir => t!("health.fix_create_dir"),
+            Fix::RepairLinks => t!("health.fix_repair"),
+            Fix::WriteLaunchOption => t!("health.fix_launch_option"),
+        }
+    }
+}
+
+/// 清单中的一项
+#[derive(Clone, Debug)]
+pub struct Finding {
+    pub level: Level,
+    pub message: String,
+    pub fix: Option<Fix>,
+}
+
+impl Finding {
+    fn ok(message: String) -> Self {
+        Finding { level: Level::Ok, message, fix: None }
+    }
+
+    fn warning(message: String, fix: Option<Fix>) -> Self {
+        Finding { level: Level::Warning, message, fix }
+    }
+
+    fn error(message: String, fix: Option<Fix>) -> Self {
+        Finding { level: Level::Error, message, fix }
+    }
+}
+
+/// 检查所需的信息，由界面线程根据当前状态收集
+pub struct Input<'a> {
+    pub game_name: &'a str,
+    pub source_path: &'a Path,
+    /// 语音文件目录的检查结果，未设置目录时为 None
+    pub data_folder: Option<DataFolderCheck>,
+    /// 检测到的安装中的版本号，没有检测到安装时为 None
+    pub build_id: Option<&'a str>,
+    pub backup_dir: &'a Path,
+    /// 恢复的链接和 .toc 文件的问题，没有恢复记录时为 None
+    pub links: Option<Vec<Problem>>,
+    /// (启动选项中的语音参数值, 所选语言的参数值)，无法读取启动选项时为 None
+    pub launch: Option<(Vec<String>, &'a str)>,
+}
+
+pub fn run(input: &Input) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    findings.push(match &input.data_folder {
+        Some(DataFolderCheck::Valid) => Finding::ok(t!("health.game_ok", input.game_name, input.source_path.display())),
+        Some(_) => Finding::error(t!("health.game_unexpected", input.source_path.display()), Some(Fix::DetectGame)),
+        None => Finding::error(t!("health.game_missing", input.game_name), Some(Fix::DetectGame)),
+    });
+
+    findings.push(match input.build_id {
+        Some(build_id) if !build_id.is_empty() => Finding::ok(t!("health.build_ok", build_id)),
+        Some(_) => Finding::warning(t!("health.build_unreadable").to_string(), None),
+        None => Finding::warning(t!("health.build_unknown").to_string(), Some(Fix::DetectGame)),
+    });
+
+    findings.push(match fs::read_dir(input.backup_dir) {
+        Ok(_) => Finding::ok(t!("health.backup_ok", input.backup_dir.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            Finding::warning(t!("health.backup_missing", input.backup_dir.display()), Some(Fix::CreateBackupDir))
+        }
+        Err(e) => Finding::error(t!("health.backup_unreadable", input.backup_dir.display(), e), None),
+    });
+
+    match &input.links {
+        Some(problems) if problems.is_empty() => findings.push(Finding::ok(t!("health.links_ok").to_string())),
+        Some(problems) => {
+            let details: Vec<String> = problems.iter().map(Problem::message).collect();
+            findings.push(Finding::error(t!("health.links_broken", problems.len(), details.join("\n")), Some(Fix::RepairLinks)));
+        }
+        None => {}
+    }
+
+    if let Some((values, selected)) = &input.launch {
+        if values.is_empty() {
+            findings.push(Finding::ok(t!("health.launch_none").to_string()));
+        } else if values.iter().all(|value| value.eq_ignore_ascii_case(selected)) {
+            findings.push(Finding::ok(t!("health.launch_ok", selected)));
+        } else {
+            findings.push(Finding::warning(t!("health.launch_mismatch", values.join(", "), selected), Some(Fix::WriteLaunchOption)));
+        }
+    }
+
+    // 备份目录可能尚未创建，按最近的已存在的上级目录查询
+    let existing = input.backup_dir.ancestors().find(|dir| dir.is_dir());
+    if let Some(available) = existing.and_then(disk::free_space) {
+        let size = disk::format_size(available);
+        findings.push(if available < LOW_SPACE {
+            Finding::warning(t!("health.space_low", size), None)
+        } else {
+            Finding::ok(t!("health.space_ok", size))
+        });
+    }
+    findings
+}
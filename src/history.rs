@@ -0,0 +1,53 @@
+//! 操作历史（history.jsonl，位于各游戏的备份目录）：记录每次备份、恢复和删除的时间、语言、游戏版本、结果和用时，
+//! 便于查看某个备份上次更新的时间
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::plan::Operation;
+
+pub const HISTORY_FILE: &str = "history.jsonl";
+/// 界面中最多显示的记录数，更早的记录仍保留在文件中
+pub const MAX_SHOWN: usize = 500;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entry {
+    /// 操作结束的本地时间
+    pub time: String,
+    pub operation: Operation,
+    /// 语言名称，批量操作时为空
+    #[serde(default)]
+    pub target: String,
+    /// 操作时的游戏版本
+    #[serde(default)]
+    pub build_id: String,
+    pub ok: bool,
+    /// 结果摘要的第一行或错误信息
+    #[serde(default)]
+    pub message: String,
+    #[serde(default)]
+    pub duration_secs: f64,
+}
+
+/// 追加一条记录，写入失败不影响操作本身
+pub fn append(backup_dir: &Path, entry: &Entry) {
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    let _ = fs::create_dir_all(backup_dir);
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(backup_dir.join(HISTORY_FILE)) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// 最近的 MAX_SHOWN 条记录，按时间从旧到新排列；无法解析的行跳过
+pub fn load(backup_dir: &Path) -> Vec<Entry> {
+    let content = fs::read_to_string(backup_dir.join(HISTORY_FILE)).unwrap_or_default();
+    let mut entries: Vec<Entry> = content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+    let skip = entries.len().saturating_sub(MAX_SHOWN);
+    entries.drain(..skip);
+    entries
+}
@@ -0,0 +1,103 @@
+//! 全局热键：通过 RegisterHotKey 注册，窗口隐藏到托盘时也能切换到预设的语言
+
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use eframe::egui;
+use tracing::{info, warn};
+use windows_sys::Win32::System::Threading::GetCurrentThreadId;
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
+    RegisterHotKey, UnregisterHotKey, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT, MOD_SHIFT, MOD_WIN,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{GetMessageW, PostThreadMessageW, MSG, WM_HOTKEY, WM_QUIT};
+
+/// 解析 "Ctrl+Alt+1" 形式的组合键，返回 RegisterHotKey 的修饰键和虚拟键码
+///
+/// 按键支持字母、数字和 F1 - F24，至少需要一个修饰键
+pub fn parse(keys: &str) -> Option<(u32, u32)> {
+    let mut modifiers = 0;
+    let mut key = None;
+    for part in keys.split('+').map(|p| p.trim().to_ascii_uppercase()) {
+        match part.as_str() {
+            "CTRL" | "CONTROL" => modifiers |= MOD_CONTROL,
+            "ALT" => modifiers |= MOD_ALT,
+            "SHIFT" => modifiers |= MOD_SHIFT,
+            "WIN" => modifiers |= MOD_WIN,
+            _ if key.is_some() => return None,
+            name if name.len() == 1 && name.chars().all(|c| c.is_ascii_alphanumeric()) => key = Some(name.as_bytes()[0] as u32),
+            name => {
+                let n: u32 = name.strip_prefix('F')?.parse().ok()?;
+                if !(1..=24).contains(&n) {
+                    return None;
+                }
+                // VK_F1 = 0x70
+                key = Some(0x70 + n - 1);
+            }
+        }
+    }
+    if modifiers == 0 {
+        return None;
+    }
+    Some((modifiers, key?))
+}
+
+/// 在单独的线程中注册的热键，按下时把对应的语言代码发给界面线程
+pub struct Hotkeys {
+    thread_id: u32,
+    events: Receiver<String>,
+}
+
+impl Hotkeys {
+    /// 注册 (修饰键, 虚拟键码, 组合键文本, 语言代码)，返回热键和注册失败（已被其他程序占用）的组合键
+    pub fn spawn(ctx: egui::Context, bindings: Vec<(u32, u32, String, String)>) -> (Self, Vec<String>) {
+        let (sender, events) = mpsc::channel();
+        let (ready, registered) = mpsc::sync_channel(1);
+        thread::spawn(move || {
+            let mut ids = Vec::new();
+            let mut failed = Vec::new();
+            for (idx, (modifiers, vk, keys, _)) in bindings.iter().enumerate() {
+                let id = idx as i32 + 1;
+                // SAFETY: 热键注册到当前线程的消息队列
+                if unsafe { RegisterHotKey(std::ptr::null_mut(), id, modifiers | MOD_NOREPEAT, *vk) } != 0 {
+                    ids.push(id);
+                } else {
+                    warn!("failed to register hotkey {}", keys);
+                    failed.push(keys.clone());
+                }
+            }
+            // SAFETY: 无参数
+            let _ = ready.send((unsafe { GetCurrentThreadId() }, failed));
+
+            // SAFETY: msg 为可写的输出结构，收到 WM_QUIT 时 GetMessageW 返回 0
+            unsafe {
+                let mut msg: MSG = std::mem::zeroed();
+                while GetMessageW(&mut msg, std::ptr::null_mut(), 0, 0) > 0 {
+                    if msg.message == WM_HOTKEY {
+                        if let Some((_, _, keys, lang)) = bindings.get(msg.wParam.wrapping_sub(1)) {
+                            info!("hotkey {} pressed", keys);
+                            let _ = sender.send(lang.clone());
+                            ctx.request_repaint();
+                        }
+                    }
+                }
+                for id in ids {
+                    UnregisterHotKey(std::ptr::null_mut(), id);
+                }
+            }
+        });
+        let (thread_id, failed) = registered.recv().unwrap_or_default();
+        (Hotkeys { thread_id, events }, failed)
+    }
+
+    /// 取出所有已按下的热键对应的语言代码
+    pub fn events(&self) -> Vec<String> {
+        self.events.try_iter().collect()
+    }
+}
+
+impl Drop for Hotkeys {
+    fn drop(&mut self) {
+        // SAFETY: 线程已结束时调用失败但无副作用
+        unsafe { PostThreadMessageW(self.thread_id, WM_QUIT, 0, 0) };
+    }
+}
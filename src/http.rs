@@ -0,0 +1,127 @@
+//! 通过 WinHTTP 发送 HTTPS GET 请求，使用系统的代理设置，重定向由 WinHTTP 自动处理
+
+use std::ffi::c_void;
+use std::io;
+use std::iter;
+
+use windows_sys::Win32::Networking::WinHttp::{
+    WinHttpCloseHandle, WinHttpConnect, WinHttpOpen, WinHttpOpenRequest, WinHttpQueryHeaders, WinHttpReadData,
+    WinHttpReceiveResponse, WinHttpSendRequest, WinHttpSetTimeouts, INTERNET_DEFAULT_HTTPS_PORT,
+    WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY, WINHTTP_FLAG_SECURE, WINHTTP_QUERY_CONTENT_LENGTH, WINHTTP_QUERY_FLAG_NUMBER,
+    WINHTTP_QUERY_STATUS_CODE,
+};
+
+use crate::copy::Progress;
+
+/// 请求使用的 User-Agent，GitHub API 拒绝没有 User-Agent 的请求
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+/// 解析、连接、发送和接收的超时（毫秒）
+const TIMEOUT_MS: i32 = 15_000;
+
+/// 结束时自动关闭的 WinHTTP 句柄
+struct Handle(*mut c_void);
+
+impl Handle {
+    fn new(handle: *mut c_void) -> io::Result<Self> {
+        if handle.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Handle(handle))
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        // SAFETY: 句柄由 WinHTTP 创建且只关闭一次
+        unsafe { WinHttpCloseHandle(self.0) };
+    }
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(iter::once(0)).collect()
+}
+
+fn check(ok: i32) -> io::Result<()> {
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// 读取数值形式的响应头，没有该响应头时返回 None
+///
+/// # Safety
+/// request 必须是已收到响应的请求句柄
+unsafe fn query_number(request: *mut c_void, info: u32) -> Option<u32> {
+    let mut value = 0u32;
+    let mut len = size_of::<u32>() as u32;
+    // SAFETY: value 和 len 是可写的输出参数，长度与 value 一致
+    let ok = unsafe {
+        WinHttpQueryHeaders(
+            request,
+            info | WINHTTP_QUERY_FLAG_NUMBER,
+            std::ptr::null(),
+            &mut value as *mut u32 as *mut c_void,
+            &mut len,
+            std::ptr::null_mut(),
+        )
+    };
+    (ok != 0).then_some(value)
+}
+
+/// 下载 url 的全部内容，headers 为附加的请求头（每行以 \r\n 分隔），状态码不是 200 时返回错误；
+/// 提供 progress 时按响应的长度报告下载进度
+pub fn get(url: &str, headers: &str, progress: Option<&Progress>) -> io::Result<Vec<u8>> {
+    let rest = url
+        .strip_prefix("https://")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, url.to_string()))?;
+    let (host, path) = match rest.find('/') {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, "/"),
+    };
+    let (agent, host, path, headers) = (wide(USER_AGENT), wide(host), wide(path), wide(headers));
+    let (verb, null) = (wide("GET"), std::ptr::null());
+
+    // SAFETY: 字符串以 0 结尾且在调用期间有效，句柄由 Handle 管理，缓冲区大小与传入的长度一致
+    unsafe {
+        let session = Handle::new(WinHttpOpen(agent.as_ptr(), WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY, null, null, 0))?;
+        check(WinHttpSetTimeouts(session.0, TIMEOUT_MS, TIMEOUT_MS, TIMEOUT_MS, TIMEOUT_MS))?;
+        let connect = Handle::new(WinHttpConnect(session.0, host.as_ptr(), INTERNET_DEFAULT_HTTPS_PORT, 0))?;
+        let request = Handle::new(WinHttpOpenRequest(
+            connect.0,
+            verb.as_ptr(),
+            path.as_ptr(),
+            null,
+            null,
+            std::ptr::null(),
+            WINHTTP_FLAG_SECURE,
+        ))?;
+        // 长度为 u32::MAX 时请求头按以 0 结尾的字符串处理
+        check(WinHttpSendRequest(request.0, headers.as_ptr(), u32::MAX, std::ptr::null(), 0, 0, 0))?;
+        check(WinHttpReceiveResponse(request.0, std::ptr::null_mut()))?;
+
+        let status = query_number(request.0, WINHTTP_QUERY_STATUS_CODE).ok_or_else(io::Error::last_os_error)?;
+        if status != 200 {
+            return Err(io::Error::other(format!("HTTP {}", status)));
+        }
+        if let (Some(progress), Some(length)) = (progress, query_number(request.0, WINHTTP_QUERY_CONTENT_LENGTH)) {
+            progress.update(|s| s.total_bytes = length as u64);
+        }
+
+        let mut body = Vec::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let mut read = 0u32;
+            check(WinHttpReadData(request.0, buffer.as_mut_ptr() as *mut c_void, buffer.len() as u32, &mut read))?;
+            if read == 0 {
+                break;
+            }
+            body.extend_from_slice(&buffer[..read as usize]);
+            if let Some(progress) = progress {
+                progress.check_cancelled()?;
+                progress.update(|s| s.done_bytes += read as u64);
+            }
+        }
+        Ok(body)
+    }
+}
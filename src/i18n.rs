@@ -0,0 +1,751 @@
+//! 界面文本的多语言支持
+//!
+//! 所有界面文本与状态消息都通过 `t!` 宏按键名查表，参数按顺序替换文本中的 `{}`。
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use windows_sys::Win32::Globalization::GetUserDefaultUILanguage;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UiLanguage {
+    #[default]
+    Zh,
+    En,
+    Ja,
+}
+
+impl UiLanguage {
+    pub const ALL: [UiLanguage; 3] = [UiLanguage::Zh, UiLanguage::En, UiLanguage::Ja];
+
+    /// 语言自身的名称，不随界面语言变化
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            UiLanguage::Zh => "中文",
+            UiLanguage::En => "English",
+            UiLanguage::Ja => "日本語",
+        }
+    }
+
+    /// 根据系统界面语言选择默认语言，非中文/日文系统使用英文
+    pub fn from_system() -> Self {
+        // SAFETY: 无参数的查询函数
+        let lang_id = unsafe { GetUserDefaultUILanguage() };
+        match lang_id & 0x3ff {
+            0x04 => UiLanguage::Zh,
+            0x11 => UiLanguage::Ja,
+            _ => UiLanguage::En,
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(lang: UiLanguage) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn current() -> UiLanguage {
+    UiLanguage::ALL[CURRENT.load(Ordering::Relaxed) as usize]
+}
+
+/// 按当前界面语言查找文本，缺少翻译时回退到中文，键不存在时返回键名
+pub fn lookup(key: &'static str) -> &'static str {
+    static TABLE: OnceLock<HashMap<&'static str, [&'static str; 3]>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| STRINGS.iter().copied().collect());
+    match table.get(key) {
+        Some(texts) => {
+            let text = texts[current() as usize];
+            if text.is_empty() { texts[0] } else { text }
+        }
+        None => key,
+    }
+}
+
+/// 依次用参数替换文本中的 `{}`
+pub fn format(key: &'static str, args: &[&dyn Display]) -> String {
+    let template = lookup(key);
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        result.push_str(&rest[..pos]);
+        if let Some(arg) = args.next() {
+            result.push_str(&arg.to_string());
+        }
+        rest = &rest[pos + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// `t!("key")` 返回 `&'static str`，`t!("key", a, b)` 返回替换参数后的 `String`
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::lookup($key)
+    };
+    ($key:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+pub(crate) use t;
+
+/// 键名 -> [中文, English, 日本語]
+const STRINGS: &[(&str, [&str; 3])] = &[
+    ("ui.title", ["战地6 语音切换工具", "Battlefield 6 Voice Switcher", "バトルフィールド6 音声切り替えツール"]),
+    ("ui.game", ["游戏:", "Game:", "ゲーム:"]),
+    ("ui.steam_connected", ["[OK] Steam 已连接", "[OK] Steam connected", "[OK] Steam に接続済み"]),
+    ("ui.game_build", ["| 游戏版本: {}", "| Game build: {}", "| ゲームバージョン: {}"]),
+    ("ui.steam_not_found", ["[!] 未检测到 Steam/游戏", "[!] Steam/game not detected", "[!] Steam/ゲームが見つかりません"]),
+    ("ui.redetect", ["重新检测", "Detect again", "再検出"]),
+    ("ui.source_linked", ["已链接备份", "linked backup", "リンクされたバックアップ"]),
+    ("ui.source_original", ["游戏原始文件", "original game files", "ゲームのオリジナルファイル"]),
+    ("ui.active_voice", ["当前生效语音: {} ({})", "Active voice: {} ({})", "現在の音声: {} ({})"]),
+    ("ui.active_voice_none", ["当前生效语音: 未检测到语音文件", "Active voice: no voice files detected", "現在の音声: 音声ファイルが見つかりません"]),
+    ("ui.voice_state_details", ["语音文件状态详情", "Voice file details", "音声ファイルの詳細"]),
+    ("ui.voice_state_empty", ["游戏目录中没有找到任何语音文件", "No voice files found in the game folder", "ゲームフォルダーに音声ファイルがありません"]),
+    ("ui.voice_state_lang", ["{} ({} 个toc文件)", "{} ({} toc files)", "{} (toc ファイル {} 個)"]),
+    ("ui.state_directory", ["普通目录", "Regular folder", "通常のフォルダー"]),
+    ("ui.state_junction", ["链接 -> {}", "Link -> {}", "リンク -> {}"]),
+    ("ui.state_junction_broken", ["链接失效 -> {}", "Broken link -> {}", "リンク切れ -> {}"]),
+    ("ui.state_junction_unknown", ["链接 (无法解析目标)", "Link (target unknown)", "リンク (リンク先不明)"]),
+    ("ui.state_restored", ["已恢复 ({})", "Restored ({})", "復元済み ({})"]),
+    ("ui.state_missing", ["缺失", "Missing", "見つかりません"]),
+    ("ui.refresh_state", ["刷新状态", "Refresh status", "状態を更新"]),
+    ("ui.next_step", ["下一步: {}", "Next: {}", "次の手順: {}"]),
+    ("ui.next_prepare", ["在 Steam 中将 {} 切换到 {} 并等待下载完成", "Switch {} to {} in Steam and wait for the download to finish", "Steam で {} を {} に切り替えてダウンロード完了まで待つ"]),
+    ("ui.next_source", ["在步骤3中选择游戏的语音文件夹", "Choose the game's voice folder in step 3", "手順3でゲームの音声フォルダーを選択"]),
+    ("ui.next_backup", ["在步骤3中备份 {} 语音", "Back up the {} voice in step 3", "手順3で {} の音声をバックアップ"]),
+    ("ui.next_restore", ["切换文本语言后，在步骤4中恢复 {} 语音", "After switching the text language, restore the {} voice in step 4", "テキスト言語を切り替えた後、手順4で {} の音声を復元"]),
+    ("ui.next_launch", ["在步骤5中写入 {} 的启动参数", "Write the {} launch option in step 5", "手順5で {} の起動オプションを書き込む"]),
+    ("ui.next_launch_mismatch", ["[!] 启动选项中的语音语言不是 {}，请在步骤5中重新写入", "[!] The launch option sets a voice other than {}; write it again in step 5", "[!] 起動オプションの音声が {} ではありません。手順5で書き込み直してください"]),
+    ("ui.wizard", ["向导模式", "Wizard", "ウィザード"]),
+    ("ui.wizard_hint", ["逐页引导完成切换语音的步骤，关闭后显示包含全部功能的经典布局", "Walk through switching the voice page by page; turn off for the classic layout with every feature", "音声の切り替え手順をページごとに案内します。オフにすると全機能を表示する従来のレイアウトになります"]),
+    ("ui.wizard_page", ["{}/{}: {}", "{}/{}: {}", "{}/{}: {}"]),
+    ("ui.wizard_detect", ["检测游戏", "Detect the game", "ゲームの検出"]),
+    ("ui.wizard_language", ["选择语音语言", "Choose the voice language", "音声言語の選択"]),
+    ("ui.wizard_backup", ["备份语音", "Back up the voice", "音声のバックアップ"]),
+    ("ui.wizard_steam_language", ["切换 Steam 语言", "Switch the Steam language", "Steam の言語を切り替え"]),
+    ("ui.wizard_restore", ["恢复语音", "Restore the voice", "音声の復元"]),
+    ("ui.wizard_launch_option", ["设置启动选项", "Set the launch option", "起動オプションの設定"]),
+    ("ui.wizard_detect_hint", ["确认下面是 {} 的语音文件夹，未检测到时请手动选择：", "Check that this is the voice folder of {}, or choose it if it was not detected:", "これが {} の音声フォルダーであることを確認してください。検出されない場合は選択してください："]),
+    ("ui.wizard_need_source", ["请先选择存在的语音文件夹", "Choose an existing voice folder first", "まず存在する音声フォルダーを選択してください"]),
+    ("ui.wizard_language_hint", ["选择想在游戏中听到的语音语言：", "Choose the voice language you want to hear in game:", "ゲーム内で使いたい音声言語を選択してください："]),
+    ("ui.wizard_backup_hint", ["游戏目录中已有 {} 语音，备份后切换 Steam 语言也不会丢失：", "The {} voice is in the game folder; back it up so it survives switching the Steam language:", "ゲームフォルダーに {} の音声があります。Steam の言語を切り替えても失われないようバックアップしてください："]),
+    ("ui.wizard_backup_done", ["已有 {} 语音的备份", "A backup of the {} voice exists", "{} の音声のバックアップがあります"]),
+    ("ui.wizard_need_backup", ["请先备份所选语言的语音", "Back up the selected voice first", "まず選択した音声をバックアップしてください"]),
+    ("ui.wizard_steam_language_hint", ["在 Steam 中将 {} 切换回想使用的文本语言并等待下载完成：", "In Steam, switch {} back to the text language you want and wait for the download:", "Steam で {} を使いたいテキスト言語に戻し、ダウンロード完了まで待ってください："]),
+    ("ui.wizard_text_language", ["Steam 当前的游戏语言: {}", "Current Steam game language: {}", "現在の Steam のゲーム言語: {}"]),
+    ("ui.wizard_restore_hint", ["将 {} 语音恢复到游戏目录：", "Restore the {} voice into the game folder:", "{} の音声をゲームフォルダーに復元します："]),
+    ("ui.wizard_restore_button", ["恢复 {} 语音", "Restore {} voice", "{} の音声を復元"]),
+    ("ui.wizard_finished", ["全部步骤已完成，启动游戏即可使用所选语音", "All steps are done; launch the game to use the selected voice", "すべての手順が完了しました。ゲームを起動すると選択した音声が使われます"]),
+    ("ui.wizard_back", ["上一步", "Back", "戻る"]),
+    ("ui.wizard_next", ["下一步", "Next", "次へ"]),
+    ("ui.step1", ["步骤1: 准备工作", "Step 1: Preparation", "手順1: 準備"]),
+    ("ui.step1_hint", ["请先在 Steam 中将 {} 切换到您想要使用的语音语言：", "First switch {} in Steam to the voice language you want to use:", "まず Steam で {} を使いたい音声言語に切り替えてください："]),
+    ("ui.step1_path", ["右键 {} -> 属性 -> 语言 -> 选择语言并等待下载完成", "Right-click {} -> Properties -> Language -> pick the language and wait for the download", "{} を右クリック -> プロパティ -> 言語 -> 言語を選んでダウンロード完了まで待つ"]),
+    ("ui.step2", ["步骤2: 选择要使用的语音语言", "Step 2: Choose the voice language", "手順2: 使用する音声言語を選択"]),
+    ("ui.lang_installed_hint", ["游戏目录中检测到该语言的原始语音文件", "Original voice files for this language were found in the game folder", "ゲームフォルダーにこの言語の元の音声ファイルがあります"]),
+    ("ui.lang_mismatch_warning", ["⚠ 游戏目录中安装的语音为 {}，与所选语言不一致，备份时将找不到语音文件", "⚠ The game folder has {} voice files installed, which doesn't match the selected language; a backup will find no voice files", "⚠ ゲームフォルダーにインストールされている音声は {} で、選択した言語と一致しません。バックアップ時に音声ファイルが見つかりません"]),
+    ("ui.step3", ["步骤3: 选择语音文件夹", "Step 3: Choose the voice folder", "手順3: 音声フォルダーを選択"]),
+    ("ui.step3_path", ["路径: 游戏安装目录\\{}", "Path: game install folder\\{}", "パス: ゲームのインストールフォルダー\\{}"]),
+    ("ui.browse", ["浏览", "Browse", "参照"]),
+    ("ui.copy_workers", ["复制线程数:", "Copy threads:", "コピースレッド数:"]),
+    ("ui.copy_workers_hint", ["同时复制的文件数，机械硬盘建议设为 1", "Number of files copied at once; use 1 for hard disk drives", "同時にコピーするファイル数。HDD では 1 を推奨します"]),
+    ("ui.backup_mode", ["备份方式:", "Backup mode:", "バックアップ方式:"]),
+    ("ui.set_default", ["设为默认", "Set as default", "既定に設定"]),
+    ("ui.move_mode_hint", ["移动语音文件到备份目录，并在游戏目录中创建链接，无需额外空间", "Moves the voice files into the backup folder and links them back, no extra space needed", "音声ファイルをバックアップフォルダーへ移動し、ゲームフォルダーにリンクを作成します（追加の容量は不要）"]),
+    ("ui.resume_backup", ["继续备份", "Resume backup", "バックアップを再開"]),
+    ("ui.discard_partial", ["放弃未完成的备份", "Discard partial backup", "未完了のバックアップを破棄"]),
+    ("ui.backup", ["备份语音文件", "Back up voice files", "音声ファイルをバックアップ"]),
+    ("ui.delete_voice", ["删除游戏语音", "Delete game voice files", "ゲーム音声を削除"]),
+    ("ui.step4", ["步骤4: 恢复语音文件", "Step 4: Restore voice files", "手順4: 音声ファイルを復元"]),
+    ("ui.step4_hint", ["切换到想使用的文本语言后，选择要恢复的语音：", "After switching to the text language you want, choose the voice to restore:", "使いたいテキスト言語に切り替えた後、復元する音声を選択してください："]),
+    ("ui.version_mismatch", ["[!] 版本不匹配: 备份({}) != 当前({})", "[!] Version mismatch: backup ({}) != current ({})", "[!] バージョン不一致: バックアップ({}) != 現在({})"]),
+    ("ui.version_mismatch_hint", ["请先删除游戏语音，再重新执行所有步骤", "Delete the game voice files first, then redo all steps", "ゲーム音声を削除してから、すべての手順をやり直してください"]),
+    ("ui.restore_mode", ["恢复方式:", "Restore mode:", "復元方式:"]),
+    ("ui.select_voice", ["选择语音:", "Voice:", "音声:"]),
+    ("ui.no_backup", ["无备份", "No backups", "バックアップなし"]),
+    ("ui.restore", ["恢复语音", "Restore voice", "音声を復元"]),
+    ("ui.delete_backup", ["删除备份", "Delete backup", "バックアップを削除"]),
+    ("ui.refresh", ["刷新", "Refresh", "更新"]),
+    ("ui.step5", ["步骤5: Steam 启动项", "Step 5: Steam launch options", "手順5: Steam 起動オプション"]),
+    ("ui.step5_hint", ["右键 {} -> 属性 -> 通用 -> 启动选项，添加以下参数：", "Right-click {} -> Properties -> General -> Launch options, and add:", "{} を右クリック -> プロパティ -> 一般 -> 起動オプションに次を追加："]),
+    ("ui.copy_clipboard", ["复制到剪贴板", "Copy to clipboard", "クリップボードにコピー"]),
+    ("ui.write_launch_option", ["写入 Steam 启动项", "Write to Steam", "Steam に書き込む"]),
+    ("ui.remove_launch_option", ["移除启动项", "Remove launch option", "起動オプションを削除"]),
+    ("ui.exit_steam_first", ["需先退出 Steam", "Steam must be closed", "Steam を終了しておく必要があります"]),
+    ("ui.launch", ["启动 {}", "Launch {}", "{} を起動"]),
+    ("ui.launch_hint", ["不修改启动项，直接以所选语音启动", "Starts with the selected voice without changing launch options", "起動オプションを変更せず、選択した音声で起動します"]),
+    ("ui.ui_language", ["界面语言:", "Language:", "表示言語:"]),
+    ("ui.ui_language_auto", ["跟随系统", "System default", "システムに従う"]),
+
+    ("ui.progress", ["{} · {}/s · 剩余 {}", "{} · {}/s · {} left", "{} · {}/s · 残り {}"]),
+    ("ui.preview", ["操作前预览", "Preview before running", "実行前にプレビュー"]),
+    ("ui.preview_hint", ["备份、恢复和删除前列出将受影响的文件，确认后再执行", "List the affected files before backing up, restoring or deleting, and run only after confirmation", "バックアップ・復元・削除の前に影響するファイルを一覧表示し、確認後に実行します"]),
+    ("ui.preview_title", ["预览: {}", "Preview: {}", "プレビュー: {}"]),
+    ("ui.preview_summary", ["共 {} 项，涉及 {}", "{} items, {} in total", "{} 件、合計 {}"]),
+    ("ui.preview_empty", ["没有需要处理的文件", "Nothing to do", "処理するファイルはありません"]),
+    ("ui.confirm_delete_title", ["确认删除", "Confirm deletion", "削除の確認"]),
+    ("ui.confirm_delete_hint", ["以下文件将被删除，此操作无法撤销", "The following will be removed. This cannot be undone.", "以下のファイルが削除されます。この操作は元に戻せません"]),
+    ("ui.confirm_recycle_hint", ["以下文件将被移到回收站", "The following will be moved to the Recycle Bin.", "以下のファイルがごみ箱に移動されます"]),
+    ("ui.snapshot_note", ["该语言还没有备份，删除前会先把原始文件保存到 originals 文件夹", "This language has no backup yet; the original files will be saved to the originals folder before deleting", "この言語にはまだバックアップがありません。削除前に元のファイルを originals フォルダーに保存します"]),
+    ("ui.snapshot_skipped", ["[!] 不保存原始文件，删除后只能通过 Steam 验证文件恢复", "[!] Original files will not be saved; only a Steam file verification can bring them back", "[!] 元のファイルは保存されません。復元するには Steam でファイルを検証する必要があります"]),
+    ("ui.skip_snapshot", ["跳过原始文件快照（不推荐）", "Skip the original files snapshot (not recommended)", "元ファイルのスナップショットをスキップ（非推奨）"]),
+    ("ui.confirm", ["确认执行", "Confirm", "実行"]),
+    ("ui.close", ["关闭", "Close", "閉じる"]),
+    ("ui.cancel", ["取消", "Cancel", "キャンセル"]),
+    ("ui.permanent_delete", ["永久删除", "Delete permanently", "完全に削除"]),
+    ("ui.permanent_delete_hint", ["删除时不移到回收站，无法恢复", "Skip the Recycle Bin when deleting; deleted files cannot be recovered", "ごみ箱に移さずに削除します。元に戻せません"]),
+    ("diff.added", ["新增", "Added", "追加"]),
+    ("diff.removed", ["删除", "Removed", "削除"]),
+    ("diff.modified", ["修改", "Modified", "変更"]),
+    ("mode.version_block", ["阻止", "Block", "ブロック"]),
+    ("mode.version_block_hint", ["阻止恢复，勾选“仍要恢复”后才可恢复", "Block the restore unless you tick \"restore anyway\"", "「復元する」にチェックを入れない限り復元しません"]),
+    ("mode.version_warn", ["警告并允许", "Warn and allow", "警告して許可"]),
+    ("mode.version_warn_hint", ["只显示警告，照常恢复", "Show a warning and restore as usual", "警告を表示して通常どおり復元します"]),
+    ("mode.version_smart", ["智能", "Smart", "スマート"]),
+    ("mode.version_smart_hint", ["游戏目录中的 .toc 文件与备份时一致（语音数据未变化）时允许恢复，否则阻止", "Allow the restore if the game's .toc files match the backup (the voice data did not change), otherwise block", "ゲームの .toc ファイルがバックアップと一致する（音声データが変わっていない）場合は復元し、それ以外はブロックします"]),
+    ("probe.source_launch_option", ["启动选项", "launch option", "起動オプション"]),
+    ("probe.source_user_cfg", ["user.cfg", "user.cfg", "user.cfg"]),
+    ("probe.source_steam", ["Steam 语言", "Steam language", "Steam の言語"]),
+    ("probe.source_only_installed", ["唯一安装的语音", "only installed voice", "唯一インストールされている音声"]),
+    ("probe.not_installed", ["[!] 游戏目录中没有可用的{}语音文件", "[!] No usable {} voice files in the game folder", "[!] ゲームフォルダーに使用できる{}の音声ファイルがありません"]),
+    ("probe.broken", ["[!] {}语音有失效的链接或缺失的文件夹", "[!] {} voice has broken links or missing folders", "[!] {}の音声にリンク切れまたは欠落したフォルダーがあります"]),
+    ("probe.missing_toc", ["[!] {}语音缺少 .toc 文件，游戏无法加载", "[!] {} voice is missing its .toc files; the game cannot load it", "[!] {}の音声に .toc ファイルがないため、ゲームが読み込めません"]),
+    ("probe.conflict", ["[!] 启动选项为{}，user.cfg 为{}，两者不一致", "[!] The launch option says {} but user.cfg says {}", "[!] 起動オプションは{}、user.cfg は{}で一致しません"]),
+    ("probe.unknown_value", ["[!] 无法识别的语音参数值: {}", "[!] Unrecognized voice parameter value: {}", "[!] 認識できない音声パラメーターの値: {}"]),
+    ("plan.cleanup_unbacked", ["[!] 以下语言没有备份，删除后需要在 Steam 中验证游戏文件才能重新下载: {}", "[!] These languages have no backup; Steam has to verify the game files to download them again: {}", "[!] 次の言語はバックアップがありません。再ダウンロードには Steam でゲームファイルの検証が必要です: {}"]),
+    ("mode.engine_native", ["内置（默认）", "Built-in (default)", "内蔵（既定）"]),
+    ("mode.engine_native_hint", ["逐个文件复制，可从旧备份创建硬链接，只复制变化的文件", "Copies file by file and can hard-link unchanged files from the previous backup", "ファイルごとにコピーし、以前のバックアップの未変更ファイルはハードリンクにできます"]),
+    ("mode.engine_robocopy", ["robocopy", "robocopy", "robocopy"]),
+    ("mode.engine_robocopy_hint", ["使用 robocopy 的多线程无缓冲复制，机械硬盘上复制大文件更快；不支持增量备份：不从旧备份创建硬链接，也不记录复制日志，继续中断的备份时由 robocopy 跳过大小和时间相同的文件", "Uses robocopy's multithreaded unbuffered copy, faster for large files on hard disk drives; incremental backup is disabled: nothing is hard-linked from the previous backup and no copy log is kept, so resuming relies on robocopy skipping files with the same size and time", "robocopy のマルチスレッド非バッファーコピーを使用します。HDD 上の大きなファイルで高速です。増分バックアップは無効です：以前のバックアップからハードリンクせず、コピーログも記録しません。中断したバックアップの再開時は robocopy がサイズと時刻が同じファイルをスキップします"]),
+    ("plan.robocopy_no_reuse", ["[!] 使用 robocopy 复制：增量备份已停用，旧备份中未变化的文件也会重新复制", "[!] Copying with robocopy: incremental backup is disabled, unchanged files in the previous backup are copied again", "[!] robocopy でコピーします：増分バックアップは無効で、以前のバックアップの未変更ファイルも再度コピーされます"]),
+    ("plan.copy", ["复制", "Copy", "コピー"]),
+    ("plan.move", ["移动", "Move", "移動"]),
+    ("plan.unlink", ["删除链接", "Remove link", "リンクを削除"]),
+    ("plan.recycle", ["移到回收站", "Move to Recycle Bin", "ごみ箱へ移動"]),
+    ("plan.extract", ["解压", "Extract", "展開"]),
+    ("plan.matched_patterns", ["匹配的模式: {}", "Matched patterns: {}", "一致したパターン: {}"]),
+    ("plan.delete", ["删除", "Delete", "削除"]),
+    ("ui.backup_in_use", ["[!] 游戏目录中有 {} 个文件夹链接到此备份", "[!] {} game folders link to this backup", "[!] ゲームフォルダーの {} 個のフォルダーがこのバックアップにリンクしています"]),
+    ("ui.materialize", ["转换为实际文件", "Convert to real files", "実ファイルに変換"]),
+    ("ui.materialize_hint", ["复制备份内容替换链接，之后删除备份不会影响游戏", "Copy the backup contents in place of the links so deleting the backup no longer affects the game", "リンクをバックアップの内容のコピーに置き換え、バックアップを削除してもゲームに影響しないようにします"]),
+    ("ui.materialize_all", ["将所有链接转换为实际文件", "Convert all links to real files", "すべてのリンクを実ファイルに変換"]),
+    ("ui.materialize_all_hint", ["把游戏目录中的 Junction 替换为备份内容的副本，之后可以删除 voice_backups 而保留当前语音", "Replace the junctions in the game folder with copies of the backup contents, so voice_backups can be removed while keeping the current voice", "ゲームフォルダー内のジャンクションをバックアップ内容のコピーに置き換えます。voice_backups を削除しても現在の音声が残ります"]),
+    ("ui.revert_all", ["撤销所有修改", "Revert all changes", "すべての変更を元に戻す"]),
+    ("ui.revert_all_hint", ["删除本工具在游戏目录中创建的所有链接、文件夹和 .toc 文件，恢复到 Steam 安装时的状态", "Remove every link, folder and .toc file this tool created in the game folder to get back to the Steam install", "このツールがゲームフォルダーに作成したリンク・フォルダー・.toc ファイルをすべて削除し、Steam でインストールした状態に戻します"]),
+    ("ui.validate_after_revert", ["完成后通过 Steam 验证游戏文件完整性", "Verify game files with Steam afterwards", "完了後に Steam でゲームファイルを検証する"]),
+    ("ui.migration_banner", ["[!] 游戏已更新，{} 个备份的版本与当前不一致", "[!] The game was updated; {} backups no longer match the current build", "[!] ゲームが更新され、{} 件のバックアップが現在のバージョンと一致しません"]),
+    ("ui.migration_open", ["迁移助手", "Migration assistant", "移行アシスタント"]),
+    ("ui.migration_title", ["备份迁移助手", "Backup migration assistant", "バックアップ移行アシスタント"]),
+    ("ui.migration_hint", ["当前版本: {}。依次在 Steam 中把游戏语言切换为需要更新的语言，下载完成后点击「更新备份」，未变化的文件不会重复复制。", "Current build: {}. For each language, switch the game language in Steam, wait for the download to finish, then click \"Update backup\". Unchanged files are not copied again.", "現在のバージョン: {}。言語ごとに Steam でゲームの言語を切り替え、ダウンロード完了後に「バックアップを更新」をクリックしてください。変更のないファイルは再コピーされません。"]),
+    ("ui.migration_done", ["✔ 已是最新", "✔ Up to date", "✔ 最新"]),
+    ("ui.migration_refresh", ["更新备份", "Update backup", "バックアップを更新"]),
+    ("ui.migration_download", ["等待下载: 在 Steam 中切换为{}", "Waiting for download: switch Steam to {}", "ダウンロード待ち: Steam で{}に切り替えてください"]),
+    ("ui.dedup", ["合并重复文件", "Deduplicate", "重複ファイルを統合"]),
+    ("ui.dedup_hint", ["查找所有备份中内容相同的文件，替换为硬链接以节省空间", "Find identical files across all backups and replace them with hardlinks to save space", "すべてのバックアップから同じ内容のファイルを探し、ハードリンクに置き換えて容量を節約します"]),
+    ("ui.compress", ["压缩备份", "Compress backup", "バックアップを圧縮"]),
+    ("ui.decompress", ["解压备份", "Decompress backup", "バックアップを展開"]),
+    ("ui.compress_hint", ["将未链接到游戏目录的备份打包为 zip 以节省空间，恢复时会自动解压", "Pack a backup that is not linked into the game folder into a zip to save space; it is decompressed automatically when restoring", "ゲームフォルダーにリンクされていないバックアップを zip に圧縮して容量を節約します。復元時に自動で展開されます"]),
+    ("ui.compression_level", ["压缩级别:", "Compression level:", "圧縮レベル:"]),
+    ("ui.compressed_tag", ["[已压缩]", "[compressed]", "[圧縮済み]"]),
+    ("ui.export_backup", ["导出备份", "Export backup", "バックアップをエクスポート"]),
+    ("ui.import_backup", ["导入备份", "Import backup", "バックアップをインポート"]),
+    ("ui.backup_label", ["名称:", "Name:", "名前:"]),
+    ("ui.backup_note", ["备注:", "Note:", "メモ:"]),
+    ("ui.edit_note", ["编辑名称和备注", "Edit name and note", "名前とメモを編集"]),
+    ("ui.save", ["保存", "Save", "保存"]),
+    ("ui.column_language", ["语言", "Language", "言語"]),
+    ("ui.column_build", ["版本", "Build", "バージョン"]),
+    ("ui.column_date", ["日期", "Date", "日付"]),
+    ("ui.column_size", ["大小", "Size", "サイズ"]),
+    ("ui.column_note", ["备注", "Note", "メモ"]),
+    ("ui.column_in_use", ["使用中", "In use", "使用中"]),
+    ("ui.in_use_count", ["{} 个链接", "{} links", "リンク {} 個"]),
+    ("ui.verify", ["校验", "Verify", "検証"]),
+    ("ui.open_folder", ["打开文件夹", "Open folder", "フォルダーを開く"]),
+    ("ui.open_game_folder", ["打开游戏文件夹", "Open game folder", "ゲームフォルダーを開く"]),
+    ("ui.open_backup_folder", ["打开备份文件夹", "Open backup folder", "バックアップフォルダーを開く"]),
+    ("ui.size_calculating", ["正在计算大小...", "Calculating size...", "サイズを計算しています..."]),
+    ("ui.backup_size", ["共 {}", "Total {}", "合計 {}"]),
+    ("ui.scanning", ["正在扫描游戏目录... 已扫描 {} 个文件夹", "Scanning the game folder... {} folders scanned", "ゲームフォルダーをスキャンしています... {} 個のフォルダーをスキャン済み"]),
+    ("ui.elevation_title", ["需要管理员权限", "Administrator rights required", "管理者権限が必要です"]),
+    ("ui.elevation_hint", ["游戏目录或备份目录拒绝写入，通常是因为游戏安装在 Program Files 等受保护的目录中。以管理员身份重新启动后即可修改这些文件。", "Writing to the game or backup folder was denied, usually because the game is installed in a protected folder such as Program Files. Restart as administrator to modify these files.", "ゲームまたはバックアップフォルダーへの書き込みが拒否されました。通常、ゲームが Program Files などの保護されたフォルダーにインストールされていることが原因です。管理者として再起動すると変更できます。"]),
+    ("ui.elevation_relaunch", ["以管理员身份重新启动", "Restart as administrator", "管理者として再起動"]),
+    ("ui.notifications", ["完成通知", "Notifications", "完了通知"]),
+    ("ui.notifications_hint", ["窗口不在前台时，备份、恢复等操作完成或失败后显示系统通知", "Show a system notification when a backup, restore or other operation finishes or fails while the window is in the background", "ウィンドウがバックグラウンドにあるとき、バックアップや復元などの完了・失敗をシステム通知で表示します"]),
+    ("ui.minimize_to_tray", ["最小化到托盘", "Minimize to tray", "トレイに最小化"]),
+    ("ui.minimize_to_tray_hint", ["最小化时隐藏到系统托盘，右键托盘图标可快速切换语音", "Hide in the system tray when minimized; right-click the tray icon to switch voices quickly", "最小化するとシステムトレイに隠れます。トレイアイコンを右クリックすると音声をすぐに切り替えられます"]),
+    ("ui.tray_switch", ["切换到 {}", "Switch to {}", "{} に切り替え"]),
+    ("ui.tray_show", ["显示窗口", "Show window", "ウィンドウを表示"]),
+    ("ui.tray_exit", ["退出", "Exit", "終了"]),
+    ("ui.profiles", ["方案", "Profiles", "プロファイル"]),
+    ("ui.profile_active", ["当前方案: {}", "Active profile: {}", "現在のプロファイル: {}"]),
+    ("ui.profile_none", ["当前设置与所有方案都不符", "The current setup matches no profile", "現在の設定はどのプロファイルにも一致しません"]),
+    ("ui.profile_detail", ["语音: {}，Steam 游戏语言: {}", "Voice: {}, Steam game language: {}", "音声: {}、Steam のゲーム言語: {}"]),
+    ("ui.profile_apply", ["应用", "Apply", "適用"]),
+    ("ui.profile_apply_hint", ["删除当前语音、恢复方案的语音并写入启动参数", "Delete the current voice, restore the profile's voice and write the launch option", "現在の音声を削除し、プロファイルの音声を復元して起動オプションを書き込みます"]),
+    ("ui.profile_delete", ["删除方案", "Delete profile", "プロファイルを削除"]),
+    ("ui.profile_name", ["方案名称", "Profile name", "プロファイル名"]),
+    ("ui.profile_save", ["保存为方案", "Save as profile", "プロファイルとして保存"]),
+    ("ui.profile_save_hint", ["保存所选的语音语言和 Steam 中当前的游戏语言", "Save the selected voice language and the game language currently set in Steam", "選択中の音声言語と Steam で現在設定されているゲーム言語を保存します"]),
+    ("ui.backup_all", ["备份所有已安装语言", "Back up all installed languages", "インストール済みの全言語をバックアップ"]),
+    ("ui.backup_all_hint", ["依次备份游戏目录中检测到的每个语言", "Back up every language found in the game folder, one after another", "ゲームフォルダーで検出された各言語を順番にバックアップします"]),
+    ("ui.queue", ["操作队列 ({})", "Operation queue ({})", "操作キュー ({})"]),
+    ("ui.queue_pending", ["等待中", "Waiting", "待機中"]),
+    ("ui.queue_running", ["执行中", "Running", "実行中"]),
+    ("ui.queue_done", ["已完成", "Done", "完了"]),
+    ("ui.queue_failed", ["失败", "Failed", "失敗"]),
+    ("ui.queue_cancelled", ["已取消", "Cancelled", "キャンセル済み"]),
+    ("ui.queue_clear", ["清除已结束的项目", "Clear finished items", "終了した項目を消去"]),
+    ("ui.check_updates", ["检查更新", "Check for updates", "更新を確認"]),
+    ("ui.check_updates_hint", ["启动时从 GitHub 检查是否有新版本", "Check GitHub for a newer version at startup", "起動時に GitHub で新しいバージョンを確認します"]),
+    ("ui.update_banner", ["有新版本 {} 可用", "Version {} is available", "新しいバージョン {} があります"]),
+    ("ui.update_download", ["前往下载", "Download", "ダウンロード"]),
+    ("ui.update_dismiss", ["忽略", "Dismiss", "閉じる"]),
+    ("ui.update_now", ["立即更新", "Update now", "今すぐ更新"]),
+    ("ui.update_now_hint", ["下载并校验新版本，替换后自动重新启动", "Download and verify the new version, then restart into it", "新しいバージョンをダウンロードして検証し、置き換えて再起動します"]),
+    ("ui.ui_scale", ["界面缩放", "UI scale", "表示倍率"]),
+    ("ui.ui_scale_hint", ["在系统缩放的基础上放大或缩小界面，也可以用 Ctrl +/- 调整", "Scale the interface on top of the system scaling, also adjustable with Ctrl +/-", "システムの拡大率に加えて画面を拡大・縮小します。Ctrl +/- でも調整できます"]),
+    ("ui.theme", ["主题", "Theme", "テーマ"]),
+    ("ui.theme_system", ["跟随系统", "Follow system", "システムに合わせる"]),
+    ("ui.theme_dark", ["深色", "Dark", "ダーク"]),
+    ("ui.theme_light", ["浅色", "Light", "ライト"]),
+    ("ui.messages", ["消息记录 ({})", "Messages ({})", "メッセージ履歴 ({})"]),
+    ("ui.messages_clear", ["清除消息记录", "Clear messages", "履歴を消去"]),
+    ("ui.elevation_relaunch_hint", ["关闭本程序并以管理员身份重新打开", "Close this program and reopen it as administrator", "このプログラムを閉じて管理者として開き直します"]),
+    ("ui.queue_cancel_hint", ["取消该项；正在执行时会在当前文件完成后停止", "Cancel this item; a running item stops after the current file", "この項目を取り消します。実行中の場合は現在のファイルの後に停止します"]),
+    ("ui.queue_clear_hint", ["从列表中移除已完成、失败和已取消的项目", "Remove finished, failed and cancelled items from the list", "完了、失敗、取り消し済みの項目を一覧から削除します"]),
+    ("ui.sort_hint", ["点击按该列排序，再次点击切换升序和降序", "Click to sort by this column; click again to reverse", "クリックでこの列で並べ替え、もう一度クリックで逆順にします"]),
+    ("ui.restore_hint", ["按所选恢复方式把该备份恢复到游戏目录", "Restore this backup into the game folder using the selected restore mode", "選択した復元方法でこのバックアップをゲームフォルダーに復元します"]),
+    ("ui.verify_hint", ["校验备份中的文件是否完整、未被修改", "Check that the files in the backup are complete and unmodified", "バックアップ内のファイルが完全で変更されていないか検証します"]),
+    ("ui.delete_backup_hint", ["删除该备份，被游戏目录中的 Junction 使用时需先转换", "Delete this backup; convert any junctions in the game folder that use it first", "このバックアップを削除します。ゲームフォルダーのジャンクションが使用中の場合は先に変換してください"]),
+    ("ui.open_folder_hint", ["在资源管理器中打开该备份的文件夹", "Open this backup's folder in Explorer", "このバックアップのフォルダーをエクスプローラーで開きます"]),
+    ("ui.profile_delete_hint", ["删除所选方案，不影响备份和游戏文件", "Delete the selected profile; backups and game files are not affected", "選択したプロファイルを削除します。バックアップとゲームファイルには影響しません"]),
+    ("ui.set_default_hint", ["保存为下次启动时默认使用的方式", "Save as the default used next time the program starts", "次回起動時の既定値として保存します"]),
+    ("ui.backup_hint", ["把所选语言的语音文件备份到本程序的备份目录", "Back up the selected language's voice files into this program's backup folder", "選択した言語の音声ファイルをこのプログラムのバックアップフォルダーにバックアップします"]),
+    ("ui.discard_partial_hint", ["删除上次中断的备份留下的临时文件，下次重新开始备份", "Delete the files left by the interrupted backup and start over next time", "中断したバックアップの一時ファイルを削除し、次回は最初からバックアップします"]),
+    ("ui.delete_voice_hint", ["删除游戏目录中所选语言的语音文件，请先确认已有备份", "Delete the selected language's voice files from the game folder; make sure a backup exists first", "ゲームフォルダーから選択した言語の音声ファイルを削除します。先にバックアップがあることを確認してください"]),
+    ("ui.refresh_hint", ["重新读取备份列表和游戏目录中的语音状态", "Reload the backup list and the voice state of the game folder", "バックアップ一覧とゲームフォルダーの音声状態を読み込み直します"]),
+    ("ui.open_backup_folder_hint", ["在资源管理器中打开保存所有备份的文件夹", "Open the folder that holds all backups in Explorer", "すべてのバックアップを保存するフォルダーをエクスプローラーで開きます"]),
+    ("ui.edit_note_hint", ["修改所选备份的显示名称和备注", "Edit the display name and note of the selected backup", "選択したバックアップの表示名とメモを編集します"]),
+    ("ui.export_backup_hint", ["把所选备份导出为 zip 文件，可在其他电脑上导入", "Export the selected backup as a zip file that can be imported on another PC", "選択したバックアップを zip ファイルに書き出し、別の PC で読み込めるようにします"]),
+    ("ui.import_backup_hint", ["从导出的 zip 文件导入备份", "Import a backup from an exported zip file", "書き出した zip ファイルからバックアップを読み込みます"]),
+    ("ui.compression_level_hint", ["压缩备份和导出时的压缩级别，越大文件越小但越慢", "Compression level for compressed backups and exports; higher is smaller but slower", "バックアップの圧縮と書き出しの圧縮レベル。大きいほど小さくなりますが時間がかかります"]),
+    ("ui.browse_hint", ["手动选择游戏的语音文件夹", "Choose the game's voice folder manually", "ゲームの音声フォルダーを手動で選択します"]),
+    ("ui.open_game_folder_hint", ["在资源管理器中打开语音文件夹", "Open the voice folder in Explorer", "音声フォルダーをエクスプローラーで開きます"]),
+    ("ui.copy_clipboard_hint", ["复制启动参数，粘贴到 Steam 的启动选项中", "Copy the launch parameter to paste into Steam's launch options", "起動パラメーターをコピーして Steam の起動オプションに貼り付けます"]),
+    ("ui.launch_param_hint", ["游戏默认按 Steam 的游戏语言加载语音，miles_language 参数让游戏改为加载指定语言的语音", "The game loads the voice matching Steam's game language; the miles_language parameter makes it load the given voice instead", "ゲームは Steam のゲーム言語の音声を読み込みます。miles_language パラメーターを指定すると、その言語の音声を読み込みます"]),
+    ("ui.write_launch_option_hint", ["把启动参数直接写入 Steam 的配置，保留其他已有的启动选项", "Write the launch parameter into Steam's configuration, keeping any other launch options", "起動パラメーターを Steam の設定に直接書き込みます。他の起動オプションは保持されます"]),
+    ("ui.remove_launch_option_hint", ["从 Steam 启动选项中移除语音参数，游戏恢复使用 Steam 的语言", "Remove the voice parameter from Steam's launch options so the game follows Steam's language again", "Steam の起動オプションから音声パラメーターを削除し、ゲームは Steam の言語に戻ります"]),
+    ("ui.launch_button_hint", ["通过 Steam 启动游戏，并临时附加当前语言的启动参数", "Start the game through Steam with the current language's parameter added for this launch", "Steam からゲームを起動し、今回だけ現在の言語のパラメーターを付けます"]),
+    ("ui.refresh_state_hint", ["重新扫描游戏目录中的语音文件夹", "Rescan the voice folders in the game folder", "ゲームフォルダーの音声フォルダーを再スキャンします"]),
+    ("ui.redetect_hint", ["重新查找 Steam 和游戏的安装位置及语言", "Look up Steam, the game's install location and its language again", "Steam とゲームのインストール場所、言語を再検出します"]),
+    ("ui.migration_refresh_hint", ["用游戏目录中的新版本语音替换该备份", "Replace this backup with the updated voice files from the game folder", "このバックアップをゲームフォルダーの新しい音声ファイルで置き換えます"]),
+    ("ui.validate_after_revert_hint", ["撤销后打开 Steam 验证游戏文件，重新下载缺失的原始语音", "After reverting, open Steam's file verification to download missing original voice files", "元に戻した後、Steam のファイル検証を開いて不足している元の音声をダウンロードします"]),
+    ("ui.skip_snapshot_hint", ["不在删除前保存快照，删除后将无法通过本程序撤销", "Do not save a snapshot before deleting; the deletion cannot be undone in this program", "削除前にスナップショットを保存しません。このプログラムで削除を元に戻せなくなります"]),
+    ("ui.ui_language_hint", ["界面显示的语言，与游戏语音无关", "Language of this program's interface; unrelated to the game voice", "このプログラムの表示言語です。ゲーム音声とは関係ありません"]),
+    ("ui.theme_hint", ["界面颜色主题，跟随系统时随 Windows 设置变化", "Color theme; System follows the Windows setting", "配色テーマ。システムを選ぶと Windows の設定に従います"]),
+    ("ui.game_hint", ["要切换语音的游戏，执行操作期间不能更改", "The game whose voice is switched; cannot be changed while an operation runs", "音声を切り替えるゲーム。操作の実行中は変更できません"]),
+    ("ui.update_dismiss_hint", ["本次不再提示该版本", "Hide this notice until the next start", "次回起動までこの通知を表示しません"]),
+    ("ui.migration_open_hint", ["打开迁移助手，用更新后的语音刷新旧备份", "Open the migration helper to refresh old backups with the updated voice files", "移行アシスタントを開き、古いバックアップを更新後の音声で置き換えます"]),
+    ("ui.messages_clear_hint", ["清空操作结果的历史记录", "Clear the history of operation results", "操作結果の履歴を消去します"]),
+    ("ui.copy_log_hint", ["复制最近的日志，反馈问题时可以附上", "Copy the recent log to include in a bug report", "最近のログをコピーします。問題の報告に添付できます"]),
+    ("ui.export_diagnostics_hint", ["把日志、设置和状态打包为 zip 文件，用于反馈问题", "Package the log, settings and state into a zip file for bug reports", "ログ、設定、状態を zip ファイルにまとめ、問題の報告に使います"]),
+    ("ui.version_mismatch_why", ["游戏更新可能修改语音文件，旧版本的备份可能导致语音缺失、错位或游戏校验失败", "Game updates can change the voice files; an older backup may cause missing or wrong lines or fail the game's file check", "ゲームの更新で音声ファイルが変わることがあります。古いバックアップは音声の欠落やずれ、ファイル検証の失敗の原因になります"]),
+    ("ui.source_path", ["语音文件夹路径", "Voice folder path", "音声フォルダーのパス"]),
+    ("ui.launch_param", ["启动参数:", "Launch parameter:", "起動パラメーター:"]),
+    ("ui.step_done", ["已完成", "Done", "完了"]),
+    ("ui.step_warning", ["需要注意", "Needs attention", "要確認"]),
+    ("ui.toast_dismiss", ["关闭该提示", "Dismiss this message", "このメッセージを閉じる"]),
+    ("ui.drop_folder", ["松开以设为语音文件夹", "Drop to use as the voice folder", "ドロップして音声フォルダーに設定"]),
+    ("ui.install", ["安装:", "Install:", "インストール:"]),
+    ("ui.install_entry", ["[{}] {}（版本 {}）", "[{}] {} (version {})", "[{}] {}（バージョン {}）"]),
+    ("ui.ea_app_detected", ["[OK] 已检测到 EA App 安装", "[OK] EA App install detected", "[OK] EA App のインストールを検出"]),
+    ("ui.step5_hint_ea", ["在 EA App 中打开 {} 的属性 -> 高级启动选项，添加以下参数：", "In EA App, open {} properties -> Advanced launch options, and add:", "EA App で {} のプロパティ -> 詳細な起動オプションを開き、次を追加："]),
+    ("ui.xbox_detected", ["[OK] 已检测到 Xbox 安装", "[OK] Xbox install detected", "[OK] Xbox のインストールを検出"]),
+    ("ui.step5_hint_xbox", ["Xbox 应用无法为 {} 设置启动选项，请使用下方的启动按钮，它会直接运行游戏并附加以下参数：", "The Xbox app cannot set launch options for {}; use the launch button below, which runs the game directly with:", "Xbox アプリでは {} の起動オプションを設定できません。下の起動ボタンを使うと、次のパラメーター付きで直接起動します："]),
+    ("ui.update_pending", ["[!] 有待安装的更新", "[!] Update pending", "[!] 更新待ち"]),
+    ("ui.update_pending_hint", ["Steam 已排队或正在下载游戏更新，更新会覆盖或校验恢复的语音文件", "Steam has queued or is downloading a game update, which will overwrite or flag the restored voice files", "Steam がゲームの更新を予定またはダウンロード中です。更新により復元した音声ファイルが上書きまたは検出されます"]),
+    ("ui.auto_relink", ["更新后重新链接", "Re-link after updates", "更新後に再リンク"]),
+    ("ui.auto_relink_hint", ["隐藏到托盘时在后台监视游戏更新，Steam 更新或验证移除了恢复的语音后，提醒重新应用上次的方案（需要开启托盘）", "While hidden in the tray, watch for game updates and offer to re-apply the last profile when a Steam update or verify removes the restored voice (requires the tray option)", "トレイに隠れている間ゲームの更新を監視し、Steam の更新や検証で復元した音声が削除されたら前回のプロファイルの再適用を提案します（トレイが必要）"]),
+    ("ui.relink_title", ["游戏已更新", "Game updated", "ゲームが更新されました"]),
+    ("ui.relink_hint", ["游戏已更新到版本 {}，{} 个恢复的语音文件夹被移除或替换。是否重新应用方案「{}」？版本与备份不符时仍按版本检查处理。", "The game was updated to build {} and {} restored voice folders were removed or replaced. Re-apply profile \"{}\"? Backups from another build are still subject to the version check.", "ゲームがビルド {} に更新され、復元した音声フォルダー {} 個が削除または置き換えられました。プロファイル「{}」を再適用しますか？ビルドが異なるバックアップにはバージョンチェックが適用されます。"]),
+    ("ui.relink_apply", ["重新应用", "Re-apply", "再適用"]),
+    ("ui.relink_apply_hint", ["删除当前语音并在后台恢复方案中的语音", "Delete the current voice and restore the profile's voice in the background", "現在の音声を削除し、プロファイルの音声をバックグラウンドで復元します"]),
+    ("ui.repair_task_installed", ["[OK] 已注册修复任务", "[OK] Repair task registered", "[OK] 修復タスク登録済み"]),
+    ("ui.repair_task_register", ["注册计划任务", "Register scheduled task", "タスクを登録"]),
+    ("ui.repair_task_register_hint", ["在 Windows 任务计划程序中注册任务：登录时和每小时以静默模式检查被游戏更新移除的语音链接并重新创建，无需保持本工具运行", "Register a Windows Task Scheduler task that runs silently at logon and every hour to recreate voice links removed by game updates, without keeping this tool open", "Windows タスク スケジューラにタスクを登録し、ログオン時と 1 時間ごとにゲームの更新で削除された音声リンクをサイレントで再作成します。このツールを起動したままにする必要はありません"]),
+    ("ui.repair_task_remove", ["移除计划任务", "Remove scheduled task", "タスクを削除"]),
+    ("ui.repair_task_remove_hint", ["从任务计划程序中删除修复任务", "Delete the repair task from Task Scheduler", "タスク スケジューラから修復タスクを削除します"]),
+    ("ui.profile_shortcut", ["创建桌面快捷方式", "Create desktop shortcut", "デスクトップにショートカットを作成"]),
+    ("ui.profile_shortcut_hint", ["在桌面创建快捷方式：运行时检查并修复该方案的语音，然后以对应的启动参数启动游戏并退出", "Create a desktop shortcut that checks and repairs this profile's voice, then starts the game with the matching launch parameter and exits", "このプロファイルの音声を確認・修復してから、対応する起動パラメーターでゲームを起動して終了するショートカットをデスクトップに作成します"]),
+    ("ui.profile_add_to_steam", ["添加到 Steam", "Add to Steam", "Steam に追加"]),
+    ("ui.profile_add_to_steam_hint", ["将本工具作为非 Steam 游戏添加到 Steam，运行时应用该方案并启动游戏，可在大屏幕模式中使用（需要先关闭 Steam）", "Add this tool to Steam as a non-Steam game that applies this profile and starts the game, usable from Big Picture (close Steam first)", "このツールを非 Steam ゲームとして Steam に追加します。起動するとこのプロファイルを適用してゲームを起動し、Big Picture からも使えます（先に Steam を終了してください）"]),
+    ("ui.export_status", ["导出状态报告", "Export status report", "状態レポートをエクスポート"]),
+    ("ui.export_status_hint", ["将检测到的安装、版本、备份、恢复的链接和启动选项导出为 JSON 或文本文件，也可通过命令行 --status 获取", "Export detected installs, build ids, backups, restored links and launch options as a JSON or text file; also available as --status on the command line", "検出したインストール、ビルド、バックアップ、復元したリンク、起動オプションを JSON またはテキストで書き出します。コマンドラインの --status でも取得できます"]),
+    ("ui.move_backups", ["移动备份", "Move backups", "バックアップを移動"]),
+    ("ui.move_backups_hint", ["将所有游戏的备份移到所选文件夹中的 voice_backups，并让已恢复的链接指向新位置", "Move all games' backups to voice_backups in the chosen folder and point restored links to the new location", "すべてのゲームのバックアップを選択したフォルダー内の voice_backups に移動し、復元済みのリンクを新しい場所に向けます"]),
+    ("ui.backup_inside_game", ["[!] 备份目录位于游戏目录中，已暂停备份和恢复，请用“移动备份”移到其他位置", "[!] The backup folder is inside the game folder; backup and restore are disabled until you use Move backups", "[!] バックアップフォルダーがゲームフォルダー内にあります。「バックアップを移動」で移動するまでバックアップと復元は無効です"]),
+    ("ui.cloud_sync", ["[!] 备份目录在 {} 同步目录中，同步会上传大量数据，仅在线的占位文件会导致恢复的链接无法使用", "[!] The backup folder is inside a {} folder; syncing uploads gigabytes and online-only placeholders break restored links", "[!] バックアップフォルダーが {} のフォルダー内にあります。同期で大量のデータがアップロードされ、オンライン専用のプレースホルダーは復元したリンクを壊します"]),
+    ("ui.cloud_pin", ["始终保留在此设备上", "Always keep on this device", "このデバイス上に常に保持する"]),
+    ("ui.cloud_pin_hint", ["把备份目录中的所有文件固定在本机，避免变为仅在线的占位文件；也可以用“移动备份”移出同步目录", "Pin every file in the backup folder so it never becomes an online-only placeholder; you can also use Move backups to leave the synced folder", "バックアップフォルダー内のすべてのファイルをこのデバイスに固定し、オンライン専用にならないようにします。「バックアップを移動」で同期フォルダーの外に移すこともできます"]),
+    ("ui.cloud_exclude", ["排除同步", "Exclude from sync", "同期から除外"]),
+    ("ui.cloud_exclude_hint", ["将备份目录标记为不同步，文件只保留在本机；也可以用“移动备份”移出同步目录", "Mark the backup folder as ignored so its files stay on this computer only; you can also use Move backups to leave the synced folder", "バックアップフォルダーを同期対象外にし、ファイルをこのコンピューターだけに保持します。「バックアップを移動」で同期フォルダーの外に移すこともできます"]),
+    ("ui.keep_builds", ["保留最近版本数:", "Keep recent builds:", "保持する最近のビルド数:"]),
+    ("ui.keep_builds_hint", ["只保留最近几个游戏版本的备份，0 为不限制；当前版本和正在使用的备份始终保留", "Keep backups of only the most recent game builds, 0 for no limit; backups of the current build and backups in use are always kept", "最近のゲームビルドのバックアップだけを保持します。0 で無制限。現在のビルドと使用中のバックアップは常に保持されます"]),
+    ("ui.max_backup_gb", ["备份大小上限:", "Backup size limit:", "バックアップサイズの上限:"]),
+    ("ui.max_backup_gb_hint", ["所有备份的总大小超过上限时提示清理旧版本的备份，0 为不限制", "Offer to clean up old builds when all backups together exceed this size, 0 for no limit", "すべてのバックアップの合計がこのサイズを超えると古いビルドの整理を提案します。0 で無制限"]),
+    ("ui.prune_now", ["立即清理", "Clean up now", "今すぐ整理"]),
+    ("ui.prune_now_hint", ["按保留规则删除旧版本的备份，正在使用的备份不会删除", "Delete old builds' backups according to the retention settings; backups in use are never deleted", "保持設定に従って古いビルドのバックアップを削除します。使用中のバックアップは削除されません"]),
+    ("ui.prune_title", ["清理备份", "Clean up backups", "バックアップの整理"]),
+    ("ui.prune_prompt", ["备份共 {}，超过了设置的上限 {}。按保留规则可以删除以下备份：", "Backups take {}, more than the configured limit of {}. The retention settings allow deleting these backups:", "バックアップの合計は {} で、設定した上限 {} を超えています。保持設定に従って次のバックアップを削除できます:"]),
+    ("ui.storage", ["备份占用", "Storage", "ストレージ"]),
+    ("ui.storage_scanning", ["正在统计备份占用...", "Measuring backups...", "バックアップの使用量を集計しています..."]),
+    ("ui.storage_total", ["{} 共 {}，实际占用 {}", "{} holds {}, using {} on disk", "{} の合計は {}、実際の使用量は {}"]),
+    ("ui.storage_dedup", ["硬链接去重节省 {}", "Hard-link deduplication saves {}", "ハードリンクの重複排除で {} 節約"]),
+    ("ui.storage_free", ["备份所在分区剩余 {}", "{} free on the backup drive", "バックアップのドライブの空き容量 {}"]),
+    ("ui.storage_build", ["版本 {}: {}", "Build {}: {}", "バージョン {}: {}"]),
+    ("ui.storage_refresh_hint", ["重新统计备份占用", "Measure the backups again", "バックアップの使用量を再集計"]),
+    ("ui.duplicate_backup", ["复制备份", "Duplicate backup", "バックアップを複製"]),
+    ("ui.duplicate_backup_hint", ["创建所选备份的副本，副本在备份列表中单独显示，可以单独恢复和修改", "Create a copy of the selected backup that is listed, restored and edited separately", "選択したバックアップのコピーを作成します。コピーは一覧に別に表示され、個別に復元・編集できます"]),
+    ("ui.duplicate_label", ["{} 副本", "{} copy", "{} のコピー"]),
+    ("ui.duplicate_hardlink", ["使用硬链接", "Use hard links", "ハードリンクを使用"]),
+    ("ui.duplicate_hardlink_hint", ["副本与原备份共用文件，不占用额外空间；直接修改文件时两者会同时改变，需要手动修改时请不要勾选", "The copy shares files with the original and takes no extra space, but editing a file in place changes both; leave unchecked if you plan to edit files", "コピーは元のバックアップとファイルを共有し追加の容量を使いませんが、ファイルを直接編集すると両方が変わります。編集する場合はオフにしてください"]),
+    ("ui.compare", ["比较", "Compare", "比較"]),
+    ("ui.compare_hint", ["比较该备份与游戏目录中的语音文件，列出游戏更新后新增、删除和修改的文件", "Compare this backup with the voice files in the game folder and list files added, removed or modified by game updates", "このバックアップとゲームフォルダーの音声ファイルを比較し、更新で追加・削除・変更されたファイルを表示します"]),
+    ("ui.diff_title", ["比较 {} 与游戏目录", "Compare {} with the game folder", "{} とゲームフォルダーの比較"]),
+    ("ui.diff_summary", ["比较了 {} 个文件，{} 个不同；更新备份时将复制 {} 个文件 ({})", "Compared {} files, {} differ; updating the backup would copy {} files ({})", "{} 個のファイルを比較し、{} 個が異なります。バックアップを更新すると {} 個のファイル ({}) をコピーします"]),
+    ("ui.diff_linked", ["{} 个语音文件夹是指向该备份的链接，未比较", "{} voice folders are links to this backup and were not compared", "{} 個の音声フォルダーはこのバックアップへのリンクのため比較しませんでした"]),
+    ("ui.diff_identical", ["✔ 备份与游戏目录中的文件一致", "✔ The backup matches the game folder", "✔ バックアップはゲームフォルダーと一致しています"]),
+    ("ui.restore_anyway", ["我了解风险，仍要恢复", "I understand, restore anyway", "リスクを理解した上で復元する"]),
+    ("ui.restore_anyway_hint", ["游戏更新未修改语音文件时旧备份仍可使用；恢复会记录在日志和操作日志中，游戏再次更新后需要重新确认", "An older backup still works if the update didn't touch the voice files; the restore is recorded in the log and journal, and you need to confirm again after the next game update", "更新で音声ファイルが変わっていなければ古いバックアップも使えます。復元はログと操作履歴に記録され、次のゲーム更新後に再度確認が必要です"]),
+    ("ui.version_policy", ["版本不匹配时:", "On version mismatch:", "バージョン不一致時:"]),
+    ("ui.version_policy_hint", ["备份与当前游戏版本不一致时如何处理，恢复、自动重新应用和方案都按此处理", "How to handle backups from a different game build; applies to restoring, automatic re-linking and profiles", "ゲームのバージョンと異なるバックアップの扱い。復元、自動再適用、プロファイルに適用されます"]),
+    ("ui.version_policy_allows", ["当前设置允许恢复版本不匹配的备份", "Your settings allow restoring backups from a different build", "現在の設定ではバージョンの異なるバックアップを復元できます"]),
+    ("ui.steam_language", ["Steam 中选择的语言: {}", "Language selected in Steam: {}", "Steam で選択されている言語: {}"]),
+    ("ui.steam_language_hint", ["读取自 appmanifest 中的 UserConfig，决定 Steam 下载的语音", "Read from UserConfig in the appmanifest; this decides which voice files Steam downloads", "appmanifest の UserConfig から読み取ります。Steam がダウンロードする音声はこの言語で決まります"]),
+    ("ui.steam_language_mismatch", ["[!] Steam 中选择的是 {}，与将要备份的 {} 不一致，请先在 Steam 中切换语言并等待下载完成", "[!] Steam is set to {}, which doesn't match {} that you are about to back up; switch the language in Steam and wait for the download first", "[!] Steam では {} が選択されており、バックアップしようとしている {} と一致しません。先に Steam で言語を切り替えてダウンロード完了まで待ってください"]),
+    ("ui.current_launch_options", ["Steam 中当前的启动选项:", "Current Steam launch options:", "現在の Steam 起動オプション:"]),
+    ("ui.launch_options_empty", ["(无)", "(none)", "(なし)"]),
+    ("ui.reload_launch_options_hint", ["重新读取 localconfig.vdf 中的启动选项", "Reload the launch options from localconfig.vdf", "localconfig.vdf から起動オプションを再読み込みします"]),
+    ("ui.launch_option_missing", ["尚未设置 {} 参数", "{} is not set yet", "{} はまだ設定されていません"]),
+    ("ui.launch_option_match", ["✔ {} 与所选语言一致", "✔ {} matches the selected language", "✔ {} は選択した言語と一致しています"]),
+    ("ui.launch_option_stale", ["[!] {} 是之前为 {} 设置的参数，与所选语言不一致", "[!] {} was set for {} and doesn't match the selected language", "[!] {} は以前 {} 用に設定したもので、選択した言語と一致しません"]),
+    ("ui.launch_option_stale_hint", ["点击“写入 Steam 启动项”会替换旧的参数", "Click \"Write to Steam\" to replace the old parameter", "「Steam に書き込む」で古いパラメーターを置き換えます"]),
+    ("ui.write_user_cfg", ["写入 user.cfg", "Write to user.cfg", "user.cfg に書き込む"]),
+    ("ui.write_user_cfg_hint", ["在游戏目录的 user.cfg 中写入 miles_language，不通过 Steam 启动（EA App、Xbox）时也能使用所选语音；保留文件中的其他设置", "Write miles_language into user.cfg in the game folder so the selected voice is used without Steam launch options (EA App, Xbox); other lines are kept", "ゲームフォルダーの user.cfg に miles_language を書き込み、Steam の起動オプションなし（EA App、Xbox）でも選択した音声を使います。他の行は保持されます"]),
+    ("ui.remove_user_cfg", ["从 user.cfg 移除", "Remove from user.cfg", "user.cfg から削除"]),
+    ("ui.remove_user_cfg_hint", ["删除 user.cfg 中的 miles_language，文件中没有其他设置时删除该文件", "Remove miles_language from user.cfg, deleting the file if nothing else is left", "user.cfg から miles_language を削除し、他に設定がなければファイルも削除します"]),
+    ("ui.user_cfg_match", ["✔ user.cfg: {}", "✔ user.cfg: {}", "✔ user.cfg: {}"]),
+    ("ui.user_cfg_stale", ["[!] user.cfg 中为 {}，与所选语言不一致", "[!] user.cfg is set to {}, which doesn't match the selected language", "[!] user.cfg は {} に設定されており、選択した言語と一致しません"]),
+    ("ui.game_will_speak", ["游戏将使用的语音: {}（依据: {}）", "Game will speak: {} (from {})", "ゲームで使われる音声: {}（根拠: {}）"]),
+    ("ui.game_will_speak_hint", ["按 Steam 启动选项、user.cfg、Steam 中选择的语言的顺序判断，并检查该语言的语音文件是否可用", "Decided from the Steam launch option, then user.cfg, then the language selected in Steam, and checked against the voice files in the game folder", "Steam の起動オプション、user.cfg、Steam で選択した言語の順に判断し、その言語の音声ファイルが使えるか確認します"]),
+    ("ui.game_will_speak_unknown", ["无法判断游戏将使用的语音", "Cannot tell which voice the game will use", "ゲームで使われる音声を判断できません"]),
+    ("ui.delete_in_use", ["[!] 游戏当前依赖{}（{}），删除其语音文件和 .toc 文件后游戏可能在启动时崩溃", "[!] The game currently depends on {} ({}); deleting its voice and .toc files can make the game crash on launch", "[!] ゲームは現在{}（{}）に依存しています。音声ファイルと .toc ファイルを削除すると起動時にクラッシュする可能性があります"]),
+    ("ui.delete_in_use_confirm", ["我了解风险，仍要删除", "I understand, delete anyway", "リスクを理解した上で削除する"]),
+    ("ui.delete_other_voices", ["删除其他语音", "Remove all other voices", "他の音声をすべて削除"]),
+    ("ui.delete_other_voices_hint", ["只保留所选语言，删除游戏目录中其他语言由游戏自身安装的语音文件以释放空间；本工具创建的链接和游戏当前依赖的语言不受影响", "Keep only the selected language and delete the other languages' voice files installed by the game to free space; links created by this tool and languages the game currently depends on are kept", "選択した言語だけを残し、ゲームがインストールした他の言語の音声ファイルを削除して空き容量を増やします。このツールが作成したリンクとゲームが現在依存している言語は残ります"]),
+    ("ui.queue_retry", ["重试", "Retry", "再試行"]),
+    ("ui.queue_retry_hint", ["按加入队列时的选择重新执行该项", "Run this item again with the choices it was queued with", "キューに追加したときの選択でこの項目を再実行します"]),
+    ("ui.antivirus_title", ["杀毒软件可能在干扰复制", "Antivirus may be interfering with copying", "ウイルス対策ソフトがコピーを妨げている可能性があります"]),
+    ("ui.antivirus_hint", ["Microsoft Defender 等实时扫描会检查每个写入的文件，使复制语音文件慢数倍，还可能占用或隔离文件。把下面的文件夹加入排除项后再重试。", "Real-time scanning such as Microsoft Defender checks every file written, which makes copying voice files several times slower and can lock or quarantine files. Add the folders below as exclusions, then retry.", "Microsoft Defender などのリアルタイムスキャンは書き込まれるファイルをすべて検査するため、音声ファイルのコピーが数倍遅くなり、ファイルがロックまたは検疫されることがあります。以下のフォルダーを除外に追加してから再試行してください。"]),
+    ("ui.antivirus_paths", ["建议排除的文件夹:", "Folders to exclude:", "除外を推奨するフォルダー:"]),
+    ("ui.antivirus_command", ["或在管理员 PowerShell 中运行:", "Or run in an administrator PowerShell:", "または管理者の PowerShell で実行:"]),
+    ("ui.antivirus_copy_hint", ["复制添加 Defender 排除项的命令", "Copy the command that adds the Defender exclusions", "Defender の除外を追加するコマンドをコピーします"]),
+    ("ui.antivirus_open_settings", ["打开 Windows 安全中心", "Open Windows Security", "Windows セキュリティを開く"]),
+    ("ui.antivirus_open_settings_hint", ["在“病毒和威胁防护设置”中的“排除项”里添加上面的文件夹", "Add the folders above under Exclusions in Virus & threat protection settings", "「ウイルスと脅威の防止の設定」の「除外」に上のフォルダーを追加します"]),
+    ("ui.antivirus_retry", ["已添加排除项，重试", "Exclusions added, retry", "除外を追加しました。再試行"]),
+    ("ui.antivirus_retry_hint", ["按原来的选择重新执行失败的操作", "Run the failed operation again with the same choices", "失敗した操作を同じ選択で再実行します"]),
+    ("antivirus.quarantined", ["{} 个文件被杀毒软件拦截或隔离", "{} files were blocked or quarantined by antivirus", "{} 個のファイルがウイルス対策ソフトによりブロックまたは検疫されました"]),
+    ("antivirus.locked", ["{} 个文件在复制时被其他程序占用", "{} files were locked by another program during copying", "{} 個のファイルがコピー中に他のプログラムによりロックされていました"]),
+    ("antivirus.slow", ["{} 个文件复制用时异常长", "{} files took abnormally long to copy", "{} 個のファイルのコピーに異常に時間がかかりました"]),
+    ("ui.background_priority", ["后台优先级", "Background priority", "バックグラウンド優先度"]),
+    ("ui.background_priority_hint", ["降低复制线程的 CPU 和磁盘优先级，复制时不影响其他程序，但复制会变慢；从下一个任务开始生效", "Lower the CPU and disk priority of copy threads so copying does not slow down other programs, at the cost of slower copies; applies from the next task", "コピースレッドの CPU とディスクの優先度を下げ、他のプログラムを妨げないようにします。コピーは遅くなります。次のタスクから適用されます"]),
+    ("ui.background_priority_note", ["后台优先级已开启：其他程序繁忙时复制可能慢数倍", "Background priority is on: copies may be several times slower while other programs are busy", "バックグラウンド優先度がオン: 他のプログラムが忙しいとコピーが数倍遅くなることがあります"]),
+    ("ui.pause", ["暂停", "Pause", "一時停止"]),
+    ("ui.pause_hint", ["当前文件复制完成后暂停，已复制的文件会保留", "Pause after the current file finishes; files already copied are kept", "現在のファイルのコピー後に一時停止します。コピー済みのファイルは保持されます"]),
+    ("ui.resume", ["继续", "Resume", "再開"]),
+    ("ui.resume_hint", ["从下一个文件继续，不重新开始", "Continue from the next file without starting over", "最初からやり直さずに次のファイルから再開します"]),
+    ("ui.progress_paused", ["已暂停（最后一个文件: {}）", "Paused (last file: {})", "一時停止中（最後のファイル: {}）"]),
+    ("ui.copy_engine", ["复制引擎:", "Copy engine:", "コピーエンジン:"]),
+    ("ui.copy_engine_hint", ["备份时复制语音文件夹的方式", "How voice folders are copied when backing up", "バックアップ時に音声フォルダーをコピーする方法"]),
+    ("ui.link_check", ["定期检查恢复的语音:", "Check restored voices every:", "復元した音声の定期チェック:"]),
+    ("ui.link_check_hint", ["程序运行或驻留托盘时每隔几分钟检查链接和 .toc 文件，被 Steam 更新或清理工具移除时立即提醒；0 为不检查", "While the app runs or sits in the tray, check links and .toc files every few minutes and alert as soon as a Steam update or cleanup tool removes them; 0 turns it off", "アプリの実行中またはトレイ常駐中に数分ごとにリンクと .toc ファイルを確認し、Steam の更新やクリーンアップツールで削除されたらすぐに通知します。0 でオフ"]),
+    ("ui.minutes_suffix", [" 分钟", " min", " 分"]),
+    ("monitor.removed", ["链接或文件夹已被移除: {}", "Link or folder was removed: {}", "リンクまたはフォルダーが削除されました: {}"]),
+    ("monitor.target_missing", ["链接指向的备份文件夹已不存在: {}", "The backup folder the link points to is gone: {}", "リンク先のバックアップフォルダーがありません: {}"]),
+    ("monitor.toc_changed", [".toc 文件缺失或已被修改: {}", ".toc file is missing or was modified: {}", ".toc ファイルがないか変更されています: {}"]),
+    ("ui.health_ok", ["启动检查：一切正常", "Startup check: all good", "起動チェック: 問題なし"]),
+    ("ui.health_problems", ["启动检查：{} 项需要注意", "Startup check: {} items need attention", "起動チェック: {} 件の確認が必要です"]),
+    ("ui.health_recheck", ["重新检查", "Check again", "再チェック"]),
+    ("ui.health_recheck_hint", ["重新读取启动选项并再次检查", "Reload the launch options and run the checks again", "起動オプションを読み直して再度チェックします"]),
+    ("health.game_ok", ["已找到 {}: {}", "{} found: {}", "{} が見つかりました: {}"]),
+    ("health.game_unexpected", ["语音文件目录看起来不对: {}", "The voice file folder looks wrong: {}", "音声ファイルフォルダーが正しくないようです: {}"]),
+    ("health.game_missing", ["未找到 {}", "{} was not found", "{} が見つかりません"]),
+    ("health.build_ok", ["游戏版本: {}", "Game build: {}", "ゲームのビルド: {}"]),
+    ("health.build_unreadable", ["无法读取游戏版本号，备份无法与版本对应", "Could not read the game build; backups cannot be matched to a build", "ゲームのビルドを読み取れません。バックアップをビルドと照合できません"]),
+    ("health.build_unknown", ["未检测到游戏安装，版本号未知", "No game install detected; the build is unknown", "ゲームのインストールが検出されず、ビルドが不明です"]),
+    ("health.backup_ok", ["备份目录可以访问: {}", "Backup folder is accessible: {}", "バックアップフォルダーにアクセスできます: {}"]),
+    ("health.backup_missing", ["备份目录尚未创建: {}", "Backup folder does not exist yet: {}", "バックアップフォルダーがまだありません: {}"]),
+    ("health.backup_unreadable", ["无法读取备份目录 {}: {}", "Could not read the backup folder {}: {}", "バックアップフォルダー {} を読み取れません: {}"]),
+    ("health.links_ok", ["恢复的链接和 .toc 文件完好", "Restored links and .toc files are intact", "復元したリンクと .toc ファイルは正常です"]),
+    ("health.links_broken", ["恢复的语音有 {} 个问题:\n{}", "The restored voice has {} problems:\n{}", "復元した音声に {} 件の問題があります:\n{}"]),
+    ("health.launch_none", ["启动选项中没有语音参数", "No voice parameter in the launch options", "起動オプションに音声パラメーターはありません"]),
+    ("health.launch_ok", ["启动选项与所选语音一致: {}", "Launch options match the selected voice: {}", "起動オプションは選択した音声と一致しています: {}"]),
+    ("health.launch_mismatch", ["启动选项中的语音 ({}) 与所选语音 ({}) 不一致", "The launch options voice ({}) differs from the selected voice ({})", "起動オプションの音声 ({}) が選択した音声 ({}) と異なります"]),
+    ("health.space_low", ["备份磁盘剩余空间不足: {}", "Low free space on the backup drive: {}", "バックアップドライブの空き容量が少なくなっています: {}"]),
+    ("health.space_ok", ["备份磁盘剩余空间: {}", "Free space on the backup drive: {}", "バックアップドライブの空き容量: {}"]),
+    ("health.fix_detect", ["重新检测", "Detect again", "再検出"]),
+    ("health.fix_create_dir", ["创建", "Create", "作成"]),
+    ("health.fix_repair", ["修复链接", "Repair links", "リンクを修復"]),
+    ("health.fix_launch_option", ["写入所选语音", "Write selected voice", "選択した音声を書き込む"]),
+    ("ui.history", ["操作历史 ({})", "Operation history ({})", "操作履歴 ({})"]),
+    ("ui.history_empty", ["还没有记录", "No operations recorded yet", "まだ記録がありません"]),
+    ("ui.history_ok", ["成功", "Succeeded", "成功"]),
+    ("ui.history_failed", ["失败", "Failed", "失敗"]),
+    ("ui.column_operation", ["操作", "Operation", "操作"]),
+    ("ui.column_result", ["结果", "Result", "結果"]),
+    ("ui.column_duration", ["用时", "Duration", "所要時間"]),
+    ("ui.install_hint", ["备份、恢复和启动参数都针对所选的安装", "Backups, restores and launch options apply to the selected install", "バックアップ、復元、起動オプションは選択したインストールに対して行われます"]),
+    ("ui.log", ["操作日志", "Operation log", "操作ログ"]),
+    ("ui.copy_log", ["复制日志", "Copy log", "ログをコピー"]),
+    ("ui.export_diagnostics", ["导出诊断信息", "Export diagnostics", "診断情報をエクスポート"]),
+    ("lang.en", ["英语 (English)", "English", "英語 (English)"]),
+    ("lang.ja", ["日语 (Japanese)", "Japanese (日本語)", "日本語"]),
+    ("lang.cn", ["中文 (Chinese)", "Chinese (中文)", "中国語 (中文)"]),
+    ("lang.tw", ["繁体中文 (Traditional Chinese)", "Traditional Chinese (繁體中文)", "繁体字中国語 (繁體中文)"]),
+    ("lang.de", ["德语 (German)", "German (Deutsch)", "ドイツ語 (Deutsch)"]),
+    ("lang.fr", ["法语 (French)", "French (Français)", "フランス語 (Français)"]),
+    ("lang.es", ["西班牙语 (Spanish)", "Spanish (Español)", "スペイン語 (Español)"]),
+    ("lang.it", ["意大利语 (Italian)", "Italian (Italiano)", "イタリア語 (Italiano)"]),
+    ("lang.br", ["巴西葡萄牙语 (Português do Brasil)", "Brazilian Portuguese (Português do Brasil)", "ポルトガル語・ブラジル (Português do Brasil)"]),
+    ("lang.pl", ["波兰语 (Polish)", "Polish (Polski)", "ポーランド語 (Polski)"]),
+    ("lang.ru", ["俄语 (Russian)", "Russian (Русский)", "ロシア語 (Русский)"]),
+    ("lang.ko", ["韩语 (Korean)", "Korean (한국어)", "韓国語 (한국어)"]),
+    ("lang.ar", ["阿拉伯语 (Arabic)", "Arabic", "アラビア語 (Arabic)"]),
+
+    ("mode.junction", ["Junction 链接", "Junction", "ジャンクション"]),
+    ("mode.symlink", ["符号链接", "Symbolic link", "シンボリックリンク"]),
+    ("mode.hardlink", ["硬链接", "Hard links", "ハードリンク"]),
+    ("mode.copy", ["完整复制", "Full copy", "完全コピー"]),
+    ("mode.fs_no_links", ["游戏目录所在分区（{}）不支持链接", "the game folder's drive ({}) does not support links", "ゲームフォルダーのドライブ（{}）はリンクに対応していません"]),
+    ("mode.fs_remote_target", ["网络共享上的备份只能复制，不能创建链接", "backups on a network share can only be copied, not linked", "ネットワーク共有上のバックアップはリンクできず、コピーのみ可能です"]),
+    ("mode.fs_cross_volume", ["备份和游戏目录不在同一分区", "the backup and game folders are on different drives", "バックアップとゲームフォルダーが別のドライブにあります"]),
+    ("mode.fs_no_hardlinks", ["游戏目录所在分区（{}）不支持硬链接", "the game folder's drive ({}) does not support hard links", "ゲームフォルダーのドライブ（{}）はハードリンクに対応していません"]),
+    ("mode.backup_copy", ["复制", "Copy", "コピー"]),
+    ("mode.backup_move", ["移动并链接", "Move and link", "移動してリンク"]),
+    ("mode.junction_hint", ["在游戏目录中创建指向备份的目录链接，不占用额外空间，游戏会像读取普通文件夹一样读取备份", "Creates a directory link in the game folder that points at the backup; it uses no extra space and the game reads it like a normal folder", "ゲームフォルダーにバックアップを指すディレクトリリンクを作成します。追加の容量は使わず、ゲームは通常のフォルダーと同じように読み込みます"]),
+    ("mode.symlink_hint", ["创建符号链接，需要管理员权限或开发者模式，可以指向其他分区", "Creates a symbolic link; needs administrator rights or Developer Mode and can point to another drive", "シンボリックリンクを作成します。管理者権限か開発者モードが必要で、別のドライブを指せます"]),
+    ("mode.hardlink_hint", ["为每个文件创建硬链接，备份和游戏目录必须在同一 NTFS 分区", "Creates a hard link for every file; the backup and game folders must be on the same NTFS drive", "ファイルごとにハードリンクを作成します。バックアップとゲームフォルダーは同じ NTFS ドライブにある必要があります"]),
+    ("mode.copy_hint", ["把备份完整复制到游戏目录，占用双倍空间，但不依赖备份文件夹", "Copies the whole backup into the game folder; uses twice the space but no longer depends on the backup folder", "バックアップをゲームフォルダーにすべてコピーします。容量は倍になりますが、バックアップフォルダーに依存しません"]),
+    ("mode.backup_copy_hint", ["把语音文件复制到备份目录，游戏目录保持不变", "Copies the voice files into the backup folder and leaves the game folder unchanged", "音声ファイルをバックアップフォルダーにコピーし、ゲームフォルダーは変更しません"]),
+    ("mode.backup_move_hint", ["把语音文件移动到备份目录并在原位置创建 Junction，几乎不需要额外空间", "Moves the voice files into the backup folder and leaves a junction in their place, needing almost no extra space", "音声ファイルをバックアップフォルダーに移動し、元の場所にジャンクションを作成します。追加の容量はほぼ不要です"]),
+
+    ("status.diagnostics_exported", ["诊断信息已导出到 {}，反馈问题时请附上该文件", "Diagnostics exported to {}, attach this file when reporting an issue", "診断情報を {} にエクスポートしました。問題を報告する際に添付してください"]),
+    ("status.backup_running", ["正在备份 {}...", "Backing up {}...", "{} をバックアップしています..."]),
+    ("status.restore_running", ["正在恢复 {}...", "Restoring {}...", "{} を復元しています..."]),
+    ("status.materialize_running", ["正在将链接转换为实际文件...", "Converting links to real files...", "リンクを実ファイルに変換しています..."]),
+    ("status.materialized", ["已将 {} 个链接转换为实际文件", "Converted {} links to real files", "{} 個のリンクを実ファイルに変換しました"]),
+    ("status.reverted", ["已撤销 {} 项修改", "Reverted {} changes", "{} 件の変更を元に戻しました"]),
+    ("status.reverted_validating", ["已撤销 {} 项修改，已请求 Steam 验证游戏文件", "Reverted {} changes and asked Steam to verify the game files", "{} 件の変更を元に戻し、Steam にゲームファイルの検証を依頼しました"]),
+    ("status.dedup_running", ["正在查找重复文件...", "Looking for duplicate files...", "重複ファイルを検索しています..."]),
+    ("status.dedup_done", ["已将 {} 个重复文件替换为硬链接，节省了 {}", "Replaced {} duplicate files with hardlinks, saved {}", "{} 個の重複ファイルをハードリンクに置き換え、{} を節約しました"]),
+    ("status.dedup_skipped_hardlink", ["[!] 跳过了 {} 个以硬链接恢复的备份，替换其中的文件会断开与游戏文件的硬链接", "[!] Skipped {} backups restored as hardlinks; replacing their files would break the links with the game files", "[!] ハードリンクで復元された {} 個のバックアップをスキップしました。これらのファイルを置き換えるとゲームファイルとのハードリンクが切れます"]),
+    ("status.dedup_none", ["没有发现重复文件", "No duplicate files found", "重複ファイルは見つかりませんでした"]),
+    ("status.compress_running", ["正在压缩备份...", "Compressing backup...", "バックアップを圧縮しています..."]),
+    ("status.compressed", ["{} 备份已压缩 ({} -> {})", "{} backup compressed ({} -> {})", "{} のバックアップを圧縮しました ({} -> {})"]),
+    ("status.decompress_running", ["正在解压备份...", "Decompressing backup...", "バックアップを展開しています..."]),
+    ("status.decompressed", ["{} 备份已解压", "{} backup decompressed", "{} のバックアップを展開しました"]),
+    ("status.export_running", ["正在导出备份...", "Exporting backup...", "バックアップをエクスポートしています..."]),
+    ("status.exported", ["{} 备份已导出到 {}", "{} backup exported to {}", "{} のバックアップを {} にエクスポートしました"]),
+    ("status.import_running", ["正在导入备份并校验文件...", "Importing backup and verifying files...", "バックアップをインポートしてファイルを検証しています..."]),
+    ("status.imported", ["{} 备份已导入 (版本: {})", "{} backup imported (build: {})", "{} のバックアップをインポートしました (バージョン: {})"]),
+    ("status.backup_note_saved", ["备份名称和备注已保存", "Backup name and note saved", "バックアップの名前とメモを保存しました"]),
+    ("status.verify_running", ["正在校验备份...", "Verifying backup...", "バックアップを検証しています..."]),
+    ("status.verified", ["{} 备份校验通过 ({} 个文件)", "{} backup verified ({} files)", "{} のバックアップを検証しました (ファイル {} 個)"]),
+    ("status.mode_fallback", ["[!] {}不可用：{}，已改为完整复制", "[!] {} is unavailable: {}; using full copy instead", "[!] {}は使用できません: {}。完全コピーを使用します"]),
+    ("status.switch_already", ["当前已是 {} 语音", "{} voice is already active", "すでに {} の音声です"]),
+    ("status.profile_saved", ["已保存方案: {}", "Profile saved: {}", "プロファイルを保存しました: {}"]),
+    ("status.profile_deleted", ["已删除方案: {}", "Profile deleted: {}", "プロファイルを削除しました: {}"]),
+    ("status.profile_text_language", ["[!] 方案要求的 Steam 游戏语言为 {}，当前为 {}，请在 Steam 游戏属性的“语言”中修改", "[!] The profile expects the Steam game language {} but it is {}; change it under Language in the game's Steam properties", "[!] プロファイルの Steam ゲーム言語は {} ですが、現在は {} です。Steam のゲームのプロパティ「言語」で変更してください"]),
+    ("status.backup_all_running", ["正在依次备份 {} 个语言...", "Backing up {} languages one after another...", "{} 個の言語を順番にバックアップしています..."]),
+    ("status.backup_all_summary", ["批量备份完成: {}/{} 个语言成功", "Batch backup finished: {}/{} languages succeeded", "一括バックアップ完了: {}/{} 言語が成功"]),
+    ("status.batch_item_failed", ["[!] {}: {}", "[!] {}: {}", "[!] {}: {}"]),
+    ("status.queued", ["已加入队列: {}（前面还有 {} 项）", "Queued: {} ({} ahead)", "キューに追加しました: {}（前に {} 件）"]),
+    ("status.queue_cancelled", ["已从队列中取消", "Removed from the queue", "キューから取り消しました"]),
+    ("status.queue_cancelling", ["正在取消当前操作，当前文件复制完成后停止...", "Cancelling the current operation, it stops after the current file...", "現在の操作をキャンセルしています。コピー中のファイルの後で停止します..."]),
+    ("status.update_installed", ["已更新到 {}，正在重新启动...", "Updated to {}, restarting...", "{} に更新しました。再起動しています..."]),
+    ("status.update_relaunch_failed", ["已更新，但无法自动重新启动，请手动启动: {}", "Updated, but could not restart automatically. Please start it manually: {}", "更新しましたが自動で再起動できませんでした。手動で起動してください: {}"]),
+    ("status.update_downloading", ["正在下载新版本 {}...", "Downloading version {}...", "バージョン {} をダウンロードしています..."]),
+    ("status.source_set", ["语音文件夹已设为 {}", "Voice folder set to {}", "音声フォルダーを {} に設定しました"]),
+    ("status.source_corrected", ["所选文件夹是游戏目录，语音文件夹已改为其中的 {}", "The selected folder is the game folder; using {} inside it as the voice folder", "選択したフォルダーはゲームフォルダーのため、その中の {} を音声フォルダーにしました"]),
+    ("status.source_wrong_game", ["[!] 语音文件夹已设为 {}，但所在的游戏目录中没有 {} 的主程序，可能选择了其他游戏", "[!] Voice folder set to {}, but its game folder has no {} executable; it may belong to another game", "[!] 音声フォルダーを {} に設定しましたが、ゲームフォルダーに {} の実行ファイルがありません。別のゲームの可能性があります"]),
+    ("status.source_unexpected", ["[!] 语音文件夹已设为 {}，但它看起来不像 {} 的 {} 文件夹", "[!] Voice folder set to {}, but it does not look like {}'s {} folder", "[!] 音声フォルダーを {} に設定しましたが、{} の {} フォルダーではないようです"]),
+    ("status.installs_found", ["[!] 在本地磁盘上找到 {} 个 {} 安装，请在上方选择要使用的安装", "[!] Found {} installs of {} on local drives; choose the one to use above", "[!] ローカルドライブに {} 個の {} のインストールが見つかりました。上で使用するものを選択してください"]),
+    ("status.xbox_not_writable", ["[!] 无法写入 Xbox 游戏目录 {}，请以管理员身份运行或检查文件夹权限", "[!] Cannot write to the Xbox game folder {}; run as administrator or check the folder permissions", "[!] Xbox のゲームフォルダー {} に書き込めません。管理者として実行するか、フォルダーの権限を確認してください"]),
+    ("status.restore_update_pending", ["[!] Steam 有待安装的更新，更新后本次恢复可能被撤销，建议更新完成后再恢复", "[!] Steam has a pending update that may undo this restore; consider restoring after the update finishes", "[!] Steam に保留中の更新があり、この復元が取り消される可能性があります。更新完了後の復元をおすすめします"]),
+    ("status.relink_ready", ["游戏已更新到版本 {}，打开窗口确认是否重新应用方案「{}」", "The game was updated to build {}; open the window to re-apply profile \"{}\"", "ゲームがビルド {} に更新されました。ウィンドウを開いてプロファイル「{}」の再適用を確認してください"]),
+    ("status.repair_task_registered", ["已注册计划任务，登录时和每小时检查并修复语音链接", "Scheduled task registered; voice links are checked and repaired at logon and every hour", "タスクを登録しました。ログオン時と 1 時間ごとに音声リンクを確認して修復します"]),
+    ("status.repair_task_removed", ["已移除计划任务", "Scheduled task removed", "タスクを削除しました"]),
+    ("status.shortcut_created", ["已创建快捷方式 {}", "Shortcut created: {}", "ショートカットを作成しました: {}"]),
+    ("status.steam_shortcut_added", ["已将「{}」添加到 Steam 库，重新启动 Steam 后可见", "Added \"{}\" to the Steam library; it appears after restarting Steam", "「{}」を Steam ライブラリに追加しました。Steam を再起動すると表示されます"]),
+    ("status.links_repaired", ["已修复 {} 个语音文件夹", "Repaired {} voice folders", "{} 個の音声フォルダーを修復しました"]),
+    ("status.repair_running", ["正在修复语音文件夹...", "Repairing voice folders...", "音声フォルダーを修復しています..."]),
+    ("status.status_exported", ["已导出状态报告到 {}", "Status report exported to {}", "状態レポートを {} に書き出しました"]),
+    ("status.backups_moving", ["正在移动备份...", "Moving backups...", "バックアップを移動しています..."]),
+    ("status.backups_moved", ["备份已移到 {}，更新了 {} 个链接", "Backups moved to {}; {} links updated", "バックアップを {} に移動し、{} 個のリンクを更新しました"]),
+    ("status.backup_inside_game", ["[!] 备份目录 {} 与游戏目录重叠，扫描时已跳过，请移到游戏目录之外", "[!] The backup folder {} overlaps the game folder; it is skipped when scanning, but should be moved outside the game folder", "[!] バックアップフォルダー {} がゲームフォルダーと重なっています。スキャンでは除外されますが、ゲームフォルダーの外に移動してください"]),
+    ("status.cloud_protecting", ["正在设置同步选项...", "Changing sync options...", "同期オプションを変更しています..."]),
+    ("status.cloud_pinned", ["{} 已设为始终保留在此设备上 ({})", "{} is now always kept on this device ({})", "{} をこのデバイス上に常に保持するように設定しました ({})"]),
+    ("status.cloud_placeholders", ["[!] 还有 {} 个文件尚未下载，同步客户端下载完成前请勿恢复", "[!] {} files are not downloaded yet; wait for the sync client to finish before restoring", "[!] まだ {} 個のファイルがダウンロードされていません。同期クライアントの完了を待ってから復元してください"]),
+    ("status.cloud_excluded", ["{} 已排除 {} 同步", "{} is now excluded from {} sync", "{} を {} の同期から除外しました"]),
+    ("status.transfer_speed", ["平均传输速度 {}/s", "Average transfer speed {}/s", "平均転送速度 {}/s"]),
+    ("status.backups_pruned", ["已清理 {} 个备份，释放 {}", "Cleaned up {} backups, freeing {}", "{} 個のバックアップを整理し、{} を解放しました"]),
+    ("status.backup_duplicating", ["正在复制备份...", "Duplicating backup...", "バックアップを複製しています..."]),
+    ("status.backup_duplicated", ["已创建备份副本 {}", "Created backup copy {}", "バックアップのコピー {} を作成しました"]),
+    ("status.restore_verified", ["✔ 已确认所有语音文件夹可以打开，文件与备份一致", "✔ Verified: every voice folder opens and matches the backup", "✔ 確認済み: すべての音声フォルダーを開け、バックアップと一致しています"]),
+    ("status.comparing", ["正在比较 {} 与游戏目录...", "Comparing {} with the game folder...", "{} とゲームフォルダーを比較しています..."]),
+    ("status.restore_version_override", ["[!] 已恢复版本不匹配的备份: 备份({}) != 当前({})", "[!] Restored a backup from a different build: backup ({}) != current ({})", "[!] バージョンが一致しないバックアップを復元しました: バックアップ({}) != 現在({})"]),
+    ("status.user_cfg_written", ["已写入 {} {} 到 {}", "Wrote {} {} to {}", "{} {} を {} に書き込みました"]),
+    ("status.user_cfg_removed", ["已移除 {}，文件: {}", "Removed {} from {}", "{} を {} から削除しました"]),
+    ("status.cleanup_running", ["正在删除 {} 个语言的语音文件 ({})...", "Removing voice files of {} languages ({})...", "{} 言語の音声ファイルを削除しています ({})..."]),
+    ("status.cleanup_summary", ["删除其他语音完成: {}/{} 个语言成功，释放 {}", "Removed other voices: {}/{} languages succeeded, {} freed", "他の音声の削除完了: {}/{} 言語が成功、{} を解放"]),
+    ("status.cleanup_item", ["✔ {}: {} 个文件夹, {} 个 .toc 文件 ({})", "✔ {}: {} folders, {} .toc files ({})", "✔ {}: {} 個のフォルダー、{} 個の .toc ファイル ({})"]),
+    ("status.cleanup_skipped", ["跳过 {}: {}", "Skipped {}: {}", "{} をスキップ: {}"]),
+    ("status.cleanup_managed", ["由本工具恢复，请使用“删除游戏语音”", "restored by this tool; use \"Delete game voice files\"", "このツールで復元されています。「ゲーム音声を削除」を使ってください"]),
+    ("status.cleanup_in_use", ["游戏当前依赖该语言（{}）", "the game currently depends on it ({})", "ゲームが現在この言語に依存しています（{}）"]),
+    ("status.space_saved", ["链接代替游戏目录中的文件夹，节省 {}；备份共占用 {}", "Links replace the game folders, saving {} in the game folder; backups take {} in total", "リンクでゲームフォルダーのフォルダーを置き換え、{} を節約しました。バックアップの合計は {} です"]),
+    ("status.space_copied", ["复制方式在游戏目录中额外占用 {}；备份共占用 {}", "Copy mode uses an extra {} in the game folder; backups take {} in total", "コピー方式ではゲームフォルダーで {} を追加で使用します。バックアップの合計は {} です"]),
+    ("status.queue_retried", ["已重新加入队列", "Queued again", "キューに再追加しました"]),
+    ("status.links_broken", ["[!] 定期检查发现 {} 个问题，游戏可能无法使用所选语音:\n{}", "[!] The periodic check found {} problems; the game may not use the selected voice:\n{}", "[!] 定期チェックで {} 件の問題が見つかりました。ゲームで選択した音声が使われない可能性があります:\n{}"]),
+    ("status.backup_dir_created", ["已创建备份目录: {}", "Created the backup folder: {}", "バックアップフォルダーを作成しました: {}"]),
+    ("status.recycle_too_long", ["[!] {} 项的路径超过 260 个字符，回收站不支持，已永久删除:\n{}", "[!] {} items have paths longer than 260 characters, which the Recycle Bin does not support; they were deleted permanently:\n{}", "[!] {} 個の項目はパスが 260 文字を超えておりごみ箱に対応していないため、完全に削除しました:\n{}"]),
+    ("status.log_copied", ["日志已复制到剪贴板", "Log copied to clipboard", "ログをクリップボードにコピーしました"]),
+    ("status.game_detected", ["已自动检测到 {} 的游戏路径，版本: {}", "{} detected automatically, build: {}", "{} のパスを自動検出しました。バージョン: {}"]),
+    ("status.launch_option_removed", ["已从 Steam 启动选项中移除语音参数", "Removed the voice parameter from Steam launch options", "Steam の起動オプションから音声パラメーターを削除しました"]),
+    ("status.launch_option_written", ["已写入 Steam 启动选项: {}", "Steam launch options set to: {}", "Steam の起動オプションを書き込みました: {}"]),
+    ("status.launching", ["正在启动 {}: {}", "Launching {}: {}", "{} を起動しています: {}"]),
+    ("status.moved_and_linked", ["已移动并链接", "moved and linked", "移動してリンクしました"]),
+    ("status.backup_done", ["备份完成", "backed up", "バックアップ完了"]),
+    ("status.backup_summary", ["{} {}！({} 个文件夹, {} 个toc文件, 版本: {})", "{} {}! ({} folders, {} toc files, build: {})", "{} {}！(フォルダー {} 個、toc ファイル {} 個、バージョン: {})"]),
+    ("status.backup_reused", ["{} 个未变化的文件 ({}) 从旧备份创建了硬链接", "{} unchanged files ({}) were hardlinked from the previous backup", "変更のない {} 個のファイル ({}) を以前のバックアップからハードリンクしました"]),
+    ("status.backup_resuming", ["正在继续备份 {}，跳过已复制的文件...", "Resuming backup of {}, skipping files already copied...", "{} のバックアップを再開しています（コピー済みのファイルはスキップ）..."]),
+    ("status.resumable_backups", ["发现未完成的备份: {}，再次备份时将继续复制", "Found unfinished backups: {}; backing up again will resume them", "未完了のバックアップがあります: {}。再度バックアップすると続きからコピーします"]),
+    ("status.partial_discarded", ["已放弃 {} 未完成的备份", "Discarded the partial backup of {}", "{} の未完了のバックアップを破棄しました"]),
+    ("status.cleaned_staging", ["已清理 {} 个未完成的备份", "Cleaned up {} unfinished backups", "未完了のバックアップを {} 件削除しました"]),
+    ("status.restore_summary", ["语音已恢复为 {}！({} 个文件夹 [{}], {} 个toc文件)\n请添加启动项: +miles_language {}", "Voice restored to {}! ({} folders [{}], {} toc files)\nAdd the launch option: +miles_language {}", "音声を {} に復元しました！(フォルダー {} 個 [{}]、toc ファイル {} 個)\n起動オプションを追加してください: +miles_language {}"]),
+    ("status.defaults_saved", ["已保存默认设置: 备份方式 {}, 恢复方式 {}", "Defaults saved: backup mode {}, restore mode {}", "既定の設定を保存しました: バックアップ方式 {}、復元方式 {}"]),
+    ("status.voice_deleted", ["{} 语音文件已删除！({} 个文件夹, {} 个toc文件)", "{} voice files deleted! ({} folders, {} toc files)", "{} の音声ファイルを削除しました！(フォルダー {} 個、toc ファイル {} 個)"]),
+    ("status.backup_deleted", ["{} 备份已删除！", "{} backup deleted!", "{} のバックアップを削除しました！"]),
+    ("status.copied", ["已复制到剪贴板！", "Copied to clipboard!", "クリップボードにコピーしました！"]),
+
+    ("error.games_file_parse", ["games.toml 格式错误，已使用内置游戏列表: {}", "games.toml is invalid, using the built-in game list: {}", "games.toml の形式が正しくないため、内蔵のゲーム一覧を使用します: {}"]),
+    ("error.game_def_id", ["games.toml 中的游戏 id \"{}\" 无效（只能包含字母、数字、- 和 _）", "Game id \"{}\" in games.toml is invalid (letters, digits, - and _ only)", "games.toml のゲーム id「{}」が無効です（英数字、-、_ のみ使用可）"]),
+    ("error.game_def_field", ["games.toml 中游戏 \"{}\" 的 {} 无效，已跳过", "Game \"{}\" in games.toml has an invalid {}, skipped", "games.toml のゲーム「{}」の {} が無効なため、スキップしました"]),
+    ("error.game_def_duplicate", ["games.toml 中游戏 id \"{}\" 重复，已跳过", "Duplicate game id \"{}\" in games.toml, skipped", "games.toml のゲーム id「{}」が重複しているため、スキップしました"]),
+    ("error.game_running", ["{} 正在运行，请先关闭游戏", "{} is running, close the game first", "{} が実行中です。先にゲームを終了してください"]),
+    ("error.custom_lang_code", ["自定义语言 \"{}\" 的代码或文件夹代码无效（只能包含字母、数字、- 和 _）", "Custom language \"{}\" has an invalid code or folder code (letters, digits, - and _ only)", "カスタム言語「{}」のコードまたはフォルダーコードが無効です（英数字、-、_ のみ使用可）"]),
+    ("error.custom_lang_miles", ["自定义语言 \"{}\" 的 miles_lang 不能为空或包含空格", "Custom language \"{}\" needs a miles_lang value without spaces", "カスタム言語「{}」の miles_lang は空にできず、空白も含められません"]),
+    ("error.no_steam_launch_option", ["未检测到 Steam，无法修改启动选项", "Steam not detected, cannot change launch options", "Steam が検出されないため、起動オプションを変更できません"]),
+    ("error.launch_option_failed", ["修改启动选项失败: {}", "Failed to change launch options: {}", "起動オプションの変更に失敗しました: {}"]),
+    ("error.launch_failed", ["启动游戏失败: {}", "Failed to launch the game: {}", "ゲームの起動に失敗しました: {}"]),
+    ("error.select_folder_first", ["请先选择语音文件夹！", "Please select the voice folder first!", "先に音声フォルダーを選択してください！"]),
+    ("error.folder_not_found", ["所选文件夹不存在！", "The selected folder does not exist!", "選択したフォルダーが存在しません！"]),
+    ("error.voice_not_found", ["未找到语音文件: {} 或 vo{}", "No voice files found: {} or vo{}", "音声ファイルが見つかりません: {} または vo{}"]),
+    ("error.backup_incomplete", ["[!] {} 备份不完整！未找到语音文件夹，已取消备份", "[!] {} backup incomplete! No voice folder found, backup cancelled", "[!] {} のバックアップが不完全です！音声フォルダーが見つからないため中止しました"]),
+    ("error.move_junction", ["{} 已是链接，无法移动，请使用复制方式备份", "{} is already a link and cannot be moved, use copy mode instead", "{} は既にリンクのため移動できません。コピー方式でバックアップしてください"]),
+    ("error.disk_space", ["[!] 备份分区空间不足！需要 {}，可用 {}", "[!] Not enough space on the backup drive! Required {}, available {}", "[!] バックアップ先の空き容量が不足しています！必要 {}、空き {}"]),
+    ("error.clean_staging", ["清理临时目录失败: {}", "Failed to clean up the temporary folder: {}", "一時フォルダーの削除に失敗しました: {}"]),
+    ("error.create_dir", ["创建目录失败: {}", "Failed to create folder: {}", "フォルダーの作成に失敗しました: {}"]),
+    ("error.backup_item", ["备份 {} 失败: {}", "Failed to back up {}: {}", "{} のバックアップに失敗しました: {}"]),
+    ("error.remove_old_backup", ["删除旧备份失败: {}", "Failed to delete the old backup: {}", "古いバックアップの削除に失敗しました: {}"]),
+    ("error.save_backup", ["保存备份失败: {}", "Failed to save the backup: {}", "バックアップの保存に失敗しました: {}"]),
+    ("error.link_rolled_back", ["创建链接 {} 失败: {}，已回滚", "Failed to create link {}: {}, changes rolled back", "リンク {} の作成に失敗しました: {}。元に戻しました"]),
+    ("error.rollback_failed", ["回滚失败！{} 仍在备份目录中: {}", "Rollback failed! {} is still in the backup folder: {}", "ロールバックに失敗しました！{} はバックアップフォルダーに残っています: {}"]),
+    ("error.orphaned_move", ["[!] 发现未完成的移动备份，请手动检查: {}", "[!] Found an unfinished move backup, please check it manually: {}", "[!] 未完了の移動バックアップがあります。手動で確認してください: {}"]),
+    ("error.select_game_folder_first", ["请先选择游戏语音文件夹！", "Please select the game voice folder first!", "先にゲームの音声フォルダーを選択してください！"]),
+    ("error.no_backups", ["没有可用的备份！", "No backups available!", "利用できるバックアップがありません！"]),
+    ("error.backup_missing", ["备份文件不存在！", "The backup files do not exist!", "バックアップファイルが存在しません！"]),
+    ("error.version_mismatch", ["[!] 版本不匹配！备份: {}, 当前: {}\n请先删除游戏中的语音文件，然后重新执行所有步骤；确认语音未变化时可以勾选“仍要恢复”", "[!] Version mismatch! Backup: {}, current: {}\nDelete the voice files from the game first, then redo all steps, or tick \"restore anyway\" if the voice files did not change", "[!] バージョンが一致しません！バックアップ: {}、現在: {}\nゲームの音声ファイルを削除してから、すべての手順をやり直してください。音声が変わっていない場合は「復元する」にチェックを入れてください"]),
+    ("error.target_exists", ["{} 已存在，请先删除游戏语音", "{} already exists, delete the game voice files first", "{} は既に存在します。先にゲーム音声を削除してください"]),
+    ("error.junction_failed", ["创建 Junction 失败: {}", "Failed to create junction: {}", "ジャンクションの作成に失敗しました: {}"]),
+    ("error.restore_item_mode", ["恢复 {} 失败 ({}): {}", "Failed to restore {} ({}): {}", "{} の復元に失敗しました ({}): {}"]),
+    ("error.restore_item", ["恢复 {} 失败: {}", "Failed to restore {}: {}", "{} の復元に失敗しました: {}"]),
+    ("error.backup_empty", ["备份中没有找到语音文件", "No voice files found in the backup", "バックアップに音声ファイルがありません"]),
+    ("error.save_settings", ["保存设置失败: {}", "Failed to save settings: {}", "設定の保存に失敗しました: {}"]),
+    ("error.delete_item", ["删除 {} 失败: {}", "Failed to delete {}: {}", "{} の削除に失敗しました: {}"]),
+    ("error.no_backup_to_delete", ["没有可删除的备份！", "No backup to delete!", "削除できるバックアップがありません！"]),
+    ("error.delete_backup", ["删除备份失败: {}", "Failed to delete the backup: {}", "バックアップの削除に失敗しました: {}"]),
+    ("error.backup_in_use", ["[!] 游戏目录中有 {} 个语音文件夹链接到此备份，删除后游戏将无法加载这些语音。请先将它们转换为实际文件", "[!] {} voice folders in the game directory link to this backup; deleting it would break them. Convert them to real files first", "[!] ゲームフォルダーの {} 個の音声フォルダーがこのバックアップにリンクしています。削除すると音声を読み込めなくなるため、先に実ファイルに変換してください"]),
+    ("error.link_target_missing", ["链接目标不存在: {}", "Link target not found: {}", "リンク先が見つかりません: {}"]),
+    ("error.materialize", ["转换 {} 失败: {}", "Failed to convert {}: {}", "{} の変換に失敗しました: {}"]),
+    ("error.backup_exists", ["已存在 {} 的备份，请先删除", "A {} backup already exists, delete it first", "{} のバックアップは既に存在します。先に削除してください"]),
+    ("error.import_invalid", ["不是有效的备份导出文件", "Not a valid exported backup", "有効なバックアップのエクスポートファイルではありません"]),
+    ("error.import_wrong_game", ["该备份属于其他游戏 ({})", "This backup belongs to another game ({})", "このバックアップは別のゲームのものです ({})"]),
+    ("error.import_version", ["[!] 版本不匹配！导入的备份: {}, 当前: {}", "[!] Version mismatch! Imported backup: {}, current: {}", "[!] バージョンが一致しません！インポートするバックアップ: {}、現在: {}"]),
+    ("error.import_unknown_language", ["导入的备份语言未知: {}", "The imported backup has an unknown language: {}", "インポートするバックアップの言語が不明です: {}"]),
+    ("error.import_bad_path", ["导入的清单中有无效的路径: {}", "The imported manifest contains an invalid path: {}", "インポートするマニフェストに無効なパスがあります: {}"]),
+    ("error.import_file_mismatch", ["文件缺失或校验失败: {}", "File missing or failed verification: {}", "ファイルが見つからないか検証に失敗しました: {}"]),
+    ("error.backup_damaged", ["备份不完整，已取消恢复：缺少 {} 个文件，{} 个 .toc 文件与备份时不一致。请重新备份该语言:\n{}", "The backup is incomplete, restore cancelled: {} files are missing and {} .toc files differ from when they were backed up. Back up this language again:\n{}", "バックアップが不完全なため復元を中止しました: {} 個のファイルがなく、{} 個の .toc ファイルがバックアップ時と異なります。この言語を再度バックアップしてください:\n{}"]),
+    ("error.restore_unverified", ["恢复后检查发现 {} 项与备份不一致，游戏可能无法加载语音，请重新恢复:\n{}", "After restoring, {} items do not match the backup and the game may fail to load the voice; restore again:\n{}", "復元後の確認で {} 項目がバックアップと一致しません。ゲームが音声を読み込めない可能性があるため、再度復元してください:\n{}"]),
+    ("error.verify_missing", ["备份中缺少 {}", "The backup is missing {}", "バックアップに {} がありません"]),
+    ("error.verify_failed", ["无法读取 {}: {}", "Cannot read {}: {}", "{} を読み取れません: {}"]),
+    ("error.no_installed_languages", ["游戏目录中没有检测到已安装的语音语言", "No installed voice languages were found in the game folder", "ゲームフォルダーにインストール済みの音声言語が見つかりません"]),
+    ("error.backup_all_failed", ["所有语言都未能备份:\n{}", "No language could be backed up:\n{}", "どの言語もバックアップできませんでした:\n{}"]),
+    ("error.backup_missing_files", [".toc 文件引用的 {} 个文件不在备份中，已取消备份:\n{}", "{} files referenced by the .toc files are missing from the backup, backup cancelled:\n{}", ".toc ファイルが参照する {} 個のファイルがバックアップにないため中止しました:\n{}"]),
+    ("error.locked_by", ["{}（文件被以下程序占用: {}，请关闭后重试）", "{} (the files are in use by: {}; close them and try again)", "{}（次のプログラムがファイルを使用中です: {}。終了してから再試行してください）"]),
+    ("error.elevate", ["无法以管理员身份重新启动: {}", "Could not restart as administrator: {}", "管理者として再起動できませんでした: {}"]),
+    ("error.profile_name_empty", ["请输入方案名称", "Enter a profile name", "プロファイル名を入力してください"]),
+    ("error.no_profile_selected", ["请先选择方案", "Select a profile first", "先にプロファイルを選択してください"]),
+    ("error.switch_in_use", ["[!] 游戏当前依赖{}（{}），切换时删除其 .toc 文件后游戏可能在启动时崩溃；请先改用其他语言，或在主界面中删除", "[!] The game currently depends on {} ({}); deleting its .toc files while switching can make the game crash on launch. Change the language first, or delete it from the main window", "[!] ゲームは現在{}（{}）に依存しています。切り替え時に .toc ファイルを削除すると起動時にクラッシュする可能性があります。先に言語を変更するか、メイン画面から削除してください"]),
+    ("error.unknown_language", ["未知的语言代码: {}", "Unknown language code: {}", "不明な言語コード: {}"]),
+    ("error.invalid_hotkey", ["设置中的热键格式无效: {}（示例: Ctrl+Alt+1）", "Invalid hotkey in settings: {} (example: Ctrl+Alt+1)", "設定のホットキーが無効です: {}（例: Ctrl+Alt+1）"]),
+    ("error.hotkey_unavailable", ["热键 {} 已被其他程序占用", "Hotkey {} is already in use by another program", "ホットキー {} は他のプログラムで使用中です"]),
+    ("error.update_download", ["下载新版本失败: {}", "Failed to download the new version: {}", "新しいバージョンをダウンロードできませんでした: {}"]),
+    ("error.update_no_checksum", ["发布中没有校验值，无法安全更新，请手动下载", "The release has no checksum, so it cannot be updated safely. Please download it manually", "リリースにチェックサムがないため安全に更新できません。手動でダウンロードしてください"]),
+    ("error.update_hash_mismatch", ["[!] 下载的文件与发布的校验值不符，已取消更新", "[!] The downloaded file does not match the published checksum, update cancelled", "[!] ダウンロードしたファイルが公開されたチェックサムと一致しないため、更新を中止しました"]),
+    ("error.update_install", ["无法替换程序文件: {}", "Could not replace the program file: {}", "プログラムファイルを置き換えられませんでした: {}"]),
+    ("error.cancelled", ["操作已取消", "Operation cancelled", "操作はキャンセルされました"]),
+    ("error.crashed", ["程序发生错误，即将退出。错误信息已保存到:\n{}\n反馈问题时请附上该文件", "The program hit an unexpected error and will close. The details were saved to:\n{}\nPlease attach this file when reporting the problem", "予期しないエラーが発生したため終了します。詳細は次の場所に保存されました:\n{}\n問題を報告する際はこのファイルを添付してください"]),
+    ("error.crashed_no_report", ["程序发生错误，即将退出，且无法保存错误报告", "The program hit an unexpected error and will close. The error report could not be saved", "予期しないエラーが発生したため終了します。エラーレポートを保存できませんでした"]),
+    ("error.launch_unsupported", ["该游戏没有设置主程序，无法直接启动", "This game has no executable configured and cannot be started directly", "このゲームには実行ファイルが設定されていないため、直接起動できません"]),
+    ("error.launch_option_ea", ["EA App 安装无法自动写入启动参数，请在 EA App 的游戏属性中手动添加", "Launch options cannot be written for EA App installs; add the parameter in the game's properties in EA App", "EA App のインストールには起動オプションを書き込めません。EA App のゲームのプロパティで手動で追加してください"]),
+    ("error.launch_option_xbox", ["Xbox 应用不支持启动参数，请使用“启动游戏”按钮以所选语音启动", "The Xbox app does not support launch options; use the launch button to start with the selected voice", "Xbox アプリは起動オプションに対応していません。起動ボタンを使って選択した音声で起動してください"]),
+    ("error.schedule_task", ["计划任务操作失败: {}", "Scheduled task operation failed: {}", "タスクの操作に失敗しました: {}"]),
+    ("error.profile_not_found", ["找不到方案: {}", "Profile not found: {}", "プロファイルが見つかりません: {}"]),
+    ("error.shortcut", ["创建快捷方式失败: {}", "Failed to create shortcut: {}", "ショートカットの作成に失敗しました: {}"]),
+    ("error.repair_incomplete", ["有 {} 个语音文件夹未能修复，请在窗口中重新恢复", "{} voice folders could not be repaired; restore them again in the window", "{} 個の音声フォルダーを修復できませんでした。ウィンドウで再度復元してください"]),
+    ("error.network_access", ["无法访问网络共享 {}: {}。请先在资源管理器中打开该共享并登录，或检查网络连接", "Cannot access the network share {}: {}. Open the share in Explorer and sign in first, or check the network connection", "ネットワーク共有 {} にアクセスできません: {}。先にエクスプローラーで共有を開いてサインインするか、ネットワーク接続を確認してください"]),
+    ("error.cloud_protect", ["设置 {} 同步选项失败: {}", "Failed to change {} sync options: {}", "{} の同期オプションの変更に失敗しました: {}"]),
+    ("error.backup_inside_game", ["备份目录 {} 与游戏目录重叠，请先用“移动备份”把备份移到游戏目录之外", "The backup folder {} overlaps the game folder; use Move backups to relocate it outside the game folder first", "バックアップフォルダー {} がゲームフォルダーと重なっています。先に「バックアップを移動」でゲームフォルダーの外に移動してください"]),
+    ("error.relocate_inside", ["新的备份位置 {} 不能与当前备份目录相同或互相包含", "The new backup location {} cannot be the current backup folder, inside it or contain it", "新しいバックアップ先 {} は現在のバックアップフォルダーと同じか、互いに含む場所にはできません"]),
+    ("error.relocate_not_empty", ["{} 已存在且不为空，请选择其他位置", "{} already exists and is not empty; choose another location", "{} は既に存在し空ではありません。別の場所を選択してください"]),
+    ("error.relocate_network_links", ["Junction 不能指向网络共享，请先将 {} 个已恢复的链接转换为实际文件", "Junctions cannot point to a network share; convert the {} restored links to real files first", "ジャンクションはネットワーク共有を指せません。先に復元済みの {} 個のリンクを実ファイルに変換してください"]),
+    ("error.compare_compressed", ["压缩的备份需要先解压才能比较", "Decompress the backup before comparing it", "比較する前にバックアップを展開してください"]),
+    ("error.empty_backup_label", ["请输入副本的名称", "Enter a name for the copy", "コピーの名前を入力してください"]),
+    ("error.retention_disabled", ["请先设置要保留的版本数或备份大小上限", "Set the number of builds to keep or a backup size limit first", "先に保持するビルド数またはバックアップサイズの上限を設定してください"]),
+    ("error.user_cfg", ["写入 user.cfg 失败: {}", "Failed to write user.cfg: {}", "user.cfg の書き込みに失敗しました: {}"]),
+    ("error.user_cfg_not_found", ["找不到游戏安装目录，无法写入 user.cfg", "Game install folder not found; cannot write user.cfg", "ゲームのインストールフォルダーが見つからないため user.cfg を書き込めません"]),
+    ("error.no_other_voices", ["游戏目录中没有可以删除的其他语言语音", "No other voice languages to remove in the game folder", "ゲームフォルダーに削除できる他の言語の音声はありません"]),
+    ("error.cleanup_failed", ["所有语言都未能删除:\n{}", "No language could be removed:\n{}", "どの言語も削除できませんでした:\n{}"]),
+    ("error.security_settings", ["无法打开 Windows 安全中心: {}", "Could not open Windows Security: {}", "Windows セキュリティを開けませんでした: {}"]),
+    ("error.robocopy", ["robocopy 复制失败（退出码 {}）: {}", "robocopy failed (exit code {}): {}", "robocopy でのコピーに失敗しました（終了コード {}）: {}"]),
+    ("error.not_a_directory", ["{} 不是文件夹，请拖入游戏的语音文件夹", "{} is not a folder; drop the game's voice folder instead", "{} はフォルダーではありません。ゲームの音声フォルダーをドロップしてください"]),
+    ("error.task_failed", ["后台任务异常终止", "The background task terminated unexpectedly", "バックグラウンド処理が異常終了しました"]),
+    ("error.task_running", ["请等待当前操作完成", "Please wait for the current operation to finish", "現在の操作が終わるまでお待ちください"]),
+    ("error.steam_running", ["请先完全退出 Steam，否则 Steam 会覆盖修改", "Please exit Steam completely first, otherwise Steam overwrites the change", "先に Steam を完全に終了してください。終了しないと変更が上書きされます"]),
+    ("error.localconfig_not_found", ["未找到 Steam 用户配置 localconfig.vdf", "Steam user config localconfig.vdf not found", "Steam のユーザー設定 localconfig.vdf が見つかりません"]),
+    ("error.localconfig_backup", ["备份 localconfig.vdf 失败: {}", "Failed to back up localconfig.vdf: {}", "localconfig.vdf のバックアップに失敗しました: {}"]),
+    ("error.vdf_unterminated", ["VDF 字符串未闭合", "Unterminated string in VDF", "VDF の文字列が閉じられていません"]),
+    ("error.vdf_invalid", ["VDF 结构无效", "Invalid VDF structure", "VDF の構造が無効です"]),
+    ("error.vdf_braces", ["VDF 括号不匹配", "Mismatched braces in VDF", "VDF の括弧が一致しません"]),
+    ("error.vdf_missing_node", ["VDF 中缺少 \"{}\" 节点", "VDF is missing the \"{}\" node", "VDF に \"{}\" ノードがありません"]),
+    ("error.vdf_write", ["VDF 写入失败", "Failed to write VDF", "VDF の書き込みに失敗しました"]),
+    ("error.vdf_not_value", ["VDF 中 \"{}\" 不是值", "\"{}\" in VDF is not a value", "VDF の \"{}\" は値ではありません"]),
+];
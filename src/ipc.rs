@@ -0,0 +1,165 @@
+//! 命名管道：窗口已打开时，命令行把命令交给窗口执行并逐行接收进度和结果，避免两个进程同时修改备份目录
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::iter;
+use std::os::windows::io::{FromRawHandle, RawHandle};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use windows_sys::Win32::Foundation::{GetLastError, ERROR_PIPE_CONNECTED, INVALID_HANDLE_VALUE};
+use windows_sys::Win32::Storage::FileSystem::PIPE_ACCESS_DUPLEX;
+use windows_sys::Win32::System::Pipes::{ConnectNamedPipe, CreateNamedPipeW, PIPE_READMODE_BYTE, PIPE_TYPE_BYTE, PIPE_WAIT};
+
+use crate::cli::{Command, Outcome};
+
+const PIPE_NAME: &str = r"\\.\pipe\BF6VoiceSwitcher";
+const BUFFER_SIZE: u32 = 4096;
+/// 管道忙（另一个命令行正在使用）时的重试次数和间隔
+const BUSY_RETRIES: u32 = 50;
+const BUSY_WAIT: Duration = Duration::from_millis(100);
+const ERROR_PIPE_BUSY: i32 = 231;
+
+/// 窗口发回命令行的消息，每条为一行 JSON
+#[derive(Serialize, Deserialize)]
+pub enum Reply {
+    Progress(String),
+    Done(Result<Outcome, String>),
+}
+
+/// 窗口收到的一条命令
+pub struct Request {
+    pub command: Command,
+    replies: Sender<Reply>,
+    /// 最近发送的进度百分比，进度没有变化时不再发送
+    last_percent: Option<u32>,
+}
+
+impl Request {
+    pub fn progress(&mut self, percent: u32, current_file: &str) {
+        if self.last_percent != Some(percent) {
+            self.last_percent = Some(percent);
+            let _ = self.replies.send(Reply::Progress(format!("{:>3}% {}", percent, current_file)));
+        }
+    }
+
+    pub fn message(&self, message: &str) {
+        let _ = self.replies.send(Reply::Progress(message.to_string()));
+    }
+
+    pub fn finish(self, result: Result<Outcome, String>) {
+        let _ = self.replies.send(Reply::Done(result));
+    }
+}
+
+pub struct Server {
+    requests: Receiver<Request>,
+}
+
+impl Server {
+    /// 在单独的线程中逐个接受命令行的连接，收到命令后唤醒界面线程
+    pub fn spawn(ctx: egui::Context) -> Self {
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || loop {
+            let Some(pipe) = accept() else {
+                warn!("failed to create named pipe");
+                return;
+            };
+            if let Err(e) = serve(pipe, &sender, &ctx) {
+                warn!("pipe client failed: {}", e);
+            }
+        });
+        Server { requests }
+    }
+
+    /// 取出下一条等待执行的命令
+    pub fn next(&self) -> Option<Request> {
+        self.requests.try_recv().ok()
+    }
+}
+
+/// 创建管道实例并等待连接
+fn accept() -> Option<File> {
+    let name: Vec<u16> = PIPE_NAME.encode_utf16().chain(iter::once(0)).collect();
+    // SAFETY: 名称以 0 结尾且在调用期间有效，使用默认的安全属性
+    let handle = unsafe {
+        CreateNamedPipeW(
+            name.as_ptr(),
+            PIPE_ACCESS_DUPLEX,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT,
+            1,
+            BUFFER_SIZE,
+            BUFFER_SIZE,
+            0,
+            std::ptr::null(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        return None;
+    }
+    // SAFETY: handle 为刚创建的管道；客户端在创建和等待之间连接时返回 ERROR_PIPE_CONNECTED，同样视为已连接
+    let connected = unsafe { ConnectNamedPipe(handle, std::ptr::null_mut()) != 0 || GetLastError() == ERROR_PIPE_CONNECTED };
+    // SAFETY: 句柄归 File 所有，释放时关闭
+    let pipe = unsafe { File::from_raw_handle(handle as RawHandle) };
+    if connected {
+        Some(pipe)
+    } else {
+        drop(pipe);
+        accept()
+    }
+}
+
+fn serve(pipe: File, sender: &Sender<Request>, ctx: &egui::Context) -> io::Result<()> {
+    let mut reader = BufReader::new(pipe.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let command: Command = serde_json::from_str(&line)?;
+    info!("received command from pipe: {:?}", command);
+
+    let (replies, receiver) = mpsc::channel();
+    let _ = sender.send(Request { command, replies, last_percent: None });
+    ctx.request_repaint();
+    let mut pipe = pipe;
+    for reply in receiver {
+        let done = matches!(reply, Reply::Done(_));
+        writeln!(pipe, "{}", serde_json::to_string(&reply)?)?;
+        if done {
+            break;
+        }
+    }
+    pipe.flush()
+}
+
+/// 窗口已打开时把命令交给窗口执行，on_progress 接收进度，返回 None 表示没有打开的窗口
+pub fn forward(command: &Command, mut on_progress: impl FnMut(&str)) -> Option<Result<Outcome, String>> {
+    let mut pipe = None;
+    for _ in 0..BUSY_RETRIES {
+        match OpenOptions::new().read(true).write(true).open(PIPE_NAME) {
+            Ok(file) => {
+                pipe = Some(file);
+                break;
+            }
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) => thread::sleep(BUSY_WAIT),
+            Err(_) => return None,
+        }
+    }
+    let mut pipe = pipe?;
+    info!("forwarding command to running instance: {:?}", command);
+    let sent = serde_json::to_string(command).map_err(io::Error::from).and_then(|line| writeln!(pipe, "{}", line));
+    if let Err(e) = sent {
+        return Some(Err(e.to_string()));
+    }
+    for line in BufReader::new(pipe).lines() {
+        match line.map_err(|e| e.to_string()).and_then(|line| serde_json::from_str(&line).map_err(|e| e.to_string())) {
+            Ok(Reply::Progress(message)) => on_progress(&message),
+            Ok(Reply::Done(result)) => return Some(result),
+            Err(e) => return Some(Err(e)),
+        }
+    }
+    // 窗口在命令完成前关闭
+    Some(Err(io::Error::from(io::ErrorKind::BrokenPipe).to_string()))
+}
@@ -0,0 +1,103 @@
+//! 操作日志（journal.jsonl，位于各游戏的备份目录）：记录本工具在游戏目录中创建和删除的链接、文件夹和 .toc 文件，
+//! 用于撤销所有修改
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::link::RestoreMode;
+
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    /// 创建了 Junction
+    Junction,
+    /// 创建了符号链接
+    Symlink,
+    /// 复制或硬链接了文件夹
+    Folder,
+    /// 复制了 .toc 文件
+    Toc,
+    /// 删除了之前创建的项目
+    Removed,
+    /// 确认后恢复了版本不匹配的备份，路径为备份目录
+    VersionOverride,
+}
+
+impl EntryKind {
+    /// 按恢复方式创建的文件夹对应的记录类型
+    pub fn for_mode(mode: RestoreMode) -> Self {
+        match mode {
+            RestoreMode::Junction => EntryKind::Junction,
+            RestoreMode::Symlink => EntryKind::Symlink,
+            RestoreMode::Hardlink | RestoreMode::Copy => EntryKind::Folder,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub kind: EntryKind,
+    pub path: PathBuf,
+    /// 补充说明，例如版本不匹配时的备份版本和当前版本
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub fn new(backup_dir: &Path) -> Self {
+        Journal { path: backup_dir.join(JOURNAL_FILE) }
+    }
+
+    /// 追加一条记录，写入失败不影响操作本身
+    pub fn record(&self, kind: EntryKind, path: &Path) {
+        self.append(JournalEntry { kind, path: path.to_path_buf(), detail: None });
+    }
+
+    /// 记录确认后恢复的版本不匹配的备份
+    pub fn record_version_override(&self, backup_path: &Path, backup: &str, current: &str) {
+        let detail = format!("backup {} != current {}", backup, current);
+        self.append(JournalEntry { kind: EntryKind::VersionOverride, path: backup_path.to_path_buf(), detail: Some(detail) });
+    }
+
+    fn append(&self, entry: JournalEntry) {
+        let Ok(line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&self.path) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+
+    /// 每个路径最后的状态，已删除的项目和版本不匹配的记录不包含在内
+    pub fn created(&self) -> Vec<JournalEntry> {
+        let content = fs::read_to_string(&self.path).unwrap_or_default();
+        let mut order = Vec::new();
+        let mut last: HashMap<PathBuf, EntryKind> = HashMap::new();
+        let entries = content.lines().filter_map(|line| serde_json::from_str::<JournalEntry>(line).ok());
+        for entry in entries.filter(|entry| entry.kind != EntryKind::VersionOverride) {
+            if last.insert(entry.path.clone(), entry.kind).is_none() {
+                order.push(entry.path);
+            }
+        }
+        order
+            .into_iter()
+            .filter_map(|path| {
+                let kind = last[&path];
+                (kind != EntryKind::Removed).then_some(JournalEntry { kind, path, detail: None })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,277 @@
+//! 恢复语音文件夹的各种方式：Junction、目录符号链接、逐文件硬链接和完整复制
+
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::os::windows::fs::OpenOptionsExt;
+use std::os::windows::io::AsRawHandle;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use windows_sys::Win32::Storage::FileSystem::{FILE_FLAG_BACKUP_SEMANTICS, FILE_FLAG_OPEN_REPARSE_POINT};
+use windows_sys::Win32::System::IO::DeviceIoControl;
+
+use crate::copy::{self, CopyOptions, Progress};
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+use crate::retry;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestoreMode {
+    /// 目录 Junction（默认，不占额外空间，但不能指向网络路径）
+    #[default]
+    Junction,
+    /// 目录符号链接（需要管理员权限或开发者模式）
+    Symlink,
+    /// 逐文件硬链接（仅限同一分区）
+    Hardlink,
+    /// 完整复制（占用双倍空间，兼容性最好）
+    Copy,
+}
+
+impl RestoreMode {
+    pub const ALL: [RestoreMode; 4] = [
+        RestoreMode::Junction,
+        RestoreMode::Symlink,
+        RestoreMode::Hardlink,
+        RestoreMode::Copy,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RestoreMode::Junction => t!("mode.junction"),
+            RestoreMode::Symlink => t!("mode.symlink"),
+            RestoreMode::Hardlink => t!("mode.hardlink"),
+            RestoreMode::Copy => t!("mode.copy"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            RestoreMode::Junction => t!("mode.junction_hint"),
+            RestoreMode::Symlink => t!("mode.symlink_hint"),
+            RestoreMode::Hardlink => t!("mode.hardlink_hint"),
+            RestoreMode::Copy => t!("mode.copy_hint"),
+        }
+    }
+}
+
+/// 检查 src 和 dst 所在分区是否支持该恢复方式，不支持时返回原因；无法查询分区信息时视为支持
+pub fn unsupported_reason(mode: RestoreMode, src: &Path, dst: &Path) -> Option<String> {
+    // 网络共享上的备份只能复制：Junction 不能指向网络路径，符号链接默认不允许从本地指向远程
+    if mode != RestoreMode::Copy && disk::is_network(src) {
+        return Some(t!("mode.fs_remote_target").to_string());
+    }
+    let dst_volume = disk::volume_info(dst)?;
+    match mode {
+        RestoreMode::Junction | RestoreMode::Symlink if !dst_volume.supports_reparse_points() => {
+            Some(t!("mode.fs_no_links", dst_volume.filesystem))
+        }
+        RestoreMode::Hardlink if !disk::same_volume(src, dst) => Some(t!("mode.fs_cross_volume").to_string()),
+        RestoreMode::Hardlink if !dst_volume.supports_hard_links() => Some(t!("mode.fs_no_hardlinks", dst_volume.filesystem)),
+        _ => None,
+    }
+}
+
+/// 按指定方式将 src 文件夹恢复到 dst（dst 不能已存在）
+pub fn restore_folder(mode: RestoreMode, src: &Path, dst: &Path, workers: usize, progress: &Progress) -> Result<(), SwitcherError> {
+    match mode {
+        RestoreMode::Junction => create_junction(src, dst),
+        RestoreMode::Symlink => Ok(std::os::windows::fs::symlink_dir(src, dst)?),
+        RestoreMode::Hardlink => Ok(hardlink_tree(src, dst)?),
+        RestoreMode::Copy => Ok(copy::copy_dir(src, dst, &CopyOptions::new(workers), progress)?),
+    }
+}
+
+/// 递归创建目录结构，并为每个文件创建硬链接
+fn hardlink_tree(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)?.flatten() {
+        let path = entry.path();
+        let target = dst.join(entry.file_name());
+        if path.is_dir() {
+            hardlink_tree(&path, &target)?;
+        } else {
+            fs::hard_link(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// 移动文件夹：同一分区内直接重命名，跨分区时复制后删除源文件夹
+pub fn move_dir(src: &Path, dst: &Path, workers: usize, progress: &Progress) -> Result<(), SwitcherError> {
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if retry::retry("move", &[src], || fs::rename(src, dst)).is_ok() {
+        return Ok(());
+    }
+    copy::copy_dir(src, dst, &CopyOptions::new(workers), progress)?;
+    retry::retry("remove", &[src], || fs::remove_dir_all(src))?;
+    Ok(())
+}
+
+/// 创建 Junction：直接写入重解析点，不经过 cmd 的 mklink，不受 MAX_PATH 限制
+pub fn create_junction(src: &Path, dst: &Path) -> Result<(), SwitcherError> {
+    let target = std::path::absolute(src)?;
+    retry::retry("create", &[dst], || fs::create_dir(dst))?;
+    if let Err(e) = retry::retry("link", &[dst], || set_mount_point(dst, &target)) {
+        let _ = fs::remove_dir(dst);
+        return Err(SwitcherError::JunctionFailed(e.to_string()));
+    }
+    info!("created junction {} -> {}", dst.display(), src.display());
+    Ok(())
+}
+
+/// 将空目录 dir 设为指向 target 的挂载点（Junction）
+fn set_mount_point(dir: &Path, target: &Path) -> io::Result<()> {
+    const FSCTL_SET_REPARSE_POINT: u32 = 0x0009_00A4;
+
+    let buffer = mount_point_buffer(target)?;
+    let file = OpenOptions::new()
+        .write(true)
+        .custom_flags(FILE_FLAG_OPEN_REPARSE_POINT | FILE_FLAG_BACKUP_SEMANTICS)
+        .open(dir)?;
+    let mut returned = 0u32;
+    // SAFETY: 句柄在 file 存活期间有效，buffer 在调用期间有效且长度与传入的大小一致
+    let ok = unsafe {
+        DeviceIoControl(
+            file.as_raw_handle(),
+            FSCTL_SET_REPARSE_POINT,
+            buffer.as_ptr().cast(),
+            buffer.len() as u32,
+            std::ptr::null_mut(),
+            0,
+            &mut returned,
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// 指向 target 的挂载点的重解析数据（REPARSE_DATA_BUFFER）
+fn mount_point_buffer(target: &Path) -> io::Result<Vec<u8>> {
+    const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA000_0003;
+    const HEADER_LEN: usize = 8;
+
+    // 重解析数据中的路径不能带 \\?\ 前缀：替代名称使用 NT 路径 \??\，显示名称使用普通路径
+    let print = target.to_string_lossy();
+    let print = print.strip_prefix(r"\\?\").unwrap_or(&print).to_string();
+    let substitute: Vec<u16> = format!(r"\??\{}", print).encode_utf16().collect();
+    let print: Vec<u16> = print.encode_utf16().collect();
+    let substitute_len = substitute.len() * 2;
+    let print_len = print.len() * 2;
+    let data_len = HEADER_LEN + substitute_len + 2 + print_len + 2;
+    let field = |value: usize| u16::try_from(value).map_err(|_| io::Error::other("junction target path is too long"));
+
+    let mut buffer = Vec::with_capacity(HEADER_LEN + data_len);
+    buffer.extend_from_slice(&IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes());
+    buffer.extend_from_slice(&field(data_len)?.to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes());
+    buffer.extend_from_slice(&field(substitute_len)?.to_le_bytes());
+    buffer.extend_from_slice(&field(substitute_len + 2)?.to_le_bytes());
+    buffer.extend_from_slice(&field(print_len)?.to_le_bytes());
+    for c in substitute.into_iter().chain([0]).chain(print).chain([0]) {
+        buffer.extend_from_slice(&c.to_le_bytes());
+    }
+    Ok(buffer)
+}
+
+/// 检查路径是否为 Junction（或其他重解析点，如目录符号链接）
+pub fn is_junction(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+
+    if let Ok(metadata) = fs::symlink_metadata(path) {
+        (metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT) != 0
+    } else {
+        false
+    }
+}
+
+/// 读取 Junction 指向的目标路径
+pub fn read_junction_target(path: &Path) -> Option<PathBuf> {
+    let target = fs::read_link(path).ok()?;
+    let target_str = target.to_string_lossy();
+    match target_str.strip_prefix("\\\\?\\") {
+        Some(stripped) => Some(PathBuf::from(stripped)),
+        None => Some(target),
+    }
+}
+
+/// 链接目标 target 是否位于 dir 中（不区分大小写）
+pub fn points_into(target: &Path, dir: &Path) -> bool {
+    let lower = |p: &Path| p.to_string_lossy().to_lowercase().trim_end_matches('\\').to_string();
+    let (target, dir) = (lower(target), lower(dir));
+    target == dir || target.starts_with(&format!("{}\\", dir))
+}
+
+/// 删除 Junction（只删除链接本身，不影响链接目标）
+pub fn remove_junction(path: &Path) -> Result<(), std::io::Error> {
+    retry::retry("remove", &[path], || fs::remove_dir(path))?;
+    info!("removed junction {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(buffer: &[u8], offset: usize) -> usize {
+        u16::from_le_bytes([buffer[offset], buffer[offset + 1]]) as usize
+    }
+
+    fn text_at(buffer: &[u8], offset: usize, len: usize) -> String {
+        let units: Vec<u16> = buffer[offset..offset + len].chunks(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        String::from_utf16(&units).unwrap()
+    }
+
+    /// 按 REPARSE_DATA_BUFFER 的布局解析出 (替代名称, 显示名称)
+    fn parse(buffer: &[u8]) -> (String, String) {
+        assert_eq!(u32::from_le_bytes(buffer[0..4].try_into().unwrap()), 0xA000_0003);
+        assert_eq!(u16_at(buffer, 4), buffer.len() - 8);
+        assert_eq!(u16_at(buffer, 6), 0);
+        let path_buffer = 16;
+        let (sub_offset, sub_len) = (u16_at(buffer, 8), u16_at(buffer, 10));
+        let (print_offset, print_len) = (u16_at(buffer, 12), u16_at(buffer, 14));
+        assert_eq!(sub_offset, 0);
+        assert_eq!(print_offset, sub_len + 2);
+        // 两个名称都以 0 结尾
+        assert_eq!(u16_at(buffer, path_buffer + sub_len), 0);
+        assert_eq!(u16_at(buffer, path_buffer + print_offset + print_len), 0);
+        assert_eq!(buffer.len(), path_buffer + print_offset + print_len + 2);
+        (text_at(buffer, path_buffer + sub_offset, sub_len), text_at(buffer, path_buffer + print_offset, print_len))
+    }
+
+    #[test]
+    fn mount_point_buffer_layout() {
+        let buffer = mount_point_buffer(Path::new(r"D:\Backups\en")).unwrap();
+        assert_eq!(parse(&buffer), (r"\??\D:\Backups\en".to_string(), r"D:\Backups\en".to_string()));
+    }
+
+    #[test]
+    fn mount_point_buffer_strips_verbatim_prefix() {
+        let buffer = mount_point_buffer(Path::new(r"\\?\D:\Backups\en")).unwrap();
+        assert_eq!(parse(&buffer), (r"\??\D:\Backups\en".to_string(), r"D:\Backups\en".to_string()));
+    }
+
+    #[test]
+    fn mount_point_buffer_non_ascii_and_long() {
+        let target = format!(r"D:\备份\日本語\{}", "语音".repeat(200));
+        let (substitute, print) = parse(&mount_point_buffer(Path::new(&target)).unwrap());
+        assert_eq!(substitute, format!(r"\??\{}", target));
+        assert_eq!(print, target);
+    }
+
+    #[test]
+    fn mount_point_buffer_too_long() {
+        let target = format!(r"D:\{}", "a".repeat(20000));
+        assert!(mount_point_buffer(Path::new(&target)).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! 通过 Restart Manager 查询占用文件的进程，操作因文件被占用失败时在错误信息中列出进程名称
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use windows_sys::Win32::System::RestartManager::{
+    RmEndSession, RmGetList, RmRegisterResources, RmStartSession, CCH_RM_SESSION_KEY, RM_PROCESS_INFO,
+};
+
+use crate::disk;
+
+/// 文件夹中最多查询的文件数，避免大文件夹注册过多资源
+const MAX_FILES: usize = 1000;
+const ERROR_MORE_DATA: u32 = 234;
+
+/// 占用 paths（文件夹则为其中的文件）的进程名称，查询失败时返回空列表
+pub fn locking_processes(paths: &[&Path]) -> Vec<String> {
+    let mut files = Vec::new();
+    for path in paths {
+        collect_files(path, &mut files);
+    }
+    if files.is_empty() {
+        return Vec::new();
+    }
+    let wide: Vec<Vec<u16>> = files.iter().map(|f| disk::wide_path(f)).collect();
+    let names: Vec<*const u16> = wide.iter().map(|w| w.as_ptr()).collect();
+
+    let mut session = 0u32;
+    let mut key = [0u16; CCH_RM_SESSION_KEY as usize + 1];
+    // SAFETY: key 的长度为 CCH_RM_SESSION_KEY + 1，names 中的字符串以 0 结尾且在调用期间有效
+    unsafe {
+        if RmStartSession(&mut session, 0, key.as_mut_ptr()) != 0 {
+            return Vec::new();
+        }
+        let registered = RmRegisterResources(session, names.len() as u32, names.as_ptr(), 0, std::ptr::null(), 0, std::ptr::null());
+        let processes = if registered == 0 { process_list(session) } else { Vec::new() };
+        RmEndSession(session);
+        processes
+    }
+}
+
+/// 读取会话中登记的占用进程，进程数可能在两次调用间增加，最多重试几次
+///
+/// # Safety
+/// session 必须是已注册资源的 Restart Manager 会话
+unsafe fn process_list(session: u32) -> Vec<String> {
+    let mut infos: Vec<RM_PROCESS_INFO> = Vec::new();
+    for _ in 0..3 {
+        let (mut needed, mut count, mut reasons) = (0u32, infos.len() as u32, 0u32);
+        // SAFETY: infos 的长度与 count 一致
+        let result = unsafe { RmGetList(session, &mut needed, &mut count, infos.as_mut_ptr(), &mut reasons) };
+        if result == ERROR_MORE_DATA {
+            infos.resize(needed as usize, RM_PROCESS_INFO::default());
+            continue;
+        }
+        if result != 0 {
+            return Vec::new();
+        }
+        let mut names: Vec<String> = infos[..count as usize]
+            .iter()
+            .map(|info| {
+                let len = info.strAppName.iter().position(|&c| c == 0).unwrap_or(info.strAppName.len());
+                format!("{} (PID {})", String::from_utf16_lossy(&info.strAppName[..len]), info.Process.dwProcessId)
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        return names;
+    }
+    Vec::new()
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if files.len() >= MAX_FILES {
+        return;
+    }
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return;
+    };
+    if metadata.is_file() {
+        files.push(path.to_path_buf());
+    } else if metadata.is_dir() && !crate::link::is_junction(path) {
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            collect_files(&entry.path(), files);
+        }
+    }
+}
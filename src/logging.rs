@@ -0,0 +1,130 @@
+//! 操作日志：写入程序所在目录的 switcher.log，并在内存中保留最近的记录供界面显示
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Mutex;
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use windows_sys::Win32::Foundation::SYSTEMTIME;
+use windows_sys::Win32::System::SystemInformation::GetLocalTime;
+
+pub const LOG_FILE: &str = "switcher.log";
+/// 超过该大小时，启动时把日志轮转为 switcher.log.1
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+/// 界面中保留的最近记录条数
+const MAX_RECENT: usize = 500;
+
+#[derive(Clone)]
+pub struct LogEntry {
+    pub time: String,
+    pub level: Level,
+    pub message: String,
+}
+
+static RECENT: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+
+/// 初始化日志，日志文件无法打开时只保留内存记录
+pub fn init(dir: &Path) {
+    let path = dir.join(LOG_FILE);
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_LOG_SIZE) {
+        let _ = fs::rename(&path, dir.join(format!("{}.1", LOG_FILE)));
+    }
+    let file_layer = File::options().create(true).append(true).open(&path).ok().map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_target(false)
+            .with_timer(LocalTime)
+            .with_writer(Mutex::new(file))
+    });
+
+    let _ = tracing_subscriber::registry()
+        .with(file_layer)
+        .with(RecentLayer)
+        .with(tracing_subscriber::filter::LevelFilter::INFO)
+        .try_init();
+}
+
+/// 最近的日志记录（从旧到新）
+pub fn recent() -> Vec<LogEntry> {
+    RECENT.lock().map(|r| r.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// 最近的日志记录，每条一行，用于复制到剪贴板
+pub fn recent_text() -> String {
+    recent()
+        .iter()
+        .map(|e| format!("{} {:>5} {}", e.time, e.level, e.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 当前本地时间，格式为 YYYY-MM-DD HH:MM:SS
+pub fn local_time() -> String {
+    // SAFETY: GetLocalTime 只写入传入的结构体
+    let st = unsafe {
+        let mut st: SYSTEMTIME = std::mem::zeroed();
+        GetLocalTime(&mut st);
+        st
+    };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        st.wYear, st.wMonth, st.wDay, st.wHour, st.wMinute, st.wSecond
+    )
+}
+
+struct LocalTime;
+
+impl FormatTime for LocalTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> std::fmt::Result {
+        w.write_str(&local_time())
+    }
+}
+
+/// 把事件的 message 字段与其他字段拼接为一行文本
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, "{:?}", value);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.push_str(value);
+        } else {
+            let _ = write!(self.0, " {}={}", field.name(), value);
+        }
+    }
+}
+
+struct RecentLayer;
+
+impl<S: Subscriber> Layer<S> for RecentLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let entry = LogEntry {
+            time: local_time(),
+            level: *event.metadata().level(),
+            message: visitor.0,
+        };
+        if let Ok(mut recent) = RECENT.lock() {
+            if recent.len() >= MAX_RECENT {
+                recent.pop_front();
+            }
+            recent.push_back(entry);
+        }
+    }
+}
@@ -1,839 +1,5315 @@
 #![windows_subsystem = "windows"]
 
+mod a11y;
+mod antivirus;
+mod archive;
+mod backup;
+mod backup_info;
+mod binary_vdf;
+mod cleanup;
+mod cli;
+mod cloud;
+mod copy;
+mod crash;
+mod dedup;
+mod diff;
+mod diagnostics;
+mod disk;
+mod drive_scan;
+mod duplicate;
+mod ea_app;
+mod elevation;
+mod error;
+mod explorer;
+mod fonts;
+mod games;
+mod hotkey;
+mod hash;
+mod health;
+mod history;
+mod http;
+mod i18n;
+mod ipc;
+mod journal;
+mod link;
+mod locks;
+mod logging;
+mod materialize;
+mod monitor;
+mod plan;
+mod priority;
+mod probe;
+mod prune;
+mod queue;
+mod recycle;
+mod relink;
+mod relocate;
+mod report;
+mod restore;
+mod retry;
+mod robocopy;
+mod scan;
+mod schedule;
+mod settings;
+mod share;
+mod shortcut;
+mod steam_config;
+mod task;
+mod theme;
+mod toast;
+mod toc;
+mod tray;
+mod update;
+mod user_cfg;
+mod usage;
+mod vdf;
+mod verify;
+mod window;
+mod wizard;
+mod workflow;
+mod xbox;
+
 use eframe::egui;
 use rfd::FileDialog;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 
-use std::path::PathBuf;
-use std::os::windows::process::CommandExt;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
 
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+use archive::CompressJob;
+use backup::{BackupJob, BatchBackupJob, MOVE_SOURCE_FILE, OLD_SUFFIX, ORIGINALS_DIR, STAGING_PREFIX};
+use backup_info::BackupInfo;
+use cli::{Command, Outcome};
+use copy::Progress;
+use dedup::DedupJob;
+use error::SwitcherError;
+use games::{DataFolderCheck, GameDef};
+use i18n::{t, UiLanguage};
+use journal::{EntryKind, Journal, JournalEntry};
+use link::RestoreMode;
+use duplicate::DuplicateJob;
+use materialize::MaterializeJob;
+use plan::{Action, Operation, Plan};
+use queue::{ItemStatus, Queue};
+use recycle::Removed;
+use relocate::RelocateJob;
+use restore::RestoreJob;
+use share::{ExportJob, ImportJob};
+use settings::{BackupMode, CopyEngine, CustomLanguage, Profile, Settings, VersionPolicy};
+use task::Task;
+use theme::Theme;
+use toast::{Severity, Toasts};
+use verify::VerifyJob;
+use wizard::WizardPage;
 
-const BF6_APP_ID: &str = "2807960";
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+/// 主窗口标题，托盘据此找到主窗口
+const WINDOW_TITLE: &str = "BF6 Voice Switcher";
+/// 未开启托盘时为显示通知临时创建的托盘图标保留的时间
+const NOTICE_DURATION: Duration = Duration::from_secs(10);
+/// 删除确认框中删除按钮的禁用时间，防止连击误删
+const DELETE_CONFIRM_DELAY: Duration = Duration::from_secs(1);
 
 #[derive(Clone)]
 struct Language {
     name: &'static str,
     miles_lang: &'static str,
+    /// 游戏目录中该语言的文件夹代码（不含 vo 前缀），与语言代码不同时需单独列出
+    folder_codes: &'static [&'static str],
 }
 
 fn get_languages() -> HashMap<&'static str, Language> {
     let mut langs = HashMap::new();
-    langs.insert("en", Language { name: "英语 (English)", miles_lang: "english" });
-    langs.insert("ja", Language { name: "日语 (Japanese)", miles_lang: "japanese" });
-    langs.insert("cn", Language { name: "中文 (Chinese)", miles_lang: "chinese" });
-    langs.insert("de", Language { name: "德语 (German)", miles_lang: "german" });
-    langs.insert("fr", Language { name: "法语 (French)", miles_lang: "french" });
-    langs.insert("es", Language { name: "西班牙语 (Spanish)", miles_lang: "spanish" });
-    langs.insert("ru", Language { name: "俄语 (Russian)", miles_lang: "russian" });
-    langs.insert("ko", Language { name: "韩语 (Korean)", miles_lang: "korean" });
+    langs.insert("en", Language { name: t!("lang.en"), miles_lang: "english", folder_codes: &["en"] });
+    langs.insert("ja", Language { name: t!("lang.ja"), miles_lang: "japanese", folder_codes: &["ja"] });
+    langs.insert("cn", Language { name: t!("lang.cn"), miles_lang: "chinese", folder_codes: &["cn"] });
+    langs.insert("tw", Language { name: t!("lang.tw"), miles_lang: "tchinese", folder_codes: &["tw", "zhtw"] });
+    langs.insert("de", Language { name: t!("lang.de"), miles_lang: "german", folder_codes: &["de"] });
+    langs.insert("fr", Language { name: t!("lang.fr"), miles_lang: "french", folder_codes: &["fr"] });
+    langs.insert("es", Language { name: t!("lang.es"), miles_lang: "spanish", folder_codes: &["es"] });
+    langs.insert("it", Language { name: t!("lang.it"), miles_lang: "italian", folder_codes: &["it"] });
+    langs.insert("br", Language { name: t!("lang.br"), miles_lang: "brazilian", folder_codes: &["br", "ptbr"] });
+    langs.insert("pl", Language { name: t!("lang.pl"), miles_lang: "polish", folder_codes: &["pl"] });
+    langs.insert("ru", Language { name: t!("lang.ru"), miles_lang: "russian", folder_codes: &["ru"] });
+    langs.insert("ko", Language { name: t!("lang.ko"), miles_lang: "korean", folder_codes: &["ko"] });
+    langs.insert("ar", Language { name: t!("lang.ar"), miles_lang: "arabic", folder_codes: &["ar"] });
     langs
 }
 
-#[derive(Clone, Default)]
-struct BackupInfo {
-    lang_code: String,
-    build_id: String,
+/// Steam 中的语言名称，除简体中文外与 +miles_language 的值相同
+fn steam_language(miles_lang: &str) -> &str {
+    match miles_lang {
+        "chinese" => "schinese",
+        other => other,
+    }
+}
+
+/// 内置语言在选择列表中的顺序
+const BUILTIN_LANG_CODES: [&str; 13] = ["en", "ja", "cn", "tw", "de", "fr", "es", "it", "br", "pl", "ru", "ko", "ar"];
+
+/// 将设置中的自定义语言转换为 Language，无效条目返回错误信息
+///
+/// 语言表在整个程序运行期间都会使用，字符串在这里转换为 'static，只在启动时执行一次。
+fn load_custom_languages(custom: &[CustomLanguage]) -> (Vec<(&'static str, Language)>, Vec<SwitcherError>) {
+    fn leak(s: &str) -> &'static str {
+        Box::leak(s.to_string().into_boxed_str())
+    }
+
+    let mut langs = Vec::new();
+    let mut errors = Vec::new();
+    for lang in custom {
+        if let Err(e) = lang.validate() {
+            errors.push(e);
+            continue;
+        }
+        let folder_codes: Vec<&'static str> = lang.folder_codes.iter().map(|c| leak(c)).collect();
+        langs.push((
+            leak(&lang.code),
+            Language {
+                name: leak(&lang.name),
+                miles_lang: leak(lang.miles_lang.trim()),
+                folder_codes: Box::leak(folder_codes.into_boxed_slice()),
+            },
+        ));
+    }
+    (langs, errors)
+}
+
+/// 合并内置语言与自定义语言，返回语言表和当前游戏可选语言的顺序
+fn build_language_table(
+    custom: &[(&'static str, Language)],
+    game: &GameDef,
+) -> (HashMap<&'static str, Language>, Vec<&'static str>) {
+    let mut languages = get_languages();
+    let mut lang_codes = BUILTIN_LANG_CODES.to_vec();
+    for (code, lang) in custom {
+        if languages.insert(code, lang.clone()).is_none() {
+            lang_codes.push(code);
+        }
+    }
+    lang_codes.retain(|code| game.supports_language(code));
+    (languages, lang_codes)
+}
+
+/// 游戏目录中单个语音文件夹的状态
+#[derive(Clone)]
+enum FolderState {
+    /// 普通目录（游戏原始文件）
+    Directory,
+    /// Junction 链接，附带解析出的目标路径
+    Junction(Option<PathBuf>),
+    /// 由本工具复制或硬链接恢复的普通目录
+    Restored(RestoreMode),
+    /// 备份中记录了该文件夹，但游戏目录中不存在
+    Missing,
+}
+
+#[derive(Clone)]
+struct VoiceFolderStatus {
+    rel_path: PathBuf,
+    state: FolderState,
+}
+
+/// 某个语言在游戏目录中的语音文件状态
+#[derive(Clone)]
+struct LangVoiceState {
+    lang_code: &'static str,
+    folders: Vec<VoiceFolderStatus>,
+    toc_files: Vec<PathBuf>,
+}
+
+impl LangVoiceState {
+    fn restored_count(&self) -> usize {
+        self.folders
+            .iter()
+            .filter(|f| matches!(f.state, FolderState::Junction(_) | FolderState::Restored(_)))
+            .count()
+    }
+
+    fn directory_count(&self) -> usize {
+        self.folders.iter().filter(|f| matches!(f.state, FolderState::Directory)).count()
+    }
+}
+
+/// 游戏安装所属的平台
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Platform {
+    #[default]
+    Steam,
+    EaApp,
+    Xbox,
+}
+
+impl Platform {
+    fn label(&self) -> &'static str {
+        match self {
+            Platform::Steam => "Steam",
+            Platform::EaApp => "EA App",
+            Platform::Xbox => "Xbox",
+        }
+    }
 }
 
+/// 检测到的游戏安装；EA App 和 Xbox 安装的 steam_path 为已检测到的 Steam 目录（可能为空），
+/// Xbox 安装的 game_path 为 Content 目录
 #[derive(Clone, Default)]
 struct SteamInfo {
+    platform: Platform,
+    steam_path: PathBuf,
     game_path: PathBuf,
+    manifest_path: PathBuf,
     build_id: String,
+    /// Steam 游戏属性中选择的游戏语言（文本语言），如 schinese
+    text_language: String,
+    /// Steam 已有待安装或正在进行的更新，更新后恢复的语音可能被覆盖
+    update_pending: bool,
+}
+
+/// 安装选择框中显示的平台、路径和版本
+fn install_label(install: &SteamInfo) -> String {
+    let version = if install.build_id.is_empty() { "-" } else { &install.build_id };
+    t!("ui.install_entry", install.platform.label(), install.game_path.display(), version)
+}
+
+/// 备份列表中可以排序的列
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BackupColumn {
+    Language,
+    Build,
+    Date,
+    Size,
+    Note,
+}
+
+impl BackupColumn {
+    const ALL: [BackupColumn; 5] =
+        [BackupColumn::Language, BackupColumn::Build, BackupColumn::Date, BackupColumn::Size, BackupColumn::Note];
+
+    fn label(&self) -> &'static str {
+        match self {
+            BackupColumn::Language => t!("ui.column_language"),
+            BackupColumn::Build => t!("ui.column_build"),
+            BackupColumn::Date => t!("ui.column_date"),
+            BackupColumn::Size => t!("ui.column_size"),
+            BackupColumn::Note => t!("ui.column_note"),
+        }
+    }
+}
+
+/// 备份列表中每一行的按钮
+#[derive(Clone, Copy)]
+enum BackupAction {
+    Restore,
+    Verify,
+    Compare,
+    Delete,
+    OpenFolder,
 }
 
 struct BF6VoiceSwitcher {
+    games: Vec<GameDef>,
+    selected_game_idx: usize,
+    /// 所有游戏备份的根目录，当前游戏的备份位于其中的子目录
+    backup_root: PathBuf,
     languages: HashMap<&'static str, Language>,
     lang_codes: Vec<&'static str>,
+    custom_languages: Vec<(&'static str, Language)>,
     selected_lang_idx: usize,
     source_path: String,
     backup_dir: PathBuf,
     available_backups: Vec<BackupInfo>,
     selected_backup_idx: usize,
-    status_message: String,
-    is_error: bool,
+    /// 各备份占用的空间，刷新备份列表时统计
+    backup_sizes: HashMap<String, u64>,
+    /// 正在进行的游戏目录扫描及扫描的语言
+    voice_scan: Option<(scan::VoiceScan, Vec<&'static str>)>,
+    /// 扫描完成后自动选择游戏已安装的语言
+    select_installed_after_scan: bool,
+    /// 游戏目录中各语言语音文件（不含 Junction）的大小，由后台线程统计
+    voice_sizes: HashMap<String, u64>,
+    size_scan: Option<disk::SizeScan>,
+    /// 备份占用统计的结果，备份变化后清空，展开占用面板时重新统计
+    usage: Option<usage::Usage>,
+    usage_scan: Option<usage::UsageScan>,
+    /// 正在比较的备份，以及比较结果（备份名称, 结果）
+    diff_scan: Option<diff::DiffScan>,
+    diff_result: Option<(String, diff::Diff)>,
+    /// 备份列表的排序列和是否升序
+    backup_sort: (BackupColumn, bool),
+    /// 操作结果的提示和历史记录
+    toasts: Toasts,
     steam_info: Option<SteamInfo>,
+    /// 扫描磁盘时找到的所有安装，多于一个时由用户选择
+    install_candidates: Vec<SteamInfo>,
+    voice_state: Vec<LangVoiceState>,
+    /// Steam 中当前游戏的启动选项，找不到配置时为 None
+    launch_options: Option<String>,
+    /// 游戏目录 user.cfg 中的语音语言，没有该命令时为 None
+    user_cfg_lang: Option<String>,
+    settings: Settings,
+    settings_path: PathBuf,
+    restore_mode: RestoreMode,
+    backup_mode: BackupMode,
+    /// 正在后台执行的备份或恢复
+    task: Option<Task>,
+    /// 排队等待依次执行的备份、恢复和删除操作
+    queue: Queue,
+    /// 向导模式当前显示的页面
+    wizard_page: WizardPage,
+    /// 等待确认的操作预览及其显示时间
+    pending_plan: Option<(Plan, Instant)>,
+    /// 撤销所有修改后请求 Steam 验证游戏文件
+    validate_after_revert: bool,
+    /// 删除没有备份的语言时不保存原始文件快照
+    skip_snapshot: bool,
+    /// 已确认删除游戏当前依赖的语言的语音文件
+    delete_in_use_confirmed: bool,
+    /// 是否显示游戏更新后的备份迁移助手
+    show_migration: bool,
+    /// 正在编辑的所选备份的 (显示名称, 备注)
+    editing_backup: Option<(String, String)>,
+    /// 正在填写的备份副本名称，以及是否使用硬链接
+    duplicating: Option<(String, bool)>,
+    /// 是否显示以管理员身份重新启动的提示
+    show_elevation: bool,
+    /// 上一个任务中发现的杀毒软件干扰，以及失败时可以重试的队列项目
+    antivirus_hint: Option<(antivirus::Interference, Option<u64>)>,
+    /// 开启最小化到托盘时的托盘图标
+    tray: Option<tray::Tray>,
+    /// 后台任务结束后总是通知结果（从托盘菜单切换语音时）
+    notify_on_finish: bool,
+    /// 未开启托盘时为显示通知临时创建的托盘图标及其创建时间
+    notice_tray: Option<(tray::Tray, Instant)>,
+    /// 设置中配置的全局热键
+    hotkeys: Option<hotkey::Hotkeys>,
+    /// 方案列表中所选的方案
+    selected_profile_idx: usize,
+    /// 保存新方案时输入的名称
+    new_profile_name: String,
+    /// 正在后台进行的更新检查
+    update_check: Option<update::UpdateCheck>,
+    /// 托盘模式下监视游戏更新的后台线程
+    relink_watcher: Option<relink::Watcher>,
+    /// 游戏更新后等待确认重新应用的方案
+    relink_prompt: Option<(String, relink::Change)>,
+    /// 当前游戏的操作历史，按时间从旧到新排列
+    history: Vec<history::Entry>,
+    /// 启动检查的结果，关闭清单后为 None
+    health: Option<Vec<health::Finding>>,
+    /// 定期检查恢复的语音的后台线程
+    link_monitor: Option<monitor::Monitor>,
+    /// 上次检查发现的问题，之后只在问题变化时通知
+    link_problems: Vec<monitor::Problem>,
+    /// 备份总大小超过上限时提示删除的备份（文件夹名称）
+    prune_prompt: Option<Vec<String>>,
+    /// 本次运行中已关闭清理提示，不再自动提示
+    prune_dismissed: bool,
+    /// 用户确认版本不匹配仍要恢复的备份：(文件夹名称, 确认时的游戏版本)，游戏再次更新后需要重新确认
+    version_override: Option<(String, String)>,
+    /// 修复链接的计划任务是否已注册
+    repair_task: bool,
+    /// 接收命令行命令的命名管道
+    ipc: Option<ipc::Server>,
+    /// 正在执行的命令行命令，以及后台任务结束后是否启动游戏
+    ipc_request: Option<(ipc::Request, bool)>,
+    /// 检查到的新版本，关闭提示后清空
+    update_available: Option<update::Release>,
+    /// 自动更新的任务结束后重新启动
+    restart_after_update: bool,
+    /// 正在移动到的备份目录，移动完成后写入设置
+    pending_backup_root: Option<PathBuf>,
+    /// 备份目录所在的云同步目录
+    cloud_sync: Option<cloud::SyncRoot>,
+    /// 最近一次应用的界面缩放，用于发现通过 Ctrl +/- 修改的缩放
+    applied_ui_scale: f32,
+}
+
+/// 重新启动后恢复界面状态的命令行参数
+const ARG_SOURCE: &str = "--source";
+const ARG_LANG: &str = "--lang";
+
+/// 程序所在目录，设置、日志和备份都保存在这里
+fn exe_dir() -> PathBuf {
+    std::env::current_exe()
+        .unwrap_or_default()
+        .parent()
+        .unwrap_or(&PathBuf::from("."))
+        .to_path_buf()
+}
+
+/// 网络共享中的路径无法访问时返回包含登录提示的错误，本地路径不检查
+fn check_network_access(path: &Path) -> Result<(), SwitcherError> {
+    if !disk::is_network(path) {
+        return Ok(());
+    }
+    match fs::metadata(path) {
+        Err(e) if disk::is_network_error(&e) => Err(SwitcherError::NetworkAccess(path.to_path_buf(), e)),
+        _ => Ok(()),
+    }
 }
 
 impl Default for BF6VoiceSwitcher {
     fn default() -> Self {
-        let backup_dir = std::env::current_exe()
-            .unwrap_or_default()
-            .parent()
-            .unwrap_or(&PathBuf::from("."))
-            .join("voice_backups");
+        let exe_dir = exe_dir();
+        let settings_path = exe_dir.join(settings::SETTINGS_FILE);
+        let settings = Settings::load(&settings_path);
+        let backup_root = settings.backup_root.clone().unwrap_or_else(|| exe_dir.join("voice_backups"));
+        let cloud_sync = cloud::sync_root(&backup_root);
+        i18n::set_language(settings.ui_language.unwrap_or_else(UiLanguage::from_system));
+        priority::set_background(settings.background_priority);
+
+        let (games, games_errors) = games::load_games(&exe_dir.join(games::GAMES_FILE));
+        let selected_game_idx = settings
+            .game
+            .as_ref()
+            .and_then(|id| games.iter().position(|g| &g.id == id))
+            .unwrap_or(0);
+        let backup_dir = games[selected_game_idx].backup_dir(&backup_root);
 
-        let languages = get_languages();
-        let lang_codes = vec!["en", "ja", "cn", "de", "fr", "es", "ru", "ko"];
+        let (custom_languages, custom_errors) = load_custom_languages(&settings.custom_languages);
+        let (languages, lang_codes) = build_language_table(&custom_languages, &games[selected_game_idx]);
 
         let mut app = Self {
+            games,
+            selected_game_idx,
+            backup_root,
             languages,
             lang_codes,
+            custom_languages,
             selected_lang_idx: 0,
             source_path: String::new(),
             backup_dir,
             available_backups: Vec::new(),
             selected_backup_idx: 0,
-            status_message: String::new(),
-            is_error: false,
+            backup_sizes: HashMap::new(),
+            voice_scan: None,
+            select_installed_after_scan: false,
+            voice_sizes: HashMap::new(),
+            size_scan: None,
+            usage: None,
+            usage_scan: None,
+            diff_scan: None,
+            diff_result: None,
+            backup_sort: (BackupColumn::Language, true),
+            toasts: Toasts::default(),
             steam_info: None,
+            voice_state: Vec::new(),
+            launch_options: None,
+            user_cfg_lang: None,
+            install_candidates: Vec::new(),
+            restore_mode: settings.restore_mode,
+            backup_mode: settings.backup_mode,
+            settings,
+            settings_path,
+            task: None,
+            queue: Queue::default(),
+            wizard_page: WizardPage::default(),
+            pending_plan: None,
+            validate_after_revert: true,
+            skip_snapshot: false,
+            delete_in_use_confirmed: false,
+            show_migration: false,
+            editing_backup: None,
+            duplicating: None,
+            show_elevation: false,
+            antivirus_hint: None,
+            tray: None,
+            notify_on_finish: false,
+            notice_tray: None,
+            hotkeys: None,
+            update_check: None,
+            relink_watcher: None,
+            relink_prompt: None,
+            history: Vec::new(),
+            health: None,
+            link_monitor: None,
+            link_problems: Vec::new(),
+            prune_prompt: None,
+            prune_dismissed: false,
+            version_override: None,
+            repair_task: false,
+            ipc: None,
+            ipc_request: None,
+            update_available: None,
+            restart_after_update: false,
+            pending_backup_root: None,
+            cloud_sync,
+            applied_ui_scale: 1.0,
+            selected_profile_idx: 0,
+            new_profile_name: String::new(),
         };
         
         // 自动检测 Steam
         app.detect_steam();
+        app.clean_orphaned_staging();
         app.refresh_backups();
+        app.refresh_voice_state();
+        app.select_installed_after_scan = true;
+        let config_errors: Vec<String> = games_errors.iter().chain(&custom_errors).map(|e| e.to_string()).collect();
+        if !config_errors.is_empty() {
+            let message = config_errors.join("\n");
+            error!("{}", message);
+            app.toasts.push(Severity::Error, message);
+        }
         app
     }
 }
 
 impl BF6VoiceSwitcher {
-    /// 检测 Steam 安装路径和游戏信息
-    fn detect_steam(&mut self) {
-        // 常见 Steam 安装路径
-        let possible_paths = vec![
-            PathBuf::from("C:\\Program Files (x86)\\Steam"),
-            PathBuf::from("C:\\Program Files\\Steam"),
-            PathBuf::from("D:\\Steam"),
-            PathBuf::from("E:\\Steam"),
-            PathBuf::from("D:\\Program Files (x86)\\Steam"),
-            PathBuf::from("E:\\Program Files (x86)\\Steam"),
-        ];
+    fn current_game(&self) -> &GameDef {
+        &self.games[self.selected_game_idx]
+    }
 
-        // 也尝试从注册表读取（简化版，直接检查路径）
-        for steam_path in possible_paths {
-            if steam_path.join("steam.exe").exists() {
-                if let Some(info) = self.parse_steam_info(&steam_path) {
-                    self.steam_info = Some(info.clone());
-                    self.source_path = info.game_path.join("Data").join("Win32").to_string_lossy().to_string();
-                    self.status_message = format!("已自动检测到游戏路径，版本: {}", info.build_id);
-                    self.is_error = false;
-                    return;
-                }
-            }
+    /// 切换当前游戏，重新检测安装目录并加载该游戏的备份
+    fn select_game(&mut self, idx: usize) {
+        self.selected_game_idx = idx;
+        self.backup_dir = self.current_game().backup_dir(&self.backup_root);
+        self.lang_codes = build_language_table(&self.custom_languages, &self.games[idx]).1;
+        self.selected_lang_idx = 0;
+        self.steam_info = None;
+        self.source_path.clear();
+
+        self.detect_steam();
+        self.clean_orphaned_staging();
+        self.refresh_backups();
+        self.refresh_voice_state();
+        self.select_installed_after_scan = true;
+
+        self.settings.game = Some(self.current_game().id.clone());
+        if let Err(e) = self.save_settings() {
+            self.report(Err(e));
         }
     }
 
-    /// 解析 Steam 信息
-    fn parse_steam_info(&self, steam_path: &PathBuf) -> Option<SteamInfo> {
-        // 读取 libraryfolders.vdf 获取所有库路径
-        let library_folders = self.get_library_folders(steam_path);
-        
-        // 在所有库中查找 BF6
-        for lib_path in library_folders {
-            let manifest_path = lib_path.join("steamapps").join(format!("appmanifest_{}.acf", BF6_APP_ID));
-            if manifest_path.exists() {
-                if let Some((install_dir, build_id)) = self.parse_app_manifest(&manifest_path) {
-                    return Some(SteamInfo {
-                        game_path: lib_path.join("steamapps").join("common").join(install_dir),
-                        build_id,
-                    });
+    /// 在状态栏显示操作结果，所有错误都在这里转换为界面文本
+    fn report(&mut self, result: Result<String, SwitcherError>) {
+        match result {
+            Ok(message) => {
+                info!("{}", message);
+                self.toasts.push(Severity::for_message(&message), message);
+            }
+            Err(e) => {
+                let message = e.to_string();
+                error!("{}", message);
+                self.toasts.push(Severity::Error, message);
+                if self.needs_elevation() {
+                    self.show_elevation = true;
                 }
             }
         }
-        None
+        self.update_crash_state();
     }
 
-    /// 获取所有 Steam 库文件夹
-    fn get_library_folders(&self, steam_path: &PathBuf) -> Vec<PathBuf> {
-        let mut folders = vec![steam_path.clone()];
-        let vdf_path = steam_path.join("steamapps").join("libraryfolders.vdf");
-        
-        if let Ok(content) = fs::read_to_string(&vdf_path) {
-            for line in content.lines() {
-                if line.contains("\"path\"") {
-                    if let Some(path) = self.extract_vdf_value(line) {
-                        let path = PathBuf::from(path.replace("\\\\", "\\"));
-                        if path.exists() && !folders.contains(&path) {
-                            folders.push(path);
-                        }
-                    }
-                }
-            }
-        }
-        folders
+    /// 记录崩溃报告中的状态摘要，每次操作结束后更新
+    fn update_crash_state(&self) {
+        let last = self.toasts.history().back().map(|t| t.message.as_str()).unwrap_or_default();
+        crash::set_state(format!("{}\nstatus: {}", self.state_summary(), last));
     }
 
-    /// 解析 appmanifest 文件
-    fn parse_app_manifest(&self, path: &PathBuf) -> Option<(String, String)> {
-        let content = fs::read_to_string(path).ok()?;
-        let mut install_dir = String::new();
-        let mut build_id = String::new();
+    /// 未以管理员身份运行且游戏目录或备份目录没有写入权限
+    fn needs_elevation(&self) -> bool {
+        !elevation::is_elevated()
+            && (elevation::write_denied(Path::new(&self.source_path)) || elevation::write_denied(&self.backup_dir))
+    }
 
-        for line in content.lines() {
-            if line.contains("\"installdir\"") {
-                install_dir = self.extract_vdf_value(line).unwrap_or_default();
-            } else if line.contains("\"buildid\"") {
-                build_id = self.extract_vdf_value(line).unwrap_or_default();
-            }
+    /// 以管理员身份重新启动，并传递当前的游戏目录和所选语言
+    fn relaunch_as_admin(&mut self, ctx: &egui::Context) {
+        let args = vec![
+            ARG_SOURCE.to_string(),
+            self.source_path.clone(),
+            ARG_LANG.to_string(),
+            self.get_selected_lang_code().to_string(),
+        ];
+        match elevation::relaunch_as_admin(&args) {
+            Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            Err(e) => self.report(Err(SwitcherError::Elevate(e))),
         }
+    }
 
-        if !install_dir.is_empty() && !build_id.is_empty() {
-            Some((install_dir, build_id))
-        } else {
-            None
+    /// 应用重新启动时传入的命令行参数
+    fn apply_launch_args(&mut self, args: &[String]) {
+        let value = |name: &str| args.iter().position(|arg| arg == name).and_then(|i| args.get(i + 1));
+        if let Some(source) = value(ARG_SOURCE).filter(|s| !s.is_empty()) {
+            self.source_path = source.clone();
+            self.refresh_voice_state();
+        }
+        if let Some(idx) = value(ARG_LANG).and_then(|code| self.lang_codes.iter().position(|c| c == code)) {
+            self.selected_lang_idx = idx;
+            self.select_installed_after_scan = false;
         }
     }
 
-    /// 从 VDF 行中提取值
-    fn extract_vdf_value(&self, line: &str) -> Option<String> {
-        let parts: Vec<&str> = line.split('"').collect();
-        if parts.len() >= 4 {
-            Some(parts[3].to_string())
-        } else {
-            None
+    /// 切换到文件夹名称为 backup_name 的备份（原始备份即语言代码）：先删除当前的游戏语音，再在后台恢复该备份
+    fn switch_voice(&mut self, backup_name: &str) -> Result<String, SwitcherError> {
+        if self.task.is_some() || self.voice_scan.is_some() {
+            return Err(SwitcherError::TaskRunning);
         }
-    }
+        let backup_idx = self
+            .available_backups
+            .iter()
+            .position(|b| b.dir_name() == backup_name)
+            .ok_or(SwitcherError::NoBackups)?;
+        let lang_code = self.available_backups[backup_idx].lang_code.clone();
+        let lang_code = lang_code.as_str();
+        let lang_idx = self.lang_codes.iter().position(|c| *c == lang_code).ok_or(SwitcherError::NoBackups)?;
+        let name = self.languages.get(lang_code).map(|l| l.name).unwrap_or(lang_code);
 
-    fn refresh_backups(&mut self) {
-        self.available_backups.clear();
-        if let Ok(entries) = fs::read_dir(&self.backup_dir) {
-            for entry in entries.flatten() {
-                if entry.path().is_dir() {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    if self.languages.contains_key(name.as_str()) {
-                        // 读取备份信息
-                        let info_path = entry.path().join("backup_info.txt");
-                        let build_id = if let Ok(content) = fs::read_to_string(&info_path) {
-                            content.lines()
-                                .find(|l| l.starts_with("build_id="))
-                                .map(|l| l.trim_start_matches("build_id=").to_string())
-                                .unwrap_or_default()
-                        } else {
-                            String::new()
-                        };
-                        
-                        self.available_backups.push(BackupInfo {
-                            lang_code: name,
-                            build_id,
-                        });
-                    }
+        match self.get_active_voice() {
+            Some((active, _)) if active == lang_code => return Ok(t!("status.switch_already", name)),
+            Some((active, _)) => {
+                // 托盘、热键和命令行切换时没有确认对话框，游戏依赖的语言不删除
+                let in_use = self.language_in_use(active);
+                if !in_use.is_empty() {
+                    let active_name = self.languages.get(active).map(|l| l.name).unwrap_or(active).to_string();
+                    return Err(SwitcherError::LanguageInUse { name: active_name, reasons: in_use.join(", ") });
+                }
+                if let Some(idx) = self.lang_codes.iter().position(|c| *c == active) {
+                    self.selected_lang_idx = idx;
+                    self.delete_voice_files()?;
                 }
             }
+            None => {}
         }
-        self.selected_backup_idx = 0;
+        self.selected_lang_idx = lang_idx;
+        self.selected_backup_idx = backup_idx;
+        self.restore_files()
     }
 
-    fn get_selected_lang_code(&self) -> &'static str {
-        self.lang_codes[self.selected_lang_idx]
+    /// 按设置创建或移除托盘图标，并处理托盘菜单的事件
+    fn update_tray(&mut self, ctx: &egui::Context) {
+        if self.settings.minimize_to_tray != self.tray.is_some() {
+            self.tray = if self.settings.minimize_to_tray { tray::Tray::spawn(ctx.clone(), WINDOW_TITLE) } else { None };
+        }
+        let Some(tray) = &self.tray else {
+            return;
+        };
+        let items = self
+            .available_backups
+            .iter()
+            .map(|b| (b.dir_name().to_string(), self.backup_name(b).to_string()))
+            .collect();
+        tray.set_items(items);
+        if ctx.input(|i| i.viewport().minimized) == Some(true) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        }
+
+        for event in tray.events() {
+            match event {
+                tray::TrayEvent::Show => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                }
+                tray::TrayEvent::Exit => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+                tray::TrayEvent::Switch(code) => self.quick_switch(ctx, &code),
+            }
+        }
     }
 
-    fn get_launch_param(&self) -> String {
-        let code = self.get_selected_lang_code();
-        if let Some(lang) = self.languages.get(code) {
-            format!("+miles_language {}", lang.miles_lang)
-        } else {
-            String::new()
+    /// 从托盘或热键切换语音，窗口可能不可见，结果通过通知显示
+    fn quick_switch(&mut self, ctx: &egui::Context, lang_code: &str) {
+        let result = self.switch_voice(lang_code);
+        match &result {
+            Ok(_) => self.notify_on_finish = self.task.is_some(),
+            Err(e) => self.notify(ctx, &e.to_string(), true),
         }
+        self.report(result);
     }
 
-    /// 递归查找所有匹配的语音文件夹和 .toc 文件，返回 (文件夹列表, toc文件列表)
-    fn find_voice_files(&self, root: &PathBuf, lang_code: &str) -> (Vec<PathBuf>, Vec<PathBuf>) {
-        let folder_names = [lang_code.to_string(), format!("vo{}", lang_code)];
-        let toc_names = [format!("{}.toc", lang_code), format!("vo{}.toc", lang_code)];
-        let mut folders = Vec::new();
-        let mut toc_files = Vec::new();
-        self.find_voice_files_recursive(root, root, &folder_names, &toc_names, &mut folders, &mut toc_files);
-        (folders, toc_files)
-    }
-
-    fn find_voice_files_recursive(
-        &self,
-        root: &PathBuf,
-        current: &PathBuf,
-        folder_names: &[String],
-        toc_names: &[String],
-        folders: &mut Vec<PathBuf>,
-        toc_files: &mut Vec<PathBuf>,
-    ) {
-        let Ok(entries) = fs::read_dir(current) else {
-            return;
+    /// 当前语音和 Steam 游戏语言与之相符的方案
+    fn active_profile(&self) -> Option<&Profile> {
+        let (voice, _) = self.get_active_voice()?;
+        let text_language = self.steam_info.as_ref().map(|s| s.text_language.as_str()).unwrap_or_default();
+        self.settings
+            .profiles
+            .iter()
+            .find(|p| p.voice == voice && (p.text_language.is_empty() || p.text_language.eq_ignore_ascii_case(text_language)))
+    }
+
+    /// 将当前所选的语音语言和 Steam 游戏语言保存为方案，同名方案会被覆盖
+    fn save_profile(&mut self) -> Result<String, SwitcherError> {
+        let name = self.new_profile_name.trim().to_string();
+        if name.is_empty() {
+            return Err(SwitcherError::ProfileNameEmpty);
+        }
+        let profile = Profile {
+            name: name.clone(),
+            voice: self.get_selected_lang_code().to_string(),
+            text_language: self.steam_info.as_ref().map(|s| s.text_language.clone()).unwrap_or_default(),
+            launch_option: true,
         };
-        for entry in entries.flatten() {
-            let path = entry.path();
-            let name = entry.file_name().to_string_lossy().to_string();
-            let is_dir = path.is_dir() || Self::is_junction(&path);
-            
-            if is_dir {
-                if folder_names.contains(&name) {
-                    if let Ok(rel) = path.strip_prefix(root) {
-                        folders.push(rel.to_path_buf());
-                    }
-                } else if !Self::is_junction(&path) {
-                    // 只递归普通目录，不递归 Junction
-                    self.find_voice_files_recursive(
-                        root,
-                        &path,
-                        folder_names,
-                        toc_names,
-                        folders,
-                        toc_files,
-                    );
-                }
-            } else if toc_names.contains(&name) {
-                if let Ok(rel) = path.strip_prefix(root) {
-                    toc_files.push(rel.to_path_buf());
-                }
+        match self.settings.profiles.iter().position(|p| p.name == name) {
+            Some(idx) => {
+                self.settings.profiles[idx] = profile;
+                self.selected_profile_idx = idx;
+            }
+            None => {
+                self.settings.profiles.push(profile);
+                self.selected_profile_idx = self.settings.profiles.len() - 1;
             }
         }
+        self.save_settings()?;
+        self.new_profile_name.clear();
+        Ok(t!("status.profile_saved", name))
     }
 
-    fn backup_files(&mut self) {
-        if self.source_path.is_empty() {
-            self.status_message = "请先选择语音文件夹！".to_string();
-            self.is_error = true;
-            return;
+    fn delete_profile(&mut self) -> Result<String, SwitcherError> {
+        if self.selected_profile_idx >= self.settings.profiles.len() {
+            return Err(SwitcherError::NoProfileSelected);
         }
+        let profile = self.settings.profiles.remove(self.selected_profile_idx);
+        self.selected_profile_idx = 0;
+        self.save_settings()?;
+        Ok(t!("status.profile_deleted", profile.name))
+    }
 
-        let source = PathBuf::from(&self.source_path);
-        if !source.exists() {
-            self.status_message = "所选文件夹不存在！".to_string();
-            self.is_error = true;
-            return;
+    /// 应用方案：写入启动参数，删除当前语音并在后台恢复方案的语音，Steam 游戏语言不符时提示手动修改
+    fn apply_profile(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() || self.voice_scan.is_some() {
+            return Err(SwitcherError::TaskRunning);
         }
+        let profile = self.settings.profiles.get(self.selected_profile_idx).cloned().ok_or(SwitcherError::NoProfileSelected)?;
+        let lang_idx = self
+            .lang_codes
+            .iter()
+            .position(|c| *c == profile.voice)
+            .ok_or_else(|| SwitcherError::UnknownLanguage(profile.voice.clone()))?;
 
-        let lang_code = self.get_selected_lang_code();
-        let target = self.backup_dir.join(lang_code);
+        let mut messages = Vec::new();
+        if profile.launch_option {
+            self.selected_lang_idx = lang_idx;
+            messages.push(self.write_launch_option(false)?);
+        }
+        let text_language = self.steam_info.as_ref().map(|s| s.text_language.as_str()).unwrap_or_default();
+        if !profile.text_language.is_empty() && !profile.text_language.eq_ignore_ascii_case(text_language) {
+            messages.push(t!("status.profile_text_language", profile.text_language, text_language));
+        }
+        messages.insert(0, self.switch_voice(&profile.voice)?);
+        self.settings.last_profile = profile.name;
+        self.save_settings()?;
+        Ok(messages.join("\n"))
+    }
 
-        // 递归查找所有语音文件夹和 .toc 文件
-        let (voice_folders, toc_files) = self.find_voice_files(&source, lang_code);
+    /// 需要监视的恢复文件夹：仅在开启托盘和自动重新链接、且上次应用的方案仍存在时监视 Steam 安装
+    fn relink_target(&self) -> Option<relink::Target> {
+        if !self.settings.auto_relink || self.tray.is_none() || self.relink_prompt.is_some() {
+            return None;
+        }
+        if !self.settings.profiles.iter().any(|p| p.name == self.settings.last_profile) {
+            return None;
+        }
+        let steam = self.steam_info.as_ref().filter(|s| s.platform == Platform::Steam)?;
+        let source = PathBuf::from(&self.source_path);
+        let folders: Vec<_> = self
+            .available_backups
+            .iter()
+            .filter_map(|b| b.restore.as_ref())
+            .filter(|r| r.target == source)
+            .flat_map(|r| r.folders.iter().map(|f| (source.join(f), r.mode)))
+            .collect();
+        if folders.is_empty() {
+            return None;
+        }
+        Some(relink::Target { manifest_path: steam.manifest_path.clone(), build_id: steam.build_id.clone(), folders })
+    }
 
-        if voice_folders.is_empty() && toc_files.is_empty() {
-            self.status_message = format!("未找到语音文件: {} 或 vo{}", lang_code, lang_code);
-            self.is_error = true;
-            return;
+    /// 按设置开始或停止监视；检测到更新后重新检测安装，并通知用户确认是否重新应用上次的方案
+    fn update_relink_watcher(&mut self, ctx: &egui::Context) {
+        if self.relink_watcher.as_ref().is_some_and(|w| w.is_finished()) {
+            if let Some(change) = self.relink_watcher.take().and_then(|w| w.join()) {
+                let profile = self.settings.last_profile.clone();
+                self.detect_steam();
+                self.refresh_voice_state();
+                self.notify(ctx, &t!("status.relink_ready", change.build_id, profile), false);
+                self.relink_prompt = Some((profile, change));
+            }
+        }
+        let target = self.relink_target();
+        if self.relink_watcher.as_ref().map(|w| w.target()) != target.as_ref() {
+            self.relink_watcher = target.map(|target| relink::Watcher::spawn(ctx.clone(), target));
         }
+    }
 
-        // 只有 toc 文件时，备份不完整，不执行备份
-        if voice_folders.is_empty() {
-            let lang_name = self.languages.get(lang_code).map(|l| l.name).unwrap_or(lang_code);
-            self.status_message = format!("[!] {} 备份不完整！未找到语音文件夹，已取消备份", lang_name);
-            self.is_error = true;
-            return;
+    /// 当前游戏中恢复的文件夹和复制的 .toc 文件，作为定期检查的目标
+    fn monitor_target(&self) -> Option<monitor::Target> {
+        let source = PathBuf::from(&self.source_path);
+        let mut target = monitor::Target::default();
+        for backup in &self.available_backups {
+            let Some(record) = backup.restore.as_ref().filter(|r| r.target == source) else {
+                continue;
+            };
+            let backup_path = backup.path(&self.backup_dir);
+            target.folders.extend(record.folders.iter().map(|f| monitor::Folder {
+                path: source.join(f),
+                mode: record.mode,
+                backup: backup_path.join(f),
+            }));
+            target.toc_files.extend(backup.toc_hashes.iter().map(|(rel_path, hash)| (source.join(rel_path), hash.clone())));
         }
+        (!target.folders.is_empty()).then_some(target)
+    }
 
-        // 清理旧备份
-        if target.exists() {
-            if let Err(e) = fs::remove_dir_all(&target) {
-                self.status_message = format!("删除旧备份失败: {}", e);
-                self.is_error = true;
-                return;
+    /// 按设置开始或停止定期检查，后台任务执行时暂停检查；出现新问题时立即通知，问题消失时只刷新状态
+    fn update_link_monitor(&mut self, ctx: &egui::Context) {
+        if self.link_monitor.as_ref().is_some_and(|m| m.is_finished()) {
+            if let Some(monitor) = self.link_monitor.take() {
+                let (target, interval) = (monitor.config().0.clone(), monitor.config().1);
+                if let Some(problems) = monitor.join() {
+                    self.refresh_voice_state();
+                    if problems.iter().any(|p| !self.link_problems.contains(p)) {
+                        let details: Vec<String> = problems.iter().map(monitor::Problem::message).collect();
+                        let message = t!("status.links_broken", problems.len(), details.join("\n"));
+                        self.notify(ctx, &message, true);
+                        self.toasts.push(Severity::Warning, message);
+                    } else if problems.is_empty() {
+                        info!("restored voice problems resolved");
+                    }
+                    self.link_problems = problems;
+                }
+                self.link_monitor = Some(monitor::Monitor::spawn(ctx.clone(), target, interval, self.link_problems.clone()));
             }
         }
+        let config = if self.task.is_some() {
+            None
+        } else {
+            self.settings.link_check_interval().and_then(|interval| self.monitor_target().map(|target| (target, interval)))
+        };
+        if self.link_monitor.as_ref().map(|m| m.config()) != config.as_ref().map(|(target, interval)| (target, *interval)) {
+            self.link_problems.clear();
+            self.link_monitor = config.map(|(target, interval)| monitor::Monitor::spawn(ctx.clone(), target, interval, Vec::new()));
+        }
+    }
 
-        // 复制所有语音文件夹，保持目录结构
-        let options = fs_extra::dir::CopyOptions::new().overwrite(true);
-        let mut success = true;
-        let mut copied_folders = 0;
-        let mut copied_files = 0;
+    /// 按当前状态执行启动检查
+    fn health_check(&self) -> Vec<health::Finding> {
+        let source = PathBuf::from(&self.source_path);
+        let game = self.current_game();
+        let game_param = &game.launch_param;
+        let selected = self.languages.get(self.get_selected_lang_code()).map(|lang| lang.miles_lang).unwrap_or_default();
+        let input = health::Input {
+            game_name: &game.name,
+            source_path: &source,
+            data_folder: (!self.source_path.is_empty() && source.is_dir()).then(|| game.check_data_folder(&source)),
+            build_id: self.steam_info.as_ref().map(|s| s.build_id.as_str()),
+            backup_dir: &self.backup_dir,
+            links: self.monitor_target().map(|target| monitor::check(&target)),
+            launch: self.launch_options.as_ref().map(|options| {
+                let values = steam_config::launch_option_values(options, game_param).into_iter().map(str::to_string).collect();
+                (values, selected)
+            }),
+        };
+        health::run(&input)
+    }
 
-        // 复制文件夹
-        for rel_path in &voice_folders {
-            let src_folder = source.join(rel_path);
-            let dst_parent = target.join(rel_path.parent().unwrap_or(rel_path));
-            
-            if let Err(e) = fs::create_dir_all(&dst_parent) {
-                self.status_message = format!("创建目录失败: {}", e);
-                self.is_error = true;
-                success = false;
-                break;
+    /// 执行启动检查中的修复，完成后重新检查
+    fn apply_health_fix(&mut self, fix: health::Fix) {
+        let result = match fix {
+            health::Fix::DetectGame => {
+                self.detect_steam();
+                self.refresh_backups();
+                self.refresh_launch_options();
+                Ok(None)
             }
-            
-            if let Err(e) = fs_extra::dir::copy(&src_folder, &dst_parent, &options) {
-                self.status_message = format!("备份 {} 失败: {}", rel_path.display(), e);
-                self.is_error = true;
-                success = false;
-                break;
-            }
-            copied_folders += 1;
-        }
-
-        // 复制 .toc 文件
-        if success {
-            for rel_path in &toc_files {
-                let src_file = source.join(rel_path);
-                let dst_file = target.join(rel_path);
-                
-                if let Some(parent) = dst_file.parent() {
-                    if let Err(e) = fs::create_dir_all(parent) {
-                        self.status_message = format!("创建目录失败: {}", e);
-                        self.is_error = true;
-                        success = false;
-                        break;
-                    }
-                }
-                
-                if let Err(e) = fs::copy(&src_file, &dst_file) {
-                    self.status_message = format!("备份 {} 失败: {}", rel_path.display(), e);
-                    self.is_error = true;
-                    success = false;
-                    break;
-                }
-                copied_files += 1;
-            }
-        }
-
-        if success {
-            // 保存备份信息
-            let build_id = self.steam_info.as_ref().map(|s| s.build_id.clone()).unwrap_or_default();
-            let folders_str: Vec<String> = voice_folders.iter().map(|p| p.to_string_lossy().to_string()).collect();
-            let files_str: Vec<String> = toc_files.iter().map(|p| p.to_string_lossy().to_string()).collect();
-            let info_content = format!("build_id={}\nlang_code={}\nfolders={}\ntoc_files={}\n", 
-                build_id, lang_code, folders_str.join(";"), files_str.join(";"));
-            let _ = fs::write(target.join("backup_info.txt"), info_content);
-            
-            let lang_name = self.languages.get(lang_code).map(|l| l.name).unwrap_or(lang_code);
-            self.status_message = format!("{} 备份完成！({} 个文件夹, {} 个toc文件, 版本: {})", 
-                lang_name, copied_folders, copied_files, build_id);
-            self.is_error = false;
-            self.refresh_backups();
+            health::Fix::CreateBackupDir => fs::create_dir_all(&self.backup_dir)
+                .map(|()| Some(t!("status.backup_dir_created", self.backup_dir.display())))
+                .map_err(SwitcherError::CreateDir),
+            health::Fix::RepairLinks => self.repair_links().map(|outcome| Some(outcome.message)),
+            health::Fix::WriteLaunchOption => self.write_launch_option(false).map(Some),
+        };
+        match result {
+            Ok(Some(message)) => self.report(Ok(message)),
+            Ok(None) => {}
+            Err(e) => self.report(Err(e)),
         }
+        self.refresh_voice_state();
+        self.health = Some(self.health_check());
     }
 
-    fn restore_files(&mut self) {
-        if self.source_path.is_empty() {
-            self.status_message = "请先选择游戏语音文件夹！".to_string();
-            self.is_error = true;
+    /// 启动检查的清单，有问题时默认展开
+    fn show_health(&mut self, ui: &mut egui::Ui) {
+        let Some(findings) = &self.health else {
             return;
+        };
+        let problems = findings.iter().filter(|f| f.level != health::Level::Ok).count();
+        let title = if problems == 0 { t!("ui.health_ok").to_string() } else { t!("ui.health_problems", problems) };
+        let mut fix = None;
+        let mut recheck = false;
+        let mut dismiss = false;
+        egui::CollapsingHeader::new(title)
+            .id_salt("health")
+            .default_open(problems > 0)
+            .show(ui, |ui| {
+                for finding in findings {
+                    ui.horizontal_wrapped(|ui| {
+                        let (icon, color) = match finding.level {
+                            health::Level::Ok => ("✔", theme::success(ui)),
+                            health::Level::Warning => ("⚠", theme::warning(ui)),
+                            health::Level::Error => ("✖", theme::error(ui)),
+                        };
+                        ui.label(egui::RichText::new(icon).color(color));
+                        ui.label(&finding.message);
+                        if let Some(action) = finding.fix {
+                            if ui.small_button(action.label()).clicked() {
+                                fix = Some(action);
+                            }
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    recheck = ui.button(t!("ui.health_recheck")).on_hover_text(t!("ui.health_recheck_hint")).clicked();
+                    dismiss = ui.button(t!("ui.close")).clicked();
+                });
+            });
+        if let Some(action) = fix {
+            self.apply_health_fix(action);
+        } else if recheck {
+            self.refresh_launch_options();
+            self.health = Some(self.health_check());
+        } else if dismiss {
+            self.health = None;
         }
+    }
 
-        if self.available_backups.is_empty() {
-            self.status_message = "没有可用的备份！".to_string();
-            self.is_error = true;
-            return;
-        }
+    /// 当前游戏中有恢复记录的备份及其目录
+    fn restored_backups(&self) -> Vec<(PathBuf, BackupInfo)> {
+        self.available_backups
+            .iter()
+            .filter(|b| b.restore.is_some())
+            .map(|b| (b.path(&self.backup_dir), b.clone()))
+            .collect()
+    }
 
-        let backup_info = self.available_backups[self.selected_backup_idx].clone();
-        let backup_path = self.backup_dir.join(&backup_info.lang_code);
-        let target = PathBuf::from(&self.source_path);
+    /// 重新创建当前游戏中被移除的恢复文件夹，游戏运行时不做任何修改
+    fn repair_links(&self) -> Result<Outcome, SwitcherError> {
+        self.ensure_game_closed()?;
+        let build_id = self.steam_info.as_ref().map(|s| s.build_id.as_str()).unwrap_or_default();
+        let paths = relink::repair(&self.restored_backups(), build_id, self.settings.version_policy, self.settings.copy_workers());
+        Ok(Outcome { message: t!("status.links_repaired", paths.len()), paths, status: None })
+    }
 
-        if !backup_path.exists() {
-            self.status_message = "备份文件不存在！".to_string();
-            self.is_error = true;
-            return;
+    /// 方案的语音恢复到游戏目录中的文件夹（来自恢复记录）
+    fn restored_paths(&self, lang_code: &str) -> Vec<PathBuf> {
+        self.available_backups
+            .iter()
+            .filter(|b| b.lang_code == lang_code)
+            .filter_map(|b| b.restore.as_ref())
+            .flat_map(|r| r.folders.iter().map(|f| r.target.join(f)))
+            .collect()
+    }
+
+    /// 确保方案的语音已恢复：修复被移除的链接，尚未恢复时直接恢复；launch_option 为 true 时按方案写入启动参数
+    fn prepare_profile(&mut self, profile: Option<&str>, launch_option: bool) -> Result<Outcome, SwitcherError> {
+        let name = profile.unwrap_or(&self.settings.last_profile).to_string();
+        let profile = self.settings.profiles.iter().find(|p| p.name == name).cloned().ok_or(SwitcherError::ProfileNotFound(name))?;
+        self.selected_lang_idx = self
+            .lang_codes
+            .iter()
+            .position(|c| *c == profile.voice)
+            .ok_or_else(|| SwitcherError::UnknownLanguage(profile.voice.clone()))?;
+        self.selected_backup_idx =
+            self.available_backups.iter().position(|b| b.dir_name() == profile.voice).ok_or(SwitcherError::NoBackups)?;
+        self.ensure_game_closed()?;
+        if let Some((backup, current)) = self.check_version_match().filter(|_| !self.version_allowed()) {
+            return Err(SwitcherError::VersionMismatch { backup, current });
         }
 
-        // 版本检查 - 不匹配时阻止恢复
-        if let Some(steam_info) = &self.steam_info {
-            if !backup_info.build_id.is_empty() && backup_info.build_id != steam_info.build_id {
-                self.status_message = format!(
-                    "[!] 版本不匹配！备份: {}, 当前: {}\n请先删除游戏中的语音文件，然后重新执行所有步骤",
-                    backup_info.build_id, steam_info.build_id
-                );
-                self.is_error = true;
-                return;
+        let backup = self.available_backups[self.selected_backup_idx].clone();
+        let source = PathBuf::from(&self.source_path);
+        let mut messages = Vec::new();
+        let paths = match backup.restore.as_ref().filter(|r| r.target == source) {
+            Some(record) => {
+                let build_id = self.steam_info.as_ref().map(|s| s.build_id.as_str()).unwrap_or_default();
+                let folders = record.folders.clone();
+                let repaired =
+                    relink::repair(&[(backup.path(&self.backup_dir), backup)], build_id, self.settings.version_policy, self.settings.copy_workers());
+                let missing = folders.iter().filter(|f| source.join(f).symlink_metadata().is_err()).count();
+                if missing > 0 {
+                    return Err(SwitcherError::RepairIncomplete(missing));
+                }
+                messages.push(t!("status.links_repaired", repaired.len()));
+                repaired
             }
+            None => {
+                let (job, total) = self.prepare_restore()?;
+                let paths = job.voice_folders.iter().map(|f| job.target.join(f)).collect();
+                messages.push(job.run(&Progress::new(total))?);
+                paths
+            }
+        };
+        if launch_option && profile.launch_option {
+            messages.push(self.write_launch_option(false)?);
         }
+        self.settings.last_profile = profile.name;
+        self.save_settings()?;
+        Ok(Outcome { message: messages.join("\n"), paths, status: None })
+    }
 
-        // 递归查找备份中的所有语音文件夹和 .toc 文件
-        let (voice_folders, toc_files) = self.find_voice_files(&backup_path, &backup_info.lang_code);
-        let mut success = true;
-        let mut restored_folders = 0;
-        let mut restored_files = 0;
+    /// 不打开窗口执行命令行命令；启动器模式确保方案的语音已恢复后以该语音的参数启动游戏
+    fn run_command(&mut self, command: &Command) -> Result<Outcome, SwitcherError> {
+        match command {
+            Command::Switch(profile) => self.prepare_profile(Some(profile), true),
+            Command::Launch(profile) => {
+                let mut outcome = self.prepare_profile(profile.as_deref(), false)?;
+                let launched = self.launch_game()?;
+                outcome.message = [outcome.message, launched].join("\n");
+                Ok(outcome)
+            }
+            Command::Repair => self.repair_links(),
+            Command::Status => Ok(self.command_outcome(command, String::new())),
+        }
+    }
 
-        // 使用 Junction 链接文件夹
-        for rel_path in &voice_folders {
-            let src_folder = backup_path.join(rel_path);
-            let dst_parent = target.join(rel_path.parent().unwrap_or(rel_path));
-            let dst_folder = target.join(rel_path);
-            
-            // 先删除目标
-            if Self::is_junction(&dst_folder) {
-                let _ = Self::remove_junction(&dst_folder);
+    /// 在窗口中执行命令行转发的命令，返回开始时的消息和后台任务结束后是否启动游戏
+    fn start_command(&mut self, command: &Command) -> Result<(String, bool), SwitcherError> {
+        let select_profile = |app: &mut Self, name: &str| match app.settings.profiles.iter().position(|p| p.name == name) {
+            Some(idx) => {
+                app.selected_profile_idx = idx;
+                Ok(())
             }
-            
-            // 创建目标父目录
-            if let Err(e) = fs::create_dir_all(&dst_parent) {
-                self.status_message = format!("创建目录失败: {}", e);
-                self.is_error = true;
-                success = false;
-                break;
+            None => Err(SwitcherError::ProfileNotFound(name.to_string())),
+        };
+        match command {
+            Command::Switch(profile) => {
+                select_profile(self, profile)?;
+                Ok((self.apply_profile()?, false))
             }
-            
-            // 创建 Junction
-            if let Err(e) = Self::create_junction(&src_folder, &dst_folder) {
-                self.status_message = format!("创建链接 {} 失败: {}", rel_path.display(), e);
-                self.is_error = true;
-                success = false;
-                break;
-            }
-            restored_folders += 1;
-        }
-
-        // 复制 .toc 文件
-        if success {
-            for rel_path in &toc_files {
-                let src_file = backup_path.join(rel_path);
-                let dst_file = target.join(rel_path);
-                
-                if let Some(parent) = dst_file.parent() {
-                    if let Err(e) = fs::create_dir_all(parent) {
-                        self.status_message = format!("创建目录失败: {}", e);
-                        self.is_error = true;
-                        success = false;
-                        break;
-                    }
-                }
-                
-                if let Err(e) = fs::copy(&src_file, &dst_file) {
-                    self.status_message = format!("恢复 {} 失败: {}", rel_path.display(), e);
-                    self.is_error = true;
-                    success = false;
-                    break;
-                }
-                restored_files += 1;
-            }
-        }
-
-        if success && (restored_folders > 0 || restored_files > 0) {
-            let lang = self.languages.get(backup_info.lang_code.as_str());
-            let lang_name = lang.map(|l| l.name).unwrap_or(&backup_info.lang_code);
-            let miles_lang = lang.map(|l| l.miles_lang).unwrap_or("");
-            self.status_message = format!("语音已链接为 {}！({} 个链接, {} 个toc文件)\n请添加启动项: +miles_language {}", 
-                lang_name, restored_folders, restored_files, miles_lang);
-            self.is_error = false;
-        } else if restored_folders == 0 && restored_files == 0 {
-            self.status_message = "备份中没有找到语音文件".to_string();
-            self.is_error = true;
-        }
-    }
-
-    /// 创建 Junction
-    fn create_junction(src: &PathBuf, dst: &PathBuf) -> Result<(), String> {
-        let output = Command::new("cmd")
-            .args(["/C", "mklink", "/J", &dst.to_string_lossy(), &src.to_string_lossy()])
-            .creation_flags(CREATE_NO_WINDOW)
-            .output()
-            .map_err(|e| e.to_string())?;
-        
-        if output.status.success() {
-            Ok(())
-        } else {
-            Err(String::from_utf8_lossy(&output.stderr).to_string())
+            Command::Launch(profile) => {
+                let name = profile.clone().unwrap_or_else(|| self.settings.last_profile.clone());
+                select_profile(self, &name)?;
+                Ok((self.apply_profile()?, true))
+            }
+            Command::Repair => {
+                if self.task.is_some() {
+                    return Err(SwitcherError::TaskRunning);
+                }
+                self.ensure_game_closed()?;
+                let backups = self.restored_backups();
+                let build_id = self.steam_info.as_ref().map(|s| s.build_id.clone()).unwrap_or_default();
+                let (policy, workers) = (self.settings.version_policy, self.settings.copy_workers());
+                self.task =
+                    Some(Task::spawn(0, move |_| Ok(t!("status.links_repaired", relink::repair(&backups, &build_id, policy, workers).len()))));
+                Ok((t!("status.repair_running").to_string(), false))
+            }
+            Command::Status => Ok((String::new(), false)),
         }
     }
 
-    /// 检查路径是否为 Junction
-    fn is_junction(path: &PathBuf) -> bool {
-        use std::os::windows::fs::MetadataExt;
-        const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
-        
-        if let Ok(metadata) = fs::symlink_metadata(path) {
-            (metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT) != 0
-        } else {
-            false
+    /// 命令结束后的结果，状态命令附上状态报告
+    fn command_outcome(&self, command: &Command, message: String) -> Outcome {
+        match command {
+            Command::Status => {
+                let report = self.status_report();
+                Outcome { message: report.to_text(), paths: Vec::new(), status: Some(Box::new(report)) }
+            }
+            _ => Outcome { message, paths: self.ipc_paths(command), status: None },
         }
     }
 
-    /// 删除 Junction
-    fn remove_junction(path: &PathBuf) -> Result<(), std::io::Error> {
-        Command::new("cmd")
-            .args(["/C", "rmdir", &path.to_string_lossy()])
-            .creation_flags(CREATE_NO_WINDOW)
-            .output()?;
-        Ok(())
+    /// 状态报告：检测到的安装、备份、恢复的链接和启动选项
+    fn status_report(&self) -> report::StatusReport {
+        let game = self.current_game();
+        let selected = self.steam_info.as_ref().map(|s| s.game_path.as_path());
+        let mut installs: Vec<&SteamInfo> = self.install_candidates.iter().collect();
+        if let Some(steam) = self.steam_info.as_ref().filter(|s| !self.install_candidates.iter().any(|c| c.game_path == s.game_path)) {
+            installs.insert(0, steam);
+        }
+        let links = self
+            .available_backups
+            .iter()
+            .filter_map(|b| b.restore.as_ref())
+            .flat_map(|r| r.folders.iter().map(move |f| (r.target.join(f), r.mode)))
+            .map(|(path, mode)| report::LinkStatus {
+                target: match mode {
+                    RestoreMode::Junction => link::read_junction_target(&path),
+                    RestoreMode::Symlink => fs::read_link(&path).ok(),
+                    RestoreMode::Hardlink | RestoreMode::Copy => None,
+                },
+                intact: relink::is_intact(&path, mode),
+                mode: format!("{:?}", mode),
+                path,
+            })
+            .collect();
+        report::StatusReport {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            game: format!("{} ({}, app {})", game.name, game.id, game.app_id),
+            installs: installs
+                .into_iter()
+                .map(|install| report::InstallStatus {
+                    platform: install.platform.label().to_string(),
+                    game_path: install.game_path.clone(),
+                    build_id: install.build_id.clone(),
+                    update_pending: install.update_pending,
+                    selected: selected == Some(install.game_path.as_path()),
+                })
+                .collect(),
+            voice_folder: self.source_path.clone(),
+            backup_dir: self.backup_dir.clone(),
+            backups: self
+                .available_backups
+                .iter()
+                .map(|b| report::BackupStatus {
+                    lang_code: b.lang_code.clone(),
+                    build_id: b.build_id.clone(),
+                    size: self.backup_sizes.get(b.dir_name()).copied().unwrap_or_default(),
+                    compressed: b.compressed,
+                    restored: b.restore.is_some(),
+                })
+                .collect(),
+            links,
+            launch_options: self.launch_options.clone(),
+            launch_language: self
+                .launch_options
+                .as_deref()
+                .and_then(|options| steam_config::launch_option_value(options, &game.launch_param))
+                .map(str::to_string),
+        }
     }
 
-    fn check_version_match(&self) -> Option<(String, String)> {
-        if self.available_backups.is_empty() {
-            return None;
-        }
-        
-        let backup = &self.available_backups[self.selected_backup_idx];
-        if let Some(steam) = &self.steam_info {
-            if !backup.build_id.is_empty() && backup.build_id != steam.build_id {
-                return Some((backup.build_id.clone(), steam.build_id.clone()));
+    /// 导出状态报告，扩展名为 .json 时导出 JSON，否则导出文本
+    fn export_status(&self, dest: &Path) -> Result<String, SwitcherError> {
+        let report = self.status_report();
+        let content = if dest.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            serde_json::to_string_pretty(&report)?
+        } else {
+            report.to_text()
+        };
+        fs::write(dest, content)?;
+        Ok(t!("status.status_exported", dest.display()))
+    }
+
+    /// 窗口中执行的命令结束后报告的文件夹：方案语音的恢复记录，修复时为所有恢复记录
+    fn ipc_paths(&self, command: &Command) -> Vec<PathBuf> {
+        match command {
+            Command::Switch(_) | Command::Launch(_) => {
+                let voice = self.settings.profiles.iter().find(|p| p.name == self.settings.last_profile).map(|p| p.voice.clone());
+                voice.map(|voice| self.restored_paths(&voice)).unwrap_or_default()
             }
+            Command::Repair => {
+                self.restored_backups().iter().flat_map(|(_, b)| self.restored_paths(&b.lang_code)).collect()
+            }
+            Command::Status => Vec::new(),
+        }
+    }
+
+    /// 处理命名管道收到的命令，并把后台任务的进度发回命令行
+    fn handle_ipc(&mut self) {
+        if self.ipc_request.is_none() {
+            if let Some(request) = self.ipc.as_ref().and_then(|server| server.next()) {
+                match self.start_command(&request.command) {
+                    Ok((message, launch)) if self.task.is_some() => {
+                        request.message(&message);
+                        self.ipc_request = Some((request, launch));
+                    }
+                    Ok((message, launch)) => {
+                        let result = if launch { self.launch_game().map(|m| format!("{}\n{}", message, m)) } else { Ok(message) };
+                        let outcome = result.map(|message| self.command_outcome(&request.command, message));
+                        request.finish(outcome.map_err(|e| e.to_string()));
+                    }
+                    Err(e) => request.finish(Err(e.to_string())),
+                }
+            }
+        }
+        if let (Some((request, _)), Some(task)) = (&mut self.ipc_request, &self.task) {
+            let progress = task.progress();
+            request.progress((progress.fraction() * 100.0) as u32, &progress.current_file);
+        }
+    }
+
+    /// 在桌面创建以启动器模式应用所选方案并启动游戏的快捷方式，图标使用游戏主程序的图标
+    fn create_profile_shortcut(&self) -> Result<String, SwitcherError> {
+        let profile = self.settings.profiles.get(self.selected_profile_idx).ok_or(SwitcherError::NoProfileSelected)?;
+        let game = self.current_game();
+        let icon = self
+            .steam_info
+            .as_ref()
+            .map(|s| s.game_path.join(&game.exe_name))
+            .filter(|path| !game.exe_name.is_empty() && path.exists());
+        let name = format!("{} ({})", game.name, profile.name);
+        let path = shortcut::create_desktop_shortcut(&name, &profile.name, icon.as_deref())?;
+        Ok(t!("status.shortcut_created", path.display()))
+    }
+
+    /// 将以启动器模式应用所选方案的本程序添加为 Steam 非 Steam 游戏，可从大屏幕模式启动
+    fn add_profile_to_steam(&self) -> Result<String, SwitcherError> {
+        let profile = self.settings.profiles.get(self.selected_profile_idx).ok_or(SwitcherError::NoProfileSelected)?;
+        let steam = self.steam_info.as_ref().filter(|s| !s.steam_path.as_os_str().is_empty()).ok_or(SwitcherError::SteamNotFound)?;
+        let game = self.current_game();
+        let icon = Some(steam.game_path.join(&game.exe_name)).filter(|path| !game.exe_name.is_empty() && path.exists());
+        let name = format!("{} ({})", game.name, profile.name);
+        let exe = std::env::current_exe()?;
+        let launch_options = shortcut::launch_args(&profile.name);
+        let shortcut = steam_config::Shortcut { name: &name, exe: &exe, launch_options: &launch_options, icon: icon.as_deref() };
+        steam_config::add_shortcut(&steam.steam_path, &shortcut)?;
+        Ok(t!("status.steam_shortcut_added", name))
+    }
+
+    fn register_repair_task(&mut self) -> Result<String, SwitcherError> {
+        schedule::register()?;
+        self.repair_task = true;
+        Ok(t!("status.repair_task_registered").to_string())
+    }
+
+    fn unregister_repair_task(&mut self) -> Result<String, SwitcherError> {
+        schedule::unregister()?;
+        self.repair_task = false;
+        Ok(t!("status.repair_task_removed").to_string())
+    }
+
+    fn show_relink_prompt(&mut self, ctx: &egui::Context) {
+        let Some((profile, change)) = &self.relink_prompt else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("relink")).show(ctx, |ui| {
+            ui.set_width(420.0);
+            ui.heading(t!("ui.relink_title"));
+            ui.label(t!("ui.relink_hint", change.build_id, change.removed, profile));
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                let button = ui.button(t!("ui.relink_apply")).on_hover_text(t!("ui.relink_apply_hint"));
+                a11y::focus_dialog(ui, &button);
+                confirmed = button.clicked();
+                cancelled = ui.button(t!("ui.cancel")).clicked();
+            });
+        });
+        if cancelled || modal.should_close() {
+            self.relink_prompt = None;
+        } else if confirmed {
+            let name = profile.clone();
+            self.relink_prompt = None;
+            let result = match self.settings.profiles.iter().position(|p| p.name == name) {
+                Some(idx) => {
+                    self.selected_profile_idx = idx;
+                    self.apply_profile()
+                }
+                None => Err(SwitcherError::NoProfileSelected),
+            };
+            self.report(result);
+        }
+    }
+
+    /// 注册设置中的全局热键，格式错误或已被其他程序占用的热键显示在状态栏
+    fn start_hotkeys(&mut self, ctx: &egui::Context) {
+        let mut errors = Vec::new();
+        let mut bindings = Vec::new();
+        for binding in &self.settings.hotkeys {
+            match hotkey::parse(&binding.keys) {
+                Some((modifiers, vk)) => bindings.push((modifiers, vk, binding.keys.clone(), binding.lang.clone())),
+                None => errors.push(SwitcherError::InvalidHotkey(binding.keys.clone())),
+            }
+        }
+        if !bindings.is_empty() {
+            let (hotkeys, failed) = hotkey::Hotkeys::spawn(ctx.clone(), bindings);
+            errors.extend(failed.into_iter().map(SwitcherError::HotkeyUnavailable));
+            self.hotkeys = Some(hotkeys);
+        }
+        if !errors.is_empty() {
+            let message = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n");
+            error!("{}", message);
+            self.toasts.push(Severity::Error, message);
+        }
+    }
+
+    /// 通过托盘图标显示通知（Windows 10 起显示为系统通知），未开启托盘时临时创建托盘图标
+    fn notify(&mut self, ctx: &egui::Context, message: &str, is_error: bool) {
+        if self.tray.is_none() {
+            self.notice_tray = tray::Tray::spawn(ctx.clone(), WINDOW_TITLE).map(|tray| (tray, Instant::now()));
+            ctx.request_repaint_after(NOTICE_DURATION);
+        }
+        if let Some(tray) = self.tray.as_ref().or(self.notice_tray.as_ref().map(|(tray, _)| tray)) {
+            tray.notify(t!("ui.title"), message, is_error);
+        }
+    }
+
+    fn show_elevation_prompt(&mut self, ctx: &egui::Context) {
+        if !self.show_elevation {
+            return;
+        }
+        let mut relaunch = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("elevation")).show(ctx, |ui| {
+            ui.set_width(420.0);
+            ui.heading(t!("ui.elevation_title"));
+            ui.label(t!("ui.elevation_hint"));
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                let button = ui.button(t!("ui.elevation_relaunch")).on_hover_text(t!("ui.elevation_relaunch_hint"));
+                a11y::focus_dialog(ui, &button);
+                relaunch = button.clicked();
+                cancelled = ui.button(t!("ui.cancel")).clicked();
+            });
+        });
+        if relaunch {
+            self.show_elevation = false;
+            self.relaunch_as_admin(ctx);
+        } else if cancelled || modal.should_close() {
+            self.show_elevation = false;
+        }
+    }
+
+    /// 建议加入杀毒软件排除项的文件夹：游戏目录和备份目录
+    fn exclusion_paths(&self) -> Vec<PathBuf> {
+        let game = PathBuf::from(&self.source_path);
+        let game = std::path::absolute(&game).unwrap_or(game);
+        let backup = std::path::absolute(&self.backup_dir).unwrap_or_else(|_| self.backup_dir.clone());
+        vec![game, backup]
+    }
+
+    /// 任务中发现杀毒软件干扰时，列出原因和需要排除的文件夹，任务失败时可以在排除后重试
+    fn show_antivirus_hint(&mut self, ctx: &egui::Context) {
+        let Some((interference, retry_id)) = self.antivirus_hint else {
+            return;
+        };
+        let paths = self.exclusion_paths();
+        let command = antivirus::exclusion_command(&paths);
+        let mut open_settings = false;
+        let mut retry = false;
+        let mut closed = false;
+        let modal = egui::Modal::new(egui::Id::new("antivirus")).show(ctx, |ui| {
+            ui.set_width(480.0);
+            ui.heading(t!("ui.antivirus_title"));
+            ui.label(t!("ui.antivirus_hint"));
+            for reason in interference.reasons() {
+                ui.colored_label(theme::warning(ui), format!("[!] {}", reason));
+            }
+            ui.add_space(5.0);
+            ui.label(t!("ui.antivirus_paths"));
+            for path in &paths {
+                ui.add(egui::Label::new(egui::RichText::new(path.display().to_string()).monospace()).selectable(true));
+            }
+            ui.add_space(5.0);
+            ui.label(t!("ui.antivirus_command"));
+            ui.add(egui::TextEdit::multiline(&mut command.clone()).code_editor().desired_rows(2).desired_width(f32::INFINITY));
+            ui.horizontal(|ui| {
+                if ui.button(t!("ui.copy_clipboard")).on_hover_text(t!("ui.antivirus_copy_hint")).clicked() {
+                    ui.ctx().copy_text(command.clone());
+                    self.toasts.push(Severity::Success, t!("status.copied").to_string());
+                }
+                open_settings = ui.button(t!("ui.antivirus_open_settings")).on_hover_text(t!("ui.antivirus_open_settings_hint")).clicked();
+            });
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if retry_id.is_some() {
+                    let button = ui.button(t!("ui.antivirus_retry")).on_hover_text(t!("ui.antivirus_retry_hint"));
+                    a11y::focus_dialog(ui, &button);
+                    retry = button.clicked();
+                }
+                let button = ui.button(t!("ui.close"));
+                if retry_id.is_none() {
+                    a11y::focus_dialog(ui, &button);
+                }
+                closed = button.clicked();
+            });
+        });
+        if open_settings {
+            if let Err(e) = antivirus::open_settings() {
+                self.report(Err(SwitcherError::SecuritySettings(e)));
+            }
+        }
+        if retry {
+            self.antivirus_hint = None;
+            if let Some(id) = retry_id {
+                self.retry_queued(id);
+            }
+        } else if closed || modal.should_close() {
+            self.antivirus_hint = None;
+        }
+    }
+
+    fn save_settings(&self) -> Result<(), SwitcherError> {
+        self.settings
+            .save(&self.settings_path)
+            .map_err(|e| SwitcherError::SaveSettings(Box::new(e)))
+    }
+
+    /// 记录窗口位置和大小，窗口关闭时保存；最大化时保留之前的位置和大小
+    fn track_window(&mut self, ctx: &egui::Context) {
+        if let Some(geometry) = window::current(ctx) {
+            match &mut self.settings.window {
+                Some(saved) if geometry.maximized => saved.maximized = true,
+                saved => *saved = Some(geometry),
+            }
+        }
+        if ctx.input(|i| i.viewport().close_requested()) {
+            if let Err(e) = self.save_settings() {
+                warn!("failed to save window geometry: {}", e);
+            }
+        }
+    }
+
+    /// 默认选中游戏当前安装的语言
+    fn select_installed_language(&mut self) {
+        if let Some(code) = self.installed_languages().first() {
+            if let Some(idx) = self.lang_codes.iter().position(|c| c == code) {
+                self.selected_lang_idx = idx;
+            }
+        }
+    }
+
+    /// 游戏运行时语音文件被占用，修改前先确认游戏已关闭
+    fn ensure_game_closed(&self) -> Result<(), SwitcherError> {
+        let game = self.current_game();
+        if !game.exe_name.is_empty() && steam_config::is_process_running(&game.exe_name) {
+            return Err(SwitcherError::GameRunning(game.name.clone()));
+        }
+        Ok(())
+    }
+
+    /// 检测 Steam 安装路径和游戏信息
+    fn detect_steam(&mut self) {
+        // 常见 Steam 安装路径
+        let possible_paths = [
+            PathBuf::from("C:\\Program Files (x86)\\Steam"),
+            PathBuf::from("C:\\Program Files\\Steam"),
+            PathBuf::from("D:\\Steam"),
+            PathBuf::from("E:\\Steam"),
+            PathBuf::from("D:\\Program Files (x86)\\Steam"),
+            PathBuf::from("E:\\Program Files (x86)\\Steam"),
+        ];
+
+        self.install_candidates.clear();
+        // 也尝试从注册表读取（简化版，直接检查路径）
+        let steam = possible_paths
+            .iter()
+            .filter(|steam_path| steam_path.join("steam.exe").exists())
+            .find_map(|steam_path| self.parse_steam_info(steam_path));
+
+        // 常见路径中找不到时扫描所有本地磁盘；同时查找 EA App 安装，找到多个安装时由用户选择
+        let mut found = match steam {
+            Some(info) => vec![info],
+            None => self.scan_drives_for_game(),
+        };
+        let steam_path = found.first().map(|info| info.steam_path.clone()).unwrap_or_default();
+        let game = self.current_game();
+        found.extend(ea_app::find_installs(&game.ea_install_dir, &game.exe_name).into_iter().map(|install| SteamInfo {
+            platform: Platform::EaApp,
+            steam_path: steam_path.clone(),
+            game_path: install.game_path,
+            manifest_path: install.installer_data,
+            build_id: install.version,
+            text_language: String::new(),
+            update_pending: false,
+        }));
+        found.extend(xbox::find_installs(&game.xbox_install_dir, &game.exe_name).into_iter().map(|install| SteamInfo {
+            platform: Platform::Xbox,
+            steam_path: steam_path.clone(),
+            game_path: install.game_path,
+            manifest_path: install.manifest,
+            build_id: install.version,
+            text_language: String::new(),
+            update_pending: false,
+        }));
+        if found.len() > 1 {
+            self.toasts.push(Severity::Warning, t!("status.installs_found", found.len(), self.current_game().name));
+        }
+        if let Some(info) = found.first().cloned() {
+            self.install_candidates = found;
+            self.use_install(info);
+        }
+    }
+
+    /// 使用检测到的游戏安装，语音文件目录看起来不对时提示
+    fn use_install(&mut self, info: SteamInfo) {
+        self.source_path = self.current_game().data_path(&info.game_path).to_string_lossy().to_string();
+        info!("detected {} at {} (build {})", self.current_game().name, info.game_path.display(), info.build_id);
+        let message = t!("status.game_detected", self.current_game().name, info.build_id);
+        let platform = info.platform;
+        self.steam_info = Some(info);
+        self.toasts.push(Severity::Success, message);
+        let data_path = PathBuf::from(&self.source_path);
+        if platform == Platform::Xbox && !xbox::is_writable(&data_path) {
+            self.toasts.push(Severity::Warning, t!("status.xbox_not_writable", data_path.display()));
+        }
+        if self.current_game().check_data_folder(&data_path) != DataFolderCheck::Valid {
+            let game = self.current_game();
+            let message = t!("status.source_unexpected", data_path.display(), game.name, game.data_subpath);
+            warn!("{}", message);
+            self.toasts.push(Severity::Warning, message);
+        }
+    }
+
+    /// 在所有本地磁盘上的 Steam 库中查找当前游戏
+    fn scan_drives_for_game(&self) -> Vec<SteamInfo> {
+        let libraries = drive_scan::find_libraries();
+        // 库目录不一定是 Steam 安装目录，写入启动选项需要 steam.exe 所在的目录
+        let steam_path = libraries.iter().find(|lib| lib.join("steam.exe").exists()).cloned();
+        libraries
+            .iter()
+            .filter_map(|lib| self.find_in_library(steam_path.as_deref().unwrap_or(lib), lib))
+            .collect()
+    }
+
+    /// 解析 Steam 信息
+    fn parse_steam_info(&self, steam_path: &Path) -> Option<SteamInfo> {
+        // 读取 libraryfolders.vdf 获取所有库路径
+        let library_folders = self.get_library_folders(steam_path);
+        
+        // 在所有库中查找当前游戏
+        library_folders.iter().find_map(|lib_path| self.find_in_library(steam_path, lib_path))
+    }
+
+    /// 读取 Steam 库中当前游戏的 appmanifest
+    fn find_in_library(&self, steam_path: &Path, lib_path: &Path) -> Option<SteamInfo> {
+        let app_id = &self.current_game().app_id;
+        let manifest_path = lib_path.join("steamapps").join(format!("appmanifest_{}.acf", app_id));
+        if !manifest_path.exists() {
+            return None;
+        }
+        let manifest = steam_config::parse_app_manifest(&manifest_path)?;
+        Some(SteamInfo {
+            platform: Platform::Steam,
+            steam_path: steam_path.to_path_buf(),
+            game_path: lib_path.join("steamapps").join("common").join(manifest.install_dir),
+            manifest_path,
+            build_id: manifest.build_id,
+            text_language: manifest.language,
+            update_pending: manifest.update_pending,
+        })
+    }
+
+    /// 获取所有 Steam 库文件夹
+    fn get_library_folders(&self, steam_path: &Path) -> Vec<PathBuf> {
+        let mut folders = vec![steam_path.to_path_buf()];
+        let vdf_path = steam_path.join("steamapps").join("libraryfolders.vdf");
+        
+        if let Ok(content) = fs::read_to_string(&vdf_path) {
+            for line in content.lines() {
+                if line.contains("\"path\"") {
+                    if let Some(path) = steam_config::extract_vdf_value(line) {
+                        let path = PathBuf::from(path.replace("\\\\", "\\"));
+                        if path.exists() && !folders.contains(&path) {
+                            folders.push(path);
+                        }
+                    }
+                }
+            }
+        }
+        folders
+    }
+
+    /// 重新读取 appmanifest 中的更新状态，Steam 可能在程序运行期间排队更新
+    fn refresh_update_state(&mut self) {
+        let Some(manifest_path) = self.steam_info.as_ref().filter(|s| s.platform == Platform::Steam).map(|s| s.manifest_path.clone()) else {
+            return;
+        };
+        let pending = steam_config::parse_app_manifest(&manifest_path).is_some_and(|m| m.update_pending);
+        if let Some(steam) = &mut self.steam_info {
+            steam.update_pending = pending;
+        }
+    }
+
+    fn refresh_backups(&mut self) {
+        self.history = history::load(&self.backup_dir);
+        let selected = self.available_backups.get(self.selected_backup_idx).map(|b| b.dir_name().to_string());
+        self.available_backups.clear();
+        self.backup_sizes.clear();
+        if let Ok(entries) = fs::read_dir(&self.backup_dir) {
+            for entry in entries.flatten() {
+                if entry.path().is_dir() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let code = backup_info::base_code(&name);
+                    if self.languages.contains_key(code) {
+                        // 读取备份信息
+                        let info = BackupInfo::load(&entry.path(), code);
+                        self.backup_sizes.insert(name, disk::dir_size(&entry.path()));
+                        self.available_backups.push(info);
+                    }
+                }
+            }
+        }
+        self.sort_backups();
+        self.usage = None;
+        self.selected_backup_idx = selected
+            .and_then(|name| self.available_backups.iter().position(|b| b.dir_name() == name))
+            .unwrap_or(0);
+        self.check_backup_limit();
+    }
+
+    /// 按所选列排序备份列表
+    fn sort_backups(&mut self) {
+        let (column, ascending) = self.backup_sort;
+        let mut backups = std::mem::take(&mut self.available_backups);
+        backups.sort_by(|a, b| {
+            let ordering = match column {
+                BackupColumn::Language => self.backup_name(a).cmp(self.backup_name(b)),
+                BackupColumn::Build => {
+                    let build = |info: &BackupInfo| (info.build_id.parse::<u64>().unwrap_or(0), info.build_id.clone());
+                    build(a).cmp(&build(b))
+                }
+                BackupColumn::Date => a.created.cmp(&b.created),
+                BackupColumn::Size => self.backup_sizes.get(a.dir_name()).cmp(&self.backup_sizes.get(b.dir_name())),
+                BackupColumn::Note => a.note.cmp(&b.note),
+            };
+            if ascending { ordering } else { ordering.reverse() }
+        });
+        self.available_backups = backups;
+    }
+
+    /// 点击列标题：同一列切换升降序，其他列按升序排序
+    fn set_backup_sort(&mut self, column: BackupColumn) {
+        let selected = self.available_backups.get(self.selected_backup_idx).map(|b| b.dir_name().to_string());
+        self.backup_sort = if self.backup_sort.0 == column { (column, !self.backup_sort.1) } else { (column, true) };
+        self.sort_backups();
+        self.selected_backup_idx = selected
+            .and_then(|name| self.available_backups.iter().position(|b| b.dir_name() == name))
+            .unwrap_or(0);
+    }
+
+    /// 备份目录位于游戏目录中，或游戏目录位于备份目录中
+    fn backup_inside_game(&self) -> bool {
+        let source = Path::new(&self.source_path);
+        !self.source_path.is_empty()
+            && (link::points_into(&self.backup_root, source) || link::points_into(source, &self.backup_root))
+    }
+
+    /// 备份目录与游戏目录重叠时拒绝备份和恢复，避免备份中再包含备份；网络共享中的备份目录先检查能否访问
+    fn check_backup_location(&self) -> Result<(), SwitcherError> {
+        if self.backup_inside_game() {
+            return Err(SwitcherError::BackupInsideGame(self.backup_root.clone()));
+        }
+        check_network_access(&self.backup_root)
+    }
+
+    /// 设置语音文件夹（浏览或拖入），看起来不像游戏的语音文件目录时提示
+    fn set_source_path(&mut self, path: PathBuf) -> Result<String, SwitcherError> {
+        if self.task.is_some() || self.queue.has_pending() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        if !path.is_dir() {
+            return Err(SwitcherError::NotADirectory(path));
+        }
+        let check = self.current_game().check_data_folder(&path);
+        let path = match &check {
+            DataFolderCheck::Corrected(data_path) => data_path.clone(),
+            _ => path,
+        };
+        self.source_path = path.to_string_lossy().to_string();
+        self.refresh_voice_state();
+        let game = self.current_game();
+        let mut message = match check {
+            DataFolderCheck::Valid => t!("status.source_set", path.display()),
+            DataFolderCheck::Corrected(_) => t!("status.source_corrected", path.display()),
+            DataFolderCheck::WrongGame => t!("status.source_wrong_game", path.display(), game.name),
+            DataFolderCheck::Unexpected => t!("status.source_unexpected", path.display(), game.name, game.data_subpath),
+        };
+        if self.backup_inside_game() {
+            message.push('\n');
+            message.push_str(&t!("status.backup_inside_game", self.backup_root.display()));
+        }
+        Ok(message)
+    }
+
+    /// 拖入窗口的文件夹设为语音文件夹，拖动经过窗口时显示提示
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let hovering = ctx.input(|i| !i.raw.hovered_files.is_empty());
+        if hovering {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("drop_folder")));
+            let rect = ctx.content_rect();
+            painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                t!("ui.drop_folder"),
+                egui::TextStyle::Heading.resolve(&ctx.style()),
+                egui::Color32::WHITE,
+            );
+        }
+        let dropped = ctx.input(|i| i.raw.dropped_files.iter().find_map(|file| file.path.clone()));
+        if let Some(path) = dropped {
+            let result = self.set_source_path(path);
+            self.report(result);
+        }
+    }
+
+    /// 在后台扫描游戏目录，完成后更新各语言的语音状态
+    fn refresh_voice_state(&mut self) {
+        self.voice_scan = None;
+        self.refresh_launch_options();
+        self.refresh_update_state();
+        let source = PathBuf::from(&self.source_path);
+        if self.source_path.is_empty() || !source.exists() {
+            self.voice_state.clear();
+            self.voice_sizes.clear();
+            self.size_scan = None;
+            return;
+        }
+        let codes = self.lang_codes.clone();
+        let matchers = codes.iter().map(|code| self.voice_matcher(code)).collect();
+        let exclude = self.current_game().exclude_patterns.clone();
+        let skip_dirs = vec![self.backup_root.clone()];
+        self.voice_scan = Some((scan::VoiceScan::spawn(source, matchers, exclude, skip_dirs, self.settings.copy_workers()), codes));
+    }
+
+    /// 记录每个语言的语音文件夹是普通目录、Junction 还是缺失
+    fn apply_voice_scan(&mut self, source: PathBuf, codes: Vec<&'static str>, found: Vec<scan::Found>) {
+        self.voice_state.clear();
+        self.voice_sizes.clear();
+        for (code, found) in codes.into_iter().zip(found) {
+            let scan::Found { folders: found_folders, toc_files, .. } = found;
+            let backup = self.available_backups.iter().find(|b| b.dir_name() == code);
+            // 恢复的可能是该语言的副本
+            let restore = self
+                .available_backups
+                .iter()
+                .filter(|b| b.lang_code == code)
+                .find_map(|b| b.restore.as_ref().filter(|r| r.target == source));
+            let mut folders: Vec<VoiceFolderStatus> = found_folders
+                .into_iter()
+                .map(|rel_path| {
+                    let full_path = source.join(&rel_path);
+                    let state = if link::is_junction(&full_path) {
+                        FolderState::Junction(link::read_junction_target(&full_path))
+                    } else if let Some(record) = restore.filter(|r| r.folders.contains(&rel_path)) {
+                        FolderState::Restored(record.mode)
+                    } else {
+                        FolderState::Directory
+                    };
+                    VoiceFolderStatus { rel_path, state }
+                })
+                .collect();
+
+            // 备份中记录但游戏目录中不存在的文件夹
+            if let Some(backup) = backup {
+                for rel_path in &backup.folders {
+                    if !folders.iter().any(|f| &f.rel_path == rel_path) {
+                        folders.push(VoiceFolderStatus {
+                            rel_path: rel_path.clone(),
+                            state: FolderState::Missing,
+                        });
+                    }
+                }
+            }
+
+            if folders.iter().all(|f| matches!(f.state, FolderState::Missing)) && toc_files.is_empty() {
+                continue;
+            }
+            self.voice_state.push(LangVoiceState {
+                lang_code: code,
+                folders,
+                toc_files,
+            });
+        }
+
+        // 备份会复制的普通文件夹和 .toc 文件，在后台统计大小
+        let groups = self
+            .voice_state
+            .iter()
+            .map(|state| {
+                let paths = state
+                    .folders
+                    .iter()
+                    .filter(|f| matches!(f.state, FolderState::Directory | FolderState::Restored(_)))
+                    .map(|f| source.join(&f.rel_path))
+                    .chain(state.toc_files.iter().map(|rel_path| source.join(rel_path)))
+                    .collect();
+                (state.lang_code.to_string(), paths)
+            })
+            .collect();
+        self.size_scan = Some(disk::SizeScan::spawn(groups));
+    }
+
+    /// 当前实际生效的语音：优先取存在 Junction 的语言，其次取存在原始文件夹的语言
+    fn get_active_voice(&self) -> Option<(&'static str, bool)> {
+        if let Some(state) = self.voice_state.iter().find(|s| s.restored_count() > 0) {
+            return Some((state.lang_code, true));
+        }
+        self.voice_state
+            .iter()
+            .find(|s| s.directory_count() > 0)
+            .map(|s| (s.lang_code, false))
+    }
+
+    /// miles_language 的值对应的语言代码
+    fn lang_code_for_miles(&self, value: &str) -> Option<&'static str> {
+        self.lang_codes.iter().copied().find(|code| self.languages.get(code).is_some_and(|l| l.miles_lang.eq_ignore_ascii_case(value)))
+    }
+
+    /// 综合游戏目录、启动选项和 user.cfg 推断游戏实际会使用的语音
+    fn voice_probe(&self) -> probe::Probe<'_> {
+        let usable_folder = |f: &VoiceFolderStatus| match &f.state {
+            FolderState::Directory | FolderState::Restored(_) => true,
+            FolderState::Junction(target) => target.as_ref().is_some_and(|t| t.exists()),
+            FolderState::Missing => false,
+        };
+        let mut input = probe::ProbeInput::default();
+        for state in &self.voice_state {
+            if state.folders.iter().any(usable_folder) {
+                input.usable.push(state.lang_code);
+            }
+            if state.folders.iter().any(|f| !usable_folder(f)) {
+                input.broken.push(state.lang_code);
+            }
+            if !state.toc_files.is_empty() {
+                input.with_toc.push(state.lang_code);
+            }
+        }
+        // 只有通过 Steam 启动时启动选项才会生效
+        let via_steam = self.steam_info.as_ref().is_none_or(|s| s.platform == Platform::Steam);
+        input.launch_option = self
+            .launch_options
+            .as_deref()
+            .filter(|_| via_steam)
+            .and_then(|options| steam_config::launch_option_value(options, &self.current_game().launch_param))
+            .map(|value| self.lang_code_for_miles(value).ok_or(value));
+        input.user_cfg = self.user_cfg_lang.as_deref().map(|value| self.lang_code_for_miles(value).ok_or(value));
+        input.steam_language = self.steam_lang_code().filter(|_| via_steam);
+        probe::evaluate(&input)
+    }
+
+    /// 游戏当前依赖该语言的原因：Steam 中选择的语言、启动选项或 user.cfg 指定了该语言
+    fn language_in_use(&self, code: &str) -> Vec<&'static str> {
+        let mut reasons = Vec::new();
+        if self.steam_lang_code() == Some(code) {
+            reasons.push(t!("probe.source_steam"));
+        }
+        let launch_option = self
+            .launch_options
+            .as_deref()
+            .and_then(|options| steam_config::launch_option_value(options, &self.current_game().launch_param));
+        if launch_option.and_then(|value| self.lang_code_for_miles(value)) == Some(code) {
+            reasons.push(t!("probe.source_launch_option"));
+        }
+        if self.user_cfg_lang.as_deref().and_then(|value| self.lang_code_for_miles(value)) == Some(code) {
+            reasons.push(t!("probe.source_user_cfg"));
+        }
+        reasons
+    }
+
+    /// 游戏目录中以原始文件夹形式存在（即游戏自身安装）的语言
+    fn installed_languages(&self) -> Vec<&'static str> {
+        self.voice_state
+            .iter()
+            .filter(|s| s.directory_count() > 0)
+            .map(|s| s.lang_code)
+            .collect()
+    }
+
+    fn get_selected_lang_code(&self) -> &'static str {
+        self.lang_codes[self.selected_lang_idx]
+    }
+
+    /// Steam 中为当前游戏选择的语言对应的语言代码，未检测到或不是已知语言时返回 None
+    fn steam_lang_code(&self) -> Option<&'static str> {
+        let language = &self.steam_info.as_ref()?.text_language;
+        if language.is_empty() {
+            return None;
+        }
+        self.lang_codes.iter().copied().find(|code| {
+            self.languages.get(code).is_some_and(|lang| steam_language(lang.miles_lang).eq_ignore_ascii_case(language))
+        })
+    }
+
+    /// Steam 中选择的语言，与将要备份的语言不一致时显示警告
+    fn show_steam_language(&self, ui: &mut egui::Ui) {
+        let Some(steam) = self.steam_info.as_ref().filter(|s| !s.text_language.is_empty()) else {
+            return;
+        };
+        let steam_code = self.steam_lang_code();
+        let name = steam_code.and_then(|code| self.languages.get(code)).map(|l| l.name).unwrap_or(&steam.text_language);
+        ui.label(egui::RichText::new(t!("ui.steam_language", name)).weak()).on_hover_text(t!("ui.steam_language_hint"));
+        let selected = self.get_selected_lang_code();
+        if steam_code.is_some_and(|code| code != selected) {
+            let selected_name = self.languages.get(selected).map(|l| l.name).unwrap_or(selected);
+            ui.label(egui::RichText::new(t!("ui.steam_language_mismatch", name, selected_name)).color(theme::warning(ui)));
+        }
+    }
+
+    fn get_launch_param(&self) -> String {
+        let code = self.get_selected_lang_code();
+        if let Some(lang) = self.languages.get(code) {
+            format!("{} {}", self.current_game().launch_param, lang.miles_lang)
+        } else {
+            String::new()
+        }
+    }
+
+    fn refresh_launch_options(&mut self) {
+        self.launch_options = self
+            .steam_info
+            .as_ref()
+            .filter(|steam| steam.platform == Platform::Steam)
+            .and_then(|steam| steam_config::read_launch_options(&steam.steam_path, &self.current_game().app_id));
+        let name = user_cfg::command_name(&self.current_game().launch_param);
+        self.user_cfg_lang = self.user_cfg_path().and_then(|path| user_cfg::read(&path, name));
+    }
+
+    /// 游戏安装目录中的 user.cfg，未检测到安装时由语音文件目录推出
+    fn user_cfg_path(&self) -> Option<PathBuf> {
+        let root = match self.steam_info.as_ref().filter(|s| !s.game_path.as_os_str().is_empty()) {
+            Some(steam) => steam.game_path.clone(),
+            None => self.current_game().game_root(Path::new(&self.source_path))?,
+        };
+        Some(user_cfg::path(&root))
+    }
+
+    /// 将当前语言写入游戏目录的 user.cfg，remove 为 true 时移除该命令
+    fn write_user_cfg(&mut self, remove: bool) -> Result<String, SwitcherError> {
+        let path = self.user_cfg_path().filter(|p| p.parent().is_some_and(Path::exists)).ok_or(SwitcherError::UserCfgNotFound)?;
+        self.ensure_game_closed()?;
+        let name = user_cfg::command_name(&self.current_game().launch_param).to_string();
+        let miles_lang = if remove { None } else { self.languages.get(self.get_selected_lang_code()).map(|l| l.miles_lang) };
+        user_cfg::write(&path, &name, miles_lang)?;
+        self.user_cfg_lang = miles_lang.map(str::to_string);
+        Ok(match miles_lang {
+            Some(lang) => t!("status.user_cfg_written", name, lang, path.display()),
+            None => t!("status.user_cfg_removed", name, path.display()),
+        })
+    }
+
+    /// 各步骤的完成状态和下一步提示
+    fn workflow(&self) -> workflow::Workflow {
+        let code = self.get_selected_lang_code();
+        let lang = self.languages.get(code);
+        let source = Path::new(&self.source_path);
+        let game = self.current_game();
+        let launch_option = self
+            .launch_options
+            .as_deref()
+            .and_then(|options| steam_config::launch_option_value(options, &game.launch_param))
+            .map(|value| lang.is_some_and(|l| value.eq_ignore_ascii_case(l.miles_lang)));
+        workflow::evaluate(&workflow::WorkflowInput {
+            game_name: &game.name,
+            lang_name: lang.map(|l| l.name).unwrap_or(code),
+            source_set: !self.source_path.is_empty(),
+            source_valid: !self.source_path.is_empty() && source.exists(),
+            installed: self.installed_languages().contains(&code),
+            backup_exists: self.available_backups.iter().any(|b| b.lang_code == code),
+            active: self.get_active_voice().is_some_and(|(active, _)| active == code),
+            launch_option,
+        })
+    }
+
+    /// 在平台中手动添加启动参数的说明
+    fn launch_option_hint(&self) -> String {
+        let name = &self.current_game().name;
+        match self.steam_info.as_ref().map(|s| s.platform) {
+            Some(Platform::EaApp) => t!("ui.step5_hint_ea", name),
+            Some(Platform::Xbox) => t!("ui.step5_hint_xbox", name),
+            _ => t!("ui.step5_hint", name),
+        }
+    }
+
+    /// 将当前语言的启动参数写入 Steam 启动选项，remove 为 true 时移除该参数
+    fn write_launch_option(&mut self, remove: bool) -> Result<String, SwitcherError> {
+        let steam = self.steam_info.as_ref().ok_or(SwitcherError::SteamNotFound)?;
+        match steam.platform {
+            Platform::Steam => {}
+            Platform::EaApp => return Err(SwitcherError::LaunchOptionEaApp),
+            Platform::Xbox => return Err(SwitcherError::LaunchOptionXbox),
+        }
+        let miles_lang = if remove {
+            None
+        } else {
+            self.languages.get(self.get_selected_lang_code()).map(|l| l.miles_lang)
+        };
+
+        let game = self.current_game();
+        let options = steam_config::apply_launch_option(&steam.steam_path, &game.app_id, &game.launch_param, miles_lang)
+            .map_err(|e| SwitcherError::LaunchOption(Box::new(e)))?;
+        self.launch_options = Some(options.clone());
+        Ok(if remove {
+            t!("status.launch_option_removed").to_string()
+        } else {
+            t!("status.launch_option_written", options)
+        })
+    }
+
+    /// 通过 Steam 直接启动游戏，并附加当前语言的启动参数
+    fn launch_game(&self) -> Result<String, SwitcherError> {
+        let param = self.get_launch_param();
+        let game = self.current_game();
+        match &self.steam_info {
+            Some(install) if install.platform != Platform::Steam => game.launch_exe(&install.game_path, &param),
+            install => steam_config::launch_game(install.as_ref().map(|s| s.steam_path.as_path()), &game.app_id, &param),
+        }
+        .map_err(|e| SwitcherError::Launch(Box::new(e)))?;
+        Ok(t!("status.launching", self.current_game().name, param))
+    }
+
+    /// 递归查找所有匹配的语音文件夹和 .toc 文件，返回 (文件夹列表, toc文件列表)
+    fn find_voice_files(&self, root: &Path, lang_code: &str) -> (Vec<PathBuf>, Vec<PathBuf>) {
+        let found = self.find_voice_matches(root, lang_code);
+        (found.folders, found.toc_files)
+    }
+
+    /// 查找语音文件并记录匹配到的模式
+    fn find_voice_matches(&self, root: &Path, lang_code: &str) -> scan::Found {
+        let exclude = &self.current_game().exclude_patterns;
+        let skip_dirs = [self.backup_root.clone()];
+        let found =
+            scan::scan(root, &[self.voice_matcher(lang_code)], exclude, &skip_dirs, self.settings.copy_workers(), &Default::default());
+        found.into_iter().next().unwrap_or_default()
+    }
+
+    /// 预览中显示的匹配模式说明
+    fn pattern_note(&self, root: &Path, lang_code: &str) -> Option<String> {
+        let found = self.find_voice_matches(root, lang_code);
+        let patterns: Vec<String> = found.patterns.iter().map(|(pattern, count)| format!("{} ({})", pattern, count)).collect();
+        (!patterns.is_empty()).then(|| t!("plan.matched_patterns", patterns.join(", ")))
+    }
+
+    /// 语言对应的语音文件夹名和 .toc 文件名
+    fn voice_matcher(&self, lang_code: &str) -> scan::Matcher {
+        let codes = self.languages.get(lang_code).map(|l| l.folder_codes).unwrap_or(&[]);
+        let codes: Vec<&str> = if codes.is_empty() { vec![lang_code] } else { codes.to_vec() };
+        let game = self.current_game();
+        scan::Matcher {
+            folder_names: codes.iter().flat_map(|c| game.folder_names(c)).collect(),
+            toc_names: codes.iter().flat_map(|c| game.toc_names(c)).collect(),
+        }
+    }
+
+    fn backup_files(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let (job, required) = self.prepare_backup(self.get_selected_lang_code())?;
+        let message = if self.resumable_staging(self.get_selected_lang_code()).is_some() {
+            t!("status.backup_resuming", job.lang_name)
+        } else {
+            t!("status.backup_running", job.lang_name)
+        };
+        self.task = Some(Task::spawn(required, move |progress| job.run(progress)));
+        Ok(message)
+    }
+
+    /// 在后台依次备份游戏目录中已安装的所有语言
+    fn backup_all(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let (job, required) = self.prepare_backup_all()?;
+        let message = t!("status.backup_all_running", job.jobs.len());
+        self.task = Some(Task::spawn(required, move |progress| job.run(progress)));
+        Ok(message)
+    }
+
+    /// 为每个已安装的语言准备备份任务，无法备份的语言记录原因后跳过
+    fn prepare_backup_all(&self) -> Result<(BatchBackupJob, u64), SwitcherError> {
+        let installed = self.installed_languages();
+        if installed.is_empty() {
+            return Err(SwitcherError::NoInstalledLanguages);
+        }
+        let mut jobs = Vec::new();
+        let mut skipped = Vec::new();
+        let mut required = 0;
+        for code in installed {
+            match self.prepare_backup(code) {
+                Ok((job, size)) => {
+                    jobs.push(job);
+                    required += size;
+                }
+                Err(e) => {
+                    let name = self.languages.get(code).map(|l| l.name).unwrap_or(code);
+                    skipped.push((name.to_string(), e.to_string()));
+                }
+            }
+        }
+        if jobs.is_empty() {
+            let reasons: Vec<String> = skipped.iter().map(|(name, reason)| format!("{}: {}", name, reason)).collect();
+            return Err(SwitcherError::BackupAllFailed(reasons.join("\n")));
+        }
+
+        // 每个语言单独检查过剩余空间，这里再检查所有语言的总和
+        let move_mode = self.backup_mode == BackupMode::Move;
+        if !(move_mode && disk::same_volume(Path::new(&self.source_path), &self.backup_dir)) {
+            if let Some(available) = disk::free_space(&self.backup_dir) {
+                if required > available {
+                    return Err(SwitcherError::DiskSpace { required, available });
+                }
+            }
+        }
+        Ok((BatchBackupJob { jobs, skipped }, required))
+    }
+
+    /// 检查备份条件并收集需要备份的文件，返回备份任务和需要复制的字节数
+    fn prepare_backup(&self, lang_code: &str) -> Result<(BackupJob, u64), SwitcherError> {
+        if self.source_path.is_empty() {
+            return Err(SwitcherError::NoFolderSelected);
+        }
+
+        let source = PathBuf::from(&self.source_path);
+        if !source.exists() {
+            return Err(SwitcherError::FolderNotFound);
+        }
+        self.check_backup_location()?;
+
+        let lang_name = self.languages.get(lang_code).map(|l| l.name).unwrap_or(lang_code);
+
+        // 递归查找所有语音文件夹和 .toc 文件
+        let (voice_folders, toc_files) = self.find_voice_files(&source, lang_code);
+
+        if voice_folders.is_empty() && toc_files.is_empty() {
+            return Err(SwitcherError::VoiceNotFound(lang_code.to_string()));
+        }
+
+        // 只有 toc 文件时，备份不完整，不执行备份
+        if voice_folders.is_empty() {
+            return Err(SwitcherError::BackupIncomplete(lang_name.to_string()));
+        }
+
+        let move_mode = self.backup_mode == BackupMode::Move;
+        if move_mode {
+            if let Some(rel_path) = voice_folders.iter().find(|p| link::is_junction(&source.join(p))) {
+                return Err(SwitcherError::MoveJunction(rel_path.clone()));
+            }
+        }
+
+        // 检查备份所在分区的剩余空间（同分区移动只需重命名，不占用额外空间）
+        // 新备份完成前旧备份仍然保留，因此不计入可用空间；继续备份时已复制的部分不再占用
+        let required: u64 = voice_folders
+            .iter()
+            .chain(toc_files.iter())
+            .map(|rel_path| disk::dir_size(&source.join(rel_path)))
+            .sum();
+        if !(move_mode && disk::same_volume(&source, &self.backup_dir)) {
+            let copied = self.resumable_staging(lang_code).map(|p| disk::dir_size(&p)).unwrap_or(0);
+            if let Some(available) = disk::free_space(&self.backup_dir) {
+                let required = required.saturating_sub(copied);
+                if required > available {
+                    return Err(SwitcherError::DiskSpace { required, available });
+                }
+            }
+        }
+
+        let job = BackupJob {
+            source,
+            backup_dir: self.backup_dir.clone(),
+            lang_code: lang_code.to_string(),
+            lang_name: lang_name.to_string(),
+            build_id: self.steam_info.as_ref().map(|s| s.build_id.clone()).unwrap_or_default(),
+            voice_folders,
+            toc_files,
+            move_mode,
+            workers: self.settings.copy_workers(),
+            engine: self.settings.copy_engine,
+            reuse_from: (!move_mode && self.available_backups.iter().any(|b| b.dir_name() == lang_code))
+                .then(|| self.backup_dir.join(lang_code)),
+        };
+        Ok((job, required))
+    }
+
+    /// 用户设置的显示名称，未设置时为语言名称
+    fn backup_name<'a>(&'a self, info: &'a BackupInfo) -> &'a str {
+        if info.label.is_empty() {
+            self.languages.get(info.lang_code.as_str()).map(|l| l.name).unwrap_or(&info.lang_code)
+        } else {
+            &info.label
+        }
+    }
+
+    /// 在后台校验所选备份的文件
+    fn verify_backup(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let info = self.available_backups.get(self.selected_backup_idx).cloned().ok_or(SwitcherError::NoBackups)?;
+        let job = VerifyJob {
+            backup_path: info.path(&self.backup_dir),
+            lang_name: self.backup_name(&info).to_string(),
+            info,
+        };
+        let total = job.total_size();
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(t!("status.verify_running").to_string())
+    }
+
+    /// 在资源管理器中打开文件夹，失败时显示错误
+    fn open_in_explorer(&mut self, path: &Path) {
+        if let Err(e) = explorer::open(path) {
+            let error = if e.kind() == std::io::ErrorKind::NotFound { SwitcherError::FolderNotFound } else { e.into() };
+            self.report(Err(error));
+        }
+    }
+
+    /// 保存所选备份的显示名称和备注，只修改 backup.json
+    fn save_backup_note(&mut self, label: String, note: String) -> Result<String, SwitcherError> {
+        let info = self.available_backups.get_mut(self.selected_backup_idx).ok_or(SwitcherError::NoBackups)?;
+        info.label = label.trim().to_string();
+        info.note = note.trim().to_string();
+        info.save(&info.path(&self.backup_dir))?;
+        Ok(t!("status.backup_note_saved").to_string())
+    }
+
+    /// 在后台复制所选备份，副本以 label 为显示名称
+    fn duplicate_backup(&mut self, label: String, hardlink: bool) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let info = self.available_backups.get(self.selected_backup_idx).ok_or(SwitcherError::NoBackups)?.clone();
+        let label = label.trim().to_string();
+        if label.is_empty() {
+            return Err(SwitcherError::EmptyBackupLabel);
+        }
+        let dir_name = (1..)
+            .map(|n| format!("{}{}{}", info.lang_code, backup_info::CLONE_SEPARATOR, n))
+            .find(|name| !self.backup_dir.join(name).exists())
+            .unwrap_or_default();
+        let job = DuplicateJob {
+            source: info.path(&self.backup_dir),
+            target: self.backup_dir.join(&dir_name),
+            staging: backup::staging_dir(&self.backup_dir, &dir_name),
+            info,
+            label,
+            hardlink,
+            workers: self.settings.copy_workers(),
+        };
+        let total = job.total_size();
+        if let Some(available) = disk::free_space(&self.backup_dir) {
+            if total > available {
+                return Err(SwitcherError::DiskSpace { required: total, available });
+            }
+        }
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(t!("status.backup_duplicating").to_string())
+    }
+
+    /// 在后台比较所选备份与游戏目录中的语音文件
+    fn compare_backup(&mut self) -> Result<String, SwitcherError> {
+        if self.diff_scan.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        if self.source_path.is_empty() {
+            return Err(SwitcherError::NoGameFolderSelected);
+        }
+        let info = self.available_backups.get(self.selected_backup_idx).ok_or(SwitcherError::NoBackups)?;
+        if info.compressed {
+            return Err(SwitcherError::CompareCompressed);
+        }
+        let backup_path = info.path(&self.backup_dir);
+        // 游戏更新后新增的语言文件夹也参与比较
+        let (mut folders, toc_files) = self.find_voice_files(Path::new(&self.source_path), &info.lang_code);
+        for folder in &info.folders {
+            if !folders.contains(folder) {
+                folders.push(folder.clone());
+            }
+        }
+        let toc_files = info.toc_files.iter().chain(&toc_files).cloned().collect::<BTreeSet<_>>().into_iter().collect();
+        let name = self.backup_name(info).to_string();
+        self.diff_scan =
+            Some(diff::DiffScan::spawn(name.clone(), backup_path, PathBuf::from(&self.source_path), folders, toc_files));
+        Ok(t!("status.comparing", name))
+    }
+
+    /// 比较结果：按目录分组列出新增、删除和修改的文件
+    fn show_diff(&mut self, ctx: &egui::Context) {
+        let Some((name, diff)) = &self.diff_result else {
+            return;
+        };
+        let mut closed = false;
+        let modal = egui::Modal::new(egui::Id::new("diff")).show(ctx, |ui| {
+            ui.set_width(560.0);
+            ui.heading(t!("ui.diff_title", name));
+            let (count, size) = diff.update_size();
+            ui.label(t!("ui.diff_summary", diff.compared, diff.entries.len(), count, disk::format_size(size)));
+            if diff.linked > 0 {
+                ui.label(egui::RichText::new(t!("ui.diff_linked", diff.linked)).weak());
+            }
+            ui.add_space(5.0);
+            egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                if diff.entries.is_empty() {
+                    ui.label(egui::RichText::new(t!("ui.diff_identical")).color(theme::success(ui)));
+                }
+                for (dir, entries) in diff.grouped() {
+                    egui::CollapsingHeader::new(dir.display().to_string()).default_open(true).show(ui, |ui| {
+                        for entry in entries {
+                            let name = entry.rel_path.file_name().unwrap_or(entry.rel_path.as_os_str()).to_string_lossy();
+                            let text = format!("[{}] {}  ({})", entry.change.label(), name, disk::format_size(entry.size));
+                            let color = match entry.change {
+                                diff::Change::Added => theme::success(ui),
+                                diff::Change::Removed => theme::error(ui),
+                                diff::Change::Modified => theme::warning(ui),
+                            };
+                            ui.label(egui::RichText::new(text).color(color));
+                        }
+                    });
+                }
+            });
+            ui.add_space(5.0);
+            let button = ui.button(t!("ui.close"));
+            a11y::focus_dialog(ui, &button);
+            closed = button.clicked();
+        });
+        if closed || modal.should_close() {
+            self.diff_result = None;
+        }
+    }
+
+    /// 版本与当前游戏不一致的备份
+    fn outdated_backups(&self) -> Vec<&BackupInfo> {
+        let Some(steam) = &self.steam_info else {
+            return Vec::new();
+        };
+        self.available_backups
+            .iter()
+            .filter(|b| !b.build_id.is_empty() && !steam.build_id.is_empty() && b.build_id != steam.build_id)
+            .collect()
+    }
+
+    /// 游戏目录中是否有该语言未链接的实际语音文件（更新后重新下载得到）
+    fn has_downloaded_voice(&self, lang_code: &str) -> bool {
+        self.voice_state
+            .iter()
+            .find(|s| s.lang_code == lang_code)
+            .is_some_and(|s| s.directory_count() > 0 && s.directory_count() == s.folders.len())
+    }
+
+    /// 用游戏目录中重新下载的语音刷新该语言的备份
+    fn refresh_outdated_backup(&mut self, lang_code: &str) {
+        if let Some(idx) = self.lang_codes.iter().position(|c| *c == lang_code) {
+            self.selected_lang_idx = idx;
+            self.request(Operation::Backup);
+        }
+    }
+
+    /// 当前所选语言在复制模式下可以继续的未完成备份
+    fn resumable_staging(&self, lang_code: &str) -> Option<PathBuf> {
+        if self.backup_mode == BackupMode::Move {
+            return None;
+        }
+        let staging = backup::staging_dir(&self.backup_dir, lang_code);
+        backup::is_resumable(&staging).then_some(staging)
+    }
+
+    /// 放弃当前所选语言未完成的备份
+    fn discard_partial_backup(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        if let Some(staging) = self.resumable_staging(self.get_selected_lang_code()) {
+            fs::remove_dir_all(&staging).map_err(SwitcherError::CleanStaging)?;
+            info!("discarded partial backup {}", staging.display());
+        }
+        let lang_code = self.get_selected_lang_code();
+        let lang_name = self.languages.get(lang_code).map(|l| l.name).unwrap_or(lang_code);
+        Ok(t!("status.partial_discarded", lang_name))
+    }
+
+    /// 清理上次程序中断时遗留的临时备份目录
+    ///
+    /// 移动模式中断时语音文件仍在临时目录中，会先尝试移回游戏目录，失败则保留该目录。
+    /// 复制模式中断的目录保留下来，再次备份该语言时继续复制。
+    fn clean_orphaned_staging(&mut self) {
+        let Ok(entries) = fs::read_dir(&self.backup_dir) else {
+            return;
+        };
+        let mut cleaned = 0;
+        let mut resumable = Vec::new();
+        let mut kept = None;
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(lang_code) = name.strip_prefix(STAGING_PREFIX) else {
+                continue;
+            };
+            let path = entry.path();
+            // 移动模式替换旧备份后、链接全部创建前中断：新备份仍有来源记录时把语音文件移回游戏目录，再放回旧备份
+            if let Some(lang_code) = lang_code.strip_suffix(OLD_SUFFIX) {
+                let target = self.backup_dir.join(lang_code);
+                if target.join(MOVE_SOURCE_FILE).is_file() {
+                    if !self.move_back_orphan(&target, lang_code) {
+                        kept = Some(target);
+                        continue;
+                    }
+                    let _ = fs::remove_dir_all(&target);
+                }
+                let restored = if target.exists() { fs::remove_dir_all(&path) } else { fs::rename(&path, &target) };
+                if restored.is_ok() {
+                    cleaned += 1;
+                }
+                continue;
+            }
+            if backup::is_resumable(&path) {
+                resumable.push(lang_code.to_string());
+                continue;
+            }
+            let keep = path.join(MOVE_SOURCE_FILE).is_file() && !self.move_back_orphan(&path, lang_code);
+            if keep {
+                kept = Some(path);
+            } else if fs::remove_dir_all(&path).is_ok() {
+                cleaned += 1;
+            }
+        }
+        if let Some(path) = kept {
+            self.report(Err(SwitcherError::OrphanedMove(path)));
+        } else if !resumable.is_empty() {
+            self.report(Ok(t!("status.resumable_backups", resumable.join(", "))));
+        } else if cleaned > 0 {
+            self.report(Ok(t!("status.cleaned_staging", cleaned)));
+        }
+    }
+
+    /// 把中断的移动备份 path 中的语音文件移回记录的游戏目录，先移除指向 path 的链接；全部移回时返回 true
+    fn move_back_orphan(&self, path: &Path, lang_code: &str) -> bool {
+        let Ok(source) = fs::read_to_string(path.join(MOVE_SOURCE_FILE)) else {
+            return false;
+        };
+        let source = PathBuf::from(source.trim());
+        let (folders, _) = self.find_voice_files(path, lang_code);
+        let workers = self.settings.copy_workers();
+        let mut all_moved = true;
+        for rel_path in folders {
+            let dst = source.join(&rel_path);
+            if link::read_junction_target(&dst).is_some_and(|target| link::points_into(&target, path)) {
+                let _ = link::remove_junction(&dst);
+            }
+            if dst.exists() || link::move_dir(&path.join(&rel_path), &dst, workers, &Progress::default()).is_err() {
+                all_moved = false;
+            }
+        }
+        all_moved
+    }
+
+    fn restore_files(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let (job, total) = self.prepare_restore()?;
+        let message = t!("status.restore_running", job.lang_name);
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(message)
+    }
+
+    /// 检查恢复条件并收集备份中的文件，返回恢复任务和需要复制的字节数
+    fn prepare_restore(&self) -> Result<(RestoreJob, u64), SwitcherError> {
+        if self.source_path.is_empty() {
+            return Err(SwitcherError::NoGameFolderSelected);
+        }
+        self.check_backup_location()?;
+
+        if self.available_backups.is_empty() {
+            return Err(SwitcherError::NoBackups);
+        }
+
+        let backup_info = self.available_backups[self.selected_backup_idx].clone();
+        let backup_path = backup_info.path(&self.backup_dir);
+        let target = PathBuf::from(&self.source_path);
+
+        if !backup_path.exists() {
+            return Err(SwitcherError::BackupMissing);
+        }
+
+        // exFAT、FAT32 或网络驱动器等不支持所选方式时改为复制，避免创建链接时才失败
+        let (mode, fallback) = match link::unsupported_reason(self.restore_mode, &backup_path, &target) {
+            Some(reason) => {
+                warn!("{} unsupported: {}", self.restore_mode.label(), reason);
+                (RestoreMode::Copy, Some(t!("status.mode_fallback", self.restore_mode.label(), reason)))
+            }
+            None => (self.restore_mode, None),
+        };
+
+        // 版本检查 - 不匹配时按版本策略阻止恢复，用户确认后仍可恢复
+        let version_override = self.check_version_match();
+        if let Some((backup, current)) = version_override.clone().filter(|_| !self.version_allowed()) {
+            return Err(SwitcherError::VersionMismatch { backup, current });
+        }
+
+        // 递归查找备份中的所有语音文件夹和 .toc 文件，压缩的备份使用备份信息中的记录
+        let (voice_folders, toc_files) = if backup_info.compressed {
+            (backup_info.folders.clone(), backup_info.toc_files.clone())
+        } else {
+            self.find_voice_files(&backup_path, &backup_info.lang_code)
+        };
+        if voice_folders.is_empty() && toc_files.is_empty() {
+            return Err(SwitcherError::BackupEmpty);
+        }
+
+        // 游戏目录中已存在的普通文件夹只有本工具恢复的才能替换
+        let mut replace_folders = Vec::new();
+        for rel_path in &voice_folders {
+            let dst_folder = target.join(rel_path);
+            if !link::is_junction(&dst_folder) && dst_folder.exists() {
+                if !self.was_restored_by_us(&backup_info.lang_code, &target, rel_path) {
+                    return Err(SwitcherError::TargetExists(rel_path.clone()));
+                }
+                replace_folders.push(rel_path.clone());
+            }
+        }
+
+        // 只有复制模式和 .toc 文件需要实际复制数据
+        let copied: Vec<&PathBuf> = if mode == RestoreMode::Copy {
+            voice_folders.iter().chain(&toc_files).collect()
+        } else {
+            toc_files.iter().collect()
+        };
+        let total: u64 = if backup_info.compressed {
+            // 解压的数据量由解压时计入，复制模式还需要再复制一次
+            if mode == RestoreMode::Copy { archive::unpacked_size(&backup_path) } else { 0 }
+        } else {
+            copied.iter().map(|rel_path| disk::dir_size(&backup_path.join(rel_path))).sum()
+        };
+
+        let lang = self.languages.get(backup_info.lang_code.as_str());
+        let lang_name = lang.map(|l| l.name).unwrap_or(&backup_info.lang_code).to_string();
+        let miles_lang = lang.map(|l| l.miles_lang).unwrap_or("").to_string();
+        let job = RestoreJob {
+            backup_info,
+            backup_path,
+            target,
+            mode,
+            fallback,
+            version_override,
+            voice_folders,
+            toc_files,
+            replace_folders,
+            lang_name,
+            miles_lang,
+            workers: self.settings.copy_workers(),
+            journal: Journal::new(&self.backup_dir),
+            backup_dir: self.backup_dir.clone(),
+        };
+        Ok((job, total))
+    }
+
+    fn save_default_modes(&mut self) -> Result<String, SwitcherError> {
+        self.settings.restore_mode = self.restore_mode;
+        self.settings.backup_mode = self.backup_mode;
+        self.save_settings()?;
+        Ok(t!(
+            "status.defaults_saved",
+            self.backup_mode.label(), self.restore_mode.label()
+        ))
+    }
+
+    fn set_ui_language(&mut self, lang: Option<UiLanguage>) {
+        self.settings.ui_language = lang;
+        i18n::set_language(lang.unwrap_or_else(UiLanguage::from_system));
+        self.languages = build_language_table(&self.custom_languages, self.current_game()).0;
+        if let Err(e) = self.save_settings() {
+            self.report(Err(e));
+        }
+    }
+
+    /// 应用并保存界面缩放
+    fn set_ui_scale(&mut self, ctx: &egui::Context, scale: f32) {
+        self.settings.ui_scale = scale;
+        self.applied_ui_scale = self.settings.ui_scale();
+        ctx.set_zoom_factor(self.applied_ui_scale);
+        if let Err(e) = self.save_settings() {
+            self.report(Err(e));
+        }
+    }
+
+    /// 检查游戏目录中的普通文件夹是否为本工具以复制/硬链接方式恢复的
+    fn was_restored_by_us(&self, lang_code: &str, target: &Path, rel_path: &Path) -> bool {
+        self.available_backups
+            .iter()
+            .filter(|b| b.lang_code == lang_code)
+            .filter_map(|b| b.restore.as_ref())
+            .any(|r| r.target == target && r.folders.iter().any(|f| f == rel_path))
+    }
+
+    /// 操作日志中本工具在游戏目录创建且仍然存在的项目
+    fn revert_targets(&self) -> Result<Vec<JournalEntry>, SwitcherError> {
+        if self.source_path.is_empty() {
+            return Err(SwitcherError::NoGameFolderSelected);
+        }
+        let source = PathBuf::from(&self.source_path);
+        let entries = Journal::new(&self.backup_dir)
+            .created()
+            .into_iter()
+            .filter(|entry| link::points_into(&entry.path, &source))
+            .filter(|entry| match entry.kind {
+                EntryKind::Junction => link::is_junction(&entry.path),
+                EntryKind::Symlink => fs::symlink_metadata(&entry.path).is_ok_and(|m| m.is_symlink()),
+                EntryKind::Folder => entry.path.is_dir() && !link::is_junction(&entry.path),
+                EntryKind::Toc => entry.path.is_file(),
+                EntryKind::Removed | EntryKind::VersionOverride => false,
+            })
+            .collect();
+        Ok(entries)
+    }
+
+    /// 撤销本工具在游戏目录中的所有修改，可选地请求 Steam 验证游戏文件
+    fn revert_all(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let entries = self.revert_targets()?;
+        let journal = Journal::new(&self.backup_dir);
+        let mut not_recycled = Vec::new();
+        for entry in &entries {
+            let result = match entry.kind {
+                EntryKind::Junction => link::remove_junction(&entry.path).map(|()| Removed::AsRequested),
+                EntryKind::Symlink => fs::remove_dir(&entry.path).map(|()| Removed::AsRequested),
+                _ => recycle::remove(&entry.path, self.settings.permanent_delete),
+            };
+            if result.map_err(|e| SwitcherError::DeleteItem { path: entry.path.clone(), source: e })? == Removed::Permanently {
+                not_recycled.push(entry.path.clone());
+            }
+            journal.record(EntryKind::Removed, &entry.path);
+            info!("reverted {}", entry.path.display());
+        }
+
+        // 清除指向当前游戏目录的恢复记录
+        let source = PathBuf::from(&self.source_path);
+        for backup in &mut self.available_backups {
+            if backup.restore.as_ref().is_some_and(|r| r.target == source) {
+                backup.restore = None;
+                let _ = backup.save(&backup.path(&self.backup_dir));
+            }
+        }
+        self.refresh_voice_state();
+
+        let mut message = if self.validate_after_revert {
+            steam_config::validate_game(&self.current_game().app_id)?;
+            t!("status.reverted_validating", entries.len())
+        } else {
+            t!("status.reverted", entries.len())
+        };
+        message.extend(recycle::note(&not_recycled).map(|note| format!("\n{}", note)));
+        Ok(message)
+    }
+
+    /// 游戏目录中目标满足 filter 的语音文件夹 Junction
+    fn game_links(&self, filter: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+        let source = PathBuf::from(&self.source_path);
+        self.voice_state
+            .iter()
+            .flat_map(|state| &state.folders)
+            .filter(|f| matches!(&f.state, FolderState::Junction(Some(target)) if filter(target)))
+            .map(|f| source.join(&f.rel_path))
+            .collect()
+    }
+
+    /// 游戏目录中指向 dir 内的 Junction
+    fn links_into(&self, dir: &Path) -> Vec<PathBuf> {
+        self.game_links(|target| link::points_into(target, dir))
+    }
+
+    /// 在后台将 Junction 转换为实际文件，之后不再依赖备份目录
+    fn materialize_links(&mut self, links: Vec<PathBuf>) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let job = self.prepare_materialize(links);
+        let total = job.total_size();
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(t!("status.materialize_running").to_string())
+    }
+
+    /// 所有游戏中由本工具恢复、指向备份目录的 Junction 和符号链接
+    fn backup_links(&self) -> Vec<PathBuf> {
+        let mut links = self.links_into(&self.backup_root);
+        for game in &self.games {
+            let Ok(entries) = fs::read_dir(game.backup_dir(&self.backup_root)) else {
+                continue;
+            };
+            for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+                let info = BackupInfo::load(&entry.path(), &entry.file_name().to_string_lossy());
+                let Some(record) = info.restore else {
+                    continue;
+                };
+                if matches!(record.mode, RestoreMode::Junction | RestoreMode::Symlink) {
+                    links.extend(record.folders.iter().map(|f| record.target.join(f)));
+                }
+            }
+        }
+        links.sort();
+        links.dedup();
+        links
+    }
+
+    /// 在后台把整个备份目录移到 to，并让游戏目录中的链接指向新位置
+    fn move_backups(&mut self, to: PathBuf) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let job = RelocateJob {
+            from: self.backup_root.clone(),
+            to: to.clone(),
+            links: self.backup_links(),
+            workers: self.settings.copy_workers(),
+        };
+        check_network_access(to.parent().unwrap_or(&to))?;
+        // Junction 不能指向网络共享，已恢复的链接需要先转换为实际文件
+        if disk::is_network(to.parent().unwrap_or(&to)) && !job.links.is_empty() {
+            return Err(SwitcherError::RelocateNetworkLinks(job.links.len()));
+        }
+        job.check()?;
+        if !self.source_path.is_empty() && link::points_into(&to, Path::new(&self.source_path)) {
+            return Err(SwitcherError::BackupInsideGame(to));
+        }
+        let total = job.total_size();
+        if let Some(available) = disk::free_space(to.parent().unwrap_or(&to)) {
+            if total > available {
+                return Err(SwitcherError::DiskSpace { required: total, available });
+            }
+        }
+        self.pending_backup_root = Some(to);
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(t!("status.backups_moving").to_string())
+    }
+
+    /// 移动结束后改用新的备份目录；出错时只要旧目录已不存在，也改用新目录
+    fn finish_move_backups(&mut self, moved: bool) {
+        let Some(to) = self.pending_backup_root.take() else {
+            return;
+        };
+        if !moved && self.backup_root.exists() {
+            return;
+        }
+        self.settings.backup_root = Some(to.clone());
+        self.cloud_sync = cloud::sync_root(&to);
+        self.backup_root = to;
+        self.backup_dir = self.current_game().backup_dir(&self.backup_root);
+        if let Err(e) = self.save_settings() {
+            self.report(Err(e));
+        }
+    }
+
+    /// 在后台把云同步目录中的备份目录设为保留在本机或排除同步
+    fn protect_cloud_backups(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let Some(root) = self.cloud_sync.clone() else {
+            return Ok(String::new());
+        };
+        let dir = self.backup_root.clone();
+        self.task = Some(Task::spawn(0, move |_| cloud::protect(&root, &dir)));
+        Ok(t!("status.cloud_protecting").to_string())
+    }
+
+    /// 游戏目录中是否有 Junction 或符号链接指向该备份
+    fn backup_in_use(&self, info: &BackupInfo) -> bool {
+        let backup_path = info.path(&self.backup_dir);
+        let linked = info.restore.as_ref().is_some_and(|record| {
+            record.folders.iter().any(|f| {
+                let path = record.target.join(f);
+                link::read_junction_target(&path)
+                    .or_else(|| fs::read_link(&path).ok())
+                    .is_some_and(|target| link::points_into(&target, &backup_path))
+            })
+        });
+        linked || !self.links_into(&backup_path).is_empty()
+    }
+
+    /// 按清理规则需要删除的备份
+    fn prune_selection(&self) -> Vec<String> {
+        let candidates: Vec<prune::Candidate> = self
+            .available_backups
+            .iter()
+            .map(|info| prune::Candidate {
+                info,
+                size: self.backup_sizes.get(info.dir_name()).copied().unwrap_or(0),
+                in_use: self.backup_in_use(info),
+            })
+            .collect();
+        let current_build = self.steam_info.as_ref().map(|s| s.build_id.as_str()).unwrap_or_default();
+        prune::select(&candidates, self.settings.retention(), current_build)
+    }
+
+    /// 按清理规则删除备份，正在使用的备份和当前版本的备份不会删除
+    fn prune_backups(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        if !self.settings.retention().is_enabled() {
+            return Err(SwitcherError::RetentionDisabled);
+        }
+        let selected = self.prune_selection();
+        let mut freed = 0;
+        let mut not_recycled = Vec::new();
+        for code in &selected {
+            let backup_path = self.backup_dir.join(code);
+            if recycle::remove(&backup_path, self.settings.permanent_delete).map_err(SwitcherError::DeleteBackup)? == Removed::Permanently {
+                not_recycled.push(backup_path.clone());
+            }
+            freed += self.backup_sizes.get(code).copied().unwrap_or(0);
+            info!("pruned backup {}", backup_path.display());
+        }
+        self.refresh_backups();
+        self.refresh_voice_state();
+        let mut message = t!("status.backups_pruned", selected.len(), disk::format_size(freed));
+        message.extend(recycle::note(&not_recycled).map(|note| format!("\n{}", note)));
+        Ok(message)
+    }
+
+    /// 备份总大小超过上限时提示清理，每次运行只自动提示一次
+    fn check_backup_limit(&mut self) {
+        let max_bytes = self.settings.retention().max_bytes;
+        if max_bytes == 0 || self.prune_dismissed || self.prune_prompt.is_some() {
+            return;
+        }
+        if self.backup_sizes.values().sum::<u64>() <= max_bytes {
+            return;
+        }
+        let selected = self.prune_selection();
+        if !selected.is_empty() {
+            self.prune_prompt = Some(selected);
+        }
+    }
+
+    fn show_prune_prompt(&mut self, ctx: &egui::Context) {
+        let Some(selected) = &self.prune_prompt else {
+            return;
+        };
+        let total = disk::format_size(self.backup_sizes.values().sum());
+        let limit = disk::format_size(self.settings.retention().max_bytes);
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("prune")).show(ctx, |ui| {
+            ui.set_width(420.0);
+            ui.heading(t!("ui.prune_title"));
+            ui.label(t!("ui.prune_prompt", total, limit));
+            for code in selected {
+                let name = self.available_backups.iter().find(|b| b.dir_name() == code).map_or(code.as_str(), |b| self.backup_name(b));
+                let size = disk::format_size(self.backup_sizes.get(code).copied().unwrap_or(0));
+                ui.label(format!("• {} ({})", name, size));
+            }
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                let button = ui.button(t!("ui.prune_now")).on_hover_text(t!("ui.prune_now_hint"));
+                a11y::focus_dialog(ui, &button);
+                confirmed = button.clicked();
+                cancelled = ui.button(t!("ui.cancel")).clicked();
+            });
+        });
+        if cancelled || modal.should_close() {
+            self.prune_prompt = None;
+            self.prune_dismissed = true;
+        } else if confirmed {
+            self.prune_prompt = None;
+            let result = self.prune_backups();
+            self.report(result);
+        }
+    }
+
+    /// 在后台合并所有备份中内容相同的文件
+    fn dedup_backups(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let skip = self
+            .available_backups
+            .iter()
+            .filter(|b| b.restore.as_ref().is_some_and(|r| r.mode == RestoreMode::Hardlink))
+            .map(|b| b.path(&self.backup_dir))
+            .collect();
+        let job = DedupJob { root: self.backup_dir.clone(), skip };
+        self.task = Some(Task::spawn(0, move |progress| job.run(progress)));
+        Ok(t!("status.dedup_running").to_string())
+    }
+
+    /// 在后台压缩所选备份，或解压已压缩的备份
+    fn toggle_compression(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let mut info = self.available_backups.get(self.selected_backup_idx).cloned().ok_or(SwitcherError::NoBackups)?;
+        let backup_path = info.path(&self.backup_dir);
+        let lang_name = self.languages.get(info.lang_code.as_str()).map(|l| l.name).unwrap_or(&info.lang_code).to_string();
+
+        if info.compressed {
+            self.task = Some(Task::spawn(0, move |progress| {
+                archive::decompress(&backup_path, &mut info, progress)?;
+                Ok(t!("status.decompressed", lang_name))
+            }));
+            return Ok(t!("status.decompress_running").to_string());
+        }
+
+        // 只压缩没有链接到游戏目录的备份
+        let links = self.links_into(&backup_path);
+        if !links.is_empty() {
+            return Err(SwitcherError::BackupInUse(links.len()));
+        }
+        let (voice_folders, toc_files) = self.find_voice_files(&backup_path, &info.lang_code);
+        if voice_folders.is_empty() && toc_files.is_empty() {
+            return Err(SwitcherError::BackupEmpty);
+        }
+        let job = CompressJob {
+            backup_path,
+            info,
+            lang_name,
+            voice_folders,
+            toc_files,
+            level: self.settings.compression_level(),
+        };
+        let total = job.total_size();
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(t!("status.compress_running").to_string())
+    }
+
+    /// 导出文件的默认文件名
+    fn export_file_name(&self) -> Option<String> {
+        let info = self.available_backups.get(self.selected_backup_idx)?;
+        Some(format!("{}-{}-{}.zip", self.current_game().id, info.lang_code, info.build_id))
+    }
+
+    /// 在后台把所选备份导出为 dest
+    fn export_backup(&mut self, dest: PathBuf) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let info = self.available_backups.get(self.selected_backup_idx).cloned().ok_or(SwitcherError::NoBackups)?;
+        let backup_path = info.path(&self.backup_dir);
+        let (voice_folders, toc_files) = if info.compressed {
+            (Vec::new(), Vec::new())
+        } else {
+            self.find_voice_files(&backup_path, &info.lang_code)
+        };
+        if !info.compressed && voice_folders.is_empty() && toc_files.is_empty() {
+            return Err(SwitcherError::BackupEmpty);
+        }
+        let lang_name = self.languages.get(info.lang_code.as_str()).map(|l| l.name).unwrap_or(&info.lang_code).to_string();
+        let job = ExportJob {
+            backup_path,
+            info,
+            game: self.current_game().id.clone(),
+            lang_name,
+            voice_folders,
+            toc_files,
+            dest,
+            level: self.settings.compression_level(),
+        };
+        let total = job.total_size();
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(t!("status.export_running").to_string())
+    }
+
+    /// 检查导出文件的清单，在后台导入到备份目录
+    fn import_backup(&mut self, archive: PathBuf) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let manifest = share::read_manifest(&archive)?;
+        if manifest.game != self.current_game().id {
+            return Err(SwitcherError::ImportWrongGame(manifest.game));
+        }
+        if let Some(steam) = &self.steam_info {
+            if !steam.build_id.is_empty() && manifest.build_id != steam.build_id {
+                return Err(SwitcherError::ImportVersionMismatch { backup: manifest.build_id, current: steam.build_id.clone() });
+            }
+        }
+        let Some(lang_name) = self.languages.get(manifest.lang_code.as_str()).map(|l| l.name.to_string()) else {
+            return Err(SwitcherError::ImportUnknownLanguage(manifest.lang_code));
+        };
+        manifest.check_voice_files(&self.voice_matcher(&manifest.lang_code))?;
+        if self.backup_dir.join(&manifest.lang_code).exists() {
+            return Err(SwitcherError::BackupExists(lang_name));
+        }
+        let required = manifest.total_size();
+        if let Some(available) = disk::free_space(&self.backup_dir) {
+            if available < required {
+                return Err(SwitcherError::DiskSpace { required, available });
+            }
+        }
+        let job = ImportJob {
+            archive,
+            backup_dir: self.backup_dir.clone(),
+            manifest,
+            lang_name,
+        };
+        self.task = Some(Task::spawn(required, move |progress| job.run(progress)));
+        Ok(t!("status.import_running").to_string())
+    }
+
+    fn prepare_materialize(&self, links: Vec<PathBuf>) -> MaterializeJob {
+        let source = PathBuf::from(&self.source_path);
+        let backups = self
+            .available_backups
+            .iter()
+            .filter(|b| {
+                b.restore.as_ref().is_some_and(|r| {
+                    r.target == source && r.folders.iter().any(|f| links.contains(&source.join(f)))
+                })
+            })
+            .map(|b| (b.path(&self.backup_dir), b.clone()))
+            .collect();
+        MaterializeJob {
+            links,
+            backups,
+            workers: self.settings.copy_workers(),
+            journal: Journal::new(&self.backup_dir),
+        }
+    }
+
+    /// 游戏目录中所有目标存在的 Junction，用于转换为独立安装
+    fn materializable_links(&self) -> Vec<PathBuf> {
+        self.game_links(|target| target.exists())
+    }
+
+    fn check_version_match(&self) -> Option<(String, String)> {
+        if self.available_backups.is_empty() {
+            return None;
+        }
+        
+        let backup = &self.available_backups[self.selected_backup_idx];
+        if let Some(steam) = &self.steam_info {
+            if !backup.build_id.is_empty() && backup.build_id != steam.build_id {
+                return Some((backup.build_id.clone(), steam.build_id.clone()));
+            }
+        }
+        None
+    }
+
+    /// 用户是否已确认在当前游戏版本下恢复所选的版本不匹配的备份
+    fn version_accepted(&self) -> bool {
+        let (Some(backup), Some(steam)) = (self.available_backups.get(self.selected_backup_idx), &self.steam_info) else {
+            return false;
+        };
+        self.version_override.as_ref().is_some_and(|(dir, build)| dir == backup.dir_name() && *build == steam.build_id)
+    }
+
+    /// 按版本策略，所选的版本不匹配的备份能否恢复到游戏目录
+    fn version_allowed(&self) -> bool {
+        let Some(backup) = self.available_backups.get(self.selected_backup_idx) else {
+            return false;
+        };
+        self.settings.version_policy.allows(backup, Path::new(&self.source_path), self.version_accepted())
+    }
+
+    /// 版本不匹配的警告和“仍要恢复”的确认
+    fn show_version_override(&mut self, ui: &mut egui::Ui, backup_ver: &str, current_ver: &str) {
+        if self.settings.version_policy == VersionPolicy::Warn {
+            ui.label(egui::RichText::new(t!("ui.version_policy_allows")).weak());
+            return;
+        }
+        let mut accepted = self.version_accepted();
+        if ui.checkbox(&mut accepted, t!("ui.restore_anyway")).on_hover_text(t!("ui.restore_anyway_hint")).changed() {
+            self.version_override = match self.available_backups.get(self.selected_backup_idx) {
+                Some(backup) if accepted => {
+                    warn!("accepted version mismatch for {}: backup {} != current {}", backup.dir_name(), backup_ver, current_ver);
+                    Some((backup.dir_name().to_string(), current_ver.to_string()))
+                }
+                _ => None,
+            };
         }
-        None
     }
 
     /// 删除游戏目录中指定语言的所有语音文件夹和 .toc 文件（递归）
-    fn delete_voice_files(&mut self) {
+    fn delete_voice_files(&mut self) -> Result<String, SwitcherError> {
+        self.ensure_game_closed()?;
+        let (source, folders, toc_files) = self.voice_files_to_delete()?;
+        let lang_code = self.get_selected_lang_code();
+
+        // 该语言没有任何备份时，先保存将被删除的原始文件
+        let originals = self.unprotected_originals();
+        if !originals.is_empty() {
+            if self.skip_snapshot {
+                info!("skipped original snapshot of {}", lang_code);
+            } else {
+                self.snapshot_originals(&source, &originals)?;
+            }
+        }
+
+        let journal = Journal::new(&self.backup_dir);
+        let mut deleted_folders = 0;
+        let mut deleted_files = 0;
+        let mut not_recycled = Vec::new();
+
+        // 删除链接，以及由本工具复制/硬链接恢复的文件夹
+        for rel_path in &folders {
+            let folder_path = source.join(rel_path);
+            let result = if link::is_junction(&folder_path) {
+                link::remove_junction(&folder_path).map(|()| Removed::AsRequested)
+            } else {
+                recycle::remove(&folder_path, self.settings.permanent_delete)
+            };
+            if result.map_err(|e| SwitcherError::DeleteItem { path: rel_path.clone(), source: e })? == Removed::Permanently {
+                not_recycled.push(folder_path.clone());
+            }
+            journal.record(EntryKind::Removed, &folder_path);
+            info!("deleted {}", folder_path.display());
+            deleted_folders += 1;
+        }
+
+        // 清除恢复记录
+        for backup in self.available_backups.iter_mut().filter(|b| b.lang_code == lang_code) {
+            if backup.restore.as_ref().is_some_and(|r| r.target == source) {
+                backup.restore = None;
+                let _ = backup.save(&backup.path(&self.backup_dir));
+            }
+        }
+
+        // 删除 .toc 文件
+        for rel_path in &toc_files {
+            let file_path = source.join(rel_path);
+            let removed = recycle::remove(&file_path, self.settings.permanent_delete)
+                .map_err(|e| SwitcherError::DeleteItem { path: rel_path.clone(), source: e })?;
+            if removed == Removed::Permanently {
+                not_recycled.push(file_path.clone());
+            }
+            journal.record(EntryKind::Removed, &file_path);
+            info!("deleted {}", file_path.display());
+            deleted_files += 1;
+        }
+
+        let lang_name = self.languages.get(lang_code).map(|l| l.name).unwrap_or(lang_code);
+        self.refresh_voice_state();
+        let mut message = t!("status.voice_deleted",
+            lang_name, deleted_folders, deleted_files);
+        message.extend(recycle::note(&not_recycled).map(|note| format!("\n{}", note)));
+        Ok(message)
+    }
+
+    /// 删除所选语言以外所有语言由游戏自身安装的语音文件夹和 .toc 文件
+    fn delete_other_voices(&mut self) -> Result<String, SwitcherError> {
+        if self.task.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        self.ensure_game_closed()?;
+        let job = self.prepare_delete_others()?;
+        let total = job.total_size();
+        let message = t!("status.cleanup_running", job.langs.len(), disk::format_size(total));
+        self.task = Some(Task::spawn(total, move |progress| job.run(progress)));
+        Ok(message)
+    }
+
+    /// 按语音文件夹的扫描结果收集其他语言的原始文件；有本工具链接或恢复的文件夹、游戏当前依赖的语言跳过
+    fn prepare_delete_others(&self) -> Result<cleanup::CleanupJob, SwitcherError> {
+        if self.source_path.is_empty() {
+            return Err(SwitcherError::NoFolderSelected);
+        }
+        let source = PathBuf::from(&self.source_path);
+        if !source.exists() {
+            return Err(SwitcherError::FolderNotFound);
+        }
+        if self.voice_scan.is_some() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let selected = self.get_selected_lang_code();
+        let mut langs = Vec::new();
+        let mut skipped = Vec::new();
+        for state in self.voice_state.iter().filter(|s| s.lang_code != selected) {
+            let lang_name = self.languages.get(state.lang_code).map(|l| l.name).unwrap_or(state.lang_code).to_string();
+            if state.restored_count() > 0 {
+                skipped.push((lang_name, t!("status.cleanup_managed").to_string()));
+                continue;
+            }
+            let in_use = self.language_in_use(state.lang_code);
+            if !in_use.is_empty() {
+                skipped.push((lang_name, t!("status.cleanup_in_use", in_use.join(", "))));
+                continue;
+            }
+            let folders: Vec<PathBuf> = state
+                .folders
+                .iter()
+                .filter(|f| matches!(f.state, FolderState::Directory))
+                .map(|f| source.join(&f.rel_path))
+                .collect();
+            let toc_files: Vec<PathBuf> = state.toc_files.iter().map(|rel_path| source.join(rel_path)).filter(|p| p.exists()).collect();
+            if folders.is_empty() && toc_files.is_empty() {
+                continue;
+            }
+            let sizes = folders.iter().chain(&toc_files).map(|path| disk::dir_size(path)).collect();
+            let backed_up = self.available_backups.iter().any(|b| b.lang_code == state.lang_code);
+            langs.push(cleanup::LangCleanup { lang_name, folders, toc_files, sizes, backed_up });
+        }
+        if langs.is_empty() {
+            return Err(SwitcherError::NoOtherVoices);
+        }
+        Ok(cleanup::CleanupJob { langs, skipped, permanent: self.settings.permanent_delete })
+    }
+
+    /// 原始文件快照目录
+    fn snapshot_dir(&self, lang_code: &str) -> PathBuf {
+        self.backup_dir.join(ORIGINALS_DIR).join(lang_code)
+    }
+
+    /// 删除游戏语音将删除、但没有备份或快照保护的原始文件（不是本工具创建的 .toc 文件）
+    fn unprotected_originals(&self) -> Vec<PathBuf> {
+        let lang_code = self.get_selected_lang_code();
+        if self.available_backups.iter().any(|b| b.lang_code == lang_code) || self.snapshot_dir(lang_code).exists() {
+            return Vec::new();
+        }
+        let Ok((source, _, toc_files)) = self.voice_files_to_delete() else {
+            return Vec::new();
+        };
+        let created: Vec<PathBuf> = Journal::new(&self.backup_dir).created().into_iter().map(|e| e.path).collect();
+        toc_files.into_iter().filter(|rel_path| !created.contains(&source.join(rel_path))).collect()
+    }
+
+    /// 将原始文件保存到快照目录，与用户管理的备份分开存放
+    fn snapshot_originals(&self, source: &Path, files: &[PathBuf]) -> Result<(), SwitcherError> {
+        let lang_code = self.get_selected_lang_code();
+        let snapshot = self.snapshot_dir(lang_code);
+        let progress = Progress::default();
+        for rel_path in files {
+            let dst = snapshot.join(rel_path);
+            if let Some(parent) = dst.parent() {
+                fs::create_dir_all(parent).map_err(SwitcherError::CreateDir)?;
+            }
+            copy::copy_file(&source.join(rel_path), &dst, &progress)
+                .map_err(|e| SwitcherError::BackupItem { path: rel_path.clone(), source: Box::new(e.into()) })?;
+        }
+        let info = BackupInfo {
+            lang_code: lang_code.to_string(),
+            build_id: self.steam_info.as_ref().map(|s| s.build_id.clone()).unwrap_or_default(),
+            folders: Vec::new(),
+            toc_files: files.to_vec(),
+            toc_hashes: Default::default(),
+            restore: None,
+            compressed: false,
+            label: String::new(),
+            note: String::new(),
+            created: logging::local_time(),
+            dir: String::new(),
+        };
+        info.save(&snapshot)?;
+        info!("saved original snapshot of {} to {}", lang_code, snapshot.display());
+        Ok(())
+    }
+
+    /// 收集可以删除的语音文件：Junction、本工具恢复的文件夹和存在的 .toc 文件
+    fn voice_files_to_delete(&self) -> Result<(PathBuf, Vec<PathBuf>, Vec<PathBuf>), SwitcherError> {
         if self.source_path.is_empty() {
-            self.status_message = "请先选择语音文件夹！".to_string();
-            self.is_error = true;
+            return Err(SwitcherError::NoFolderSelected);
+        }
+
+        let source = PathBuf::from(&self.source_path);
+        if !source.exists() {
+            return Err(SwitcherError::FolderNotFound);
+        }
+
+        let lang_code = self.get_selected_lang_code();
+
+        // 递归查找所有语音文件夹和 .toc 文件
+        let (voice_folders, toc_files) = self.find_voice_files(&source, lang_code);
+
+        if voice_folders.is_empty() && toc_files.is_empty() {
+            return Err(SwitcherError::VoiceNotFound(lang_code.to_string()));
+        }
+
+        let folders = voice_folders
+            .into_iter()
+            .filter(|rel_path| {
+                link::is_junction(&source.join(rel_path)) || self.was_restored_by_us(lang_code, &source, rel_path)
+            })
+            .collect();
+        let toc_files = toc_files.into_iter().filter(|rel_path| source.join(rel_path).exists()).collect();
+        Ok((source, folders, toc_files))
+    }
+
+    /// 列出操作将执行的动作，不修改任何文件
+    fn plan(&self, operation: Operation) -> Result<Plan, SwitcherError> {
+        let delete = if self.settings.permanent_delete { Action::Delete } else { Action::Recycle };
+        match operation {
+            Operation::Backup => {
+                let mut plan = self.prepare_backup(self.get_selected_lang_code())?.0.plan();
+                let source = PathBuf::from(&self.source_path);
+                plan.notes.extend(self.pattern_note(&source, self.get_selected_lang_code()));
+                Ok(plan)
+            }
+            Operation::BackupAll => Ok(self.prepare_backup_all()?.0.plan()),
+            Operation::Restore => {
+                let mut plan = self.prepare_restore()?.0.plan();
+                if self.steam_info.as_ref().is_some_and(|s| s.update_pending) {
+                    plan.notes.push(t!("status.restore_update_pending").to_string());
+                }
+                Ok(plan)
+            }
+            Operation::Materialize => Ok(self.prepare_materialize(self.materializable_links()).plan()),
+            Operation::Revert => {
+                let mut plan = Plan::new(operation);
+                for entry in self.revert_targets()? {
+                    match entry.kind {
+                        EntryKind::Junction | EntryKind::Symlink => plan.push(Action::Unlink, entry.path, 0),
+                        _ => {
+                            let size = disk::dir_size(&entry.path);
+                            plan.push(delete, entry.path, size);
+                        }
+                    }
+                }
+                Ok(plan)
+            }
+            Operation::DeleteVoice => {
+                let (source, folders, toc_files) = self.voice_files_to_delete()?;
+                let mut plan = Plan::new(operation);
+                plan.notes.extend(self.pattern_note(&source, self.get_selected_lang_code()));
+                for rel_path in folders {
+                    let path = source.join(rel_path);
+                    if link::is_junction(&path) {
+                        plan.push(Action::Unlink, path, 0);
+                    } else {
+                        let size = disk::dir_size(&path);
+                        plan.push(delete, path, size);
+                    }
+                }
+                for rel_path in toc_files {
+                    let path = source.join(rel_path);
+                    let size = disk::dir_size(&path);
+                    plan.push(delete, path, size);
+                }
+                Ok(plan)
+            }
+            Operation::DeleteOthers => Ok(self.prepare_delete_others()?.plan()),
+            Operation::DeleteBackup => {
+                let backup_info = self.available_backups.get(self.selected_backup_idx).ok_or(SwitcherError::NoBackupToDelete)?;
+                let path = backup_info.path(&self.backup_dir);
+                let links = self.links_into(&path);
+                if !links.is_empty() {
+                    return Err(SwitcherError::BackupInUse(links.len()));
+                }
+                let mut plan = Plan::new(operation);
+                let size = disk::dir_size(&path);
+                plan.push(delete, path, size);
+                Ok(plan)
+            }
+        }
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<String, SwitcherError> {
+        match operation {
+            Operation::Backup => self.backup_files(),
+            Operation::BackupAll => self.backup_all(),
+            Operation::Restore => self.restore_files(),
+            Operation::DeleteVoice => self.delete_voice_files(),
+            Operation::DeleteOthers => self.delete_other_voices(),
+            Operation::DeleteBackup => self.delete_backup(),
+            Operation::Materialize => self.materialize_links(self.materializable_links()),
+            Operation::Revert => self.revert_all(),
+        }
+    }
+
+    /// 在后台下载并替换为新版本，完成后自动重新启动
+    fn install_update(&mut self, release: update::Release) -> Result<String, SwitcherError> {
+        if self.task.is_some() || self.queue.has_pending() {
+            return Err(SwitcherError::TaskRunning);
+        }
+        let asset = release.asset.ok_or(SwitcherError::UpdateNoChecksum)?;
+        let exe = std::env::current_exe().map_err(SwitcherError::UpdateInstall)?;
+        let message = t!("status.update_downloading", release.version);
+        let job = update::SelfUpdateJob { version: release.version, asset, exe };
+        self.task = Some(Task::spawn(0, move |progress| job.run(progress)));
+        self.restart_after_update = true;
+        Ok(message)
+    }
+
+    /// 启动更新后的程序并关闭当前窗口
+    fn restart_updated(&mut self, ctx: &egui::Context) {
+        let exe = std::env::current_exe().unwrap_or_default();
+        match update::relaunch(&exe) {
+            Ok(()) => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+            Err(e) => {
+                warn!("failed to relaunch: {}", e);
+                self.report(Ok(t!("status.update_relaunch_failed", exe.display())));
+            }
+        }
+    }
+
+    /// 加入操作队列，没有正在执行的任务时立即开始
+    fn enqueue(&mut self, operation: Operation) {
+        let lang_code = self.get_selected_lang_code();
+        let backup_code = self.available_backups.get(self.selected_backup_idx).map(|b| b.dir_name().to_string());
+        let target = match operation {
+            Operation::Backup | Operation::DeleteVoice => Some(lang_code),
+            Operation::Restore | Operation::DeleteBackup => backup_code.as_deref().map(backup_info::base_code),
+            _ => None,
+        };
+        let target = target.map(|code| self.languages.get(code).map(|l| l.name).unwrap_or(code).to_string()).unwrap_or_default();
+        let ahead = self.queue.push(operation, lang_code, backup_code, target);
+        info!("queued {:?} ({} ahead)", operation, ahead);
+        if self.task.is_some() || self.voice_scan.is_some() {
+            self.report(Ok(t!("status.queued", operation.label(), ahead)));
+        } else {
+            self.start_queued();
+        }
+    }
+
+    /// 依次开始等待中的项目，直到有一项在后台执行
+    fn start_queued(&mut self) {
+        while self.task.is_none() && self.voice_scan.is_none() {
+            let Some(item) = self.queue.next_pending() else {
+                return;
+            };
+            item.status = ItemStatus::Running;
+            item.started = Some(Instant::now());
+            let (operation, lang_code, backup_code) = (item.operation, item.lang_code, item.backup_code.clone());
+            let result = self.select_queued(operation, lang_code, backup_code.as_deref()).and_then(|()| self.execute(operation));
+            // 没有启动后台任务的操作（例如删除）已经完成
+            if self.task.is_none() {
+                let finished = self.queue.finish_running(result.as_deref().map_err(|e| e.to_string()));
+                self.record_history(finished, &result);
+                self.finish_move_backups(result.is_ok());
+            }
+            self.report(result);
+        }
+    }
+
+    /// 把结束的队列项目写入操作历史
+    fn record_history(&mut self, id: Option<u64>, result: &Result<String, SwitcherError>) {
+        let Some(item) = id.and_then(|id| self.queue.get(id)) else {
+            return;
+        };
+        let entry = history::Entry {
+            time: logging::local_time(),
+            operation: item.operation,
+            target: item.target.clone(),
+            build_id: self.steam_info.as_ref().map(|s| s.build_id.clone()).unwrap_or_default(),
+            ok: result.is_ok(),
+            message: match result {
+                Ok(message) => message.lines().next().unwrap_or_default().to_string(),
+                Err(e) => e.to_string(),
+            },
+            duration_secs: item.started.map(|s| s.elapsed().as_secs_f64()).unwrap_or_default(),
+        };
+        history::append(&self.backup_dir, &entry);
+        self.history.push(entry);
+    }
+
+    /// 恢复加入队列时所选的语言和备份，所选备份已不存在时不执行
+    fn select_queued(&mut self, operation: Operation, lang_code: &str, backup_code: Option<&str>) -> Result<(), SwitcherError> {
+        self.selected_lang_idx = self
+            .lang_codes
+            .iter()
+            .position(|c| *c == lang_code)
+            .ok_or_else(|| SwitcherError::UnknownLanguage(lang_code.to_string()))?;
+        let backup_idx = backup_code.and_then(|name| self.available_backups.iter().position(|b| b.dir_name() == name));
+        match backup_idx {
+            Some(idx) => self.selected_backup_idx = idx,
+            None if matches!(operation, Operation::Restore | Operation::DeleteBackup) => return Err(SwitcherError::NoBackups),
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// 取消队列中的项目：等待中的不再执行，正在执行的请求后台任务停止
+    fn cancel_queued(&mut self, id: u64) -> String {
+        if self.queue.cancel(id) {
+            return t!("status.queue_cancelled").to_string();
+        }
+        if let Some(task) = &self.task {
+            task.cancel();
+        }
+        t!("status.queue_cancelling").to_string()
+    }
+
+    /// 显示操作队列和每一项的状态
+    fn show_queue(&mut self, ui: &mut egui::Ui) {
+        if self.queue.items().is_empty() {
+            return;
+        }
+        let mut cancel = None;
+        let mut retry = None;
+        let mut clear = false;
+        egui::CollapsingHeader::new(t!("ui.queue", self.queue.items().len()))
+            .id_salt("queue")
+            .default_open(true)
+            .show(ui, |ui| {
+                for item in self.queue.items() {
+                    ui.horizontal(|ui| {
+                        if item.target.is_empty() {
+                            ui.label(item.operation.label());
+                        } else {
+                            ui.label(format!("{} - {}", item.operation.label(), item.target));
+                        }
+                        let color = match item.status {
+                            ItemStatus::Done(_) => theme::success(ui),
+                            ItemStatus::Failed(_) => theme::error(ui),
+                            ItemStatus::Running => theme::warning(ui),
+                            ItemStatus::Pending | ItemStatus::Cancelled => ui.visuals().weak_text_color(),
+                        };
+                        let status = ui.label(egui::RichText::new(item.status.label()).color(color));
+                        if let ItemStatus::Done(message) | ItemStatus::Failed(message) = &item.status {
+                            status.on_hover_text(message);
+                        }
+                        if !item.status.is_finished() && ui.small_button(t!("ui.cancel")).on_hover_text(t!("ui.queue_cancel_hint")).clicked() {
+                            cancel = Some(item.id);
+                        }
+                        if matches!(item.status, ItemStatus::Failed(_)) && ui.small_button(t!("ui.queue_retry")).on_hover_text(t!("ui.queue_retry_hint")).clicked() {
+                            retry = Some(item.id);
+                        }
+                    });
+                }
+                if self.queue.items().iter().any(|item| item.status.is_finished()) && ui.button(t!("ui.queue_clear")).on_hover_text(t!("ui.queue_clear_hint")).clicked() {
+                    clear = true;
+                }
+            });
+        if let Some(id) = cancel {
+            let message = self.cancel_queued(id);
+            self.report(Ok(message));
+        }
+        if let Some(id) = retry {
+            self.retry_queued(id);
+        }
+        if clear {
+            self.queue.clear_finished();
+        }
+    }
+
+    /// 重新执行失败的项目，例如添加杀毒软件排除项之后
+    fn retry_queued(&mut self, id: u64) {
+        if self.queue.retry(id) {
+            self.report(Ok(t!("status.queue_retried").to_string()));
+            self.start_queued();
+        }
+    }
+
+    /// 执行操作；开启预览时或删除操作先显示计划，确认后加入队列
+    fn request(&mut self, operation: Operation) {
+        // Steam 有待安装的更新时，恢复前总是显示预览中的提醒
+        let update_pending = operation == Operation::Restore && self.steam_info.as_ref().is_some_and(|s| s.update_pending);
+        if !self.settings.preview && !operation.is_delete() && !update_pending {
+            self.enqueue(operation);
+            return;
+        }
+        self.skip_snapshot = false;
+        self.delete_in_use_confirmed = false;
+        match self.plan(operation) {
+            Ok(plan) => self.pending_plan = Some((plan, Instant::now())),
+            Err(e) => self.report(Err(e)),
+        }
+    }
+
+    /// 程序版本、当前游戏、检测到的路径等概要信息，用于诊断信息和崩溃报告
+    fn state_summary(&self) -> String {
+        let game = self.current_game();
+        let steam = self.steam_info.as_ref();
+        let active = self.get_active_voice().map(|(code, linked)| format!("{} (linked: {})", code, linked));
+        [
+            format!("version: {}", env!("CARGO_PKG_VERSION")),
+            format!("game: {} ({}, app {})", game.name, game.id, game.app_id),
+            format!("platform: {}", steam.map(|s| s.platform.label()).unwrap_or_default()),
+            format!("steam_path: {}", steam.map(|s| s.steam_path.display().to_string()).unwrap_or_default()),
+            format!("game_path: {}", steam.map(|s| s.game_path.display().to_string()).unwrap_or_default()),
+            format!("build_id: {}", steam.map(|s| s.build_id.as_str()).unwrap_or_default()),
+            format!("voice_folder: {}", self.source_path),
+            format!("backup_dir: {}", self.backup_dir.display()),
+            format!("ui_language: {:?}", i18n::current()),
+            format!("selected_language: {}", self.get_selected_lang_code()),
+            format!("installed_languages: {}", self.installed_languages().join(", ")),
+            format!("active_voice: {}", active.unwrap_or_default()),
+            format!("backup_mode: {:?}, restore_mode: {:?}, copy_workers: {}",
+                self.backup_mode, self.restore_mode, self.settings.copy_workers()),
+        ]
+        .join("\n")
+    }
+
+    /// 导出诊断信息压缩包，反馈问题时附上
+    fn export_diagnostics(&self, dest: &Path) -> Result<String, SwitcherError> {
+        let steam = self.steam_info.as_ref();
+        let summary = self.state_summary();
+
+        let exe_dir = exe_dir();
+        let data_dir = PathBuf::from(&self.source_path);
+        let info = diagnostics::DiagnosticInfo {
+            summary,
+            exe_dir: &exe_dir,
+            backup_root: &self.backup_root,
+            manifest: steam.map(|s| s.manifest_path.as_path()),
+            data_dir: (!self.source_path.is_empty() && data_dir.exists()).then_some(data_dir.as_path()),
+        };
+        diagnostics::export(dest, &info)?;
+        Ok(t!("status.diagnostics_exported", dest.display()))
+    }
+
+    /// 删除备份
+    fn delete_backup(&mut self) -> Result<String, SwitcherError> {
+        if self.available_backups.is_empty() {
+            return Err(SwitcherError::NoBackupToDelete);
+        }
+
+        let backup_info = self.available_backups[self.selected_backup_idx].clone();
+        let backup_path = backup_info.path(&self.backup_dir);
+        let links = self.links_into(&backup_path);
+        if !links.is_empty() {
+            return Err(SwitcherError::BackupInUse(links.len()));
+        }
+
+        let mut not_recycled = Vec::new();
+        if backup_path.exists() {
+            if recycle::remove(&backup_path, self.settings.permanent_delete).map_err(SwitcherError::DeleteBackup)? == Removed::Permanently {
+                not_recycled.push(backup_path.clone());
+            }
+            info!("deleted backup {}", backup_path.display());
+        }
+
+        let lang_name = self.languages.get(backup_info.lang_code.as_str()).map(|l| l.name).unwrap_or(&backup_info.lang_code);
+        self.refresh_backups();
+        self.refresh_voice_state();
+        let mut message = t!("status.backup_deleted", lang_name);
+        message.extend(recycle::note(&not_recycled).map(|note| format!("\n{}", note)));
+        Ok(message)
+    }
+}
+
+
+impl BF6VoiceSwitcher {
+    /// 备份列表：点击列标题排序，点击名称选中，每行提供恢复、校验、删除和打开文件夹
+    fn show_backup_table(&mut self, ui: &mut egui::Ui) {
+        if self.available_backups.is_empty() {
+            ui.label(t!("ui.no_backup"));
+            return;
+        }
+        let mut sort = None;
+        let mut clicked = None;
+        let mut action = None;
+        egui::ScrollArea::vertical().id_salt("backups_scroll").max_height(180.0).show(ui, |ui| {
+            egui::Grid::new("backups").num_columns(7).striped(true).show(ui, |ui| {
+                for column in BackupColumn::ALL {
+                    let mut text = column.label().to_string();
+                    if self.backup_sort.0 == column {
+                        text.push_str(if self.backup_sort.1 { " ⏶" } else { " ⏷" });
+                    }
+                    if ui.add(egui::Button::new(egui::RichText::new(text).strong()).frame(false)).on_hover_text(t!("ui.sort_hint")).clicked() {
+                        sort = Some(column);
+                    }
+                }
+                ui.label(egui::RichText::new(t!("ui.column_in_use")).strong());
+                ui.label("");
+                ui.end_row();
+
+                for (idx, info) in self.available_backups.iter().enumerate() {
+                    let mut name = self.backup_name(info).to_string();
+                    if info.compressed {
+                        name.push(' ');
+                        name.push_str(t!("ui.compressed_tag"));
+                    }
+                    if ui.selectable_label(idx == self.selected_backup_idx, name).clicked() {
+                        clicked = Some(idx);
+                    }
+                    ui.label(if info.build_id.is_empty() { "-" } else { &info.build_id });
+                    ui.label(if info.created.is_empty() { "-" } else { &info.created });
+                    ui.label(self.backup_sizes.get(info.dir_name()).map(|s| disk::format_size(*s)).unwrap_or_default());
+                    ui.label(&info.note);
+                    let links = self.links_into(&info.path(&self.backup_dir)).len();
+                    if links > 0 {
+                        ui.label(egui::RichText::new(t!("ui.in_use_count", links)).color(theme::warning(ui)));
+                    } else {
+                        ui.label("");
+                    }
+                    ui.horizontal(|ui| {
+                        for (row_action, label, hint) in [
+                            (BackupAction::Restore, t!("ui.restore"), t!("ui.restore_hint")),
+                            (BackupAction::Verify, t!("ui.verify"), t!("ui.verify_hint")),
+                            (BackupAction::Compare, t!("ui.compare"), t!("ui.compare_hint")),
+                            (BackupAction::Delete, t!("ui.delete_backup"), t!("ui.delete_backup_hint")),
+                            (BackupAction::OpenFolder, t!("ui.open_folder"), t!("ui.open_folder_hint")),
+                        ] {
+                            if ui.small_button(label).on_hover_text(hint).clicked() {
+                                action = Some((idx, row_action));
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(column) = sort {
+            self.set_backup_sort(column);
+        }
+        if let Some(idx) = clicked.or(action.map(|(idx, _)| idx)) {
+            if idx != self.selected_backup_idx {
+                self.editing_backup = None;
+            }
+            self.selected_backup_idx = idx;
+        }
+        match action.map(|(_, a)| a) {
+            Some(BackupAction::Restore) => self.request(Operation::Restore),
+            Some(BackupAction::Verify) => {
+                let result = self.verify_backup();
+                self.report(result);
+            }
+            Some(BackupAction::Compare) => {
+                let result = self.compare_backup();
+                self.report(result);
+            }
+            Some(BackupAction::Delete) => self.request(Operation::DeleteBackup),
+            Some(BackupAction::OpenFolder) => {
+                let path = self.available_backups[self.selected_backup_idx].path(&self.backup_dir);
+                self.open_in_explorer(&path);
+            }
+            None => {}
+        }
+    }
+
+    /// 经典布局：方案和全部步骤显示在同一页
+    fn show_advanced(&mut self, ui: &mut egui::Ui, queue_active: bool) {
+        // 方案
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(t!("ui.profiles")).strong());
+                match self.active_profile() {
+                    Some(profile) => {
+                        ui.label(egui::RichText::new(t!("ui.profile_active", profile.name)).color(theme::success(ui)))
+                    }
+                    None => ui.label(egui::RichText::new(t!("ui.profile_none")).weak()),
+                };
+            });
+            if !self.settings.profiles.is_empty() {
+                ui.horizontal(|ui| {
+                    let selected = self.settings.profiles.get(self.selected_profile_idx).map(|p| p.name.as_str()).unwrap_or_default();
+                    egui::ComboBox::from_id_salt("profile").selected_text(selected).show_ui(ui, |ui| {
+                        for (idx, profile) in self.settings.profiles.iter().enumerate() {
+                            let voice = self.languages.get(profile.voice.as_str()).map(|l| l.name).unwrap_or(&profile.voice);
+                            let text = if profile.text_language.is_empty() { "-" } else { &profile.text_language };
+                            ui.selectable_value(&mut self.selected_profile_idx, idx, &profile.name)
+                                .on_hover_text(t!("ui.profile_detail", voice, text));
+                        }
+                    });
+                    if ui.button(t!("ui.profile_apply")).on_hover_text(t!("ui.profile_apply_hint")).clicked() {
+                        let result = self.apply_profile();
+                        self.report(result);
+                    }
+                    if ui.button(t!("ui.profile_shortcut")).on_hover_text(t!("ui.profile_shortcut_hint")).clicked() {
+                        let result = self.create_profile_shortcut();
+                        self.report(result);
+                    }
+                    if ui.button(t!("ui.profile_add_to_steam")).on_hover_text(t!("ui.profile_add_to_steam_hint")).clicked() {
+                        let result = self.add_profile_to_steam();
+                        self.report(result);
+                    }
+                    if ui.button(t!("ui.profile_delete")).on_hover_text(t!("ui.profile_delete_hint")).clicked() {
+                        let result = self.delete_profile();
+                        self.report(result);
+                    }
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.new_profile_name).hint_text(t!("ui.profile_name")).desired_width(200.0));
+                if ui.button(t!("ui.profile_save")).on_hover_text(t!("ui.profile_save_hint")).clicked() {
+                    let result = self.save_profile();
+                    self.report(result);
+                }
+            });
+        });
+
+        ui.add_space(5.0);
+
+        let workflow = self.workflow();
+        if let Some(next) = &workflow.next {
+            ui.label(egui::RichText::new(t!("ui.next_step", next)).strong());
+            ui.add_space(5.0);
+        }
+
+        // 步骤1
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(t!("ui.step1")).strong());
+                workflow.steps[0].show(ui);
+            });
+            ui.label(t!("ui.step1_hint", self.current_game().name));
+            ui.label(t!("ui.step1_path", self.current_game().name));
+            self.show_steam_language(ui);
+        });
+
+        ui.add_space(5.0);
+
+        // 步骤2
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(t!("ui.step2")).strong());
+                workflow.steps[1].show(ui);
+            });
+            self.show_language_choice(ui);
+        });
+
+        ui.add_space(5.0);
+
+        // 步骤3
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(t!("ui.step3")).strong());
+                workflow.steps[2].show(ui);
+            });
+            ui.label(egui::RichText::new(t!("ui.step3_path", self.current_game().data_subpath)).weak());
+
+            self.show_source_path(ui, queue_active);
+
+            ui.horizontal(|ui| {
+                let label = ui.label(t!("ui.backup_mode")).on_hover_text(self.backup_mode.hint());
+                egui::ComboBox::from_id_salt("backup_mode")
+                    .selected_text(self.backup_mode.label())
+                    .show_ui(ui, |ui| {
+                        for mode in BackupMode::ALL {
+                            ui.selectable_value(&mut self.backup_mode, mode, mode.label()).on_hover_text(mode.hint());
+                        }
+                    })
+                    .response
+                    .labelled_by(label.id);
+                if self.backup_mode != self.settings.backup_mode && ui.button(t!("ui.set_default")).on_hover_text(t!("ui.set_default_hint")).clicked() {
+                    let result = self.save_default_modes();
+                    self.report(result);
+                }
+            });
+            if self.backup_mode == BackupMode::Move {
+                ui.label(egui::RichText::new(t!("ui.move_mode_hint")).weak());
+            }
+
+            ui.horizontal(|ui| {
+                let label = ui.label(t!("ui.copy_workers"));
+                let mut workers = self.settings.copy_workers();
+                let response = ui
+                    .add(egui::DragValue::new(&mut workers).range(1..=settings::MAX_COPY_WORKERS))
+                    .on_hover_text(t!("ui.copy_workers_hint"))
+                    .labelled_by(label.id);
+                if response.changed() {
+                    self.settings.copy_workers = workers;
+                    if let Err(e) = self.save_settings() {
+                        self.report(Err(e));
+                    }
+                }
+                if ui
+                    .checkbox(&mut self.settings.background_priority, t!("ui.background_priority"))
+                    .on_hover_text(t!("ui.background_priority_hint"))
+                    .changed()
+                {
+                    priority::set_background(self.settings.background_priority);
+                    if let Err(e) = self.save_settings() {
+                        self.report(Err(e));
+                    }
+                }
+            });
+            if self.settings.background_priority {
+                ui.label(egui::RichText::new(t!("ui.background_priority_note")).weak());
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                let resumable = self.resumable_staging(self.get_selected_lang_code()).is_some();
+                let label = if resumable { t!("ui.resume_backup") } else { t!("ui.backup") };
+                if ui.button(label).on_hover_text(t!("ui.backup_hint")).clicked() {
+                    self.request(Operation::Backup);
+                }
+                if ui.button(t!("ui.backup_all")).on_hover_text(t!("ui.backup_all_hint")).clicked() {
+                    self.request(Operation::BackupAll);
+                }
+                if resumable && ui.button(t!("ui.discard_partial")).on_hover_text(t!("ui.discard_partial_hint")).clicked() {
+                    let result = self.discard_partial_backup();
+                    self.report(result);
+                }
+                if self.size_scan.is_some() {
+                    ui.label(egui::RichText::new(t!("ui.size_calculating")).weak());
+                } else if let Some(size) = self.voice_sizes.get(self.get_selected_lang_code()) {
+                    ui.label(egui::RichText::new(t!("ui.backup_size", disk::format_size(*size))).weak());
+                }
+                if ui.button(t!("ui.delete_voice")).on_hover_text(t!("ui.delete_voice_hint")).clicked() {
+                    self.request(Operation::DeleteVoice);
+                }
+                if ui.button(t!("ui.delete_other_voices")).on_hover_text(t!("ui.delete_other_voices_hint")).clicked() {
+                    self.request(Operation::DeleteOthers);
+                }
+            });
+        });
+
+        ui.add_space(5.0);
+
+        // 步骤4
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(t!("ui.step4")).strong());
+                workflow.steps[3].show(ui);
+            });
+            ui.label(t!("ui.step4_hint"));
+
+            // 版本警告
+            if let Some((backup_ver, current_ver)) = self.check_version_match() {
+                ui.label(egui::RichText::new(t!("ui.version_mismatch", backup_ver, current_ver))
+                    .color(theme::error(ui)))
+                    .on_hover_text(t!("ui.version_mismatch_why"));
+                ui.label(egui::RichText::new(t!("ui.version_mismatch_hint")).small());
+                self.show_version_override(ui, &backup_ver, &current_ver);
+            }
+
+            ui.horizontal(|ui| {
+                let label = ui.label(t!("ui.restore_mode")).on_hover_text(self.restore_mode.hint());
+                egui::ComboBox::from_id_salt("restore_mode")
+                    .selected_text(self.restore_mode.label())
+                    .show_ui(ui, |ui| {
+                        for mode in RestoreMode::ALL {
+                            ui.selectable_value(&mut self.restore_mode, mode, mode.label()).on_hover_text(mode.hint());
+                        }
+                    })
+                    .response
+                    .labelled_by(label.id);
+                if self.restore_mode != self.settings.restore_mode && ui.button(t!("ui.set_default")).on_hover_text(t!("ui.set_default_hint")).clicked() {
+                    let result = self.save_default_modes();
+                    self.report(result);
+                }
+            });
+
+            ui.label(t!("ui.select_voice"));
+            if self.backup_inside_game() {
+                ui.label(egui::RichText::new(t!("ui.backup_inside_game")).color(theme::warning(ui)));
+            }
+            if let Some(sync) = &self.cloud_sync {
+                let provider = sync.provider;
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(egui::RichText::new(t!("ui.cloud_sync", provider.name())).color(theme::warning(ui)));
+                    let (label, hint) = if provider.supports_pinning() {
+                        (t!("ui.cloud_pin"), t!("ui.cloud_pin_hint"))
+                    } else {
+                        (t!("ui.cloud_exclude"), t!("ui.cloud_exclude_hint"))
+                    };
+                    if ui.button(label).on_hover_text(hint).clicked() {
+                        let result = self.protect_cloud_backups();
+                        self.report(result);
+                    }
+                });
+            }
+            self.show_backup_table(ui);
+
+            ui.horizontal(|ui| {
+                if ui.button(t!("ui.refresh")).on_hover_text(t!("ui.refresh_hint")).clicked() {
+                    self.refresh_backups();
+                    self.refresh_voice_state();
+                }
+                if ui.button(t!("ui.dedup")).on_hover_text(t!("ui.dedup_hint")).clicked() {
+                    let result = self.dedup_backups();
+                    self.report(result);
+                }
+                if ui.button(t!("ui.open_backup_folder")).on_hover_text(t!("ui.open_backup_folder_hint")).clicked() {
+                    let path = self.backup_dir.clone();
+                    self.open_in_explorer(&path);
+                }
+                if ui.button(t!("ui.move_backups")).on_hover_text(t!("ui.move_backups_hint")).clicked() {
+                    if let Some(to) = rfd::FileDialog::new().set_title(t!("ui.move_backups")).pick_folder() {
+                        let result = self.move_backups(to.join("voice_backups"));
+                        self.report(result);
+                    }
+                }
+            });
+
+            // 所选备份的显示名称和备注
+            if let Some(info) = self.available_backups.get(self.selected_backup_idx) {
+                let mut save = None;
+                let copy_label = t!("ui.duplicate_label", self.backup_name(info));
+                ui.horizontal(|ui| match &mut self.editing_backup {
+                    Some((label, note)) => {
+                        let label_id = ui.label(t!("ui.backup_label")).id;
+                        ui.add(egui::TextEdit::singleline(label).desired_width(120.0)).labelled_by(label_id);
+                        let note_id = ui.label(t!("ui.backup_note")).id;
+                        ui.add(egui::TextEdit::singleline(note).desired_width(200.0)).labelled_by(note_id);
+                        if ui.button(t!("ui.save")).clicked() {
+                            save = Some((label.clone(), note.clone()));
+                        }
+                        if ui.button(t!("ui.cancel")).clicked() {
+                            self.editing_backup = None;
+                        }
+                    }
+                    None => {
+                        if !info.note.is_empty() {
+                            ui.label(egui::RichText::new(&info.note).weak());
+                        }
+                        if ui.button(t!("ui.edit_note")).on_hover_text(t!("ui.edit_note_hint")).clicked() {
+                            self.editing_backup = Some((info.label.clone(), info.note.clone()));
+                        }
+                        if self.duplicating.is_none()
+                            && ui.button(t!("ui.duplicate_backup")).on_hover_text(t!("ui.duplicate_backup_hint")).clicked()
+                        {
+                            self.duplicating = Some((copy_label, false));
+                        }
+                    }
+                });
+                if let Some((label, note)) = save {
+                    self.editing_backup = None;
+                    let result = self.save_backup_note(label, note);
+                    self.report(result);
+                }
+                let mut duplicate = None;
+                let mut cancel = false;
+                if let Some((label, hardlink)) = &mut self.duplicating {
+                    ui.horizontal(|ui| {
+                        let label_id = ui.label(t!("ui.backup_label")).id;
+                        ui.add(egui::TextEdit::singleline(label).desired_width(160.0)).labelled_by(label_id);
+                        ui.checkbox(hardlink, t!("ui.duplicate_hardlink")).on_hover_text(t!("ui.duplicate_hardlink_hint"));
+                        if ui.button(t!("ui.duplicate_backup")).clicked() {
+                            duplicate = Some((label.clone(), *hardlink));
+                        }
+                        cancel = ui.button(t!("ui.cancel")).clicked();
+                    });
+                }
+                if cancel {
+                    self.duplicating = None;
+                }
+                if let Some((label, hardlink)) = duplicate {
+                    self.duplicating = None;
+                    let result = self.duplicate_backup(label, hardlink);
+                    self.report(result);
+                }
+            }
+
+            ui.horizontal(|ui| {
+                let compressed = self.available_backups.get(self.selected_backup_idx).is_some_and(|b| b.compressed);
+                let label = if compressed { t!("ui.decompress") } else { t!("ui.compress") };
+                if ui.button(label).on_hover_text(t!("ui.compress_hint")).clicked() {
+                    let result = self.toggle_compression();
+                    self.report(result);
+                }
+                if ui.button(t!("ui.export_backup")).on_hover_text(t!("ui.export_backup_hint")).clicked() {
+                    if let Some(name) = self.export_file_name() {
+                        let dialog = FileDialog::new().set_file_name(name).add_filter("zip", &["zip"]);
+                        if let Some(dest) = dialog.save_file() {
+                            let result = self.export_backup(dest);
+                            self.report(result);
+                        }
+                    }
+                }
+                if ui.button(t!("ui.import_backup")).on_hover_text(t!("ui.import_backup_hint")).clicked() {
+                    if let Some(archive) = FileDialog::new().add_filter("zip", &["zip"]).pick_file() {
+                        let result = self.import_backup(archive);
+                        self.report(result);
+                    }
+                }
+                let label = ui.label(t!("ui.compression_level"));
+                let mut level = self.settings.compression_level();
+                if ui
+                    .add(egui::DragValue::new(&mut level).range(1..=settings::MAX_COMPRESSION_LEVEL))
+                    .on_hover_text(t!("ui.compression_level_hint"))
+                    .labelled_by(label.id)
+                    .changed()
+                {
+                    self.settings.compression_level = level;
+                    if let Err(e) = self.save_settings() {
+                        self.report(Err(e));
+                    }
+                }
+            });
+
+            ui.horizontal_wrapped(|ui| {
+                let label = ui.label(t!("ui.keep_builds"));
+                let builds = ui
+                    .add(egui::DragValue::new(&mut self.settings.keep_builds).range(0..=20))
+                    .on_hover_text(t!("ui.keep_builds_hint"))
+                    .labelled_by(label.id);
+                let label = ui.label(t!("ui.max_backup_gb"));
+                let size = ui
+                    .add(egui::DragValue::new(&mut self.settings.max_backup_gb).range(0..=10000).suffix(" GB"))
+                    .on_hover_text(t!("ui.max_backup_gb_hint"))
+                    .labelled_by(label.id);
+                if builds.changed() || size.changed() {
+                    self.prune_dismissed = false;
+                    if let Err(e) = self.save_settings() {
+                        self.report(Err(e));
+                    }
+                }
+                if ui.button(t!("ui.prune_now")).on_hover_text(t!("ui.prune_now_hint")).clicked() {
+                    let result = self.prune_backups();
+                    self.report(result);
+                }
+            });
+            egui::CollapsingHeader::new(t!("ui.storage")).id_salt("storage").show(ui, |ui| self.show_storage(ui));
+
+            // 所选备份被游戏目录中的 Junction 使用时不能直接删除
+            if let Some(info) = self.available_backups.get(self.selected_backup_idx) {
+                let links = self.links_into(&info.path(&self.backup_dir));
+                if !links.is_empty() {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("ui.backup_in_use", links.len())).color(theme::warning(ui)));
+                        if ui.button(t!("ui.materialize")).on_hover_text(t!("ui.materialize_hint")).clicked() {
+                            let result = self.materialize_links(links);
+                            self.report(result);
+                        }
+                    });
+                }
+            }
+        });
+
+        ui.add_space(5.0);
+
+        // 步骤5
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(t!("ui.step5")).strong());
+                workflow.steps[4].show(ui);
+            });
+            ui.label(self.launch_option_hint());
+
+            self.show_launch_option(ui);
+        });
+    }
+
+    /// 备份占用面板：总大小、硬链接节省的空间、剩余空间，以及按语言和版本的大小
+    fn show_storage(&mut self, ui: &mut egui::Ui) {
+        if self.usage.is_none() && self.usage_scan.is_none() {
+            let backups = self
+                .available_backups
+                .iter()
+                .map(|b| (b.lang_code.clone(), b.build_id.clone(), b.path(&self.backup_dir)))
+                .collect();
+            self.usage_scan = Some(usage::UsageScan::spawn(self.backup_root.clone(), backups));
+        }
+        let Some(usage) = &self.usage else {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(t!("ui.storage_scanning"));
+            });
+            return;
+        };
+        ui.label(t!("ui.storage_total", self.backup_root.display(), disk::format_size(usage.total), disk::format_size(usage.on_disk)));
+        ui.label(t!("ui.storage_dedup", disk::format_size(usage.dedup_savings())));
+        if let Some(free) = usage.free {
+            ui.label(t!("ui.storage_free", disk::format_size(free)));
+        }
+        egui::Grid::new("storage_languages").num_columns(3).striped(true).show(ui, |ui| {
+            ui.label(egui::RichText::new(t!("ui.column_language")).strong());
+            ui.label(egui::RichText::new(t!("ui.column_build")).strong());
+            ui.label(egui::RichText::new(t!("ui.column_size")).strong());
+            ui.end_row();
+            for backup in &usage.backups {
+                ui.label(self.languages.get(backup.lang_code.as_str()).map(|l| l.name).unwrap_or(&backup.lang_code));
+                ui.label(&backup.build_id);
+                ui.label(disk::format_size(backup.size));
+                ui.end_row();
+            }
+        });
+        for (build, size) in usage.by_build() {
+            ui.label(t!("ui.storage_build", build, disk::format_size(size)));
+        }
+        if ui.button(t!("ui.refresh")).on_hover_text(t!("ui.storage_refresh_hint")).clicked() {
+            self.usage = None;
+        }
+    }
+
+    /// 选择语音语言，标出游戏目录中已安装的语言
+    fn show_language_choice(&mut self, ui: &mut egui::Ui) {
+        let installed = self.installed_languages();
+        ui.horizontal_wrapped(|ui| {
+            for (idx, code) in self.lang_codes.iter().enumerate() {
+                if let Some(lang) = self.languages.get(*code) {
+                    let label = if installed.contains(code) {
+                        let size = self.voice_sizes.get(*code).map(|s| format!(" ({})", disk::format_size(*s))).unwrap_or_default();
+                        egui::RichText::new(format!("{} ✔{}", lang.name, size)).color(theme::success(ui))
+                    } else {
+                        egui::RichText::new(lang.name)
+                    };
+                    let response = ui.selectable_label(self.selected_lang_idx == idx, label);
+                    let response = if installed.contains(code) {
+                        response.on_hover_text(t!("ui.lang_installed_hint"))
+                    } else {
+                        response
+                    };
+                    if response.clicked() {
+                        self.selected_lang_idx = idx;
+                        if let Some(backup_idx) = self.available_backups.iter().position(|b| b.dir_name() == *code) {
+                            self.selected_backup_idx = backup_idx;
+                        }
+                    }
+                }
+            }
+        });
+        if !installed.is_empty() && !installed.contains(&self.get_selected_lang_code()) {
+            let names: Vec<&str> = installed
+                .iter()
+                .map(|code| self.languages.get(code).map(|l| l.name).unwrap_or(code))
+                .collect();
+            ui.label(
+                egui::RichText::new(t!("ui.lang_mismatch_warning", names.join(", ")))
+                    .color(theme::warning(ui)),
+            );
+        }
+    }
+
+    /// 语音文件夹路径和浏览按钮，队列执行期间不能修改路径
+    fn show_source_path(&mut self, ui: &mut egui::Ui, queue_active: bool) {
+        ui.horizontal(|ui| {
+            // 留出右侧两个按钮的宽度，窗口变宽时路径输入框随之变宽
+            let path_width = (ui.available_width() - 200.0).max(240.0);
+            let edit = egui::TextEdit::singleline(&mut self.source_path)
+                .hint_text(t!("ui.source_path"))
+                .desired_width(path_width);
+            ui.add_enabled(!queue_active, edit);
+            if ui.add_enabled(!queue_active, egui::Button::new(t!("ui.browse"))).on_hover_text(t!("ui.browse_hint")).clicked() {
+                if let Some(path) = FileDialog::new().pick_folder() {
+                    let result = self.set_source_path(path);
+                    self.report(result);
+                }
+            }
+            if ui.button(t!("ui.open_game_folder")).on_hover_text(t!("ui.open_game_folder_hint")).clicked() {
+                let path = PathBuf::from(&self.source_path);
+                self.open_in_explorer(&path);
+            }
+        });
+    }
+
+    /// Steam 中当前的启动选项，逐个标出语音参数与所选语言是否一致
+    fn show_current_launch_options(&mut self, ui: &mut egui::Ui) {
+        let Some(options) = self.launch_options.clone() else {
             return;
+        };
+        let game_param = self.current_game().launch_param.clone();
+        ui.horizontal_wrapped(|ui| {
+            ui.label(t!("ui.current_launch_options"));
+            let shown = if options.trim().is_empty() { t!("ui.launch_options_empty") } else { options.as_str() };
+            ui.label(egui::RichText::new(shown).monospace());
+            if ui.small_button("⟳").on_hover_text(t!("ui.reload_launch_options_hint")).clicked() {
+                self.refresh_launch_options();
+            }
+        });
+        let selected = self.languages.get(self.get_selected_lang_code());
+        let values = steam_config::launch_option_values(&options, &game_param);
+        if values.is_empty() {
+            ui.label(egui::RichText::new(t!("ui.launch_option_missing", game_param)).weak());
+        }
+        for value in values {
+            let text = format!("{} {}", game_param, value);
+            if selected.is_some_and(|lang| value.eq_ignore_ascii_case(lang.miles_lang)) {
+                ui.label(egui::RichText::new(t!("ui.launch_option_match", text)).color(theme::success(ui)));
+            } else {
+                // 其他语言留下的参数
+                let name = self.languages.values().find(|lang| value.eq_ignore_ascii_case(lang.miles_lang)).map(|l| l.name).unwrap_or(value);
+                ui.label(egui::RichText::new(t!("ui.launch_option_stale", text, name)).color(theme::warning(ui)))
+                    .on_hover_text(t!("ui.launch_option_stale_hint"));
+            }
         }
+    }
+
+    /// 当前语言的启动参数以及写入 Steam 和启动游戏的按钮
+    fn show_launch_option(&mut self, ui: &mut egui::Ui) {
+        self.show_current_launch_options(ui);
+        let param = self.get_launch_param();
+        ui.horizontal(|ui| {
+            let label = ui.label(t!("ui.launch_param"));
+            ui.add(egui::TextEdit::singleline(&mut param.clone()).desired_width(250.0))
+                .on_hover_text(t!("ui.launch_param_hint"))
+                .labelled_by(label.id);
+            if ui.button(t!("ui.copy_clipboard")).on_hover_text(t!("ui.copy_clipboard_hint")).clicked() {
+                ui.ctx().copy_text(param.clone());
+                self.toasts.push(Severity::Success, t!("status.copied").to_string());
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(t!("ui.write_launch_option")).on_hover_text(t!("ui.write_launch_option_hint")).clicked() {
+                let result = self.write_launch_option(false);
+                self.report(result);
+            }
+            if ui.button(t!("ui.remove_launch_option")).on_hover_text(t!("ui.remove_launch_option_hint")).clicked() {
+                let result = self.write_launch_option(true);
+                self.report(result);
+            }
+            ui.label(egui::RichText::new(t!("ui.exit_steam_first")).weak());
+        });
+        ui.horizontal_wrapped(|ui| {
+            if ui.button(t!("ui.write_user_cfg")).on_hover_text(t!("ui.write_user_cfg_hint")).clicked() {
+                let result = self.write_user_cfg(false);
+                self.report(result);
+            }
+            if self.user_cfg_lang.is_some() && ui.button(t!("ui.remove_user_cfg")).on_hover_text(t!("ui.remove_user_cfg_hint")).clicked() {
+                let result = self.write_user_cfg(true);
+                self.report(result);
+            }
+            let selected = self.languages.get(self.get_selected_lang_code()).map(|l| l.miles_lang);
+            match &self.user_cfg_lang {
+                Some(lang) if selected.is_some_and(|s| lang.eq_ignore_ascii_case(s)) => {
+                    ui.label(egui::RichText::new(t!("ui.user_cfg_match", lang)).color(theme::success(ui)));
+                }
+                Some(lang) => {
+                    ui.label(egui::RichText::new(t!("ui.user_cfg_stale", lang)).color(theme::warning(ui)));
+                }
+                None => {}
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(t!("ui.launch", self.current_game().name)).on_hover_text(t!("ui.launch_button_hint")).clicked() {
+                let result = self.launch_game();
+                self.report(result);
+            }
+            ui.label(egui::RichText::new(t!("ui.launch_hint")).weak());
+        });
+    }
+
+    /// 向导模式：每页只显示一个步骤的控件，用上一步和下一步按钮切换
+    fn show_wizard(&mut self, ui: &mut egui::Ui, queue_active: bool) {
+        let page = self.wizard_page;
+        let workflow = self.workflow();
+        let code = self.get_selected_lang_code();
+        let lang_name = self.languages.get(code).map(|l| l.name).unwrap_or(code);
+        let game_name = self.current_game().name.clone();
+        let source_valid = !self.source_path.is_empty() && Path::new(&self.source_path).exists();
+        let backup_idx = self.available_backups.iter().position(|b| b.dir_name() == code);
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                let title = t!("ui.wizard_page", page.number(), WizardPage::ALL.len(), page.title());
+                ui.label(egui::RichText::new(title).strong());
+                if let Some(step) = page.step() {
+                    workflow.steps[step].show(ui);
+                }
+            });
+            ui.add_space(5.0);
+
+            // 当前页能否继续，不能继续时显示原因
+            let mut blocked = None;
+            match page {
+                WizardPage::Detect => {
+                    ui.label(t!("ui.wizard_detect_hint", game_name));
+                    self.show_source_path(ui, queue_active);
+                    if !source_valid {
+                        blocked = Some(t!("ui.wizard_need_source"));
+                    }
+                }
+                WizardPage::Language => {
+                    ui.label(t!("ui.wizard_language_hint"));
+                    self.show_language_choice(ui);
+                }
+                WizardPage::Backup => {
+                    self.show_steam_language(ui);
+                    if self.installed_languages().contains(&code) {
+                        ui.label(t!("ui.wizard_backup_hint", lang_name));
+                        ui.horizontal_wrapped(|ui| {
+                            let resumable = self.resumable_staging(code).is_some();
+                            let label = if resumable { t!("ui.resume_backup") } else { t!("ui.backup") };
+                            if ui.button(label).on_hover_text(t!("ui.backup_hint")).clicked() {
+                                self.request(Operation::Backup);
+                            }
+                            if resumable && ui.button(t!("ui.discard_partial")).on_hover_text(t!("ui.discard_partial_hint")).clicked() {
+                                let result = self.discard_partial_backup();
+                                self.report(result);
+                            }
+                            if let Some(size) = self.voice_sizes.get(code) {
+                                ui.label(egui::RichText::new(t!("ui.backup_size", disk::format_size(*size))).weak());
+                            }
+                        });
+                    } else if backup_idx.is_none() {
+                        ui.label(t!("ui.step1_hint", game_name));
+                        ui.label(t!("ui.step1_path", game_name));
+                        if ui.button(t!("ui.refresh_state")).on_hover_text(t!("ui.refresh_state_hint")).clicked() {
+                            self.refresh_voice_state();
+                        }
+                    }
+                    if backup_idx.is_some() {
+                        ui.label(egui::RichText::new(t!("ui.wizard_backup_done", lang_name)).color(theme::success(ui)));
+                    } else {
+                        blocked = Some(t!("ui.wizard_need_backup"));
+                    }
+                }
+                WizardPage::SteamLanguage => {
+                    ui.label(t!("ui.wizard_steam_language_hint", game_name));
+                    ui.label(t!("ui.step1_path", game_name));
+                    if let Some(steam) = &self.steam_info {
+                        ui.label(egui::RichText::new(t!("ui.wizard_text_language", steam.text_language)).weak());
+                    }
+                    if ui.button(t!("ui.redetect")).on_hover_text(t!("ui.redetect_hint")).clicked() {
+                        self.detect_steam();
+                        self.refresh_voice_state();
+                    }
+                }
+                WizardPage::Restore => {
+                    ui.label(t!("ui.wizard_restore_hint", lang_name));
+                    if let Some((backup_ver, current_ver)) = self.check_version_match() {
+                        ui.label(egui::RichText::new(t!("ui.version_mismatch", backup_ver, current_ver)).color(theme::error(ui)))
+                            .on_hover_text(t!("ui.version_mismatch_why"));
+                        self.show_version_override(ui, &backup_ver, &current_ver);
+                    }
+                    if self.steam_info.as_ref().is_some_and(|s| s.update_pending) {
+                        ui.label(egui::RichText::new(t!("status.restore_update_pending")).color(theme::warning(ui)));
+                    }
+                    ui.horizontal(|ui| {
+                        let label = ui.label(t!("ui.restore_mode")).on_hover_text(self.restore_mode.hint());
+                        egui::ComboBox::from_id_salt("wizard_restore_mode")
+                            .selected_text(self.restore_mode.label())
+                            .show_ui(ui, |ui| {
+                                for mode in RestoreMode::ALL {
+                                    ui.selectable_value(&mut self.restore_mode, mode, mode.label()).on_hover_text(mode.hint());
+                                }
+                            })
+                            .response
+                            .labelled_by(label.id);
+                    });
+                    match backup_idx {
+                        Some(idx) => {
+                            if ui.button(t!("ui.wizard_restore_button", lang_name)).clicked() {
+                                self.selected_backup_idx = idx;
+                                self.request(Operation::Restore);
+                            }
+                        }
+                        None => {
+                            ui.label(egui::RichText::new(t!("ui.no_backup")).color(theme::warning(ui)));
+                        }
+                    }
+                }
+                WizardPage::LaunchOption => {
+                    ui.label(self.launch_option_hint());
+                    self.show_launch_option(ui);
+                    if workflow.next.is_none() {
+                        ui.label(egui::RichText::new(t!("ui.wizard_finished")).color(theme::success(ui)));
+                    }
+                }
+            }
 
-        let source = PathBuf::from(&self.source_path);
-        if !source.exists() {
-            self.status_message = "所选文件夹不存在！".to_string();
-            self.is_error = true;
-            return;
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                if let Some(previous) = page.previous() {
+                    if ui.button(t!("ui.wizard_back")).clicked() {
+                        self.wizard_page = previous;
+                    }
+                }
+                if let Some(next) = page.next() {
+                    if ui.add_enabled(blocked.is_none(), egui::Button::new(t!("ui.wizard_next"))).clicked() {
+                        self.wizard_page = next;
+                    }
+                }
+                if let Some(reason) = blocked {
+                    ui.label(egui::RichText::new(reason).weak());
+                }
+            });
+        });
+    }
+
+    /// 游戏更新后逐个语言刷新备份的迁移助手
+    fn show_migration_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_migration;
+        let mut refresh = None;
+        let mut rescan = false;
+        let current = self.steam_info.as_ref().map(|s| s.build_id.clone()).unwrap_or_default();
+        egui::Window::new(t!("ui.migration_title"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label(t!("ui.migration_hint", current));
+                ui.add_space(5.0);
+                egui::Grid::new("migration").num_columns(3).striped(true).show(ui, |ui| {
+                    for backup in &self.available_backups {
+                        let code = backup.lang_code.as_str();
+                        let name = self.languages.get(code).map(|l| l.name).unwrap_or(code);
+                        ui.label(name);
+                        ui.label(if backup.build_id.is_empty() { "-" } else { &backup.build_id });
+                        if backup.build_id.is_empty() || backup.build_id == current {
+                            ui.label(egui::RichText::new(t!("ui.migration_done")).color(theme::success(ui)));
+                        } else if self.has_downloaded_voice(code) {
+                            if ui.button(t!("ui.migration_refresh")).on_hover_text(t!("ui.migration_refresh_hint")).clicked() {
+                                refresh = Some(code.to_string());
+                            }
+                        } else {
+                            ui.label(egui::RichText::new(t!("ui.migration_download", name)).color(theme::warning(ui)));
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(5.0);
+                rescan = ui.button(t!("ui.refresh_state")).on_hover_text(t!("ui.refresh_state_hint")).clicked();
+            });
+        self.show_migration = open;
+        if rescan {
+            self.detect_steam();
+            self.refresh_backups();
+            self.refresh_voice_state();
         }
+        if let Some(code) = refresh {
+            self.refresh_outdated_backup(&code);
+        }
+    }
 
-        let lang_code = self.get_selected_lang_code();
-        
-        // 递归查找所有语音文件夹和 .toc 文件
-        let (voice_folders, toc_files) = self.find_voice_files(&source, lang_code);
-        
-        if voice_folders.is_empty() && toc_files.is_empty() {
-            self.status_message = format!("未找到语音文件: {} 或 vo{}", lang_code, lang_code);
-            self.is_error = true;
+    /// 显示等待确认的操作预览，确认后执行
+    fn show_pending_plan(&mut self, ctx: &egui::Context) {
+        let needs_snapshot = self
+            .pending_plan
+            .as_ref()
+            .is_some_and(|(plan, _)| plan.operation == Operation::DeleteVoice)
+            && !self.unprotected_originals().is_empty();
+        // 删除游戏当前依赖的语言会导致游戏启动时崩溃，需要额外确认
+        let code = self.get_selected_lang_code();
+        let in_use = match &self.pending_plan {
+            Some((plan, _)) if plan.operation == Operation::DeleteVoice => self.language_in_use(code),
+            _ => Vec::new(),
+        };
+        let in_use_name = self.languages.get(code).map(|l| l.name).unwrap_or(code);
+        let Some((plan, shown_at)) = &self.pending_plan else {
             return;
-        }
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        let modal = egui::Modal::new(egui::Id::new("preview")).show(ctx, |ui| {
+            ui.set_width(560.0);
+            if plan.operation.is_delete() {
+                ui.heading(t!("ui.confirm_delete_title"));
+                let hint = if self.settings.permanent_delete { t!("ui.confirm_delete_hint") } else { t!("ui.confirm_recycle_hint") };
+                ui.label(egui::RichText::new(hint).color(theme::warning(ui)));
+            } else {
+                ui.heading(t!("ui.preview_title", plan.operation.label()));
+            }
+            if plan.items.is_empty() {
+                ui.label(t!("ui.preview_empty"));
+            } else {
+                ui.label(t!("ui.preview_summary", plan.items.len(), disk::format_size(plan.total_size())));
+            }
+            for note in &plan.notes {
+                ui.label(egui::RichText::new(note).weak());
+            }
+            ui.add_space(5.0);
+            egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                for (dir, items) in plan.grouped() {
+                    egui::CollapsingHeader::new(dir.display().to_string())
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for item in items {
+                                let name = item.path.file_name().unwrap_or(item.path.as_os_str()).to_string_lossy();
+                                let mut text = format!("[{}] {}", item.action.label(), name);
+                                if item.size > 0 {
+                                    text = format!("{}  ({})", text, disk::format_size(item.size));
+                                }
+                                let mut text = egui::RichText::new(text);
+                                if item.action.is_destructive() {
+                                    text = text.color(theme::error(ui));
+                                }
+                                ui.label(text);
+                            }
+                        });
+                }
+            });
+            ui.add_space(5.0);
+            if plan.operation == Operation::Revert {
+                ui.checkbox(&mut self.validate_after_revert, t!("ui.validate_after_revert"))
+                    .on_hover_text(t!("ui.validate_after_revert_hint"));
+            }
+            if needs_snapshot {
+                let text = if self.skip_snapshot { t!("ui.snapshot_skipped") } else { t!("ui.snapshot_note") };
+                ui.label(egui::RichText::new(text).color(theme::warning(ui)));
+                ui.checkbox(&mut self.skip_snapshot, t!("ui.skip_snapshot")).on_hover_text(t!("ui.skip_snapshot_hint"));
+            }
+            if !in_use.is_empty() {
+                ui.label(egui::RichText::new(t!("ui.delete_in_use", in_use_name, in_use.join(", "))).color(theme::error(ui)));
+                ui.checkbox(&mut self.delete_in_use_confirmed, t!("ui.delete_in_use_confirm"));
+            }
+            let confirmable = in_use.is_empty() || self.delete_in_use_confirmed;
+            ui.horizontal(|ui| {
+                if plan.operation.is_delete() {
+                    let elapsed = shown_at.elapsed();
+                    if elapsed < DELETE_CONFIRM_DELAY {
+                        ctx.request_repaint_after(DELETE_CONFIRM_DELAY - elapsed);
+                    }
+                    let button = egui::Button::new(
+                        egui::RichText::new(plan.operation.label()).color(egui::Color32::WHITE),
+                    )
+                    .fill(egui::Color32::from_rgb(180, 50, 50));
+                    confirmed = ui.add_enabled(elapsed >= DELETE_CONFIRM_DELAY && confirmable, button).clicked();
+                    let cancel = ui.button(t!("ui.cancel"));
+                    // 删除默认聚焦在取消上，避免误按回车
+                    a11y::focus_dialog(ui, &cancel);
+                    cancelled = cancel.clicked();
+                } else {
+                    let confirm = ui.button(t!("ui.confirm"));
+                    a11y::focus_dialog(ui, &confirm);
+                    confirmed = confirm.clicked();
+                    cancelled = ui.button(t!("ui.cancel")).clicked();
+                }
+            });
+        });
 
-        let mut deleted_folders = 0;
-        let mut deleted_files = 0;
+        if confirmed {
+            if let Some((plan, _)) = self.pending_plan.take() {
+                self.enqueue(plan.operation);
+            }
+        } else if cancelled || modal.should_close() {
+            self.pending_plan = None;
+        }
+    }
+}
 
-        // 删除 Junction
-        for rel_path in &voice_folders {
-            let folder_path = source.join(rel_path);
-            if Self::is_junction(&folder_path) {
-                if let Err(e) = Self::remove_junction(&folder_path) {
-                    self.status_message = format!("删除 {} 失败: {}", rel_path.display(), e);
-                    self.is_error = true;
-                    return;
+impl eframe::App for BF6VoiceSwitcher {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // 后台任务结束后刷新状态并显示结果
+        if self.task.as_ref().is_some_and(|task| task.is_finished()) {
+            if let Some(task) = self.task.take() {
+                let interference = task.progress().interference;
+                let result = task.join();
+                let finished = self.queue.finish_running(result.as_deref().map_err(|e| e.to_string()));
+                self.record_history(finished, &result);
+                if interference.is_suspicious() {
+                    warn!("possible antivirus interference: {:?}", interference);
+                    self.antivirus_hint = Some((interference, finished.filter(|_| result.is_err())));
+                }
+                if let Some((request, launch)) = self.ipc_request.take() {
+                    self.refresh_backups();
+                    let mut reply = result.as_ref().map(String::clone).map_err(|e| e.to_string());
+                    if launch && reply.is_ok() {
+                        reply = match self.launch_game() {
+                            Ok(launched) => reply.map(|message| format!("{}\n{}", message, launched)),
+                            Err(e) => Err(e.to_string()),
+                        };
+                    }
+                    let outcome = reply.map(|message| self.command_outcome(&request.command, message));
+                    request.finish(outcome);
+                }
+                self.refresh_backups();
+                self.refresh_voice_state();
+                // 从托盘切换，或窗口不在前台时通知结果
+                let background = ctx.input(|i| !i.focused || i.viewport().minimized == Some(true));
+                if std::mem::take(&mut self.notify_on_finish) || (self.settings.notifications && background) {
+                    match &result {
+                        Ok(message) => self.notify(ctx, message, false),
+                        Err(e) => self.notify(ctx, &e.to_string(), true),
+                    }
+                }
+                let restart = std::mem::take(&mut self.restart_after_update) && result.is_ok();
+                self.report(result);
+                if restart {
+                    self.restart_updated(ctx);
                 }
-                deleted_folders += 1;
+                self.start_queued();
             }
         }
 
-        // 删除 .toc 文件
-        for rel_path in &toc_files {
-            let file_path = source.join(rel_path);
-            if file_path.exists() {
-                if let Err(e) = fs::remove_file(&file_path) {
-                    self.status_message = format!("删除 {} 失败: {}", rel_path.display(), e);
-                    self.is_error = true;
-                    return;
+        // 底部面板的高度，提示显示在面板上方
+        let mut bottom = 0.0;
+        if self.voice_scan.as_ref().is_some_and(|(scan, _)| scan.is_finished()) {
+            if let Some((scan, codes)) = self.voice_scan.take() {
+                let source = scan.root.clone();
+                self.apply_voice_scan(source, codes, scan.join());
+                if std::mem::take(&mut self.select_installed_after_scan) {
+                    self.select_installed_language();
                 }
-                deleted_files += 1;
+                self.start_queued();
             }
+        } else if let Some((scan, _)) = &self.voice_scan {
+            let dirs = scan.dirs();
+            bottom += egui::TopBottomPanel::bottom("scan").show(ctx, |ui| {
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(t!("ui.scanning", dirs));
+                });
+                ui.add_space(4.0);
+            }).response.rect.height();
+            ctx.request_repaint_after(Duration::from_millis(100));
         }
 
-        let lang_name = self.languages.get(lang_code).map(|l| l.name).unwrap_or(lang_code);
-        self.status_message = format!("{} 语音文件已删除！({} 个文件夹, {} 个toc文件)", 
-            lang_name, deleted_folders, deleted_files);
-        self.is_error = false;
-    }
+        if self.update_check.as_ref().is_some_and(|check| check.is_finished()) {
+            if let Some(check) = self.update_check.take() {
+                // 检查失败不影响使用，只记录日志
+                match check.join() {
+                    Ok(release) => self.update_available = release,
+                    Err(e) => warn!("update check failed: {}", e),
+                }
+            }
+        }
 
-    /// 删除备份
-    fn delete_backup(&mut self) {
-        if self.available_backups.is_empty() {
-            self.status_message = "没有可删除的备份！".to_string();
-            self.is_error = true;
-            return;
+        if self.diff_scan.as_ref().is_some_and(|scan| scan.is_finished()) {
+            if let Some(scan) = self.diff_scan.take() {
+                let name = scan.name.clone();
+                self.diff_result = Some((name, scan.join()));
+            }
+        } else if self.diff_scan.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(100));
         }
 
-        let backup_info = self.available_backups[self.selected_backup_idx].clone();
-        let backup_path = self.backup_dir.join(&backup_info.lang_code);
+        if self.usage_scan.as_ref().is_some_and(|scan| scan.is_finished()) {
+            if let Some(scan) = self.usage_scan.take() {
+                self.usage = Some(scan.join());
+            }
+        } else if self.usage_scan.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
 
-        if backup_path.exists() {
-            if let Err(e) = fs::remove_dir_all(&backup_path) {
-                self.status_message = format!("删除备份失败: {}", e);
-                self.is_error = true;
-                return;
+        if self.size_scan.as_ref().is_some_and(|scan| scan.is_finished()) {
+            if let Some(scan) = self.size_scan.take() {
+                self.voice_sizes = scan.join();
             }
+        } else if self.size_scan.is_some() {
+            ctx.request_repaint_after(Duration::from_millis(100));
         }
 
-        let lang_name = self.languages.get(backup_info.lang_code.as_str()).map(|l| l.name).unwrap_or(&backup_info.lang_code);
-        self.status_message = format!("{} 备份已删除！", lang_name);
-        self.is_error = false;
-        self.refresh_backups();
-    }
-}
+        if self.task.is_some() || !self.queue.items().is_empty() {
+            let progress = self.task.as_ref().map(|task| task.progress());
+            bottom += egui::TopBottomPanel::bottom("progress").show(ctx, |ui| {
+                ui.add_space(4.0);
+                if let Some(progress) = &progress {
+                    let eta = progress
+                        .eta()
+                        .map(|d| format!("{:02}:{:02}", d.as_secs() / 60, d.as_secs() % 60))
+                        .unwrap_or_else(|| "--:--".to_string());
+                    ui.add(egui::ProgressBar::new(progress.fraction()).show_percentage());
+                    let paused = progress.paused_since.is_some();
+                    ui.horizontal(|ui| {
+                        if paused {
+                            if ui.button(t!("ui.resume")).on_hover_text(t!("ui.resume_hint")).clicked() {
+                                if let Some(task) = &self.task {
+                                    task.resume();
+                                }
+                            }
+                            ui.colored_label(theme::warning(ui), t!("ui.progress_paused", progress.current_file));
+                        } else {
+                            if ui.button(t!("ui.pause")).on_hover_text(t!("ui.pause_hint")).clicked() {
+                                if let Some(task) = &self.task {
+                                    task.pause();
+                                }
+                            }
+                            ui.label(t!("ui.progress",
+                                progress.current_file, disk::format_size(progress.throughput() as u64), eta));
+                        }
+                    });
+                }
+                self.show_queue(ui);
+                ui.add_space(4.0);
+            }).response.rect.height();
+            if progress.is_some() {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+        }
 
+        self.track_window(ctx);
+        let zoom = ctx.zoom_factor();
+        if zoom != self.applied_ui_scale {
+            self.set_ui_scale(ctx, zoom);
+        }
+        self.update_tray(ctx);
+        self.update_relink_watcher(ctx);
+        self.update_link_monitor(ctx);
+        self.handle_ipc();
+        let pressed = self.hotkeys.as_ref().map(|h| h.events()).unwrap_or_default();
+        for code in pressed {
+            self.quick_switch(ctx, &code);
+        }
+        if self.notice_tray.as_ref().is_some_and(|(_, created)| created.elapsed() >= NOTICE_DURATION) {
+            self.notice_tray = None;
+        }
+        self.show_pending_plan(ctx);
+        self.show_diff(ctx);
+        self.show_migration_window(ctx);
+        self.show_elevation_prompt(ctx);
+        self.show_antivirus_hint(ctx);
+        self.show_relink_prompt(ctx);
+        self.show_prune_prompt(ctx);
 
-impl eframe::App for BF6VoiceSwitcher {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // 后台任务执行时仍可把操作加入队列，只禁用会改变游戏或语音文件夹的选项
+        let busy = self.voice_scan.is_some();
+        let queue_active = self.task.is_some() || self.queue.has_pending();
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("战地6 语音切换工具");
-            ui.add_space(5.0);
-
-            // Steam 状态
-            ui.horizontal(|ui| {
-                if let Some(steam) = &self.steam_info {
-                    ui.label(egui::RichText::new("[OK] Steam 已连接").color(egui::Color32::GREEN));
-                    ui.label(format!("| 游戏版本: {}", steam.build_id));
-                } else {
-                    ui.label(egui::RichText::new("[!] 未检测到 Steam/游戏").color(egui::Color32::YELLOW));
-                    if ui.button("重新检测").clicked() {
-                        self.detect_steam();
-                    }
-                }
-            });
-
-            ui.add_space(5.0);
-            ui.separator();
-            ui.add_space(5.0);
+            if busy {
+                ui.disable();
+            }
+            // 窗口较小时内容可以横向和纵向滚动
+            egui::ScrollArea::both().show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading(t!("ui.title"));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let current = self.settings.ui_language;
+                        let mut selected = current;
+                        let text = current.map(|l| l.native_name()).unwrap_or(t!("ui.ui_language_auto"));
+                        let combo = egui::ComboBox::from_id_salt("ui_language")
+                            .selected_text(text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut selected, None, t!("ui.ui_language_auto"));
+                                for lang in UiLanguage::ALL {
+                                    ui.selectable_value(&mut selected, Some(lang), lang.native_name());
+                                }
+                            })
+                            .response
+                            .on_hover_text(t!("ui.ui_language_hint"));
+                        let label = ui.label(t!("ui.ui_language"));
+                        combo.labelled_by(label.id);
+                        if selected != current {
+                            self.set_ui_language(selected);
+                        }
 
-            // 步骤1
-            ui.group(|ui| {
-                ui.label(egui::RichText::new("步骤1: 准备工作").strong());
-                ui.label("请先在 Steam 中将战地6切换到您想要使用的语音语言：");
-                ui.label("右键战地6 -> 属性 -> 语言 -> 选择语言并等待下载完成");
-            });
+                        // 拖动时不立即缩放，否则滑块会在鼠标下移动
+                        let response = ui
+                            .add(
+                                egui::Slider::new(&mut self.settings.ui_scale, settings::MIN_UI_SCALE..=settings::MAX_UI_SCALE)
+                                    .step_by(0.05)
+                                    .fixed_decimals(2),
+                            )
+                            .on_hover_text(t!("ui.ui_scale_hint"));
+                        if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                            self.set_ui_scale(ctx, self.settings.ui_scale);
+                        }
+                        let label = ui.label(t!("ui.ui_scale"));
+                        response.labelled_by(label.id);
 
-            ui.add_space(5.0);
+                        let current = self.settings.theme;
+                        let mut selected = current;
+                        let combo = egui::ComboBox::from_id_salt("theme").selected_text(current.label()).show_ui(ui, |ui| {
+                            for theme in Theme::ALL {
+                                ui.selectable_value(&mut selected, theme, theme.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text(t!("ui.theme_hint"));
+                        let label = ui.label(t!("ui.theme"));
+                        combo.labelled_by(label.id);
+                        if selected != current {
+                            self.settings.theme = selected;
+                            selected.apply(ctx);
+                            if let Err(e) = self.save_settings() {
+                                self.report(Err(e));
+                            }
+                        }
+                    });
+                });
+                ui.add_space(5.0);
 
-            // 步骤2
-            ui.group(|ui| {
-                ui.label(egui::RichText::new("步骤2: 选择要使用的语音语言").strong());
+                // 游戏选择，缩放较大时选项换行显示
                 ui.horizontal_wrapped(|ui| {
-                    for (idx, code) in self.lang_codes.iter().enumerate() {
-                        if let Some(lang) = self.languages.get(*code) {
-                            if ui.selectable_label(self.selected_lang_idx == idx, lang.name).clicked() {
-                                self.selected_lang_idx = idx;
-                                if let Some(backup_idx) = self.available_backups.iter().position(|b| b.lang_code == *code) {
-                                    self.selected_backup_idx = backup_idx;
+                    let label = ui.label(t!("ui.game"));
+                    let mut selected = self.selected_game_idx;
+                    ui.add_enabled_ui(!queue_active, |ui| {
+                        egui::ComboBox::from_id_salt("game")
+                            .selected_text(&self.current_game().name)
+                            .show_ui(ui, |ui| {
+                                for (idx, game) in self.games.iter().enumerate() {
+                                    ui.selectable_value(&mut selected, idx, &game.name);
+                                }
+                            })
+                            .response
+                            .on_hover_text(t!("ui.game_hint"))
+                            .labelled_by(label.id);
+                    });
+                    if selected != self.selected_game_idx {
+                        self.select_game(selected);
+                    }
+                    ui.add_space(10.0);
+                    if ui
+                        .checkbox(&mut self.settings.wizard, t!("ui.wizard"))
+                        .on_hover_text(t!("ui.wizard_hint"))
+                        .changed()
+                    {
+                        if let Err(e) = self.save_settings() {
+                            self.report(Err(e));
+                        }
+                    }
+                    // 向导模式只保留与步骤相关的控件
+                    if !self.settings.wizard {
+                        if ui
+                            .checkbox(&mut self.settings.preview, t!("ui.preview"))
+                            .on_hover_text(t!("ui.preview_hint"))
+                            .changed()
+                        {
+                            if let Err(e) = self.save_settings() {
+                                self.report(Err(e));
+                            }
+                        }
+                        if ui
+                            .checkbox(&mut self.settings.permanent_delete, t!("ui.permanent_delete"))
+                            .on_hover_text(t!("ui.permanent_delete_hint"))
+                            .changed()
+                        {
+                            if let Err(e) = self.save_settings() {
+                                self.report(Err(e));
+                            }
+                        }
+                        ui.horizontal(|ui| {
+                            let current = self.settings.version_policy;
+                            let mut selected = current;
+                            let label = ui.label(t!("ui.version_policy")).on_hover_text(t!("ui.version_policy_hint"));
+                            egui::ComboBox::from_id_salt("version_policy")
+                                .selected_text(current.label())
+                                .show_ui(ui, |ui| {
+                                    for policy in VersionPolicy::ALL {
+                                        ui.selectable_value(&mut selected, policy, policy.label()).on_hover_text(policy.hint());
+                                    }
+                                })
+                                .response
+                                .on_hover_text(current.hint())
+                                .labelled_by(label.id);
+                            if selected != current {
+                                self.settings.version_policy = selected;
+                                if let Err(e) = self.save_settings() {
+                                    self.report(Err(e));
+                                }
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            let current = self.settings.copy_engine;
+                            let mut selected = current;
+                            let label = ui.label(t!("ui.copy_engine")).on_hover_text(t!("ui.copy_engine_hint"));
+                            egui::ComboBox::from_id_salt("copy_engine")
+                                .selected_text(current.label())
+                                .show_ui(ui, |ui| {
+                                    for engine in CopyEngine::ALL {
+                                        ui.selectable_value(&mut selected, engine, engine.label()).on_hover_text(engine.hint());
+                                    }
+                                })
+                                .response
+                                .on_hover_text(current.hint())
+                                .labelled_by(label.id);
+                            if selected != current {
+                                self.settings.copy_engine = selected;
+                                if let Err(e) = self.save_settings() {
+                                    self.report(Err(e));
+                                }
+                            }
+                        });
+                        if ui
+                            .checkbox(&mut self.settings.notifications, t!("ui.notifications"))
+                            .on_hover_text(t!("ui.notifications_hint"))
+                            .changed()
+                        {
+                            if let Err(e) = self.save_settings() {
+                                self.report(Err(e));
+                            }
+                        }
+                        if ui
+                            .checkbox(&mut self.settings.check_updates, t!("ui.check_updates"))
+                            .on_hover_text(t!("ui.check_updates_hint"))
+                            .changed()
+                        {
+                            if self.settings.check_updates && self.update_check.is_none() {
+                                self.update_check = Some(update::UpdateCheck::spawn(ctx.clone()));
+                            }
+                            if let Err(e) = self.save_settings() {
+                                self.report(Err(e));
+                            }
+                        }
+                        if ui
+                            .checkbox(&mut self.settings.minimize_to_tray, t!("ui.minimize_to_tray"))
+                            .on_hover_text(t!("ui.minimize_to_tray_hint"))
+                            .changed()
+                        {
+                            if let Err(e) = self.save_settings() {
+                                self.report(Err(e));
+                            }
+                        }
+                        let relink = ui.add_enabled(
+                            self.settings.minimize_to_tray,
+                            egui::Checkbox::new(&mut self.settings.auto_relink, t!("ui.auto_relink")),
+                        );
+                        if relink.on_hover_text(t!("ui.auto_relink_hint")).changed() {
+                            if let Err(e) = self.save_settings() {
+                                self.report(Err(e));
+                            }
+                        }
+                        ui.horizontal(|ui| {
+                            let label = ui.label(t!("ui.link_check"));
+                            let response = ui
+                                .add(egui::DragValue::new(&mut self.settings.link_check_minutes).range(0..=settings::MAX_LINK_CHECK_MINUTES).suffix(t!("ui.minutes_suffix")))
+                                .on_hover_text(t!("ui.link_check_hint"))
+                                .labelled_by(label.id);
+                            if response.changed() {
+                                if let Err(e) = self.save_settings() {
+                                    self.report(Err(e));
                                 }
                             }
+                        });
+                        if self.repair_task {
+                            ui.label(egui::RichText::new(t!("ui.repair_task_installed")).color(theme::success(ui)));
+                            if ui.button(t!("ui.repair_task_remove")).on_hover_text(t!("ui.repair_task_remove_hint")).clicked() {
+                                let result = self.unregister_repair_task();
+                                self.report(result);
+                            }
+                        } else if ui.button(t!("ui.repair_task_register")).on_hover_text(t!("ui.repair_task_register_hint")).clicked() {
+                            let result = self.register_repair_task();
+                            self.report(result);
                         }
                     }
                 });
-            });
-
-            ui.add_space(5.0);
 
-            // 步骤3
-            ui.group(|ui| {
-                ui.label(egui::RichText::new("步骤3: 选择语音文件夹").strong());
-                ui.label(egui::RichText::new("路径: ...\\Battlefield 6\\Data\\Win32").weak());
-                
-                ui.horizontal(|ui| {
-                    ui.add(egui::TextEdit::singleline(&mut self.source_path).desired_width(420.0));
-                    if ui.button("浏览").clicked() {
-                        if let Some(path) = FileDialog::new().pick_folder() {
-                            self.source_path = path.to_string_lossy().to_string();
+                // Steam 状态
+                let mut switch_install = None;
+                ui.horizontal_wrapped(|ui| {
+                    if let Some(steam) = &self.steam_info {
+                        let connected = match steam.platform {
+                            Platform::Steam => t!("ui.steam_connected"),
+                            Platform::EaApp => t!("ui.ea_app_detected"),
+                            Platform::Xbox => t!("ui.xbox_detected"),
+                        };
+                        ui.label(egui::RichText::new(connected).color(theme::success(ui)));
+                        ui.label(t!("ui.game_build", if steam.build_id.is_empty() { "-" } else { &steam.build_id }));
+                        if steam.update_pending {
+                            ui.label(egui::RichText::new(t!("ui.update_pending")).color(theme::warning(ui)))
+                                .on_hover_text(t!("ui.update_pending_hint"));
+                        }
+                        if self.install_candidates.len() > 1 {
+                            let current = self.install_candidates.iter().position(|c| c.game_path == steam.game_path);
+                            let mut selected = current;
+                            let label = ui.label(t!("ui.install"));
+                            ui.add_enabled_ui(!queue_active, |ui| {
+                                egui::ComboBox::from_id_salt("install")
+                                    .selected_text(install_label(steam))
+                                    .show_ui(ui, |ui| {
+                                        for (idx, candidate) in self.install_candidates.iter().enumerate() {
+                                            ui.selectable_value(&mut selected, Some(idx), install_label(candidate));
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text(t!("ui.install_hint"))
+                                    .labelled_by(label.id);
+                            });
+                            if let Some(idx) = selected.filter(|_| selected != current) {
+                                switch_install = Some(idx);
+                            }
+                        }
+                    } else {
+                        ui.label(egui::RichText::new(t!("ui.steam_not_found")).color(theme::warning(ui)));
+                        if ui.button(t!("ui.redetect")).on_hover_text(t!("ui.redetect_hint")).clicked() {
+                            self.detect_steam();
+                            self.refresh_voice_state();
                         }
                     }
                 });
+                if let Some(idx) = switch_install {
+                    let info = self.install_candidates[idx].clone();
+                    self.use_install(info);
+                    self.refresh_voice_state();
+                }
 
-                ui.horizontal(|ui| {
-                    if ui.button("备份语音文件").clicked() {
-                        self.backup_files();
-                    }
-                    if ui.button("删除游戏语音").clicked() {
-                        self.delete_voice_files();
+                // 有新版本时提示下载
+                let mut install = None;
+                if let Some(release) = &self.update_available {
+                    let mut dismissed = false;
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("ui.update_banner", release.version)).color(theme::warning(ui)));
+                        ui.hyperlink_to(t!("ui.update_download"), &release.url);
+                        if release.asset.is_some()
+                            && ui.button(t!("ui.update_now")).on_hover_text(t!("ui.update_now_hint")).clicked()
+                        {
+                            install = Some(release.clone());
+                        }
+                        dismissed = ui.button(t!("ui.update_dismiss")).on_hover_text(t!("ui.update_dismiss_hint")).clicked();
+                    });
+                    if dismissed {
+                        self.update_available = None;
                     }
-                });
-            });
+                }
 
-            ui.add_space(5.0);
+                if let Some(release) = install {
+                    let result = self.install_update(release);
+                    self.report(result);
+                }
 
-            // 步骤4
-            ui.group(|ui| {
-                ui.label(egui::RichText::new("步骤4: 恢复语音文件").strong());
-                ui.label("切换到想使用的文本语言后，选择要恢复的语音：");
-                
-                // 版本警告
-                if let Some((backup_ver, current_ver)) = self.check_version_match() {
-                    ui.label(egui::RichText::new(format!("[!] 版本不匹配: 备份({}) != 当前({})", backup_ver, current_ver))
-                        .color(egui::Color32::RED));
-                    ui.label(egui::RichText::new("请先删除游戏语音，再重新执行所有步骤").small());
-                }
-                
-                ui.horizontal(|ui| {
-                    ui.label("选择语音:");
-                    egui::ComboBox::from_id_salt("backup_select")
-                        .selected_text(if self.available_backups.is_empty() {
-                            "无备份".to_string()
-                        } else {
-                            let info = &self.available_backups[self.selected_backup_idx];
-                            let name = self.languages.get(info.lang_code.as_str()).map(|l| l.name).unwrap_or(&info.lang_code);
-                            if info.build_id.is_empty() {
-                                name.to_string()
-                            } else {
-                                format!("{} (v{})", name, info.build_id)
-                            }
-                        })
-                        .show_ui(ui, |ui| {
-                            for (idx, info) in self.available_backups.iter().enumerate() {
-                                let name = self.languages.get(info.lang_code.as_str()).map(|l| l.name).unwrap_or(&info.lang_code);
-                                let label = if info.build_id.is_empty() {
-                                    name.to_string()
-                                } else {
-                                    format!("{} (v{})", name, info.build_id)
-                                };
-                                if ui.selectable_label(self.selected_backup_idx == idx, label).clicked() {
-                                    self.selected_backup_idx = idx;
-                                }
-                            }
-                        });
-                    
-                    if ui.button("恢复语音").clicked() {
-                        self.restore_files();
+                // 游戏更新后提示迁移备份
+                let outdated = self.outdated_backups().len();
+                if outdated > 0 {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(t!("ui.migration_banner", outdated)).color(theme::warning(ui)));
+                        if ui.button(t!("ui.migration_open")).on_hover_text(t!("ui.migration_open_hint")).clicked() {
+                            self.show_migration = true;
+                        }
+                    });
+                }
+
+                self.show_health(ui);
+
+                // 游戏实际会使用的语音
+                let probe = self.voice_probe();
+                let lang_name = |code: &str| self.languages.get(code).map(|l| l.name).unwrap_or(code).to_string();
+                match (probe.lang, probe.source) {
+                    (Some(code), Some(source)) => {
+                        let color = if probe.issues.is_empty() { theme::success(ui) } else { theme::warning(ui) };
+                        ui.label(egui::RichText::new(t!("ui.game_will_speak", lang_name(code), source.label())).strong().color(color))
+                            .on_hover_text(t!("ui.game_will_speak_hint"));
+                    }
+                    _ => {
+                        ui.label(egui::RichText::new(t!("ui.game_will_speak_unknown")).weak());
+                    }
+                }
+                for issue in &probe.issues {
+                    ui.label(egui::RichText::new(issue.message(lang_name)).color(theme::warning(ui)));
+                }
+                // 游戏目录中实际生效的语音文件
+                let active = self.get_active_voice().map(|(code, linked)| {
+                    let source = if linked { t!("ui.source_linked") } else { t!("ui.source_original") };
+                    t!("ui.active_voice", lang_name(code), source)
+                });
+                ui.collapsing(t!("ui.voice_state_details"), |ui| {
+                    match active {
+                        Some(active) => {
+                            ui.label(active);
+                        }
+                        None => {
+                            ui.label(egui::RichText::new(t!("ui.active_voice_none")).weak());
+                        }
                     }
-                    if ui.button("删除备份").clicked() {
-                        self.delete_backup();
+                    if self.voice_state.is_empty() {
+                        ui.label(egui::RichText::new(t!("ui.voice_state_empty")).weak());
                     }
-                    if ui.button("刷新").clicked() {
-                        self.refresh_backups();
+                    for state in &self.voice_state {
+                        let name = self.languages.get(state.lang_code).map(|l| l.name).unwrap_or(state.lang_code);
+                        ui.label(egui::RichText::new(t!("ui.voice_state_lang", name, state.toc_files.len())).strong());
+                        for folder in &state.folders {
+                            let (text, color) = match &folder.state {
+                                FolderState::Directory => (t!("ui.state_directory").to_string(), ui.visuals().weak_text_color()),
+                                FolderState::Junction(Some(target)) if target.exists() => {
+                                    (t!("ui.state_junction", target.display()), theme::success(ui))
+                                }
+                                FolderState::Junction(Some(target)) => {
+                                    (t!("ui.state_junction_broken", target.display()), theme::error(ui))
+                                }
+                                FolderState::Junction(None) => (t!("ui.state_junction_unknown").to_string(), theme::warning(ui)),
+                                FolderState::Restored(mode) => (t!("ui.state_restored", mode.label()), theme::success(ui)),
+                                FolderState::Missing => (t!("ui.state_missing").to_string(), theme::error(ui)),
+                            };
+                            ui.horizontal(|ui| {
+                                ui.label(format!("  {}", folder.rel_path.display()));
+                                ui.label(egui::RichText::new(text).color(color));
+                            });
+                        }
                     }
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("ui.refresh_state")).on_hover_text(t!("ui.refresh_state_hint")).clicked() {
+                            self.refresh_voice_state();
+                        }
+                        if ui.button(t!("ui.revert_all")).on_hover_text(t!("ui.revert_all_hint")).clicked() {
+                            self.request(Operation::Revert);
+                        }
+                        if !self.materializable_links().is_empty()
+                            && ui.button(t!("ui.materialize_all")).on_hover_text(t!("ui.materialize_all_hint")).clicked()
+                        {
+                            self.request(Operation::Materialize);
+                        }
+                    });
                 });
-            });
 
-            ui.add_space(5.0);
+                ui.add_space(5.0);
+                ui.separator();
+                ui.add_space(5.0);
 
-            // 步骤5
-            ui.group(|ui| {
-                ui.label(egui::RichText::new("步骤5: Steam 启动项").strong());
-                ui.label("右键战地6 -> 属性 -> 通用 -> 启动选项，添加以下参数：");
-                
-                let param = self.get_launch_param();
-                ui.horizontal(|ui| {
-                    ui.add(egui::TextEdit::singleline(&mut param.clone()).desired_width(250.0));
-                    if ui.button("复制到剪贴板").clicked() {
-                        ctx.copy_text(param.clone());
-                        self.status_message = "已复制到剪贴板！".to_string();
-                        self.is_error = false;
+                if self.settings.wizard {
+                    self.show_wizard(ui, queue_active);
+                } else {
+                    self.show_advanced(ui, queue_active);
+                }
+
+                ui.add_space(10.0);
+
+                // 最近的操作结果
+                let mut clear_history = false;
+                egui::CollapsingHeader::new(t!("ui.messages", self.toasts.history().len()))
+                    .id_salt("messages")
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .id_salt("messages_scroll")
+                            .max_height(150.0)
+                            .show(ui, |ui| {
+                                for toast in self.toasts.history().iter().rev() {
+                                    let text = format!("{} {}", toast.time, toast.message);
+                                    ui.label(egui::RichText::new(text).color(toast.severity.color(ui)));
+                                }
+                            });
+                        if !self.toasts.history().is_empty() && ui.button(t!("ui.messages_clear")).on_hover_text(t!("ui.messages_clear_hint")).clicked() {
+                            clear_history = true;
+                        }
+                    });
+                if clear_history {
+                    self.toasts.clear_history();
+                }
+
+                // 操作历史
+                ui.collapsing(t!("ui.history", self.history.len()), |ui| {
+                    if self.history.is_empty() {
+                        ui.label(egui::RichText::new(t!("ui.history_empty")).weak());
+                        return;
                     }
+                    egui::ScrollArea::vertical().id_salt("history").max_height(200.0).show(ui, |ui| {
+                        egui::Grid::new("history").num_columns(6).striped(true).show(ui, |ui| {
+                            for header in [t!("ui.column_date"), t!("ui.column_operation"), t!("ui.column_language"), t!("ui.column_build"), t!("ui.column_result"), t!("ui.column_duration")] {
+                                ui.label(egui::RichText::new(header).strong());
+                            }
+                            ui.end_row();
+                            for entry in self.history.iter().rev() {
+                                ui.label(&entry.time);
+                                ui.label(entry.operation.label());
+                                ui.label(&entry.target);
+                                ui.label(&entry.build_id);
+                                let (text, color) = if entry.ok { (t!("ui.history_ok"), theme::success(ui)) } else { (t!("ui.history_failed"), theme::error(ui)) };
+                                ui.label(egui::RichText::new(text).color(color)).on_hover_text(&entry.message);
+                                let secs = entry.duration_secs as u64;
+                                ui.label(format!("{:02}:{:02}", secs / 60, secs % 60));
+                                ui.end_row();
+                            }
+                        });
+                    });
                 });
-            });
 
-            ui.add_space(10.0);
-
-            // 状态消息
-            if !self.status_message.is_empty() {
-                let color = if self.is_error {
-                    egui::Color32::RED
-                } else {
-                    egui::Color32::GREEN
-                };
-                ui.label(egui::RichText::new(&self.status_message).color(color));
-            }
+                // 操作日志
+                ui.collapsing(t!("ui.log"), |ui| {
+                    egui::ScrollArea::vertical()
+                        .id_salt("log")
+                        .max_height(150.0)
+                        .stick_to_bottom(true)
+                        .show(ui, |ui| {
+                            for entry in logging::recent() {
+                                let color = match entry.level {
+                                    tracing::Level::ERROR => theme::error(ui),
+                                    tracing::Level::WARN => theme::warning(ui),
+                                    _ => ui.visuals().weak_text_color(),
+                                };
+                                let text = format!("{} {}", entry.time, entry.message);
+                                ui.label(egui::RichText::new(text).monospace().color(color));
+                            }
+                        });
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("ui.copy_log")).on_hover_text(t!("ui.copy_log_hint")).clicked() {
+                            ui.ctx().copy_text(logging::recent_text());
+                            self.toasts.push(Severity::Success, t!("status.log_copied").to_string());
+                        }
+                        if ui.button(t!("ui.export_diagnostics")).on_hover_text(t!("ui.export_diagnostics_hint")).clicked() {
+                            let dialog = FileDialog::new()
+                                .set_file_name("bf6-voice-switcher-diagnostics.zip")
+                                .add_filter("zip", &["zip"]);
+                            if let Some(dest) = dialog.save_file() {
+                                let result = self.export_diagnostics(&dest);
+                                self.report(result);
+                            }
+                        }
+                        if ui.button(t!("ui.export_status")).on_hover_text(t!("ui.export_status_hint")).clicked() {
+                            let dialog = FileDialog::new()
+                                .set_file_name("bf6-voice-switcher-status.json")
+                                .add_filter("JSON", &["json"])
+                                .add_filter("Text", &["txt"]);
+                            if let Some(dest) = dialog.save_file() {
+                                let result = self.export_status(&dest);
+                                self.report(result);
+                            }
+                        }
+                    });
+                });
+            });
         });
+        self.handle_dropped_files(ctx);
+        self.toasts.show(ctx, bottom);
     }
 }
 
 fn main() -> eframe::Result<()> {
+    logging::init(&exe_dir());
+    crash::install(&exe_dir());
+    info!("BF6 Voice Switcher {} started", env!("CARGO_PKG_VERSION"));
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut launch_error = None;
+    if let Some(command) = Command::parse(&args) {
+        cli::attach_console();
+        let json = args.iter().any(|arg| arg == cli::ARG_JSON);
+        // 窗口已打开时交给窗口执行，否则在本进程中执行
+        let result = match ipc::forward(&command, |line| cli::progress(json, line)) {
+            Some(result) => result,
+            None => BF6VoiceSwitcher::default().run_command(&command).map_err(|e| e.to_string()),
+        };
+        match &result {
+            Ok(outcome) => info!("{}", outcome.message),
+            Err(e) => error!("{}", e),
+        }
+        match result {
+            // 启动器模式失败时打开窗口显示错误
+            Err(e) if !json && matches!(command, Command::Launch(_)) => launch_error = Some(e),
+            result => std::process::exit(cli::print(&command, &result, json)),
+        }
+    }
+
+    let settings = Settings::load(&exe_dir().join(settings::SETTINGS_FILE));
     let options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([620.0, 550.0])
-            .with_resizable(false),
+        viewport: window::restore(egui::ViewportBuilder::default(), settings.window.as_ref()),
         ..Default::default()
     };
     
     eframe::run_native(
-        "BF6 Voice Switcher",
+        WINDOW_TITLE,
         options,
-        Box::new(|cc| {
-            // 加载中文字体
-            let mut fonts = egui::FontDefinitions::default();
+        Box::new(move |cc| {
+            fonts::setup_fonts(&cc.egui_ctx);
             
-            if let Ok(font_data) = std::fs::read("C:\\Windows\\Fonts\\msyh.ttc") {
-                fonts.font_data.insert(
-                    "msyh".to_owned(),
-                    egui::FontData::from_owned(font_data).into(),
-                );
-                
-                fonts.families
-                    .entry(egui::FontFamily::Proportional)
-                    .or_default()
-                    .insert(0, "msyh".to_owned());
-                    
-                fonts.families
-                    .entry(egui::FontFamily::Monospace)
-                    .or_default()
-                    .insert(0, "msyh".to_owned());
+            let mut app = BF6VoiceSwitcher::default();
+            app.apply_launch_args(&args);
+            if let Some(message) = launch_error {
+                error!("{}", message);
+                app.toasts.push(Severity::Error, message);
             }
-            
-            cc.egui_ctx.set_fonts(fonts);
-            
-            Ok(Box::new(BF6VoiceSwitcher::default()))
+            app.ipc = Some(ipc::Server::spawn(cc.egui_ctx.clone()));
+            app.start_hotkeys(&cc.egui_ctx);
+            app.update_crash_state();
+            app.repair_task = schedule::is_registered();
+            app.health = Some(app.health_check());
+            // 系统 DPI 缩放由 eframe 处理，这里只应用额外的界面缩放
+            app.applied_ui_scale = app.settings.ui_scale();
+            cc.egui_ctx.set_zoom_factor(app.applied_ui_scale);
+            app.settings.theme.apply(&cc.egui_ctx);
+            update::remove_old(&std::env::current_exe().unwrap_or_default());
+            if app.settings.check_updates {
+                app.update_check = Some(update::UpdateCheck::spawn(cc.egui_ctx.clone()));
+            }
+            Ok(Box::new(app))
         }),
     )
 }
@@ -0,0 +1,96 @@
+//! 将游戏目录中的 Junction 转换为实际文件：复制链接目标到临时目录，删除链接后移到原位置
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::backup_info::BackupInfo;
+use crate::copy::{self, CopyOptions, Progress};
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+use crate::journal::{EntryKind, Journal};
+use crate::link::{self, RestoreMode};
+use crate::plan::{Action, Operation, Plan};
+use crate::retry;
+
+/// 转换过程中使用的临时目录后缀
+const TEMP_SUFFIX: &str = ".materialize";
+
+/// 转换所需的全部信息，在界面线程中收集后交给后台线程执行
+pub struct MaterializeJob {
+    /// 游戏目录中需要转换的 Junction
+    pub links: Vec<PathBuf>,
+    /// 恢复记录涉及这些文件夹的备份及其目录，转换后改为复制方式
+    pub backups: Vec<(PathBuf, BackupInfo)>,
+    pub workers: usize,
+    pub journal: Journal,
+}
+
+impl MaterializeJob {
+    /// 需要复制的总字节数
+    pub fn total_size(&self) -> u64 {
+        self.links
+            .iter()
+            .filter_map(|path| link::read_junction_target(path))
+            .map(|target| disk::dir_size(&target))
+            .sum()
+    }
+
+    /// 列出转换将执行的动作
+    pub fn plan(&self) -> Plan {
+        let mut plan = Plan::new(Operation::Materialize);
+        for path in &self.links {
+            let size = link::read_junction_target(path).map(|target| disk::dir_size(&target)).unwrap_or(0);
+            plan.push(Action::Unlink, path.clone(), 0);
+            plan.push(Action::Copy, path.clone(), size);
+        }
+        plan
+    }
+
+    pub fn run(mut self, progress: &Progress) -> Result<String, SwitcherError> {
+        for path in &self.links {
+            materialize(path, self.workers, progress)
+                .map_err(|e| SwitcherError::Materialize { path: path.clone(), source: Box::new(e) })?;
+            self.journal.record(EntryKind::Folder, path);
+        }
+
+        // 文件夹已是实际文件，恢复记录改为复制方式，删除游戏语音时仍按本工具恢复的文件夹处理
+        for (backup_path, info) in &mut self.backups {
+            if let Some(record) = &mut info.restore {
+                record.mode = RestoreMode::Copy;
+                let _ = info.save(backup_path);
+            }
+        }
+        Ok(t!("status.materialized", self.links.len()))
+    }
+}
+
+fn materialize(path: &Path, workers: usize, progress: &Progress) -> Result<(), SwitcherError> {
+    let target = link::read_junction_target(path).ok_or_else(|| SwitcherError::LinkTargetMissing(path.to_path_buf()))?;
+    if !target.exists() {
+        return Err(SwitcherError::LinkTargetMissing(path.to_path_buf()));
+    }
+
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(TEMP_SUFFIX);
+    let temp = PathBuf::from(temp);
+    if temp.exists() {
+        fs::remove_dir_all(&temp).map_err(SwitcherError::CleanStaging)?;
+    }
+    if let Err(e) = copy::copy_dir(&target, &temp, &CopyOptions::new(workers), progress) {
+        let _ = fs::remove_dir_all(&temp);
+        return Err(e.into());
+    }
+
+    link::remove_junction(path)?;
+    if let Err(e) = retry::retry("move", &[&temp], || fs::rename(&temp, path)) {
+        // 移动失败时恢复链接，保证游戏仍能加载语音
+        let _ = link::create_junction(&target, path);
+        let _ = fs::remove_dir_all(&temp);
+        return Err(e.into());
+    }
+    info!("materialized {} from {}", path.display(), target.display());
+    Ok(())
+}
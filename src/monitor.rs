@@ -0,0 +1,114 @@
+//! 定期检查恢复的语音：程序运行或驻留托盘时按设置的间隔检查游戏目录中的链接和 .toc 文件，
+//! Steam 更新、磁盘清理或系统优化工具悄悄移除链接后立即提醒，而不是等到启动游戏时才发现
+
+use std::path::PathBuf;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use eframe::egui;
+use tracing::{info, warn};
+
+use crate::hash;
+use crate::i18n::t;
+use crate::link::RestoreMode;
+use crate::relink;
+use crate::task::{self, Stop};
+
+/// 一个恢复到游戏目录的文件夹
+#[derive(Clone, Debug, PartialEq)]
+pub struct Folder {
+    /// 游戏目录中的完整路径
+    pub path: PathBuf,
+    pub mode: RestoreMode,
+    /// 备份中对应的文件夹，链接指向这里
+    pub backup: PathBuf,
+}
+
+/// 需要检查的文件夹和 .toc 文件
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Target {
+    pub folders: Vec<Folder>,
+    /// (游戏目录中 .toc 文件的完整路径, 备份时的哈希)
+    pub toc_files: Vec<(PathBuf, String)>,
+}
+
+/// 发现的问题
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// 链接或恢复的文件夹已被移除或替换
+    Removed(PathBuf),
+    /// 链接仍在，但指向的备份文件夹已不存在
+    TargetMissing(PathBuf),
+    /// .toc 文件缺失或与备份时不一致
+    TocChanged(PathBuf),
+}
+
+impl Problem {
+    pub fn message(&self) -> String {
+        match self {
+            Problem::Removed(path) => t!("monitor.removed", path.display()),
+            Problem::TargetMissing(path) => t!("monitor.target_missing", path.display()),
+            Problem::TocChanged(path) => t!("monitor.toc_changed", path.display()),
+        }
+    }
+}
+
+/// 检查一次，返回发现的所有问题
+pub fn check(target: &Target) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for folder in &target.folders {
+        if !relink::is_intact(&folder.path, folder.mode) {
+            problems.push(Problem::Removed(folder.path.clone()));
+        } else if matches!(folder.mode, RestoreMode::Junction | RestoreMode::Symlink) && !folder.backup.is_dir() {
+            problems.push(Problem::TargetMissing(folder.path.clone()));
+        }
+    }
+    for (path, expected) in &target.toc_files {
+        if !hash::file_hash(path).is_ok_and(|actual| actual == *expected) {
+            problems.push(Problem::TocChanged(path.clone()));
+        }
+    }
+    problems
+}
+
+/// 后台的检查线程，释放时停止
+pub struct Monitor {
+    target: Target,
+    interval: Duration,
+    _stop: Stop,
+    handle: JoinHandle<Option<Vec<Problem>>>,
+}
+
+impl Monitor {
+    /// 每隔 interval 检查一次，问题与 known 不同时（新出现问题或问题已消失）结束并唤醒界面线程
+    pub fn spawn(ctx: egui::Context, target: Target, interval: Duration, known: Vec<Problem>) -> Self {
+        let (stop, signal) = task::stop_signal();
+        let checked = target.clone();
+        info!("checking {} restored folders every {:?}", checked.folders.len(), interval);
+        let handle = thread::spawn(move || {
+            while signal.sleep(interval) {
+                let problems = check(&checked);
+                if problems != known {
+                    warn!("restored voice check found {} problems", problems.len());
+                    ctx.request_repaint();
+                    return Some(problems);
+                }
+            }
+            None
+        });
+        Monitor { target, interval, _stop: stop, handle }
+    }
+
+    /// 检查的目标和间隔，变化时需要重新开始检查
+    pub fn config(&self) -> (&Target, Duration) {
+        (&self.target, self.interval)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn join(self) -> Option<Vec<Problem>> {
+        self.handle.join().unwrap_or(None)
+    }
+}
@@ -0,0 +1,119 @@
+//! 操作预览：执行前列出将受影响的路径、计划的动作和大小，确认后再执行
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+use crate::link::RestoreMode;
+
+/// 可以预览的操作，也用于操作历史
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    Backup,
+    BackupAll,
+    Restore,
+    DeleteVoice,
+    DeleteOthers,
+    DeleteBackup,
+    Materialize,
+    Revert,
+}
+
+impl Operation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Operation::Backup => t!("ui.backup"),
+            Operation::BackupAll => t!("ui.backup_all"),
+            Operation::Restore => t!("ui.restore"),
+            Operation::DeleteVoice => t!("ui.delete_voice"),
+            Operation::DeleteOthers => t!("ui.delete_other_voices"),
+            Operation::DeleteBackup => t!("ui.delete_backup"),
+            Operation::Materialize => t!("ui.materialize_all"),
+            Operation::Revert => t!("ui.revert_all"),
+        }
+    }
+
+    /// 删除操作无论是否开启预览都需要确认
+    pub fn is_delete(&self) -> bool {
+        matches!(self, Operation::DeleteVoice | Operation::DeleteOthers | Operation::DeleteBackup | Operation::Revert)
+    }
+}
+
+/// 对单个路径计划执行的动作
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// 复制到目标位置
+    Copy,
+    /// 移动到目标位置
+    Move,
+    /// 按恢复方式创建链接（或复制）
+    Link(RestoreMode),
+    /// 删除 Junction 链接，不影响链接目标
+    Unlink,
+    /// 删除文件或文件夹
+    Delete,
+    /// 将文件或文件夹移到回收站
+    Recycle,
+    /// 解压压缩的备份
+    Extract,
+}
+
+impl Action {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Copy => t!("plan.copy"),
+            Action::Move => t!("plan.move"),
+            Action::Link(mode) => mode.label(),
+            Action::Unlink => t!("plan.unlink"),
+            Action::Delete => t!("plan.delete"),
+            Action::Recycle => t!("plan.recycle"),
+            Action::Extract => t!("plan.extract"),
+        }
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, Action::Move | Action::Delete)
+    }
+}
+
+pub struct PlanItem {
+    pub action: Action,
+    /// 受影响的路径（复制、移动和链接为目标路径）
+    pub path: PathBuf,
+    /// 涉及的数据量，链接和 Junction 为 0
+    pub size: u64,
+}
+
+/// 一次操作的全部计划动作
+pub struct Plan {
+    pub operation: Operation,
+    pub items: Vec<PlanItem>,
+    /// 显示在列表上方的补充说明，例如匹配到的文件夹名称模式
+    pub notes: Vec<String>,
+}
+
+impl Plan {
+    pub fn new(operation: Operation) -> Self {
+        Plan { operation, items: Vec::new(), notes: Vec::new() }
+    }
+
+    pub fn push(&mut self, action: Action, path: PathBuf, size: u64) {
+        self.items.push(PlanItem { action, path, size });
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.items.iter().map(|item| item.size).sum()
+    }
+
+    /// 按所在目录分组，用于按树形显示
+    pub fn grouped(&self) -> BTreeMap<&Path, Vec<&PlanItem>> {
+        let mut groups: BTreeMap<&Path, Vec<&PlanItem>> = BTreeMap::new();
+        for item in &self.items {
+            groups.entry(item.path.parent().unwrap_or(&item.path)).or_default().push(item);
+        }
+        groups
+    }
+}
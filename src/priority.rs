@@ -0,0 +1,66 @@
+//! 后台优先级：开启后复制线程进入 Windows 的后台模式，CPU、I/O 和内存优先级都降低，
+//! 复制让位于前台程序，代价是系统繁忙时复制明显变慢
+
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing::warn;
+use windows_sys::Win32::Foundation::{GetLastError, ERROR_THREAD_MODE_ALREADY_BACKGROUND};
+use windows_sys::Win32::System::Threading::{
+    GetCurrentThread, SetThreadInformation, SetThreadPriority, ThreadMemoryPriority, MEMORY_PRIORITY,
+    MEMORY_PRIORITY_INFORMATION, MEMORY_PRIORITY_LOW, MEMORY_PRIORITY_NORMAL, THREAD_MODE_BACKGROUND_BEGIN,
+    THREAD_MODE_BACKGROUND_END,
+};
+
+static BACKGROUND: AtomicBool = AtomicBool::new(false);
+
+/// 设置之后启动的工作线程是否使用后台优先级
+pub fn set_background(enabled: bool) {
+    BACKGROUND.store(enabled, Ordering::Relaxed);
+}
+
+/// 按设置让当前线程进入后台模式，在工作线程开始时调用；返回值释放时恢复，线程已处于后台模式时不做任何修改
+#[must_use]
+pub fn apply_to_current_thread() -> Background {
+    if !BACKGROUND.load(Ordering::Relaxed) {
+        return Background(false);
+    }
+    // SAFETY: GetCurrentThread 返回的伪句柄始终有效
+    unsafe {
+        if SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN) == 0 {
+            // 例如单线程复制时在已进入后台模式的任务线程中再次调用
+            if GetLastError() != ERROR_THREAD_MODE_ALREADY_BACKGROUND {
+                warn!("failed to enter background mode: {}", std::io::Error::last_os_error());
+            }
+            return Background(false);
+        }
+    }
+    // 复制的文件占用的缓存页优先被回收，不挤占前台程序的内存
+    set_memory_priority(MEMORY_PRIORITY_LOW);
+    Background(true)
+}
+
+fn set_memory_priority(priority: MEMORY_PRIORITY) {
+    let info = MEMORY_PRIORITY_INFORMATION { MemoryPriority: priority };
+    let size = std::mem::size_of::<MEMORY_PRIORITY_INFORMATION>() as u32;
+    // SAFETY: GetCurrentThread 返回的伪句柄始终有效，info 在调用期间有效
+    if unsafe { SetThreadInformation(GetCurrentThread(), ThreadMemoryPriority, &info as *const _ as *const c_void, size) } == 0 {
+        warn!("failed to set memory priority: {}", std::io::Error::last_os_error());
+    }
+}
+
+/// 当前线程由 apply_to_current_thread 进入的后台模式，释放时退出；线程结束时后台模式也随之结束
+pub struct Background(bool);
+
+impl Drop for Background {
+    fn drop(&mut self) {
+        if !self.0 {
+            return;
+        }
+        // SAFETY: GetCurrentThread 返回的伪句柄始终有效
+        if unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_END) } == 0 {
+            warn!("failed to leave background mode: {}", std::io::Error::last_os_error());
+        }
+        set_memory_priority(MEMORY_PRIORITY_NORMAL);
+    }
+}
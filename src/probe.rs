@@ -0,0 +1,119 @@
+//! 推断游戏实际会使用的语音：综合游戏目录中可用的语音文件夹和 .toc 文件、链接状态、Steam 启动选项和 user.cfg，
+//! 得出唯一的结论，并列出相互矛盾的地方
+
+use crate::i18n::t;
+
+/// 游戏选择语音语言的依据，按优先级排列
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// Steam 启动选项中的 +miles_language
+    LaunchOption,
+    /// 游戏目录 user.cfg 中的 miles_language
+    UserCfg,
+    /// 没有指定语音时使用 Steam 中选择的语言
+    SteamLanguage,
+    /// 无法得知选择的语言，游戏目录中只有这一种语音
+    OnlyInstalled,
+}
+
+impl Source {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Source::LaunchOption => t!("probe.source_launch_option"),
+            Source::UserCfg => t!("probe.source_user_cfg"),
+            Source::SteamLanguage => t!("probe.source_steam"),
+            Source::OnlyInstalled => t!("probe.source_only_installed"),
+        }
+    }
+}
+
+/// 推断时发现的问题，参数为语言代码
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue<'a> {
+    /// 将使用的语言在游戏目录中没有可用的语音文件夹
+    NotInstalled(&'a str),
+    /// 将使用的语言有链接目标丢失或文件夹缺失
+    Broken(&'a str),
+    /// 将使用的语言有语音文件夹但没有 .toc 文件
+    MissingToc(&'a str),
+    /// 启动选项和 user.cfg 指定了不同的语言
+    Conflict(&'a str, &'a str),
+    /// 启动选项或 user.cfg 中的值不是已知的语言
+    UnknownValue(&'a str),
+}
+
+impl Issue<'_> {
+    /// name 将语言代码转换为显示名称
+    pub fn message(&self, name: impl Fn(&str) -> String) -> String {
+        match self {
+            Issue::NotInstalled(code) => t!("probe.not_installed", name(code)),
+            Issue::Broken(code) => t!("probe.broken", name(code)),
+            Issue::MissingToc(code) => t!("probe.missing_toc", name(code)),
+            Issue::Conflict(launch, cfg) => t!("probe.conflict", name(launch), name(cfg)),
+            Issue::UnknownValue(value) => t!("probe.unknown_value", value),
+        }
+    }
+}
+
+/// 推断所需的信息，由界面线程根据当前状态收集
+#[derive(Default)]
+pub struct ProbeInput<'a> {
+    /// 语音文件夹可用的语言：原始文件夹、目标存在的链接或本工具恢复的文件夹
+    pub usable: Vec<&'a str>,
+    /// 有链接目标丢失或记录的文件夹缺失的语言
+    pub broken: Vec<&'a str>,
+    /// 有 .toc 文件的语言
+    pub with_toc: Vec<&'a str>,
+    /// 启动选项中的语音：Ok 为语言代码，Err 为无法识别的值
+    pub launch_option: Option<Result<&'a str, &'a str>>,
+    /// user.cfg 中的语音，格式同上
+    pub user_cfg: Option<Result<&'a str, &'a str>>,
+    /// Steam 中选择的语言对应的语言代码
+    pub steam_language: Option<&'a str>,
+}
+
+pub struct Probe<'a> {
+    /// 游戏将使用的语言代码，无法判断时为 None
+    pub lang: Option<&'a str>,
+    pub source: Option<Source>,
+    pub issues: Vec<Issue<'a>>,
+}
+
+/// 按启动选项、user.cfg、Steam 语言的顺序确定游戏会请求的语音，再检查该语音的文件是否可用
+pub fn evaluate<'a>(input: &ProbeInput<'a>) -> Probe<'a> {
+    let mut issues = Vec::new();
+    for value in [input.launch_option, input.user_cfg].into_iter().flatten() {
+        if let Err(value) = value {
+            issues.push(Issue::UnknownValue(value));
+        }
+    }
+    let launch = input.launch_option.and_then(Result::ok);
+    let cfg = input.user_cfg.and_then(Result::ok);
+    if let (Some(launch), Some(cfg)) = (launch, cfg) {
+        if launch != cfg {
+            issues.push(Issue::Conflict(launch, cfg));
+        }
+    }
+
+    let requested = launch
+        .map(|code| (code, Source::LaunchOption))
+        .or(cfg.map(|code| (code, Source::UserCfg)))
+        .or(input.steam_language.map(|code| (code, Source::SteamLanguage)))
+        .or(match input.usable.as_slice() {
+            [only] => Some((*only, Source::OnlyInstalled)),
+            _ => None,
+        });
+    let Some((lang, source)) = requested else {
+        return Probe { lang: None, source: None, issues };
+    };
+
+    if !input.usable.contains(&lang) {
+        issues.push(Issue::NotInstalled(lang));
+    } else if !input.with_toc.contains(&lang) {
+        issues.push(Issue::MissingToc(lang));
+    }
+    if input.broken.contains(&lang) {
+        issues.push(Issue::Broken(lang));
+    }
+    Probe { lang: Some(lang), source: Some(source), issues }
+}
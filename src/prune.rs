@@ -0,0 +1,118 @@
+//! 备份清理：只保留最近几个游戏版本的备份，或把备份总大小控制在上限以内；正在使用的备份不会被删除
+
+use std::cmp::Reverse;
+
+use crate::backup_info::BackupInfo;
+
+/// 清理规则，0 表示不限制
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Retention {
+    /// 保留最近几个游戏版本的备份
+    pub keep_builds: usize,
+    /// 所有备份的总大小上限（字节）
+    pub max_bytes: u64,
+}
+
+impl Retention {
+    pub fn is_enabled(&self) -> bool {
+        self.keep_builds > 0 || self.max_bytes > 0
+    }
+}
+
+/// 参与清理的备份
+pub struct Candidate<'a> {
+    pub info: &'a BackupInfo,
+    pub size: u64,
+    /// 游戏目录中有链接指向该备份
+    pub in_use: bool,
+}
+
+/// Steam 版本号为数字，按数值比较；无法解析的版本视为最旧
+fn build_key(build_id: &str) -> (u64, &str) {
+    (build_id.parse().unwrap_or(0), build_id)
+}
+
+/// 按规则选出需要删除的备份的文件夹名称
+///
+/// 先删除不在最近 keep_builds 个版本中的备份，总大小仍超过上限时再按版本从旧到新删除，
+/// 当前游戏版本的备份和正在使用的备份始终保留。
+pub fn select(candidates: &[Candidate], retention: Retention, current_build: &str) -> Vec<String> {
+    let keep = |c: &Candidate| c.in_use || (!current_build.is_empty() && c.info.build_id == current_build);
+    let mut order: Vec<&Candidate> = candidates.iter().collect();
+    order.sort_by_key(|c| (build_key(&c.info.build_id), c.info.created.clone()));
+
+    let mut builds: Vec<&str> = candidates.iter().map(|c| c.info.build_id.as_str()).collect();
+    builds.sort_by_key(|b| Reverse(build_key(b)));
+    builds.dedup();
+    let recent = if retention.keep_builds > 0 { &builds[..retention.keep_builds.min(builds.len())] } else { &builds[..] };
+
+    let mut selected = Vec::new();
+    let mut total: u64 = candidates.iter().map(|c| c.size).sum();
+    for candidate in &order {
+        if !keep(candidate) && !recent.contains(&candidate.info.build_id.as_str()) {
+            selected.push(candidate.info.dir_name().to_string());
+            total = total.saturating_sub(candidate.size);
+        }
+    }
+    if retention.max_bytes > 0 {
+        for candidate in &order {
+            if total <= retention.max_bytes {
+                break;
+            }
+            if !keep(candidate) && !selected.iter().any(|name| name == candidate.info.dir_name()) {
+                selected.push(candidate.info.dir_name().to_string());
+                total = total.saturating_sub(candidate.size);
+            }
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(dir: &str, lang_code: &str, build_id: &str) -> BackupInfo {
+        BackupInfo { lang_code: lang_code.to_string(), build_id: build_id.to_string(), dir: dir.to_string(), ..Default::default() }
+    }
+
+    fn candidates<'a>(infos: &'a [BackupInfo], size: u64, in_use: &[&str]) -> Vec<Candidate<'a>> {
+        infos.iter().map(|info| Candidate { info, size, in_use: in_use.contains(&info.dir_name()) }).collect()
+    }
+
+    #[test]
+    fn keep_builds_counts_builds_not_languages() {
+        let infos = [info("en", "en", "300"), info("de", "de", "300"), info("en-200", "en", "200"), info("fr", "fr", "200")];
+        let retention = Retention { keep_builds: 1, max_bytes: 0 };
+        assert_eq!(select(&candidates(&infos, 10, &[]), retention, ""), ["en-200", "fr"]);
+        let retention = Retention { keep_builds: 2, max_bytes: 0 };
+        assert!(select(&candidates(&infos, 10, &[]), retention, "").is_empty());
+    }
+
+    #[test]
+    fn max_bytes_removes_oldest_first() {
+        let infos = [info("c", "en", "300"), info("a", "en", "100"), info("b", "en", "200")];
+        let retention = Retention { keep_builds: 0, max_bytes: 15 };
+        assert_eq!(select(&candidates(&infos, 10, &[]), retention, ""), ["a", "b"]);
+        let retention = Retention { keep_builds: 0, max_bytes: 30 };
+        assert!(select(&candidates(&infos, 10, &[]), retention, "").is_empty());
+    }
+
+    #[test]
+    fn current_build_and_in_use_are_kept() {
+        let infos = [info("a", "en", "100"), info("b", "en", "200"), info("c", "en", "300"), info("d", "en", "250")];
+        let retention = Retention { keep_builds: 1, max_bytes: 1 };
+        let selected = select(&candidates(&infos, 10, &["b"]), retention, "100");
+        assert_eq!(selected, ["d", "c"]);
+    }
+
+    #[test]
+    fn unparsable_builds_are_oldest() {
+        let infos = [info("new", "en", "500"), info("abc", "en", "abc"), info("empty", "en", "")];
+        let retention = Retention { keep_builds: 1, max_bytes: 0 };
+        // 当前版本未知时，版本号为空的备份不算当前版本
+        assert_eq!(select(&candidates(&infos, 10, &[]), retention, ""), ["empty", "abc"]);
+        let retention = Retention { keep_builds: 0, max_bytes: 15 };
+        assert_eq!(select(&candidates(&infos, 10, &[]), retention, ""), ["empty", "abc"]);
+    }
+}
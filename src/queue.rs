@@ -0,0 +1,114 @@
+//! 操作队列：备份、恢复和删除请求依次排队，由后台任务逐个执行，界面中显示每一项的状态
+
+use std::time::Instant;
+
+use crate::i18n::t;
+use crate::plan::Operation;
+
+/// 队列中一项的状态
+pub enum ItemStatus {
+    Pending,
+    Running,
+    Done(String),
+    Failed(String),
+    Cancelled,
+}
+
+impl ItemStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemStatus::Pending => t!("ui.queue_pending"),
+            ItemStatus::Running => t!("ui.queue_running"),
+            ItemStatus::Done(_) => t!("ui.queue_done"),
+            ItemStatus::Failed(_) => t!("ui.queue_failed"),
+            ItemStatus::Cancelled => t!("ui.queue_cancelled"),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, ItemStatus::Done(_) | ItemStatus::Failed(_) | ItemStatus::Cancelled)
+    }
+}
+
+/// 加入队列时的操作和当时所选的语言、备份，开始执行时再按这些选择准备任务
+pub struct QueueItem {
+    pub id: u64,
+    pub operation: Operation,
+    pub lang_code: &'static str,
+    pub backup_code: Option<String>,
+    /// 显示用的说明，例如语言名称
+    pub target: String,
+    pub status: ItemStatus,
+    /// 开始执行的时间，用于记录用时
+    pub started: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct Queue {
+    items: Vec<QueueItem>,
+    next_id: u64,
+}
+
+impl Queue {
+    /// 加入队列，返回前面还未完成的项目数
+    pub fn push(&mut self, operation: Operation, lang_code: &'static str, backup_code: Option<String>, target: String) -> usize {
+        let ahead = self.items.iter().filter(|item| !item.status.is_finished()).count();
+        self.next_id += 1;
+        self.items.push(QueueItem { id: self.next_id, operation, lang_code, backup_code, target, status: ItemStatus::Pending, started: None });
+        ahead
+    }
+
+    pub fn items(&self) -> &[QueueItem] {
+        &self.items
+    }
+
+    pub fn has_pending(&self) -> bool {
+        self.items.iter().any(|item| matches!(item.status, ItemStatus::Pending))
+    }
+
+    pub fn get(&self, id: u64) -> Option<&QueueItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    /// 下一个等待执行的项目
+    pub fn next_pending(&mut self) -> Option<&mut QueueItem> {
+        self.items.iter_mut().find(|item| matches!(item.status, ItemStatus::Pending))
+    }
+
+    /// 以后台任务的结果结束正在执行的项目，返回该项目的 id
+    pub fn finish_running(&mut self, result: Result<&str, String>) -> Option<u64> {
+        let item = self.items.iter_mut().find(|item| matches!(item.status, ItemStatus::Running))?;
+        item.status = match result {
+            Ok(message) => ItemStatus::Done(message.to_string()),
+            Err(e) => ItemStatus::Failed(e),
+        };
+        Some(item.id)
+    }
+
+    /// 失败的项目按加入时的选择重新等待执行，返回是否可以重试
+    pub fn retry(&mut self, id: u64) -> bool {
+        match self.items.iter_mut().find(|item| item.id == id) {
+            Some(item) if matches!(item.status, ItemStatus::Failed(_)) => {
+                item.status = ItemStatus::Pending;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 取消等待中的项目，返回是否已取消；正在执行的项目由调用方取消后台任务
+    pub fn cancel(&mut self, id: u64) -> bool {
+        match self.items.iter_mut().find(|item| item.id == id) {
+            Some(item) if matches!(item.status, ItemStatus::Pending) => {
+                item.status = ItemStatus::Cancelled;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 移除已完成、失败和已取消的项目
+    pub fn clear_finished(&mut self) {
+        self.items.retain(|item| !item.status.is_finished());
+    }
+}
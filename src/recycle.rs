@@ -0,0 +1,87 @@
+//! 删除文件或文件夹：默认移到回收站，设置中可改为永久删除；
+//! 回收站使用的 SHFileOperationW 不支持超过 MAX_PATH 的路径，这样的路径改为永久删除并告知用户
+
+use std::fs;
+use std::io;
+use std::iter;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+use windows_sys::Win32::UI::Shell::{SHFileOperationW, FOF_ALLOWUNDO, FOF_NO_UI, FO_DELETE, SHFILEOPSTRUCTW};
+
+use crate::i18n::t;
+use crate::link;
+use crate::retry;
+
+/// SHFileOperationW 能处理的最长路径（UTF-16 单元，不含结尾的 0）
+const MAX_PATH: usize = 260;
+
+/// 删除的方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Removed {
+    /// 按设置移到回收站或永久删除
+    AsRequested,
+    /// 路径过长，无法移到回收站，已永久删除
+    Permanently,
+}
+
+/// 删除 path，permanent 为 false 时移到回收站；文件被短暂占用时重试
+pub fn remove(path: &Path, permanent: bool) -> io::Result<Removed> {
+    // SHFileOperationW 要求绝对路径
+    let path = std::path::absolute(path)?;
+    if permanent {
+        retry::retry("remove", &[&path], || remove_permanently(&path))?;
+        return Ok(Removed::AsRequested);
+    }
+    if too_long(&path) {
+        warn!("{} exceeds MAX_PATH, deleting permanently instead of recycling", path.display());
+        retry::retry("remove", &[&path], || remove_permanently(&path))?;
+        return Ok(Removed::Permanently);
+    }
+    retry::retry("remove", &[&path], || recycle_once(&path))?;
+    Ok(Removed::AsRequested)
+}
+
+/// 未能移到回收站、已永久删除的路径的提示，没有这样的路径时为 None
+pub fn note(paths: &[PathBuf]) -> Option<String> {
+    let paths: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+    (!paths.is_empty()).then(|| t!("status.recycle_too_long", paths.len(), paths.join("\n")))
+}
+
+fn remove_permanently(path: &Path) -> io::Result<()> {
+    if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) }
+}
+
+/// path 或其中的某个文件（不进入 Junction）的路径是否超过 MAX_PATH
+fn too_long(path: &Path) -> bool {
+    if path.as_os_str().encode_wide().count() >= MAX_PATH {
+        return true;
+    }
+    let is_dir = fs::symlink_metadata(path).is_ok_and(|m| m.is_dir() && !link::is_junction(path));
+    is_dir && fs::read_dir(path).is_ok_and(|entries| entries.flatten().any(|entry| too_long(&entry.path())))
+}
+
+fn recycle_once(path: &Path) -> io::Result<()> {
+    // SHFileOperationW 要求以两个 0 结尾
+    let from: Vec<u16> = path.as_os_str().encode_wide().chain(iter::repeat_n(0, 2)).collect();
+    let mut op = SHFILEOPSTRUCTW {
+        wFunc: FO_DELETE,
+        pFrom: from.as_ptr(),
+        fFlags: (FOF_ALLOWUNDO | FOF_NO_UI) as u16,
+        ..Default::default()
+    };
+    // SAFETY: op 中的字符串在调用期间有效，其余字段为空
+    let result = unsafe { SHFileOperationW(&mut op) };
+    // 0x71 以下的返回值为 Win32 错误码，保留以便判断是否可以重试
+    if result != 0 && result < 0x71 {
+        return Err(io::Error::from_raw_os_error(result));
+    }
+    if result != 0 {
+        return Err(io::Error::other(format!("SHFileOperationW failed: 0x{:x}", result)));
+    }
+    if op.fAnyOperationsAborted != 0 {
+        return Err(io::Error::new(io::ErrorKind::Interrupted, "recycle aborted"));
+    }
+    Ok(())
+}
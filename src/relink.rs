@@ -0,0 +1,137 @@
+//! 更新后重新链接：托盘模式下在后台检查游戏版本和恢复的文件夹，Steam 更新或验证后提醒重新应用上次的方案；
+//! 计划任务以静默模式运行时直接重新创建被移除的文件夹
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use eframe::egui;
+use tracing::{info, warn};
+
+use crate::backup_info::BackupInfo;
+use crate::copy::Progress;
+use crate::link::{self, RestoreMode};
+use crate::settings::VersionPolicy;
+use crate::steam_config;
+use crate::task::{self, Stop};
+
+/// 两次检查之间的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 需要监视的安装和恢复的文件夹
+#[derive(Clone, PartialEq)]
+pub struct Target {
+    pub manifest_path: PathBuf,
+    pub build_id: String,
+    /// 恢复到游戏目录的文件夹及其恢复方式
+    pub folders: Vec<(PathBuf, RestoreMode)>,
+}
+
+/// 检测到的变化，在更新完成后报告
+pub struct Change {
+    pub build_id: String,
+    /// 被移除或替换的文件夹数
+    pub removed: usize,
+}
+
+/// 恢复的文件夹是否仍是本工具创建的链接或副本
+pub fn is_intact(path: &Path, mode: RestoreMode) -> bool {
+    match mode {
+        RestoreMode::Junction => link::is_junction(path),
+        RestoreMode::Symlink => path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink()),
+        RestoreMode::Hardlink | RestoreMode::Copy => path.exists(),
+    }
+}
+
+/// 后台的监视线程，释放时停止
+pub struct Watcher {
+    target: Target,
+    _stop: Stop,
+    handle: JoinHandle<Option<Change>>,
+}
+
+impl Watcher {
+    /// 开始监视，只检查当前仍然完好的文件夹；版本变化或文件夹被移除且更新结束后唤醒界面线程
+    pub fn spawn(ctx: egui::Context, target: Target) -> Self {
+        let (stop, signal) = task::stop_signal();
+        let watched = Target {
+            folders: target.folders.iter().filter(|(path, mode)| is_intact(path, *mode)).cloned().collect(),
+            ..target.clone()
+        };
+        info!("watching {} restored folders for game updates", watched.folders.len());
+        let handle = thread::spawn(move || {
+            while signal.sleep(POLL_INTERVAL) {
+                // 更新或验证仍在进行时继续等待，结束后再报告
+                let Some(manifest) = steam_config::parse_app_manifest(&watched.manifest_path) else {
+                    continue;
+                };
+                if manifest.update_pending {
+                    continue;
+                }
+                let removed = watched.folders.iter().filter(|(path, mode)| !is_intact(path, *mode)).count();
+                if manifest.build_id != watched.build_id || removed > 0 {
+                    info!("game updated to build {}, {} restored folders removed", manifest.build_id, removed);
+                    ctx.request_repaint();
+                    return Some(Change { build_id: manifest.build_id, removed });
+                }
+            }
+            None
+        });
+        Watcher { target, _stop: stop, handle }
+    }
+
+    /// 监视的目标，目标变化时需要重新开始监视
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn join(self) -> Option<Change> {
+        self.handle.join().unwrap_or(None)
+    }
+}
+
+/// 重新创建被移除的恢复文件夹，返回修复的文件夹
+///
+/// 只处理目标位置已不存在的文件夹，不会覆盖游戏重新下载的文件；压缩的备份和版本策略不允许的备份跳过
+pub fn repair(backups: &[(PathBuf, BackupInfo)], build_id: &str, policy: VersionPolicy, workers: usize) -> Vec<PathBuf> {
+    let progress = Progress::default();
+    let mut repaired = Vec::new();
+    for (backup_path, info) in backups {
+        let Some(record) = &info.restore else {
+            continue;
+        };
+        if info.compressed {
+            warn!("skipping compressed backup {}", backup_path.display());
+            continue;
+        }
+        if !info.build_id.is_empty() && !build_id.is_empty() && info.build_id != build_id {
+            if !policy.allows(info, &record.target, false) {
+                warn!("skipping backup {} from build {} (game build {})", backup_path.display(), info.build_id, build_id);
+                continue;
+            }
+            warn!("repairing backup {} from build {} (game build {}) as allowed by {:?}", backup_path.display(), info.build_id, build_id, policy);
+        }
+        for rel_path in &record.folders {
+            let dst = record.target.join(rel_path);
+            if dst.symlink_metadata().is_ok() {
+                continue;
+            }
+            if let Some(parent) = dst.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            match link::restore_folder(record.mode, &backup_path.join(rel_path), &dst, workers, &progress) {
+                Ok(()) => {
+                    info!("repaired {} ({})", dst.display(), record.mode.label());
+                    repaired.push(dst);
+                }
+                Err(e) => warn!("failed to repair {}: {}", dst.display(), e),
+            }
+        }
+    }
+    repaired
+}
@@ -0,0 +1,99 @@
+//! 移动备份目录：把整个备份目录移到新位置，并把游戏目录中指向旧位置的 Junction 和符号链接改为指向新位置
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::copy::{self, CopyOptions, Progress};
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+use crate::link;
+use crate::retry;
+
+/// 移动所需的全部信息，在界面线程中收集后交给后台线程执行
+pub struct RelocateJob {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// 游戏目录中指向旧备份目录的 Junction 和符号链接
+    pub links: Vec<PathBuf>,
+    pub workers: usize,
+}
+
+/// 链接当前指向的位置
+fn link_target(path: &Path) -> Option<PathBuf> {
+    link::read_junction_target(path).or_else(|| fs::read_link(path).ok())
+}
+
+/// target 位于 from 中时，返回 to 中对应的位置
+fn rebase(target: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+    if !link::points_into(target, from) {
+        return None;
+    }
+    let rel = target.to_string_lossy()[from.to_string_lossy().trim_end_matches('\\').len()..].trim_start_matches('\\').to_string();
+    Some(if rel.is_empty() { to.to_path_buf() } else { to.join(rel) })
+}
+
+impl RelocateJob {
+    /// 新位置必须不存在或为空文件夹，且不能位于旧目录中
+    pub fn check(&self) -> Result<(), SwitcherError> {
+        if link::points_into(&self.to, &self.from) || link::points_into(&self.from, &self.to) {
+            return Err(SwitcherError::RelocateInside(self.to.clone()));
+        }
+        if fs::read_dir(&self.to).is_ok_and(|mut entries| entries.next().is_some()) {
+            return Err(SwitcherError::RelocateNotEmpty(self.to.clone()));
+        }
+        Ok(())
+    }
+
+    /// 跨分区时需要复制的字节数
+    pub fn total_size(&self) -> u64 {
+        if disk::same_volume(&self.from, self.to.parent().unwrap_or(&self.to)) {
+            0
+        } else {
+            disk::dir_size(&self.from)
+        }
+    }
+
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        self.check()?;
+        if let Some(parent) = self.to.parent() {
+            fs::create_dir_all(parent).map_err(SwitcherError::CreateDir)?;
+        }
+        // 空的目标文件夹先删除，才能直接重命名
+        if self.to.exists() {
+            fs::remove_dir(&self.to)?;
+        }
+        let renamed = retry::retry("move", &[&self.from], || fs::rename(&self.from, &self.to)).is_ok();
+        if !renamed {
+            // 跨分区：先复制，链接全部改为新位置后再删除旧目录
+            if let Err(e) = copy::copy_dir(&self.from, &self.to, &CopyOptions::new(self.workers), progress) {
+                let _ = fs::remove_dir_all(&self.to);
+                return Err(e.into());
+            }
+        }
+
+        let mut relinked = 0;
+        for path in &self.links {
+            let Some(new_target) = link_target(path).and_then(|target| rebase(&target, &self.from, &self.to)) else {
+                continue;
+            };
+            if link::is_junction(path) {
+                link::remove_junction(path)?;
+                link::create_junction(&new_target, path)?;
+            } else {
+                retry::retry("remove", &[path], || fs::remove_dir(path))?;
+                std::os::windows::fs::symlink_dir(&new_target, path)?;
+            }
+            info!("relinked {} to {}", path.display(), new_target.display());
+            relinked += 1;
+        }
+
+        if !renamed {
+            retry::retry("remove", &[&self.from], || fs::remove_dir_all(&self.from))?;
+        }
+        info!("moved backups from {} to {}", self.from.display(), self.to.display());
+        Ok(t!("status.backups_moved", self.to.display(), relinked))
+    }
+}
@@ -0,0 +1,91 @@
+//! 状态报告：检测到的安装、备份、恢复的链接和启动选项，可导出为 JSON 或文本，用于反馈问题和脚本
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct InstallStatus {
+    pub platform: String,
+    pub game_path: PathBuf,
+    pub build_id: String,
+    pub update_pending: bool,
+    /// 当前使用的安装
+    pub selected: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BackupStatus {
+    pub lang_code: String,
+    pub build_id: String,
+    pub size: u64,
+    pub compressed: bool,
+    /// 有恢复到游戏目录的记录
+    pub restored: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LinkStatus {
+    pub path: PathBuf,
+    pub mode: String,
+    /// Junction 或符号链接指向的位置
+    pub target: Option<PathBuf>,
+    /// 仍是本工具创建的链接或副本
+    pub intact: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StatusReport {
+    pub tool_version: String,
+    pub game: String,
+    pub installs: Vec<InstallStatus>,
+    pub voice_folder: String,
+    pub backup_dir: PathBuf,
+    pub backups: Vec<BackupStatus>,
+    pub links: Vec<LinkStatus>,
+    /// Steam 中设置的启动选项，未读取到时为 None
+    pub launch_options: Option<String>,
+    /// 启动选项中语音参数的值
+    pub launch_language: Option<String>,
+}
+
+impl StatusReport {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "version: {}", self.tool_version);
+        let _ = writeln!(text, "game: {}", self.game);
+        for install in &self.installs {
+            let _ = writeln!(
+                text,
+                "install: {}{} {} (build {}{})",
+                if install.selected { "* " } else { "" },
+                install.platform,
+                install.game_path.display(),
+                install.build_id,
+                if install.update_pending { ", update pending" } else { "" },
+            );
+        }
+        let _ = writeln!(text, "voice_folder: {}", self.voice_folder);
+        let _ = writeln!(text, "backup_dir: {}", self.backup_dir.display());
+        for backup in &self.backups {
+            let _ = writeln!(
+                text,
+                "backup: {} (build {}, {} bytes{}{})",
+                backup.lang_code,
+                backup.build_id,
+                backup.size,
+                if backup.compressed { ", compressed" } else { "" },
+                if backup.restored { ", restored" } else { "" },
+            );
+        }
+        for link in &self.links {
+            let target = link.target.as_ref().map(|t| t.display().to_string()).unwrap_or_else(|| "-".to_string());
+            let state = if link.intact { "ok" } else { "missing" };
+            let _ = writeln!(text, "link: {} -> {} ({}, {})", link.path.display(), target, link.mode, state);
+        }
+        let _ = writeln!(text, "launch_options: {}", self.launch_options.as_deref().unwrap_or("-"));
+        let _ = write!(text, "launch_language: {}", self.launch_language.as_deref().unwrap_or("-"));
+        text
+    }
+}
@@ -0,0 +1,174 @@
+//! 恢复任务：按所选方式把备份中的语音文件夹恢复到游戏目录
+
+use std::fs;
+use std::path::PathBuf;
+
+use tracing::{info, warn};
+
+use crate::archive;
+use crate::backup_info::{BackupInfo, RestoreRecord};
+use crate::copy::{self, Progress};
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::i18n::t;
+use crate::journal::{EntryKind, Journal};
+use crate::link::{self, RestoreMode};
+use crate::plan::{Action, Operation, Plan};
+use crate::retry;
+use crate::usage;
+use crate::verify;
+
+/// 恢复所需的全部信息，在界面线程中收集并检查后交给后台线程执行
+pub struct RestoreJob {
+    pub backup_info: BackupInfo,
+    pub backup_path: PathBuf,
+    pub target: PathBuf,
+    pub mode: RestoreMode,
+    /// 所选方式不被分区支持而改为复制时的说明
+    pub fallback: Option<String>,
+    /// 用户确认仍要恢复的版本不匹配：(备份版本, 当前版本)
+    pub version_override: Option<(String, String)>,
+    pub voice_folders: Vec<PathBuf>,
+    pub toc_files: Vec<PathBuf>,
+    /// 需要先删除的本工具恢复的普通文件夹（Junction 总是会被替换）
+    pub replace_folders: Vec<PathBuf>,
+    pub lang_name: String,
+    pub miles_lang: String,
+    /// 并行复制的线程数
+    pub workers: usize,
+    pub journal: Journal,
+    /// 当前游戏的备份目录，用于报告备份占用的空间
+    pub backup_dir: PathBuf,
+}
+
+impl RestoreJob {
+    /// 列出恢复将执行的动作
+    pub fn plan(&self) -> Plan {
+        let mut plan = Plan::new(Operation::Restore);
+        plan.notes.extend(self.fallback.clone());
+        if self.backup_info.compressed {
+            plan.push(Action::Extract, archive::archive_path(&self.backup_path), archive::unpacked_size(&self.backup_path));
+        }
+        for rel_path in &self.voice_folders {
+            let dst_folder = self.target.join(rel_path);
+            if link::is_junction(&dst_folder) {
+                plan.push(Action::Unlink, dst_folder.clone(), 0);
+            } else if self.replace_folders.contains(rel_path) {
+                plan.push(Action::Delete, dst_folder.clone(), disk::dir_size(&dst_folder));
+            }
+            let size = if self.mode == RestoreMode::Copy { disk::dir_size(&self.backup_path.join(rel_path)) } else { 0 };
+            plan.push(Action::Link(self.mode), dst_folder, size);
+        }
+        for rel_path in &self.toc_files {
+            plan.push(Action::Copy, self.target.join(rel_path), disk::dir_size(&self.backup_path.join(rel_path)));
+        }
+        plan
+    }
+
+    pub fn run(mut self, progress: &Progress) -> Result<String, SwitcherError> {
+        let target = &self.target;
+        let mode = self.mode;
+
+        // 压缩的备份先解压回备份目录
+        if self.backup_info.compressed {
+            archive::decompress(&self.backup_path, &mut self.backup_info, progress)?;
+        }
+        // 创建链接前确认备份完整，避免游戏加载缺失或损坏的语音
+        verify::check_before_restore(&self.backup_path, &self.backup_info)?;
+        if let Some((backup, current)) = &self.version_override {
+            warn!("restoring {} despite version mismatch: backup {} != current {}", self.backup_path.display(), backup, current);
+            self.journal.record_version_override(&self.backup_path, backup, current);
+        }
+
+        // 按所选方式恢复文件夹
+        let mut restored_paths = Vec::new();
+        let result: Result<(), SwitcherError> = self.voice_folders.iter().try_for_each(|rel_path| {
+            let src_folder = self.backup_path.join(rel_path);
+            let dst_parent = target.join(rel_path.parent().unwrap_or(rel_path));
+            let dst_folder = target.join(rel_path);
+
+            // 先删除目标（仅限之前由本工具创建的链接或副本）
+            if link::is_junction(&dst_folder) {
+                let _ = link::remove_junction(&dst_folder);
+                self.journal.record(EntryKind::Removed, &dst_folder);
+            } else if self.replace_folders.contains(rel_path) {
+                let _ = retry::retry("remove", &[&dst_folder], || fs::remove_dir_all(&dst_folder));
+                self.journal.record(EntryKind::Removed, &dst_folder);
+            }
+
+            // 创建目标父目录
+            fs::create_dir_all(&dst_parent).map_err(SwitcherError::CreateDir)?;
+
+            link::restore_folder(mode, &src_folder, &dst_folder, self.workers, progress).map_err(|e| SwitcherError::RestoreItem {
+                path: rel_path.clone(),
+                mode,
+                source: Box::new(e),
+            })?;
+            self.journal.record(EntryKind::for_mode(mode), &dst_folder);
+            info!("restored {} ({})", dst_folder.display(), mode.label());
+            restored_paths.push(rel_path.clone());
+            Ok(())
+        });
+
+        // 记录恢复方式，删除游戏语音时据此撤销
+        if !restored_paths.is_empty() {
+            self.backup_info.restore = Some(RestoreRecord {
+                mode,
+                target: target.clone(),
+                folders: restored_paths,
+            });
+            let _ = self.backup_info.save(&self.backup_path);
+        }
+
+        // 复制 .toc 文件
+        result?;
+        for rel_path in &self.toc_files {
+            let dst_file = target.join(rel_path);
+            if let Some(parent) = dst_file.parent() {
+                fs::create_dir_all(parent).map_err(SwitcherError::CreateDir)?;
+            }
+            // 只记录新增的 .toc 文件，覆盖游戏原有的文件不在撤销范围内
+            let existed = dst_file.exists();
+            copy::copy_file(&self.backup_path.join(rel_path), &dst_file, progress)
+                .map_err(|e| SwitcherError::RestoreToc { path: rel_path.clone(), source: e })?;
+            if !existed {
+                self.journal.record(EntryKind::Toc, &dst_file);
+            }
+        }
+
+        // 确认链接可以打开、文件与备份一致，而不是只依赖创建链接成功
+        let mismatched = verify::check_restored(&self.backup_path, target, &self.backup_info, &self.voice_folders, &self.toc_files);
+        if !mismatched.is_empty() {
+            return Err(SwitcherError::RestoreUnverified(mismatched));
+        }
+        info!("verified {} restored folders and {} .toc files", self.voice_folders.len(), self.toc_files.len());
+
+        let mut message = t!("status.restore_summary",
+            self.lang_name, self.voice_folders.len(), mode.label(), self.toc_files.len(), self.miles_lang);
+        message.push('\n');
+        message.push_str(t!("status.restore_verified"));
+        if let Some((backup, current)) = &self.version_override {
+            message.push('\n');
+            message.push_str(&t!("status.restore_version_override", backup, current));
+        }
+        if let Some(fallback) = &self.fallback {
+            message.push('\n');
+            message.push_str(fallback);
+        }
+        // 链接方式不在游戏目录中占用空间，复制方式则游戏目录和备份各有一份
+        let folder_size: u64 = self.voice_folders.iter().map(|rel_path| disk::dir_size(&self.backup_path.join(rel_path))).sum();
+        let footprint = disk::format_size(usage::footprint(&self.backup_dir));
+        message.push('\n');
+        message.push_str(&if mode == RestoreMode::Copy {
+            t!("status.space_copied", disk::format_size(folder_size), footprint)
+        } else {
+            t!("status.space_saved", disk::format_size(folder_size), footprint)
+        });
+        // 从网络共享复制较慢，显示平均传输速度
+        if mode == RestoreMode::Copy && disk::is_network(&self.backup_path) {
+            message.push('\n');
+            message.push_str(&t!("status.transfer_speed", disk::format_size(progress.snapshot().throughput() as u64)));
+        }
+        Ok(message)
+    }
+}
@@ -0,0 +1,60 @@
+//! 文件操作重试：杀毒软件和 Windows 搜索会短暂占用刚写入的文件，拒绝访问或共享冲突时按指数退避重试，
+//! 重试用尽后在错误信息中列出占用文件的进程
+
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use tracing::warn;
+
+use crate::i18n::t;
+use crate::locks;
+
+/// 最多尝试的次数
+const ATTEMPTS: u32 = 5;
+/// 第一次重试前的等待时间，之后每次加倍
+const INITIAL_DELAY: Duration = Duration::from_millis(100);
+
+const ERROR_ACCESS_DENIED: i32 = 5;
+const ERROR_SHARING_VIOLATION: i32 = 32;
+const ERROR_LOCK_VIOLATION: i32 = 33;
+
+/// 是否为可能由其他进程短暂占用导致的错误
+pub fn is_transient(e: &io::Error) -> bool {
+    e.raw_os_error() == Some(ERROR_ACCESS_DENIED) || is_sharing_violation(e)
+}
+
+/// 文件正被其他进程（通常是杀毒软件扫描）打开，与拒绝访问不同，不是权限问题
+pub fn is_sharing_violation(e: &io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION))
+}
+
+/// 对 paths 执行 op，遇到短暂的占用错误时重试，重试用尽后返回最后一次的错误
+pub fn retry<T>(action: &str, paths: &[&Path], mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    let mut delay = INITIAL_DELAY;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if attempt < ATTEMPTS && is_transient(&e) => {
+                let path = paths.first().map(|p| p.display().to_string()).unwrap_or_default();
+                warn!("{} {} failed (attempt {}/{}): {}, retrying in {:?}", action, path, attempt, ATTEMPTS, e, delay);
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) if is_transient(&e) => return Err(with_lockers(e, paths)),
+            result => return result,
+        }
+    }
+}
+
+/// 能查到占用进程时把进程名称附加到错误信息中
+fn with_lockers(e: io::Error, paths: &[&Path]) -> io::Error {
+    let processes = locks::locking_processes(paths);
+    if processes.is_empty() {
+        return e;
+    }
+    warn!("locked by {}", processes.join(", "));
+    io::Error::new(e.kind(), t!("error.locked_by", e, processes.join(", ")))
+}
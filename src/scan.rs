@@ -0,0 +1,200 @@
+//! 并行扫描游戏目录，按名称模式查找各语言的语音文件夹和 .toc 文件，不进入 Junction
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::link;
+
+/// 一个语言的语音文件夹名和 .toc 文件名模式（支持 * 和 ?，不区分大小写）
+pub struct Matcher {
+    pub folder_names: Vec<String>,
+    pub toc_names: Vec<String>,
+}
+
+impl Matcher {
+    fn folder_pattern(&self, name: &str) -> Option<&str> {
+        self.folder_names.iter().find(|p| glob_match(p, name)).map(String::as_str)
+    }
+
+    fn toc_pattern(&self, name: &str) -> Option<&str> {
+        self.toc_names.iter().find(|p| glob_match(p, name)).map(String::as_str)
+    }
+}
+
+/// 不区分大小写的通配符匹配，* 匹配任意个字符，? 匹配一个字符
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // 最近一个 * 的位置和它匹配到的名称位置，失配时回溯
+    let mut star = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// 扫描结果，路径相对扫描的根目录
+#[derive(Clone, Default)]
+pub struct Found {
+    pub folders: Vec<PathBuf>,
+    pub toc_files: Vec<PathBuf>,
+    /// 各模式匹配到的文件夹和文件数
+    pub patterns: BTreeMap<String, usize>,
+}
+
+/// 扫描进度，由工作线程更新
+#[derive(Default)]
+pub struct ScanProgress {
+    dirs: AtomicUsize,
+}
+
+impl ScanProgress {
+    /// 已扫描的目录数
+    pub fn dirs(&self) -> usize {
+        self.dirs.load(Ordering::Relaxed)
+    }
+}
+
+/// 用 workers 个线程扫描 root，结果与 matchers 一一对应
+///
+/// 匹配某个语言的文件夹不再为该语言向下查找，所有语言都已匹配时不再进入该文件夹；
+/// 名称匹配 exclude 的文件夹和位于 skip_dirs 中的文件夹（例如备份目录）直接跳过。
+pub fn scan(
+    root: &Path,
+    matchers: &[Matcher],
+    exclude: &[String],
+    skip_dirs: &[PathBuf],
+    workers: usize,
+    progress: &ScanProgress,
+) -> Vec<Found> {
+    let results: Vec<Mutex<Found>> = matchers.iter().map(|_| Mutex::new(Found::default())).collect();
+    // 待扫描的目录及其中已匹配（不再查找）的语言
+    let queue = Mutex::new(vec![(root.to_path_buf(), vec![false; matchers.len()])]);
+    // 已加入队列但尚未扫描完成的目录数
+    let pending = AtomicUsize::new(1);
+
+    let visit = |dir: &Path, skip: &[bool]| {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Ok(rel) = path.strip_prefix(root) else {
+                continue;
+            };
+            let is_junction = link::is_junction(&path);
+            if path.is_dir() || is_junction {
+                if exclude.iter().any(|p| glob_match(p, &name)) || skip_dirs.iter().any(|dir| link::points_into(&path, dir)) {
+                    continue;
+                }
+                let mut matched = skip.to_vec();
+                for (idx, matcher) in matchers.iter().enumerate() {
+                    if skip[idx] {
+                        continue;
+                    }
+                    if let Some(pattern) = matcher.folder_pattern(&name) {
+                        matched[idx] = true;
+                        if let Ok(mut found) = results[idx].lock() {
+                            found.folders.push(rel.to_path_buf());
+                            *found.patterns.entry(pattern.to_string()).or_default() += 1;
+                        }
+                    }
+                }
+                // 只递归普通目录，不递归 Junction
+                if !is_junction && !matched.iter().all(|m| *m) {
+                    pending.fetch_add(1, Ordering::SeqCst);
+                    if let Ok(mut queue) = queue.lock() {
+                        queue.push((path, matched));
+                    }
+                }
+            } else {
+                for (idx, matcher) in matchers.iter().enumerate() {
+                    let pattern = if skip[idx] { None } else { matcher.toc_pattern(&name) };
+                    if let Some(pattern) = pattern {
+                        if let Ok(mut found) = results[idx].lock() {
+                            found.toc_files.push(rel.to_path_buf());
+                            *found.patterns.entry(pattern.to_string()).or_default() += 1;
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            scope.spawn(|| loop {
+                let next = queue.lock().ok().and_then(|mut queue| queue.pop());
+                match next {
+                    Some((dir, skip)) => {
+                        visit(&dir, &skip);
+                        progress.dirs.fetch_add(1, Ordering::Relaxed);
+                        pending.fetch_sub(1, Ordering::SeqCst);
+                    }
+                    // 队列为空但其他线程仍在扫描，可能还会加入新的目录
+                    None if pending.load(Ordering::SeqCst) > 0 => thread::sleep(Duration::from_millis(1)),
+                    None => break,
+                }
+            });
+        }
+    });
+
+    // 多线程扫描的顺序不固定，排序后结果保持稳定
+    results
+        .into_iter()
+        .map(|found| {
+            let mut found = found.into_inner().unwrap_or_default();
+            found.folders.sort();
+            found.toc_files.sort();
+            found
+        })
+        .collect()
+}
+
+/// 在后台线程中扫描，界面线程轮询进度和结果
+pub struct VoiceScan {
+    pub root: PathBuf,
+    progress: Arc<ScanProgress>,
+    handle: JoinHandle<Vec<Found>>,
+}
+
+impl VoiceScan {
+    pub fn spawn(root: PathBuf, matchers: Vec<Matcher>, exclude: Vec<String>, skip_dirs: Vec<PathBuf>, workers: usize) -> Self {
+        let progress = Arc::new(ScanProgress::default());
+        let worker_progress = Arc::clone(&progress);
+        let scan_root = root.clone();
+        let handle = thread::spawn(move || scan(&scan_root, &matchers, &exclude, &skip_dirs, workers, &worker_progress));
+        VoiceScan { root, progress, handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn dirs(&self) -> usize {
+        self.progress.dirs()
+    }
+
+    pub fn join(self) -> Vec<Found> {
+        self.handle.join().unwrap_or_default()
+    }
+}
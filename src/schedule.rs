@@ -0,0 +1,114 @@
+//! 计划任务：通过 schtasks 注册登录时和每小时运行的任务，以静默模式检查并修复被移除的语音链接
+
+use std::env;
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::process::{Command, Output};
+
+use tracing::info;
+
+use crate::cli::ARG_REPAIR;
+use crate::error::SwitcherError;
+use crate::CREATE_NO_WINDOW;
+
+/// 计划任务的名称
+const TASK_NAME: &str = "BF6 Voice Switcher Repair";
+
+fn schtasks(args: &[&str]) -> Result<Output, SwitcherError> {
+    Ok(Command::new("schtasks").args(args).creation_flags(CREATE_NO_WINDOW).output()?)
+}
+
+/// 失败时以 schtasks 的输出作为错误信息
+fn check(output: Output) -> Result<(), SwitcherError> {
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let message = if stderr.is_empty() { String::from_utf8_lossy(&output.stdout).trim().to_string() } else { stderr };
+    Err(SwitcherError::ScheduleTask(message))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// 任务定义：当前用户登录两分钟后运行一次，之后每小时运行，不需要管理员权限
+fn task_xml(exe: &str, user: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <Triggers>
+    <LogonTrigger>
+      <Enabled>true</Enabled>
+      <UserId>{user}</UserId>
+      <Delay>PT2M</Delay>
+    </LogonTrigger>
+    <TimeTrigger>
+      <Enabled>true</Enabled>
+      <StartBoundary>2024-01-01T00:00:00</StartBoundary>
+      <Repetition>
+        <Interval>PT1H</Interval>
+      </Repetition>
+    </TimeTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Author">
+      <UserId>{user}</UserId>
+      <LogonType>InteractiveToken</LogonType>
+      <RunLevel>LeastPrivilege</RunLevel>
+    </Principal>
+  </Principals>
+  <Settings>
+    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <StartWhenAvailable>true</StartWhenAvailable>
+    <ExecutionTimeLimit>PT1H</ExecutionTimeLimit>
+    <Enabled>true</Enabled>
+  </Settings>
+  <Actions Context="Author">
+    <Exec>
+      <Command>{exe}</Command>
+      <Arguments>{arg}</Arguments>
+    </Exec>
+  </Actions>
+</Task>
+"#,
+        user = escape(user),
+        exe = escape(exe),
+        arg = ARG_REPAIR,
+    )
+}
+
+/// 注册（或覆盖）计划任务
+pub fn register() -> Result<(), SwitcherError> {
+    let exe = env::current_exe()?;
+    let user = match (env::var("USERDOMAIN"), env::var("USERNAME")) {
+        (Ok(domain), Ok(name)) => format!("{}\\{}", domain, name),
+        (_, name) => name.unwrap_or_default(),
+    };
+    // schtasks 按文件头的 BOM 识别 UTF-16 编码的任务定义
+    let xml: Vec<u8> = [0xFEFFu16]
+        .into_iter()
+        .chain(task_xml(&exe.to_string_lossy(), &user).encode_utf16())
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let path = env::temp_dir().join("bf6-voice-switcher-task.xml");
+    fs::write(&path, xml)?;
+    let result = schtasks(&["/Create", "/TN", TASK_NAME, "/XML", &path.to_string_lossy(), "/F"]).and_then(check);
+    let _ = fs::remove_file(&path);
+    result?;
+    info!("registered scheduled task {}", TASK_NAME);
+    Ok(())
+}
+
+pub fn unregister() -> Result<(), SwitcherError> {
+    check(schtasks(&["/Delete", "/TN", TASK_NAME, "/F"])?)?;
+    info!("removed scheduled task {}", TASK_NAME);
+    Ok(())
+}
+
+/// 计划任务是否已注册
+pub fn is_registered() -> bool {
+    schtasks(&["/Query", "/TN", TASK_NAME]).is_ok_and(|output| output.status.success())
+}
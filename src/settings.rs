@@ -0,0 +1,337 @@
+//! 用户设置（settings.toml，保存在程序所在目录）
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backup_info::BackupInfo;
+use crate::error::SwitcherError;
+use crate::i18n::{t, UiLanguage};
+use crate::link::RestoreMode;
+use crate::prune::Retention;
+use crate::theme::Theme;
+use crate::verify;
+
+pub const SETTINGS_FILE: &str = "settings.toml";
+/// 默认的并行复制线程数
+pub const DEFAULT_COPY_WORKERS: usize = 4;
+/// 并行复制线程数上限
+pub const MAX_COPY_WORKERS: usize = 16;
+/// 默认的压缩级别
+pub const DEFAULT_COMPRESSION_LEVEL: i64 = 6;
+/// deflate 压缩级别上限
+pub const MAX_COMPRESSION_LEVEL: i64 = 9;
+/// 默认每隔几分钟检查一次恢复的语音
+pub const DEFAULT_LINK_CHECK_MINUTES: u64 = 10;
+/// 检查间隔上限（分钟）
+pub const MAX_LINK_CHECK_MINUTES: u64 = 1440;
+/// 界面缩放范围
+pub const MIN_UI_SCALE: f32 = 0.75;
+pub const MAX_UI_SCALE: f32 = 2.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupMode {
+    /// 复制语音文件到备份目录，游戏文件保持不变
+    #[default]
+    Copy,
+    /// 将语音文件移动到备份目录，并立即在游戏目录中创建 Junction
+    Move,
+}
+
+impl BackupMode {
+    pub const ALL: [BackupMode; 2] = [BackupMode::Copy, BackupMode::Move];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BackupMode::Copy => t!("mode.backup_copy"),
+            BackupMode::Move => t!("mode.backup_move"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            BackupMode::Copy => t!("mode.backup_copy_hint"),
+            BackupMode::Move => t!("mode.backup_move_hint"),
+        }
+    }
+}
+
+/// 备份与当前游戏版本不一致时如何处理，恢复、自动重新应用和方案都按此处理
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionPolicy {
+    /// 阻止恢复，用户确认后才可恢复
+    #[default]
+    Block,
+    /// 只显示警告，允许恢复
+    Warn,
+    /// 游戏目录中的 .toc 文件与备份时一致（语音数据未变化）时允许恢复，否则阻止
+    Smart,
+}
+
+impl VersionPolicy {
+    pub const ALL: [VersionPolicy; 3] = [VersionPolicy::Block, VersionPolicy::Warn, VersionPolicy::Smart];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VersionPolicy::Block => t!("mode.version_block"),
+            VersionPolicy::Warn => t!("mode.version_warn"),
+            VersionPolicy::Smart => t!("mode.version_smart"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            VersionPolicy::Block => t!("mode.version_block_hint"),
+            VersionPolicy::Warn => t!("mode.version_warn_hint"),
+            VersionPolicy::Smart => t!("mode.version_smart_hint"),
+        }
+    }
+
+    /// 版本不匹配的备份 info 能否恢复到游戏目录 target，confirmed 为用户是否已确认仍要恢复
+    pub fn allows(&self, info: &BackupInfo, target: &Path, confirmed: bool) -> bool {
+        match self {
+            VersionPolicy::Block => confirmed,
+            VersionPolicy::Warn => true,
+            VersionPolicy::Smart => confirmed || verify::toc_unchanged(info, target),
+        }
+    }
+}
+
+/// 备份时复制文件夹使用的引擎
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyEngine {
+    /// 逐个文件调用 CopyFileExW，支持从旧备份创建硬链接
+    #[default]
+    Native,
+    /// 调用 robocopy 的多线程无缓冲复制，机械硬盘上复制大文件更快
+    Robocopy,
+}
+
+impl CopyEngine {
+    pub const ALL: [CopyEngine; 2] = [CopyEngine::Native, CopyEngine::Robocopy];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CopyEngine::Native => t!("mode.engine_native"),
+            CopyEngine::Robocopy => t!("mode.engine_robocopy"),
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            CopyEngine::Native => t!("mode.engine_native_hint"),
+            CopyEngine::Robocopy => t!("mode.engine_robocopy_hint"),
+        }
+    }
+}
+
+/// 用户在设置文件中自定义的语音语言，代码与内置语言相同时覆盖内置定义
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CustomLanguage {
+    /// 语言代码，同时用作备份文件夹名
+    pub code: String,
+    /// 界面中显示的名称
+    pub name: String,
+    /// +miles_language 参数的值
+    pub miles_lang: String,
+    /// 游戏目录中的文件夹代码（不含 vo 前缀），为空时使用语言代码
+    #[serde(default)]
+    pub folder_codes: Vec<String>,
+}
+
+impl CustomLanguage {
+    pub fn validate(&self) -> Result<(), SwitcherError> {
+        let valid_code = |c: &str| !c.is_empty() && c.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+        if !valid_code(&self.code) || self.folder_codes.iter().any(|c| !valid_code(c)) {
+            return Err(SwitcherError::CustomLangCode(self.code.clone()));
+        }
+        if self.miles_lang.trim().is_empty() || self.miles_lang.contains(char::is_whitespace) {
+            return Err(SwitcherError::CustomLangMiles(self.code.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// 方案：一组语音语言、Steam 游戏语言和启动参数的组合
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// 语音语言代码
+    pub voice: String,
+    /// 期望的 Steam 游戏语言（文本语言），为空时不检查
+    #[serde(default)]
+    pub text_language: String,
+    /// 应用时写入该语音的启动参数
+    #[serde(default = "default_true")]
+    pub launch_option: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 窗口位置和大小（逻辑像素）
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+/// 全局热键，按下时切换到该语言的备份
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    /// 组合键，如 "Ctrl+Alt+1"
+    pub keys: String,
+    /// 切换到的语言代码
+    pub lang: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// 恢复语音时默认使用的方式
+    pub restore_mode: RestoreMode,
+    /// 备份语音时默认使用的方式
+    pub backup_mode: BackupMode,
+    /// 备份与当前游戏版本不一致时的处理方式
+    pub version_policy: VersionPolicy,
+    /// 界面语言，未设置时跟随系统
+    pub ui_language: Option<UiLanguage>,
+    /// 上次选择的游戏 id
+    pub game: Option<String>,
+    /// 备份目录，未设置时使用程序所在目录中的 voice_backups
+    pub backup_root: Option<PathBuf>,
+    /// 自定义语音语言（[[custom_languages]]）
+    pub custom_languages: Vec<CustomLanguage>,
+    /// 并行复制的线程数，机械硬盘建议设为 1
+    pub copy_workers: usize,
+    /// 备份时复制文件夹使用的引擎
+    pub copy_engine: CopyEngine,
+    /// 复制线程使用后台优先级，让位于前台程序
+    pub background_priority: bool,
+    /// 执行备份、恢复和删除前先预览受影响的文件
+    pub preview: bool,
+    /// 删除时直接永久删除，不移到回收站
+    pub permanent_delete: bool,
+    /// 压缩备份时的 deflate 压缩级别
+    pub compression_level: i64,
+    /// 只保留最近几个游戏版本的备份，0 为不限制
+    pub keep_builds: usize,
+    /// 备份总大小上限（GB），超过时提示清理，0 为不限制
+    pub max_backup_gb: u64,
+    /// 最小化时隐藏到系统托盘
+    pub minimize_to_tray: bool,
+    /// 窗口不在前台时，后台任务结束后显示系统通知
+    pub notifications: bool,
+    /// 切换语音的全局热键
+    pub hotkeys: Vec<HotkeyBinding>,
+    /// 保存的语音、文本语言和启动参数组合
+    pub profiles: Vec<Profile>,
+    /// 上次应用的方案名称
+    pub last_profile: String,
+    /// 托盘模式下监视游戏更新，更新移除恢复的语音后提醒重新应用上次的方案
+    pub auto_relink: bool,
+    /// 程序运行时每隔几分钟检查恢复的链接和 .toc 文件，0 为不检查
+    pub link_check_minutes: u64,
+    /// 启动时检查 GitHub 上是否有新版本
+    pub check_updates: bool,
+    /// 上次关闭时的窗口位置和大小
+    pub window: Option<WindowGeometry>,
+    /// 界面缩放，在系统 DPI 缩放的基础上再放大或缩小
+    pub ui_scale: f32,
+    /// 界面主题
+    pub theme: Theme,
+    /// 按向导逐页显示步骤；没有设置文件时默认开启，旧设置文件中没有该项时保持经典布局
+    #[serde(default)]
+    pub wizard: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            restore_mode: RestoreMode::default(),
+            backup_mode: BackupMode::default(),
+            version_policy: VersionPolicy::default(),
+            ui_language: None,
+            game: None,
+            backup_root: None,
+            custom_languages: Vec::new(),
+            copy_workers: DEFAULT_COPY_WORKERS,
+            copy_engine: CopyEngine::default(),
+            background_priority: false,
+            preview: false,
+            permanent_delete: false,
+            compression_level: DEFAULT_COMPRESSION_LEVEL,
+            keep_builds: 0,
+            max_backup_gb: 0,
+            minimize_to_tray: false,
+            notifications: true,
+            hotkeys: Vec::new(),
+            profiles: Vec::new(),
+            last_profile: String::new(),
+            auto_relink: false,
+            link_check_minutes: DEFAULT_LINK_CHECK_MINUTES,
+            check_updates: false,
+            window: None,
+            ui_scale: 1.0,
+            theme: Theme::default(),
+            wizard: true,
+        }
+    }
+}
+
+impl Settings {
+    pub fn retention(&self) -> Retention {
+        Retention { keep_builds: self.keep_builds, max_bytes: self.max_backup_gb.saturating_mul(1024 * 1024 * 1024) }
+    }
+
+    /// 定期检查恢复的语音的间隔，关闭时为 None
+    pub fn link_check_interval(&self) -> Option<Duration> {
+        (self.link_check_minutes > 0).then(|| Duration::from_secs(self.link_check_minutes.min(MAX_LINK_CHECK_MINUTES) * 60))
+    }
+
+    /// 实际使用的复制线程数
+    pub fn copy_workers(&self) -> usize {
+        self.copy_workers.clamp(1, MAX_COPY_WORKERS)
+    }
+
+    /// 实际使用的界面缩放
+    pub fn ui_scale(&self) -> f32 {
+        if self.ui_scale.is_finite() {
+            self.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+        } else {
+            1.0
+        }
+    }
+
+    /// 实际使用的压缩级别
+    pub fn compression_level(&self) -> i64 {
+        self.compression_level.clamp(1, MAX_COMPRESSION_LEVEL)
+    }
+
+    /// 读取设置，文件不存在或格式错误时使用默认值
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SwitcherError> {
+        let content = toml::to_string_pretty(self)?;
+        // 通过临时文件替换，写入中断时保留原来的设置
+        let tmp_path = path.with_extension("toml.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+//! 导出和导入备份：把备份文件和清单（游戏、语言、版本和各文件的哈希）打包为一个 zip，便于分享给他人
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::archive;
+use crate::backup::STAGING_PREFIX;
+use crate::backup_info::BackupInfo;
+use crate::copy::Progress;
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::hash;
+use crate::i18n::t;
+use crate::logging;
+use crate::scan::{self, Matcher};
+
+/// zip 根目录中的清单文件
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// zip 中存放备份文件的目录
+const FILES_DIR: &str = "files";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    /// 游戏 id（games.toml 中的 id）
+    pub game: String,
+    pub lang_code: String,
+    pub build_id: String,
+    pub folders: Vec<PathBuf>,
+    pub toc_files: Vec<PathBuf>,
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// 相对备份目录的路径，以 / 分隔
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl Manifest {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+
+    /// 检查语言代码和文件夹、.toc 文件路径能否安全地用于备份目录：只接受不含 .. 和盘符的相对路径
+    pub fn check_paths(&self) -> Result<(), SwitcherError> {
+        if !is_plain_relative(Path::new(&self.lang_code)) || Path::new(&self.lang_code).components().count() != 1 {
+            return Err(SwitcherError::ImportUnknownLanguage(self.lang_code.clone()));
+        }
+        match self.folders.iter().chain(&self.toc_files).find(|p| !is_plain_relative(p)) {
+            Some(path) => Err(SwitcherError::ImportBadPath(path.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// 在 check_paths 的基础上检查文件夹和 .toc 文件名是否是游戏中这种语言的语音文件
+    pub fn check_voice_files(&self, matcher: &Matcher) -> Result<(), SwitcherError> {
+        self.check_paths()?;
+        let matches = |path: &Path, patterns: &[String]| {
+            path.file_name().is_some_and(|name| patterns.iter().any(|p| scan::glob_match(p, &name.to_string_lossy())))
+        };
+        let folders = self.folders.iter().map(|p| (p, &matcher.folder_names));
+        let toc_files = self.toc_files.iter().map(|p| (p, &matcher.toc_names));
+        match folders.chain(toc_files).find(|(path, patterns)| !matches(path, patterns)) {
+            Some((path, _)) => Err(SwitcherError::ImportBadPath(path.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// 非空且只由普通名称组成的相对路径
+fn is_plain_relative(path: &Path) -> bool {
+    path.components().next().is_some() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// 读取导出文件中的清单
+pub fn read_manifest(archive: &Path) -> Result<Manifest, SwitcherError> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    let entry = zip.by_name(MANIFEST_FILE).map_err(|_| SwitcherError::ImportInvalid)?;
+    serde_json::from_reader(entry).map_err(|_| SwitcherError::ImportInvalid)
+}
+
+/// 导出所需的信息，在界面线程中收集后交给后台线程执行
+pub struct ExportJob {
+    pub backup_path: PathBuf,
+    pub info: BackupInfo,
+    pub game: String,
+    pub lang_name: String,
+    /// 未压缩的备份中的语音文件夹和 .toc 文件
+    pub voice_folders: Vec<PathBuf>,
+    pub toc_files: Vec<PathBuf>,
+    pub dest: PathBuf,
+    pub level: i64,
+}
+
+impl ExportJob {
+    pub fn total_size(&self) -> u64 {
+        if self.info.compressed {
+            archive::unpacked_size(&self.backup_path)
+        } else {
+            self.voice_folders.iter().chain(&self.toc_files).map(|rel_path| disk::dir_size(&self.backup_path.join(rel_path))).sum()
+        }
+    }
+
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        if let Err(e) = self.write(progress) {
+            let _ = fs::remove_file(&self.dest);
+            return Err(e);
+        }
+        info!("exported {} to {}", self.backup_path.display(), self.dest.display());
+        Ok(t!("status.exported", self.lang_name, self.dest.display()))
+    }
+
+    fn write(&self, progress: &Progress) -> Result<(), SwitcherError> {
+        let mut zip = ZipWriter::new(File::create(&self.dest)?);
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .compression_level(Some(self.level))
+            .large_file(true);
+        let mut files = Vec::new();
+        let mut add = |zip: &mut ZipWriter<File>, name: String, reader: &mut dyn std::io::Read| -> Result<(), SwitcherError> {
+            progress.update(|s| s.current_file = name.clone());
+            zip.start_file(format!("{}/{}", FILES_DIR, name), options)?;
+            let mut size = 0;
+            let sha256 = hash::hash_reader(reader, |data| {
+                size += data.len() as u64;
+                progress.update(|s| s.done_bytes += data.len() as u64);
+                zip.write_all(data)
+            })?;
+            files.push(ManifestFile { path: name, size, sha256 });
+            Ok(())
+        };
+
+        // 压缩的备份直接从 backup.zip 读取
+        let (folders, toc_files) = if self.info.compressed {
+            let mut source = ZipArchive::new(File::open(archive::archive_path(&self.backup_path))?)?;
+            for i in 0..source.len() {
+                let mut entry = source.by_index(i)?;
+                if entry.is_file() {
+                    add(&mut zip, entry.name().to_string(), &mut entry)?;
+                }
+            }
+            (self.info.folders.clone(), self.info.toc_files.clone())
+        } else {
+            let mut paths = Vec::new();
+            for rel_path in self.voice_folders.iter().chain(&self.toc_files) {
+                archive::list_files(&self.backup_path, &self.backup_path.join(rel_path), &mut paths);
+            }
+            for rel_path in paths {
+                add(&mut zip, archive::entry_name(&rel_path), &mut File::open(self.backup_path.join(&rel_path))?)?;
+            }
+            (self.voice_folders.clone(), self.toc_files.clone())
+        };
+
+        let manifest = Manifest {
+            game: self.game.clone(),
+            lang_code: self.info.lang_code.clone(),
+            build_id: self.info.build_id.clone(),
+            folders,
+            toc_files,
+            files,
+        };
+        zip.start_file(MANIFEST_FILE, SimpleFileOptions::default())?;
+        zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+        zip.finish()?;
+        Ok(())
+    }
+}
+
+/// 导入所需的信息，清单已在界面线程中按语言和游戏的语音文件检查
+pub struct ImportJob {
+    pub archive: PathBuf,
+    pub backup_dir: PathBuf,
+    pub manifest: Manifest,
+    pub lang_name: String,
+}
+
+impl ImportJob {
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        self.manifest.check_paths()?;
+        let lang_code = &self.manifest.lang_code;
+        let staging = self.backup_dir.join(format!("{}import-{}", STAGING_PREFIX, lang_code));
+        if staging.exists() {
+            fs::remove_dir_all(&staging).map_err(SwitcherError::CleanStaging)?;
+        }
+        if let Err(e) = self.extract(&staging, progress) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+
+        let info = BackupInfo {
+            lang_code: lang_code.clone(),
+            build_id: self.manifest.build_id.clone(),
+            folders: self.manifest.folders.clone(),
+            toc_files: self.manifest.toc_files.clone(),
+            toc_hashes: Default::default(),
+            restore: None,
+            compressed: false,
+            label: String::new(),
+            note: String::new(),
+            created: logging::local_time(),
+            dir: String::new(),
+        };
+        info.save(&staging)?;
+        fs::rename(&staging, self.backup_dir.join(lang_code))?;
+        info!("imported {} from {}", lang_code, self.archive.display());
+        Ok(t!("status.imported", self.lang_name, self.manifest.build_id))
+    }
+
+    /// 按清单解压文件并逐个校验哈希
+    fn extract(&self, staging: &Path, progress: &Progress) -> Result<(), SwitcherError> {
+        let mut zip = ZipArchive::new(File::open(&self.archive)?)?;
+        for file in &self.manifest.files {
+            let mut entry = zip
+                .by_name(&format!("{}/{}", FILES_DIR, file.path))
+                .map_err(|_| SwitcherError::ImportFileMismatch(file.path.clone()))?;
+            // 只接受位于 files 目录内的相对路径
+            let rel_path = entry
+                .enclosed_name()
+                .and_then(|p| p.strip_prefix(FILES_DIR).ok().map(Path::to_path_buf))
+                .ok_or(SwitcherError::ImportInvalid)?;
+            let path = staging.join(rel_path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(SwitcherError::CreateDir)?;
+            }
+            progress.update(|s| s.current_file = file.path.clone());
+            let mut out = File::create(&path)?;
+            let sha256 = hash::hash_reader(&mut entry, |data| {
+                progress.update(|s| s.done_bytes += data.len() as u64);
+                out.write_all(data)
+            })?;
+            if sha256 != file.sha256 {
+                return Err(SwitcherError::ImportFileMismatch(file.path.clone()));
+            }
+        }
+        Ok(())
+    }
+}
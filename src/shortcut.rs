@@ -0,0 +1,57 @@
+//! 桌面快捷方式：通过 PowerShell 调用 WScript.Shell 创建以启动器模式运行本程序的 .lnk 文件
+
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::info;
+
+use crate::cli::ARG_LAUNCH;
+use crate::error::SwitcherError;
+use crate::CREATE_NO_WINDOW;
+
+/// PowerShell 单引号字符串中的单引号需要重复
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// 快捷方式文件名中不能使用的字符替换为下划线
+fn file_name(name: &str) -> String {
+    name.chars().map(|c| if r#"\/:*?"<>|"#.contains(c) { '_' } else { c }).collect()
+}
+
+/// 启动器模式运行本程序的参数
+pub fn launch_args(profile: &str) -> String {
+    format!("{} \"{}\"", ARG_LAUNCH, profile.replace('"', ""))
+}
+
+/// 在桌面创建（或覆盖）名为 name 的快捷方式，返回快捷方式路径
+pub fn create_desktop_shortcut(name: &str, profile: &str, icon: Option<&Path>) -> Result<PathBuf, SwitcherError> {
+    let exe = std::env::current_exe()?;
+    let work_dir = exe.parent().unwrap_or(Path::new("."));
+    let link_name = format!("{}.lnk", file_name(name));
+    let mut script = format!(
+        "$path = Join-Path ([Environment]::GetFolderPath('Desktop')) {}; \
+         $link = (New-Object -ComObject WScript.Shell).CreateShortcut($path); \
+         $link.TargetPath = {}; $link.Arguments = {}; $link.WorkingDirectory = {}; ",
+        quote(&link_name),
+        quote(&exe.to_string_lossy()),
+        quote(&launch_args(profile)),
+        quote(&work_dir.to_string_lossy()),
+    );
+    if let Some(icon) = icon {
+        script.push_str(&format!("$link.IconLocation = {}; ", quote(&format!("{},0", icon.display()))));
+    }
+    script.push_str("$link.Save(); Write-Output $path");
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()?;
+    if !output.status.success() {
+        return Err(SwitcherError::Shortcut(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    info!("created shortcut {}", path.display());
+    Ok(path)
+}
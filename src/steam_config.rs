@@ -0,0 +1,346 @@
+//! Steam 客户端交互：读取 appmanifest，用户配置（localconfig.vdf）中启动选项的读写，添加非 Steam 游戏快捷方式，以及带参数启动游戏
+
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::info;
+
+use crate::binary_vdf::{self, Value};
+use crate::error::SwitcherError;
+use crate::vdf;
+use crate::CREATE_NO_WINDOW;
+
+/// SteamID64 与账号 ID (userdata 目录名) 之间的偏移
+const STEAM_ID64_BASE: u64 = 76561197960265728;
+
+/// appmanifest StateFlags 中表示需要更新、正在更新、更新已暂停和更新已开始的位
+const STATE_UPDATE_PENDING: u32 = 2 | 256 | 512 | 1024;
+
+/// appmanifest 中使用的字段
+pub struct AppManifest {
+    pub install_dir: String,
+    pub build_id: String,
+    /// UserConfig 中的语言，即游戏属性中选择的下载语言
+    pub language: String,
+    pub update_pending: bool,
+}
+
+/// 解析 appmanifest 文件
+pub fn parse_app_manifest(path: &Path) -> Option<AppManifest> {
+    let content = fs::read_to_string(path).ok()?;
+    let mut install_dir = String::new();
+    let mut build_id = String::new();
+    let mut language = String::new();
+    let mut state_flags = 0u32;
+    let mut target_build_id = String::new();
+    let mut scheduled_update = false;
+    // MountedConfig 中也有 language，为已安装的语言，游戏属性中选择的语言以 UserConfig 为准
+    let mut in_user_config = false;
+    let mut user_language = String::new();
+
+    for line in content.lines() {
+        if line.contains("\"UserConfig\"") {
+            in_user_config = true;
+        } else if line.trim() == "}" {
+            in_user_config = false;
+        }
+        if line.contains("\"installdir\"") {
+            install_dir = extract_vdf_value(line).unwrap_or_default();
+        } else if line.contains("\"buildid\"") {
+            build_id = extract_vdf_value(line).unwrap_or_default();
+        } else if line.contains("\"language\"") {
+            let value = extract_vdf_value(line).unwrap_or_default();
+            if in_user_config {
+                user_language = value;
+            } else if language.is_empty() {
+                language = value;
+            }
+        } else if line.contains("\"StateFlags\"") {
+            state_flags = extract_vdf_value(line).and_then(|v| v.parse().ok()).unwrap_or(0);
+        } else if line.contains("\"TargetBuildID\"") {
+            target_build_id = extract_vdf_value(line).unwrap_or_default();
+        } else if line.contains("\"ScheduledAutoUpdate\"") {
+            scheduled_update = extract_vdf_value(line).is_some_and(|v| v != "0");
+        }
+    }
+
+    // TargetBuildID 为 0 或与当前版本相同时没有待下载的版本
+    let new_build = !target_build_id.is_empty() && target_build_id != "0" && target_build_id != build_id;
+    if !install_dir.is_empty() && !build_id.is_empty() {
+        Some(AppManifest {
+            install_dir,
+            build_id,
+            language: if user_language.is_empty() { language } else { user_language },
+            update_pending: state_flags & STATE_UPDATE_PENDING != 0 || scheduled_update || new_build,
+        })
+    } else {
+        None
+    }
+}
+
+/// 从 VDF 行中提取值
+pub fn extract_vdf_value(line: &str) -> Option<String> {
+    let parts: Vec<&str> = line.split('"').collect();
+    if parts.len() >= 4 {
+        Some(parts[3].to_string())
+    } else {
+        None
+    }
+}
+
+fn app_path(app_id: &str) -> [&str; 6] {
+    ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", app_id]
+}
+
+/// 检查指定进程是否正在运行
+pub fn is_process_running(image_name: &str) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("IMAGENAME eq {}", image_name), "/NH"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .to_lowercase()
+                .contains(&image_name.to_lowercase())
+        })
+        .unwrap_or(false)
+}
+
+/// 定位当前用户的 localconfig.vdf：优先使用最近登录的账号，否则取最近修改的文件
+pub fn find_localconfig(steam_path: &Path) -> Option<PathBuf> {
+    let userdata = steam_path.join("userdata");
+
+    if let Some(account_id) = most_recent_account_id(steam_path) {
+        let path = userdata.join(account_id.to_string()).join("config").join("localconfig.vdf");
+        if path.exists() {
+            return Some(path);
+        }
+    }
+
+    fs::read_dir(&userdata)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path().join("config").join("localconfig.vdf"))
+        .filter(|path| path.exists())
+        .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+/// 从 loginusers.vdf 中读取 MostRecent 账号
+fn most_recent_account_id(steam_path: &Path) -> Option<u64> {
+    let content = fs::read_to_string(steam_path.join("config").join("loginusers.vdf")).ok()?;
+    let mut current_id: Option<u64> = None;
+    for line in content.lines() {
+        let parts: Vec<&str> = line.split('"').collect();
+        if parts.len() == 3 {
+            // 形如 "7656119xxxxxxxxxx" 的块名
+            current_id = parts[1].parse().ok();
+        } else if parts.len() >= 4 && parts[1].eq_ignore_ascii_case("MostRecent") && parts[3] == "1" {
+            return current_id.and_then(|id| id.checked_sub(STEAM_ID64_BASE));
+        }
+    }
+    None
+}
+
+/// 移除已有的语音语言参数（如 +miles_language），并在 miles_lang 不为空时追加新的参数
+pub fn merge_launch_options(existing: &str, param: &str, miles_lang: Option<&str>) -> String {
+    let mut parts = Vec::new();
+    let mut tokens = existing.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token.eq_ignore_ascii_case(param) {
+            tokens.next();
+        } else {
+            parts.push(token.to_string());
+        }
+    }
+    if let Some(lang) = miles_lang {
+        parts.push(format!("{} {}", param, lang));
+    }
+    parts.join(" ")
+}
+
+/// 启动选项中 param 后面的值，例如 +miles_language 后的语言
+pub fn launch_option_value<'a>(options: &'a str, param: &str) -> Option<&'a str> {
+    let mut tokens = options.split_whitespace();
+    tokens.find(|token| token.eq_ignore_ascii_case(param))?;
+    tokens.next()
+}
+
+/// 启动选项中 param 后面的所有值，多次设置时游戏只使用其中一个，其余为之前留下的参数
+pub fn launch_option_values<'a>(options: &'a str, param: &str) -> Vec<&'a str> {
+    let mut values = Vec::new();
+    let mut tokens = options.split_whitespace();
+    while tokens.any(|token| token.eq_ignore_ascii_case(param)) {
+        values.extend(tokens.next());
+    }
+    values
+}
+
+/// 读取当前用户为 app_id 设置的启动选项，找不到配置时返回 None
+pub fn read_launch_options(steam_path: &Path, app_id: &str) -> Option<String> {
+    let content = fs::read_to_string(find_localconfig(steam_path)?).ok()?;
+    Some(vdf::get_value(&content, &app_path(app_id), "LaunchOptions").ok()?.unwrap_or_default())
+}
+
+/// 写入（或在 miles_lang 为 None 时移除）启动选项，返回写入后的完整启动选项
+pub fn apply_launch_option(
+    steam_path: &Path,
+    app_id: &str,
+    param: &str,
+    miles_lang: Option<&str>,
+) -> Result<String, SwitcherError> {
+    if is_process_running("steam.exe") {
+        return Err(SwitcherError::SteamRunning);
+    }
+
+    let localconfig = find_localconfig(steam_path).ok_or(SwitcherError::LocalConfigNotFound)?;
+    let content = fs::read_to_string(&localconfig)?;
+    let existing = vdf::get_value(&content, &app_path(app_id), "LaunchOptions")?.unwrap_or_default();
+    let options = merge_launch_options(&existing, param, miles_lang);
+    let new_content = vdf::set_value(&content, &app_path(app_id), "LaunchOptions", &options)?;
+
+    // 先备份原文件，再通过临时文件替换，避免写入中断损坏配置
+    let backup_path = localconfig.with_extension("vdf.bak");
+    fs::copy(&localconfig, &backup_path).map_err(SwitcherError::LocalConfigBackup)?;
+    let tmp_path = localconfig.with_extension("vdf.tmp");
+    fs::write(&tmp_path, new_content)?;
+    fs::rename(&tmp_path, &localconfig)?;
+    info!("wrote launch options to {}: {}", localconfig.display(), options);
+
+    Ok(options)
+}
+
+/// 非 Steam 游戏的快捷方式
+pub struct Shortcut<'a> {
+    pub name: &'a str,
+    pub exe: &'a Path,
+    pub launch_options: &'a str,
+    pub icon: Option<&'a Path>,
+}
+
+/// 按 Steam 的方式由程序路径和名称计算快捷方式的 appid
+fn shortcut_app_id(exe: &str, name: &str) -> u32 {
+    let mut crc = !0u32;
+    for byte in exe.bytes().chain(name.bytes()) {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc | 0x8000_0000
+}
+
+/// 在当前用户的 shortcuts.vdf 中添加非 Steam 游戏，已有同名项目时更新该项目，返回文件路径
+///
+/// Steam 运行时会在退出时覆盖该文件，因此要求先关闭 Steam
+pub fn add_shortcut(steam_path: &Path, shortcut: &Shortcut) -> Result<PathBuf, SwitcherError> {
+    if is_process_running("steam.exe") {
+        return Err(SwitcherError::SteamRunning);
+    }
+    let path = find_localconfig(steam_path).ok_or(SwitcherError::LocalConfigNotFound)?.with_file_name("shortcuts.vdf");
+    let mut root = if path.exists() { binary_vdf::parse(&fs::read(&path)?)? } else { Vec::new() };
+    if !root.iter().any(|(key, _)| key.eq_ignore_ascii_case("shortcuts")) {
+        root.push(("shortcuts".to_string(), Value::Map(Vec::new())));
+    }
+    let Some((_, Value::Map(shortcuts))) = root.iter_mut().find(|(key, _)| key.eq_ignore_ascii_case("shortcuts")) else {
+        return Err(SwitcherError::VdfInvalid);
+    };
+
+    let exe = format!("\"{}\"", shortcut.exe.display());
+    let start_dir = format!("\"{}\"", shortcut.exe.parent().unwrap_or(Path::new("")).display());
+    let str_value = |text: &str| Value::Str(text.to_string());
+    let entry = vec![
+        ("appid".to_string(), Value::Int(shortcut_app_id(&exe, shortcut.name))),
+        ("AppName".to_string(), str_value(shortcut.name)),
+        ("Exe".to_string(), Value::Str(exe)),
+        ("StartDir".to_string(), Value::Str(start_dir)),
+        ("icon".to_string(), Value::Str(shortcut.icon.map(|p| p.display().to_string()).unwrap_or_default())),
+        ("ShortcutPath".to_string(), str_value("")),
+        ("LaunchOptions".to_string(), str_value(shortcut.launch_options)),
+        ("IsHidden".to_string(), Value::Int(0)),
+        ("AllowDesktopConfig".to_string(), Value::Int(1)),
+        ("AllowOverlay".to_string(), Value::Int(1)),
+        ("OpenVR".to_string(), Value::Int(0)),
+        ("tags".to_string(), Value::Map(Vec::new())),
+    ];
+    let existing = shortcuts.iter_mut().find(|(_, value)| match value {
+        Value::Map(fields) => binary_vdf::get(fields, "AppName").and_then(Value::as_str) == Some(shortcut.name),
+        _ => false,
+    });
+    match existing {
+        Some((_, value)) => *value = Value::Map(entry),
+        None => {
+            // 项目的键为从 0 开始的序号
+            let next = shortcuts.iter().filter_map(|(key, _)| key.parse::<usize>().ok()).max().map_or(0, |n| n + 1);
+            shortcuts.push((next.to_string(), Value::Map(entry)));
+        }
+    }
+
+    // 与 localconfig.vdf 相同，先备份原文件，再通过临时文件替换
+    if path.exists() {
+        fs::copy(&path, path.with_extension("vdf.bak")).map_err(SwitcherError::LocalConfigBackup)?;
+    }
+    let tmp_path = path.with_extension("vdf.tmp");
+    fs::write(&tmp_path, binary_vdf::write(&root))?;
+    fs::rename(&tmp_path, &path)?;
+    info!("added non-steam shortcut {} to {}", shortcut.name, path.display());
+    Ok(path)
+}
+
+/// 通过 Steam 验证游戏文件完整性，恢复被删除或修改的文件
+pub fn validate_game(app_id: &str) -> Result<(), SwitcherError> {
+    let url = format!("steam://validate/{}", app_id);
+    Command::new("cmd")
+        .args(["/C", "start", "", &url])
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()?;
+    info!("requested steam validation for {}", app_id);
+    Ok(())
+}
+
+/// 通过 Steam 启动游戏并附加启动参数
+///
+/// 已知 Steam 路径时使用 `steam.exe -applaunch`，否则回退到 steam://run 协议链接。
+pub fn launch_game(steam_path: Option<&Path>, app_id: &str, args: &str) -> Result<(), SwitcherError> {
+    if let Some(steam_exe) = steam_path.map(|p| p.join("steam.exe")).filter(|p| p.exists()) {
+        Command::new(steam_exe)
+            .arg("-applaunch")
+            .arg(app_id)
+            .args(args.split_whitespace())
+            .spawn()?;
+        return Ok(());
+    }
+
+    let url = format!("steam://run/{}//{}/", app_id, args.replace(' ', "%20"));
+    Command::new("cmd")
+        .args(["/C", "start", "", &url])
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAM: &str = "+miles_language";
+
+    #[test]
+    fn merge_adds_param() {
+        assert_eq!(merge_launch_options("", PARAM, Some("enUS")), "+miles_language enUS");
+        assert_eq!(merge_launch_options("-novid", PARAM, Some("enUS")), "-novid +miles_language enUS");
+    }
+
+    #[test]
+    fn merge_replaces_existing_params() {
+        let existing = "-novid +MILES_LANGUAGE zhCN -high +miles_language jaJP";
+        assert_eq!(merge_launch_options(existing, PARAM, Some("enUS")), "-novid -high +miles_language enUS");
+    }
+
+    #[test]
+    fn merge_removes_param() {
+        assert_eq!(merge_launch_options("-novid  +miles_language zhCN", PARAM, None), "-novid");
+        assert_eq!(merge_launch_options("+miles_language", PARAM, None), "");
+    }
+}
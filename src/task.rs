@@ -0,0 +1,94 @@
+//! 在后台线程中执行耗时操作（备份、恢复），界面线程轮询进度和结果；另提供后台监视线程共用的停止标志
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::copy::{Progress, ProgressState};
+use crate::error::SwitcherError;
+use crate::priority;
+
+/// 等待期间检查是否需要停止的间隔
+const STOP_CHECK: Duration = Duration::from_secs(1);
+
+pub struct Task {
+    progress: Arc<Progress>,
+    handle: JoinHandle<Result<String, SwitcherError>>,
+}
+
+impl Task {
+    /// 启动后台任务，total_bytes 为预计复制的总字节数
+    pub fn spawn<F>(total_bytes: u64, job: F) -> Self
+    where
+        F: FnOnce(&Progress) -> Result<String, SwitcherError> + Send + 'static,
+    {
+        let progress = Arc::new(Progress::new(total_bytes));
+        let worker_progress = Arc::clone(&progress);
+        let handle = thread::spawn(move || {
+            let _background = priority::apply_to_current_thread();
+            job(&worker_progress)
+        });
+        Task { progress, handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn progress(&self) -> ProgressState {
+        self.progress.snapshot()
+    }
+
+    /// 请求取消，任务在下一次检查时以错误结束
+    pub fn cancel(&self) {
+        self.progress.cancel();
+    }
+
+    /// 暂停后任务在当前文件完成后等待，继续后从下一个文件开始
+    pub fn pause(&self) {
+        self.progress.pause();
+    }
+
+    pub fn resume(&self) {
+        self.progress.resume();
+    }
+
+    /// 等待任务结束并取得结果
+    pub fn join(self) -> Result<String, SwitcherError> {
+        self.handle.join().unwrap_or(Err(SwitcherError::TaskFailed))
+    }
+}
+
+/// 创建后台监视线程的停止标志：界面线程持有 Stop，释放时通知持有 StopSignal 的线程停止
+pub fn stop_signal() -> (Stop, StopSignal) {
+    let flag = Arc::new(AtomicBool::new(false));
+    (Stop(Arc::clone(&flag)), StopSignal(flag))
+}
+
+/// 释放时通知后台线程停止
+pub struct Stop(Arc<AtomicBool>);
+
+impl Drop for Stop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+pub struct StopSignal(Arc<AtomicBool>);
+
+impl StopSignal {
+    /// 等待 duration，期间定期检查是否需要停止；需要停止时提前返回 false
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let mut waited = Duration::ZERO;
+        while waited < duration && !self.is_stopped() {
+            thread::sleep(STOP_CHECK);
+            waited += STOP_CHECK;
+        }
+        !self.is_stopped()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
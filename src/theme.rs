@@ -0,0 +1,50 @@
+//! 界面主题：跟随系统、深色或浅色；状态颜色按当前主题选择，在两种主题下都清晰可读
+
+use eframe::egui::{self, Color32};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Dark, Theme::Light];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::System => t!("ui.theme_system"),
+            Theme::Dark => t!("ui.theme_dark"),
+            Theme::Light => t!("ui.theme_light"),
+        }
+    }
+
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.set_theme(match self {
+            Theme::System => egui::ThemePreference::System,
+            Theme::Dark => egui::ThemePreference::Dark,
+            Theme::Light => egui::ThemePreference::Light,
+        });
+    }
+}
+
+/// 成功、已连接等正常状态
+pub fn success(ui: &egui::Ui) -> Color32 {
+    if ui.visuals().dark_mode { Color32::from_rgb(110, 210, 120) } else { Color32::from_rgb(20, 120, 45) }
+}
+
+/// 需要注意但不影响使用的状态
+pub fn warning(ui: &egui::Ui) -> Color32 {
+    if ui.visuals().dark_mode { Color32::from_rgb(235, 200, 80) } else { Color32::from_rgb(150, 95, 0) }
+}
+
+/// 错误和会删除数据的操作
+pub fn error(ui: &egui::Ui) -> Color32 {
+    if ui.visuals().dark_mode { Color32::from_rgb(255, 110, 100) } else { Color32::from_rgb(190, 30, 30) }
+}
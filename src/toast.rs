@@ -0,0 +1,123 @@
+//! 操作结果的浮动提示：每条结果显示为可关闭的通知，错误需要手动关闭；最近的消息保留在历史记录中
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::a11y;
+use crate::i18n::t;
+use crate::logging;
+use crate::theme;
+
+/// 非错误提示自动消失的时间
+const TOAST_DURATION: Duration = Duration::from_secs(6);
+/// 同时显示的提示数，超出时先移除最早的
+const MAX_ACTIVE: usize = 5;
+const MAX_HISTORY: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Success,
+    /// 操作完成但需要注意（消息中包含 [!]）
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// 成功的结果中包含 [!] 标记时视为警告
+    pub fn for_message(message: &str) -> Self {
+        if message.contains("[!]") { Severity::Warning } else { Severity::Success }
+    }
+
+    pub fn color(&self, ui: &egui::Ui) -> egui::Color32 {
+        match self {
+            Severity::Success => theme::success(ui),
+            Severity::Warning => theme::warning(ui),
+            Severity::Error => theme::error(ui),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Toast {
+    id: u64,
+    pub severity: Severity,
+    pub message: String,
+    /// 显示在历史记录中的本地时间
+    pub time: String,
+    shown: Instant,
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    active: Vec<Toast>,
+    /// 最近的消息，最新的在最后
+    history: VecDeque<Toast>,
+    next_id: u64,
+}
+
+impl Toasts {
+    pub fn push(&mut self, severity: Severity, message: String) {
+        self.next_id += 1;
+        let toast = Toast { id: self.next_id, severity, message, time: logging::local_time(), shown: Instant::now() };
+        if self.history.len() >= MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(toast.clone());
+        if self.active.len() >= MAX_ACTIVE {
+            self.active.remove(0);
+        }
+        self.active.push(toast);
+    }
+
+    pub fn history(&self) -> &VecDeque<Toast> {
+        &self.history
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// 在窗口右下角显示提示，bottom 为底部面板占用的高度
+    pub fn show(&mut self, ctx: &egui::Context, bottom: f32) {
+        self.active.retain(|t| t.severity == Severity::Error || t.shown.elapsed() < TOAST_DURATION);
+        if self.active.is_empty() {
+            return;
+        }
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-12.0, -12.0 - bottom])
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+                for toast in self.active.iter().rev() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            let color = toast.severity.color(ui);
+                            ui.add(egui::Label::new(egui::RichText::new(&toast.message).color(color)).wrap());
+                            let close = ui.small_button("✕").on_hover_text(t!("ui.toast_dismiss"));
+                            a11y::name(&close, egui::WidgetType::Button, t!("ui.toast_dismiss"));
+                            if close.clicked() {
+                                dismissed = Some(toast.id);
+                            }
+                        });
+                    });
+                    ui.add_space(4.0);
+                }
+            });
+        if let Some(id) = dismissed {
+            self.active.retain(|t| t.id != id);
+        }
+        // 自动消失前需要重新绘制
+        if let Some(remaining) = self
+            .active
+            .iter()
+            .filter(|t| t.severity != Severity::Error)
+            .map(|t| TOAST_DURATION.saturating_sub(t.shown.elapsed()))
+            .min()
+        {
+            ctx.request_repaint_after(remaining);
+        }
+    }
+}
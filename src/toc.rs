@@ -0,0 +1,118 @@
+//! 简单的 .toc 解析：从文件中提取引用的 bundle/cas 文件名，检查备份是否包含语音文件夹内被引用的文件
+//!
+//! 不解析完整的 toc 结构，只查找以 .sb/.cas/.cat 等结尾的路径字符串，加密或压缩的 toc 中找不到引用时不做检查。
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 被引用的文件可能使用的扩展名
+const REFERENCE_EXTENSIONS: [&str; 4] = [".sb", ".cas", ".cat", ".toc"];
+/// 路径字符串的最小长度，过滤掉偶然出现的可打印字节
+const MIN_REFERENCE_LEN: usize = 4;
+
+/// 提取 toc 文件中引用的文件路径
+pub fn references(path: &Path) -> io::Result<Vec<String>> {
+    Ok(references_in(&fs::read(path)?))
+}
+
+/// 从 toc 的内容中提取引用的文件路径（小写，去重）
+fn references_in(data: &[u8]) -> Vec<String> {
+    let mut refs = Vec::new();
+    for run in data.split(|b| !is_path_byte(*b)) {
+        if run.len() < MIN_REFERENCE_LEN {
+            continue;
+        }
+        let text = String::from_utf8_lossy(run).to_ascii_lowercase();
+        if REFERENCE_EXTENSIONS.iter().any(|ext| text.ends_with(ext)) && !refs.contains(&text) {
+            refs.push(text);
+        }
+    }
+    refs
+}
+
+fn is_path_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'/' | b'\\' | b'_' | b'-' | b'.')
+}
+
+/// 检查 root 中的 toc 文件引用的、位于语音文件夹内的文件是否都存在，返回缺失的文件（相对 root）
+pub fn missing_references(root: &Path, toc_files: &[PathBuf], voice_folders: &[PathBuf]) -> Vec<PathBuf> {
+    let mut missing = Vec::new();
+    for toc in toc_files {
+        let Ok(refs) = references(&root.join(toc)) else {
+            continue;
+        };
+        for reference in refs {
+            if let Some(rel_path) = resolve(&reference, voice_folders) {
+                if !root.join(&rel_path).exists() && !missing.contains(&rel_path) {
+                    missing.push(rel_path);
+                }
+            }
+        }
+    }
+    missing
+}
+
+/// 引用路径中包含某个语音文件夹时，返回该文件相对根目录的路径；不在语音文件夹内的引用返回 None
+fn resolve(reference: &str, voice_folders: &[PathBuf]) -> Option<PathBuf> {
+    let parts: Vec<&str> = reference.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
+    voice_folders.iter().find_map(|folder| {
+        let folder_parts: Vec<String> =
+            folder.components().map(|c| c.as_os_str().to_string_lossy().to_ascii_lowercase()).collect();
+        // 手动修改或旧版本的 backup_info.json 中可能有空路径
+        if folder_parts.is_empty() {
+            return None;
+        }
+        let start = parts.windows(folder_parts.len()).position(|w| w.iter().zip(&folder_parts).all(|(a, b)| a == b))?;
+        let rest = &parts[start + folder_parts.len()..];
+        (!rest.is_empty()).then(|| rest.iter().fold(folder.clone(), |path, part| path.join(part)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voice_folder() -> PathBuf {
+        PathBuf::from("Data").join("Win32").join("Loc").join("en")
+    }
+
+    #[test]
+    fn references_in_binary_data() {
+        let mut data = vec![0x00, 0x13, 0xff];
+        data.extend_from_slice(b"Win32/Loc/EN/voice.cas");
+        data.extend_from_slice(&[0x01, 0x80]);
+        data.extend_from_slice(b"win32/loc/en/voice.cas");
+        data.push(0x00);
+        data.extend_from_slice(b"a.sb");
+        data.push(0x02);
+        data.extend_from_slice(b".sb");
+        data.extend_from_slice(&[0x00, 0x05]);
+        data.extend_from_slice(b"not_a_reference.txt");
+        assert_eq!(references_in(&data), ["win32/loc/en/voice.cas", "a.sb"]);
+    }
+
+    #[test]
+    fn resolve_inside_voice_folder() {
+        let folders = [voice_folder()];
+        assert_eq!(resolve("data/win32/loc/en/chunk0.cas", &folders), Some(voice_folder().join("chunk0.cas")));
+        assert_eq!(resolve("win32\\loc\\en\\sub\\chunk1.cas", &folders), None);
+        assert_eq!(resolve("data/win32/loc/de/chunk0.cas", &folders), None);
+        // 只有语音文件夹本身，不是其中的文件
+        assert_eq!(resolve("data/win32/loc/en", &folders), None);
+    }
+
+    #[test]
+    fn resolve_nested_reference() {
+        let folders = [voice_folder()];
+        let expected = voice_folder().join("sub").join("chunk1.cas");
+        assert_eq!(resolve("data\\win32\\loc\\en\\sub\\chunk1.cas", &folders), Some(expected));
+    }
+
+    #[test]
+    fn resolve_ignores_empty_folder() {
+        let folders = [PathBuf::new(), voice_folder()];
+        assert_eq!(resolve("data/win32/loc/en/chunk0.cas", &folders), Some(voice_folder().join("chunk0.cas")));
+        assert_eq!(resolve("data/chunk0.cas", &[PathBuf::new()]), None);
+    }
+}
@@ -0,0 +1,308 @@
+//! 系统托盘：最小化后隐藏到托盘，托盘菜单列出可用的备份，点击后在后台切换到该语言；气泡通知也通过托盘图标显示
+
+use std::cell::RefCell;
+use std::iter;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use eframe::egui;
+use tracing::{info, warn};
+use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM};
+use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows_sys::Win32::UI::Shell::{
+    Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_ERROR, NIIF_INFO, NIM_ADD, NIM_DELETE, NIM_MODIFY,
+    NOTIFYICONDATAW,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    AppendMenuW, CreatePopupMenu, CreateWindowExW, DefWindowProcW, DestroyMenu, DestroyWindow, DispatchMessageW, FindWindowW,
+    GetCursorPos, GetMessageW, LoadIconW, PostMessageW, PostQuitMessage, RegisterClassW, SetForegroundWindow, ShowWindow,
+    TrackPopupMenu, HWND_MESSAGE, IDI_APPLICATION, MF_SEPARATOR, MF_STRING, MSG, SW_RESTORE, SW_SHOW, TPM_RETURNCMD,
+    TPM_RIGHTBUTTON, WM_APP, WM_CLOSE, WM_DESTROY, WM_LBUTTONUP, WM_RBUTTONUP, WNDCLASSW,
+};
+
+use crate::i18n::t;
+
+/// 托盘图标的回调消息
+const WM_TRAY: u32 = WM_APP + 1;
+/// 界面线程请求显示气泡通知
+const WM_NOTIFY: u32 = WM_APP + 2;
+const ICON_ID: u32 = 1;
+const MENU_SHOW: usize = 1;
+const MENU_EXIT: usize = 2;
+/// 备份菜单项的起始 ID
+const MENU_BACKUP: usize = 100;
+
+/// 托盘发给界面线程的事件
+pub enum TrayEvent {
+    /// 显示主窗口
+    Show,
+    /// 切换到该语言代码的备份
+    Switch(String),
+    Exit,
+}
+
+/// 界面线程与托盘线程共享的数据
+#[derive(Default)]
+struct Shared {
+    /// 菜单中的备份：(语言代码, 显示名称)
+    items: Mutex<Vec<(String, String)>>,
+    /// 等待显示的通知：(标题, 内容, 是否为错误)
+    notification: Mutex<Option<(String, String, bool)>>,
+    hwnd: AtomicIsize,
+}
+
+/// 托盘线程中窗口过程使用的状态
+struct TrayState {
+    shared: Arc<Shared>,
+    events: Sender<TrayEvent>,
+    ctx: egui::Context,
+    /// 主窗口标题，用于在界面线程未响应时直接显示主窗口
+    window_title: Vec<u16>,
+}
+
+thread_local! {
+    static STATE: RefCell<Option<TrayState>> = const { RefCell::new(None) };
+}
+
+pub struct Tray {
+    shared: Arc<Shared>,
+    events: Receiver<TrayEvent>,
+}
+
+impl Tray {
+    /// 在单独的线程中创建托盘图标和接收消息的隐藏窗口，创建失败时返回 None
+    pub fn spawn(ctx: egui::Context, window_title: &str) -> Option<Self> {
+        let shared = Arc::new(Shared::default());
+        let (events, receiver) = mpsc::channel();
+        let (ready, created) = mpsc::sync_channel(1);
+        let state = TrayState { shared: Arc::clone(&shared), events, ctx, window_title: wide(window_title) };
+        thread::spawn(move || {
+            let hwnd = create_window();
+            let _ = ready.send(!hwnd.is_null());
+            if hwnd.is_null() {
+                return;
+            }
+            state.shared.hwnd.store(hwnd as isize, Ordering::Relaxed);
+            STATE.with(|s| *s.borrow_mut() = Some(state));
+            add_icon(hwnd);
+            run_message_loop();
+        });
+        if !created.recv().unwrap_or(false) {
+            warn!("failed to create tray icon");
+            return None;
+        }
+        info!("tray icon created");
+        Some(Tray { shared, events: receiver })
+    }
+
+    /// 更新托盘菜单中的备份列表
+    pub fn set_items(&self, items: Vec<(String, String)>) {
+        if let Ok(mut current) = self.shared.items.lock() {
+            *current = items;
+        }
+    }
+
+    /// 取出所有未处理的事件
+    pub fn events(&self) -> Vec<TrayEvent> {
+        self.events.try_iter().collect()
+    }
+
+    /// 在托盘图标上显示气泡通知
+    pub fn notify(&self, title: &str, text: &str, is_error: bool) {
+        if let Ok(mut notification) = self.shared.notification.lock() {
+            *notification = Some((title.to_string(), text.to_string(), is_error));
+        }
+        self.post(WM_NOTIFY);
+    }
+
+    fn post(&self, message: u32) {
+        let hwnd = self.shared.hwnd.load(Ordering::Relaxed) as HWND;
+        // SAFETY: hwnd 为托盘线程创建的窗口，窗口已销毁时调用失败但无副作用
+        unsafe { PostMessageW(hwnd, message, 0, 0) };
+    }
+}
+
+impl Drop for Tray {
+    fn drop(&mut self) {
+        self.post(WM_CLOSE);
+    }
+}
+
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(iter::once(0)).collect()
+}
+
+/// 将文本复制到定长的 UTF-16 缓冲区，超出部分截断并保留结尾的 0
+fn fill(buffer: &mut [u16], text: &str) {
+    let len = buffer.len() - 1;
+    for (dst, src) in buffer[..len].iter_mut().zip(text.encode_utf16().chain(iter::repeat(0))) {
+        *dst = src;
+    }
+    buffer[len] = 0;
+}
+
+fn create_window() -> HWND {
+    let class_name = wide("BF6VoiceSwitcherTray");
+    // SAFETY: 类名以 0 结尾且在调用期间有效，窗口过程为本模块的 window_proc
+    unsafe {
+        let instance = GetModuleHandleW(std::ptr::null());
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance,
+            lpszClassName: class_name.as_ptr(),
+            ..std::mem::zeroed()
+        };
+        RegisterClassW(&class);
+        CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            std::ptr::null(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            std::ptr::null_mut(),
+            instance,
+            std::ptr::null(),
+        )
+    }
+}
+
+fn icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    NOTIFYICONDATAW {
+        cbSize: size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: ICON_ID,
+        ..Default::default()
+    }
+}
+
+fn add_icon(hwnd: HWND) {
+    let mut data = icon_data(hwnd);
+    data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+    data.uCallbackMessage = WM_TRAY;
+    fill(&mut data.szTip, t!("ui.title"));
+    // SAFETY: data 在调用期间有效，系统图标无需释放
+    unsafe {
+        data.hIcon = LoadIconW(std::ptr::null_mut(), IDI_APPLICATION);
+        Shell_NotifyIconW(NIM_ADD, &data);
+    }
+}
+
+fn run_message_loop() {
+    // SAFETY: msg 为可写的输出结构，消息循环在创建窗口的线程中运行
+    unsafe {
+        let mut msg: MSG = std::mem::zeroed();
+        while GetMessageW(&mut msg, std::ptr::null_mut(), 0, 0) > 0 {
+            DispatchMessageW(&msg);
+        }
+    }
+}
+
+/// 发送事件并唤醒界面线程
+fn send(event: TrayEvent) {
+    STATE.with(|s| {
+        if let Some(state) = s.borrow().as_ref() {
+            let _ = state.events.send(event);
+            state.ctx.request_repaint();
+        }
+    });
+}
+
+/// 直接显示主窗口：隐藏的窗口可能不再刷新，不能只依赖界面线程处理显示事件
+fn show_main_window() {
+    STATE.with(|s| {
+        if let Some(state) = s.borrow().as_ref() {
+            // SAFETY: 标题以 0 结尾，找不到窗口时返回空句柄，后续调用失败但无副作用
+            unsafe {
+                let hwnd = FindWindowW(std::ptr::null(), state.window_title.as_ptr());
+                ShowWindow(hwnd, SW_SHOW);
+                ShowWindow(hwnd, SW_RESTORE);
+                SetForegroundWindow(hwnd);
+            }
+        }
+    });
+    send(TrayEvent::Show);
+}
+
+/// 显示右键菜单并处理所选的项目
+fn show_menu(hwnd: HWND) {
+    let items = STATE.with(|s| {
+        s.borrow().as_ref().and_then(|state| state.shared.items.lock().ok().map(|items| items.clone())).unwrap_or_default()
+    });
+    // SAFETY: 菜单在本函数内创建和销毁，文本以 0 结尾且在调用期间有效
+    let selected = unsafe {
+        let menu = CreatePopupMenu();
+        for (idx, (_, name)) in items.iter().enumerate() {
+            AppendMenuW(menu, MF_STRING, MENU_BACKUP + idx, wide(&t!("ui.tray_switch", name)).as_ptr());
+        }
+        if !items.is_empty() {
+            AppendMenuW(menu, MF_SEPARATOR, 0, std::ptr::null());
+        }
+        AppendMenuW(menu, MF_STRING, MENU_SHOW, wide(t!("ui.tray_show")).as_ptr());
+        AppendMenuW(menu, MF_STRING, MENU_EXIT, wide(t!("ui.tray_exit")).as_ptr());
+
+        let mut point = POINT { x: 0, y: 0 };
+        GetCursorPos(&mut point);
+        // 菜单所属窗口必须在前台，否则点击菜单外部时菜单不会关闭
+        SetForegroundWindow(hwnd);
+        let selected = TrackPopupMenu(menu, TPM_RETURNCMD | TPM_RIGHTBUTTON, point.x, point.y, 0, hwnd, std::ptr::null());
+        DestroyMenu(menu);
+        selected as usize
+    };
+    match selected {
+        0 => {}
+        MENU_SHOW => show_main_window(),
+        MENU_EXIT => send(TrayEvent::Exit),
+        id => {
+            if let Some((code, _)) = items.get(id - MENU_BACKUP) {
+                send(TrayEvent::Switch(code.clone()));
+            }
+        }
+    }
+}
+
+fn show_notification(hwnd: HWND) {
+    let notification = STATE.with(|s| {
+        s.borrow().as_ref().and_then(|state| state.shared.notification.lock().ok().and_then(|mut n| n.take()))
+    });
+    let Some((title, text, is_error)) = notification else {
+        return;
+    };
+    let mut data = icon_data(hwnd);
+    data.uFlags = NIF_INFO;
+    data.dwInfoFlags = if is_error { NIIF_ERROR } else { NIIF_INFO };
+    fill(&mut data.szInfoTitle, &title);
+    fill(&mut data.szInfo, &text);
+    // SAFETY: data 在调用期间有效
+    unsafe { Shell_NotifyIconW(NIM_MODIFY, &data) };
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match message {
+        WM_TRAY => match lparam as u32 {
+            WM_LBUTTONUP => show_main_window(),
+            WM_RBUTTONUP => show_menu(hwnd),
+            _ => {}
+        },
+        WM_NOTIFY => show_notification(hwnd),
+        WM_CLOSE => {
+            // SAFETY: hwnd 为本线程创建的窗口
+            unsafe { DestroyWindow(hwnd) };
+        }
+        WM_DESTROY => {
+            // SAFETY: 删除本线程添加的托盘图标并结束消息循环
+            unsafe {
+                Shell_NotifyIconW(NIM_DELETE, &icon_data(hwnd));
+                PostQuitMessage(0);
+            }
+        }
+        // SAFETY: 其余消息交给默认窗口过程处理
+        _ => return unsafe { DefWindowProcW(hwnd, message, wparam, lparam) },
+    }
+    0
+}
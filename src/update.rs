@@ -0,0 +1,205 @@
+//! 检查 GitHub 上是否有新版本，在后台线程中请求 releases API，不阻塞界面；
+//! 自动更新时下载并校验新程序，重命名正在运行的程序后放到原位置，再重新启动
+
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread::{self, JoinHandle};
+
+use eframe::egui;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::copy::Progress;
+use crate::error::SwitcherError;
+use crate::hash;
+use crate::http;
+use crate::i18n::t;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/JohnsonRan/BF6-Voice-Switcher/releases/latest";
+
+/// 比当前版本新的发布
+#[derive(Clone, Debug)]
+pub struct Release {
+    /// 去掉 v 前缀的版本号
+    pub version: String,
+    /// 发布页面
+    pub url: String,
+    /// 可用于自动更新的程序文件，发布中没有 .exe 时为 None
+    pub asset: Option<ReleaseAsset>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReleaseAsset {
+    pub url: String,
+    /// GitHub 提供的 SHA-256
+    pub sha256: Option<String>,
+    /// 没有 SHA-256 时改用同名的 .sha256 文件
+    pub checksum_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LatestRelease {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+    /// 形如 sha256:<十六进制>
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+/// 从发布的文件中选出程序和它的校验文件
+fn find_asset(assets: &[Asset]) -> Option<ReleaseAsset> {
+    let exe = assets.iter().find(|a| a.name.to_lowercase().ends_with(".exe"))?;
+    let checksum_name = format!("{}.sha256", exe.name).to_lowercase();
+    Some(ReleaseAsset {
+        url: exe.browser_download_url.clone(),
+        sha256: exe.digest.as_deref().and_then(|d| d.strip_prefix("sha256:")).map(str::to_string),
+        checksum_url: assets
+            .iter()
+            .find(|a| a.name.to_lowercase() == checksum_name)
+            .map(|a| a.browser_download_url.clone()),
+    })
+}
+
+/// 解析 1.2.3 或 v1.2.3-beta 形式的版本号，返回各位数字和是否为预发布版本
+fn parse_version(text: &str) -> Option<(Vec<u64>, bool)> {
+    let text = text.trim().trim_start_matches(['v', 'V']);
+    let text = text.split('+').next()?;
+    let (core, pre) = match text.split_once('-') {
+        Some((core, _)) => (core, true),
+        None => (text, false),
+    };
+    let mut parts: Vec<u64> = core.split('.').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    // 1.2 与 1.2.0 视为同一版本
+    while parts.len() > 1 && parts.last() == Some(&0) {
+        parts.pop();
+    }
+    Some((parts, pre))
+}
+
+/// latest 是否比 current 新，同一版本号的正式版比预发布版新；无法解析时视为不新
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some((latest, latest_pre)), Some((current, current_pre))) => {
+            latest > current || (latest == current && current_pre && !latest_pre)
+        }
+        _ => false,
+    }
+}
+
+/// 请求最新发布，比当前版本新时返回发布信息
+pub fn check() -> io::Result<Option<Release>> {
+    let body = http::get(LATEST_RELEASE_URL, "Accept: application/vnd.github+json", None)?;
+    let latest: LatestRelease = serde_json::from_slice(&body).map_err(io::Error::other)?;
+    let current = env!("CARGO_PKG_VERSION");
+    info!("latest release {}, current {}", latest.tag_name, current);
+    if !is_newer(&latest.tag_name, current) {
+        return Ok(None);
+    }
+    let version = latest.tag_name.trim_start_matches(['v', 'V']).to_string();
+    let asset = find_asset(&latest.assets);
+    Ok(Some(Release { version, url: latest.html_url, asset }))
+}
+
+/// 在程序路径后加上后缀，例如 xxx.exe.old
+fn sibling(exe: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(exe.as_os_str());
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// 删除上次更新时留下的旧程序，上次更新后的进程退出前可能仍被占用，失败时下次再删
+pub fn remove_old(exe: &Path) {
+    let old = sibling(exe, ".old");
+    if old.exists() {
+        match fs::remove_file(&old) {
+            Ok(()) => info!("removed {}", old.display()),
+            Err(e) => warn!("failed to remove {}: {}", old.display(), e),
+        }
+    }
+}
+
+/// 以相同的参数启动新程序
+pub fn relaunch(exe: &Path) -> io::Result<()> {
+    Command::new(exe).args(std::env::args_os().skip(1)).spawn()?;
+    Ok(())
+}
+
+/// 下载、校验并替换程序
+pub struct SelfUpdateJob {
+    pub version: String,
+    pub asset: ReleaseAsset,
+    pub exe: PathBuf,
+}
+
+impl SelfUpdateJob {
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        let expected = match (&self.asset.sha256, &self.asset.checksum_url) {
+            (Some(sha256), _) => sha256.clone(),
+            (None, Some(url)) => {
+                // 校验文件为 sha256sum 的输出格式：<哈希> <文件名>
+                let content = http::get(url, "", None).map_err(SwitcherError::UpdateDownload)?;
+                String::from_utf8_lossy(&content).split_whitespace().next().unwrap_or_default().to_string()
+            }
+            (None, None) => return Err(SwitcherError::UpdateNoChecksum),
+        };
+        progress.update(|s| s.current_file = self.asset.url.rsplit('/').next().unwrap_or_default().to_string());
+        let data = http::get(&self.asset.url, "", Some(progress)).map_err(SwitcherError::UpdateDownload)?;
+        let actual = hash::hash_reader(&mut data.as_slice(), |_| Ok(())).map_err(SwitcherError::UpdateDownload)?;
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            return Err(SwitcherError::UpdateHashMismatch);
+        }
+
+        // 正在运行的程序不能覆盖，但可以重命名
+        let new = sibling(&self.exe, ".new");
+        let old = sibling(&self.exe, ".old");
+        fs::write(&new, &data).map_err(SwitcherError::UpdateInstall)?;
+        let _ = fs::remove_file(&old);
+        if let Err(e) = fs::rename(&self.exe, &old) {
+            let _ = fs::remove_file(&new);
+            return Err(SwitcherError::UpdateInstall(e));
+        }
+        if let Err(e) = fs::rename(&new, &self.exe) {
+            let _ = fs::rename(&old, &self.exe);
+            let _ = fs::remove_file(&new);
+            return Err(SwitcherError::UpdateInstall(e));
+        }
+        info!("updated {} to {}", self.exe.display(), self.version);
+        Ok(t!("status.update_installed", self.version))
+    }
+}
+
+/// 后台的更新检查
+pub struct UpdateCheck {
+    handle: JoinHandle<io::Result<Option<Release>>>,
+}
+
+impl UpdateCheck {
+    /// 在后台检查，结束后唤醒界面线程显示结果
+    pub fn spawn(ctx: egui::Context) -> Self {
+        let handle = thread::spawn(move || {
+            let result = check();
+            ctx.request_repaint();
+            result
+        });
+        UpdateCheck { handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn join(self) -> io::Result<Option<Release>> {
+        self.handle.join().unwrap_or_else(|_| Err(io::Error::other("update check panicked")))
+    }
+}
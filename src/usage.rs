@@ -0,0 +1,107 @@
+//! 备份占用统计：在后台线程中统计备份目录的总大小、各语言和各版本的大小、硬链接去重节省的空间和剩余空间
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+
+use crate::dedup;
+use crate::disk;
+use crate::link;
+
+/// 单个备份的占用
+pub struct BackupUsage {
+    pub lang_code: String,
+    pub build_id: String,
+    pub size: u64,
+}
+
+#[derive(Default)]
+pub struct Usage {
+    /// 当前游戏各语言的备份
+    pub backups: Vec<BackupUsage>,
+    /// 整个备份目录中所有文件的大小之和
+    pub total: u64,
+    /// 同一文件的多个硬链接只计算一次后的实际占用
+    pub on_disk: u64,
+    /// 备份所在分区的剩余空间
+    pub free: Option<u64>,
+}
+
+impl Usage {
+    /// 硬链接去重节省的空间
+    pub fn dedup_savings(&self) -> u64 {
+        self.total.saturating_sub(self.on_disk)
+    }
+
+    /// 按版本汇总的大小，版本号从新到旧
+    pub fn by_build(&self) -> Vec<(&str, u64)> {
+        let mut builds: BTreeMap<&str, u64> = BTreeMap::new();
+        for backup in &self.backups {
+            *builds.entry(backup.build_id.as_str()).or_default() += backup.size;
+        }
+        let mut builds: Vec<(&str, u64)> = builds.into_iter().collect();
+        builds.sort_by_key(|(build, _)| std::cmp::Reverse(build.parse::<u64>().unwrap_or(0)));
+        builds
+    }
+}
+
+/// 统计 dir 中所有文件的大小之和，以及按硬链接去重后的大小，不进入 Junction
+fn walk(dir: &Path, seen: &mut HashSet<(u32, u64)>, total: &mut u64, on_disk: &mut u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if !link::is_junction(&path) {
+                walk(&path, seen, total, on_disk);
+            }
+        } else if metadata.is_file() {
+            *total += metadata.len();
+            // 无法读取文件索引时按独立文件计算
+            if dedup::file_id(&path).map_or(true, |id| seen.insert(id)) {
+                *on_disk += metadata.len();
+            }
+        }
+    }
+}
+
+/// dir 按硬链接去重后实际占用的空间
+pub fn footprint(dir: &Path) -> u64 {
+    let (mut total, mut on_disk) = (0, 0);
+    walk(dir, &mut HashSet::new(), &mut total, &mut on_disk);
+    on_disk
+}
+
+/// 在后台线程中统计，界面线程轮询结果
+pub struct UsageScan {
+    handle: JoinHandle<Usage>,
+}
+
+impl UsageScan {
+    /// root 为整个备份目录，backups 为当前游戏的备份：(语言代码, 版本, 备份目录)
+    pub fn spawn(root: PathBuf, backups: Vec<(String, String, PathBuf)>) -> Self {
+        let handle = thread::spawn(move || {
+            let mut usage = Usage { free: disk::free_space(&root), ..Default::default() };
+            for (lang_code, build_id, path) in backups {
+                usage.backups.push(BackupUsage { lang_code, build_id, size: disk::dir_size(&path) });
+            }
+            usage.backups.sort_by_key(|b| std::cmp::Reverse(b.size));
+            walk(&root, &mut HashSet::new(), &mut usage.total, &mut usage.on_disk);
+            usage
+        });
+        UsageScan { handle }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    pub fn join(self) -> Usage {
+        self.handle.join().unwrap_or_default()
+    }
+}
@@ -0,0 +1,63 @@
+//! 游戏目录中的 user.cfg：Frostbite 游戏启动时读取其中的控制台命令，写入 miles_language 后
+//! 不经过 Steam 启动（EA App、Xbox 或直接运行主程序）也能使用所选的语音语言
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::error::SwitcherError;
+
+pub const USER_CFG_FILE: &str = "user.cfg";
+
+/// 游戏安装目录中的 user.cfg
+pub fn path(game_root: &Path) -> PathBuf {
+    game_root.join(USER_CFG_FILE)
+}
+
+/// 启动参数去掉开头的 + 即为 user.cfg 中的命令名，如 +miles_language → miles_language
+pub fn command_name(launch_param: &str) -> &str {
+    launch_param.trim_start_matches('+')
+}
+
+/// 一行是否为 name 命令，忽略大小写
+fn is_command(line: &str, name: &str) -> bool {
+    line.split_whitespace().next().is_some_and(|first| first.eq_ignore_ascii_case(name))
+}
+
+/// user.cfg 中 name 命令的值，文件或命令不存在时返回 None
+pub fn read(path: &Path, name: &str) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let line = content.lines().rev().find(|line| is_command(line, name))?;
+    line.split_whitespace().nth(1).map(|value| value.trim_matches('"').to_string())
+}
+
+/// 写入（或在 value 为 None 时移除）name 命令，其余行保持不变；移除后文件为空时删除该文件
+pub fn write(path: &Path, name: &str, value: Option<&str>) -> Result<(), SwitcherError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(SwitcherError::UserCfg(e)),
+    };
+    let newline = if content.contains("\r\n") || content.is_empty() { "\r\n" } else { "\n" };
+    let mut lines: Vec<String> = content.lines().filter(|line| !is_command(line, name)).map(str::to_string).collect();
+    if let Some(value) = value {
+        lines.push(format!("{} {}", name, value));
+    }
+
+    if lines.iter().all(|line| line.trim().is_empty()) {
+        if path.exists() {
+            fs::remove_file(path).map_err(SwitcherError::UserCfg)?;
+            info!("removed empty {}", path.display());
+        }
+        return Ok(());
+    }
+    let mut new_content = lines.join(newline);
+    new_content.push_str(newline);
+    // 通过临时文件替换，避免写入中断损坏原有的设置
+    let tmp_path = path.with_extension("cfg.tmp");
+    fs::write(&tmp_path, new_content).map_err(SwitcherError::UserCfg)?;
+    fs::rename(&tmp_path, path).map_err(SwitcherError::UserCfg)?;
+    info!("wrote {} {:?} to {}", name, value, path.display());
+    Ok(())
+}
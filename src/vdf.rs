@@ -0,0 +1,259 @@
+//! 文本格式 VDF (KeyValues) 的最小化解析与就地修改
+//!
+//! 只修改目标值所在的字节范围，文件其余部分（缩进、注释、未知字段）保持原样。
+
+use crate::error::SwitcherError;
+
+enum Token {
+    Str { value: String, start: usize, end: usize },
+    Open,
+    Close(usize),
+}
+
+enum Child {
+    Value(usize),
+    Block(usize, usize),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, SwitcherError> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b' ' | b'\t' | b'\r' | b'\n' => i += 1,
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'{' => {
+                tokens.push(Token::Open);
+                i += 1;
+            }
+            b'}' => {
+                tokens.push(Token::Close(i));
+                i += 1;
+            }
+            b'"' => {
+                let start = i;
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    let Some(&c) = bytes.get(i) else {
+                        return Err(SwitcherError::VdfUnterminated);
+                    };
+                    match c {
+                        b'"' => break,
+                        b'\\' if i + 1 < bytes.len() => {
+                            // 被转义的可能是多字节字符，同样整体读取
+                            let ch = text[i + 1..].chars().next().unwrap_or('?');
+                            match ch {
+                                'n' => value.push('\n'),
+                                't' => value.push('\t'),
+                                other => value.push(other),
+                            }
+                            i += 1 + ch.len_utf8();
+                        }
+                        _ => {
+                            // 按 UTF-8 字符整体读取，避免拆开多字节字符
+                            let ch = text[i..].chars().next().unwrap_or('?');
+                            value.push(ch);
+                            i += ch.len_utf8();
+                        }
+                    }
+                }
+                i += 1;
+                tokens.push(Token::Str { value, start, end: i });
+            }
+            _ => {
+                // 无引号的值
+                let start = i;
+                while i < bytes.len() && !matches!(bytes[i], b' ' | b'\t' | b'\r' | b'\n' | b'{' | b'}' | b'"') {
+                    i += 1;
+                }
+                tokens.push(Token::Str { value: text[start..i].to_string(), start, end: i });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// 在 tokens[from..to] 范围内查找子键（不区分大小写）
+fn find_child(tokens: &[Token], from: usize, to: usize, key: &str) -> Result<Option<Child>, SwitcherError> {
+    let mut i = from;
+    while i < to {
+        let Token::Str { value: name, .. } = &tokens[i] else {
+            return Err(SwitcherError::VdfInvalid);
+        };
+        match tokens.get(i + 1) {
+            Some(Token::Str { .. }) => {
+                if name.eq_ignore_ascii_case(key) {
+                    return Ok(Some(Child::Value(i + 1)));
+                }
+                i += 2;
+            }
+            Some(Token::Open) => {
+                let close = matching_close(tokens, i + 1)?;
+                if name.eq_ignore_ascii_case(key) {
+                    return Ok(Some(Child::Block(i + 1, close)));
+                }
+                i = close + 1;
+            }
+            _ => return Err(SwitcherError::VdfInvalid),
+        }
+    }
+    Ok(None)
+}
+
+fn matching_close(tokens: &[Token], open: usize) -> Result<usize, SwitcherError> {
+    let mut depth = 0;
+    for (idx, token) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Open => depth += 1,
+            Token::Close(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(idx);
+                }
+            }
+            Token::Str { .. } => {}
+        }
+    }
+    Err(SwitcherError::VdfBraces)
+}
+
+/// 按路径定位块，返回块的 (Open, Close) token 索引
+fn find_block(tokens: &[Token], path: &[&str]) -> Result<Option<(usize, usize)>, SwitcherError> {
+    let mut range = (0, tokens.len());
+    let mut block = None;
+    for key in path {
+        match find_child(tokens, range.0, range.1, key)? {
+            Some(Child::Block(open, close)) => {
+                range = (open + 1, close);
+                block = Some((open, close));
+            }
+            _ => return Ok(None),
+        }
+    }
+    Ok(block)
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// 读取路径下某个键的值
+pub fn get_value(text: &str, path: &[&str], key: &str) -> Result<Option<String>, SwitcherError> {
+    let tokens = tokenize(text)?;
+    let Some((open, close)) = find_block(&tokens, path)? else {
+        return Ok(None);
+    };
+    match find_child(&tokens, open + 1, close, key)? {
+        Some(Child::Value(idx)) => match &tokens[idx] {
+            Token::Str { value, .. } => Ok(Some(value.clone())),
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// 设置路径下某个键的值，缺失的块和键会自动创建，返回修改后的完整文本
+pub fn set_value(text: &str, path: &[&str], key: &str, value: &str) -> Result<String, SwitcherError> {
+    let mut text = text.to_string();
+
+    // 逐级确保路径上的块都存在
+    for depth in 1..=path.len() {
+        let tokens = tokenize(&text)?;
+        if find_block(&tokens, &path[..depth])?.is_some() {
+            continue;
+        }
+        let Some((_, parent_close)) = find_block(&tokens, &path[..depth - 1])? else {
+            return Err(SwitcherError::VdfMissingNode(path[0].to_string()));
+        };
+        let Token::Close(pos) = tokens[parent_close] else {
+            unreachable!();
+        };
+        // 插入到父块的右括号之前，缩进与 Steam 写出的格式一致
+        let child_indent = "\t".repeat(depth - 1);
+        let parent_indent = "\t".repeat(depth.saturating_sub(2));
+        let insert = format!(
+            "\t\"{}\"\n{}{{\n{}}}\n{}",
+            escape(path[depth - 1]), child_indent, child_indent, parent_indent
+        );
+        text.insert_str(pos, &insert);
+    }
+
+    let tokens = tokenize(&text)?;
+    let Some((open, close)) = find_block(&tokens, path)? else {
+        return Err(SwitcherError::VdfWrite);
+    };
+    match find_child(&tokens, open + 1, close, key)? {
+        Some(Child::Value(idx)) => {
+            let Token::Str { start, end, .. } = tokens[idx] else {
+                unreachable!();
+            };
+            text.replace_range(start..end, &format!("\"{}\"", escape(value)));
+        }
+        Some(Child::Block(..)) => return Err(SwitcherError::VdfNotValue(key.to_string())),
+        None => {
+            let Token::Close(pos) = tokens[close] else {
+                unreachable!();
+            };
+            let indent = "\t".repeat(path.len() - 1);
+            let insert = format!("\t\"{}\"\t\t\"{}\"\n{}", escape(key), escape(value), indent);
+            text.insert_str(pos, &insert);
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(text: &str) -> Vec<String> {
+        tokenize(text)
+            .unwrap()
+            .into_iter()
+            .filter_map(|token| match token {
+                Token::Str { value, .. } => Some(value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_quoted_unquoted_and_comments() {
+        let text = "// comment\n\"a\" { \"b\" c }\n";
+        assert_eq!(strings(text), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn tokenize_escapes() {
+        assert_eq!(strings(r#""a\"b\\c\nd\te""#), ["a\"b\\c\nd\te"]);
+    }
+
+    #[test]
+    fn tokenize_escaped_multibyte_char() {
+        assert_eq!(strings("\"\\语音\" \"\\é\""), ["语音", "é"]);
+    }
+
+    #[test]
+    fn tokenize_unterminated() {
+        assert!(matches!(tokenize("\"abc"), Err(SwitcherError::VdfUnterminated)));
+        assert!(matches!(tokenize("\"abc\\"), Err(SwitcherError::VdfUnterminated)));
+    }
+
+    #[test]
+    fn get_and_set_value() {
+        let text = "\"Root\"\n{\n\t\"Key\"\t\t\"old\"\n}\n";
+        assert_eq!(get_value(text, &["root"], "key").unwrap().as_deref(), Some("old"));
+        let text = set_value(text, &["Root"], "Key", "new \"value\"").unwrap();
+        assert_eq!(get_value(&text, &["Root"], "Key").unwrap().as_deref(), Some("new \"value\""));
+        let text = set_value(&text, &["Root", "Child"], "Other", "1").unwrap();
+        assert_eq!(get_value(&text, &["Root", "Child"], "Other").unwrap().as_deref(), Some("1"));
+        assert_eq!(get_value(&text, &["Root"], "Key").unwrap().as_deref(), Some("new \"value\""));
+    }
+}
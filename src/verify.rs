@@ -0,0 +1,157 @@
+//! 校验备份：检查记录的文件夹和 .toc 文件是否都存在，并完整读取每个文件，确认没有无法读取或损坏的文件
+
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+use zip::ZipArchive;
+
+use crate::archive;
+use crate::backup_info::BackupInfo;
+use crate::copy::Progress;
+use crate::disk;
+use crate::error::SwitcherError;
+use crate::hash;
+use crate::i18n::t;
+use crate::toc;
+
+/// 恢复前的快速检查：记录的文件夹和 .toc 文件都存在、.toc 文件与备份时的哈希一致、.toc 引用的语音文件都在备份中
+///
+/// 不完整读取语音文件，压缩的备份在解压时由 zip 检查 CRC。
+pub fn check_before_restore(backup_path: &Path, info: &BackupInfo) -> Result<(), SwitcherError> {
+    let mut missing: Vec<PathBuf> = info
+        .folders
+        .iter()
+        .chain(&info.toc_files)
+        .filter(|rel_path| !backup_path.join(rel_path).exists())
+        .cloned()
+        .collect();
+    let corrupted: Vec<PathBuf> = info
+        .toc_hashes
+        .iter()
+        .filter(|(rel_path, expected)| {
+            let path = backup_path.join(rel_path);
+            path.exists() && hash::file_hash(&path).map_or(true, |actual| &actual != *expected)
+        })
+        .map(|(rel_path, _)| rel_path.clone())
+        .collect();
+    if missing.is_empty() {
+        missing = toc::missing_references(backup_path, &info.toc_files, &info.folders);
+    }
+    if missing.is_empty() && corrupted.is_empty() {
+        return Ok(());
+    }
+    info!("backup {} failed the restore check: {} missing, {} corrupted", backup_path.display(), missing.len(), corrupted.len());
+    Err(SwitcherError::BackupDamaged { missing, corrupted })
+}
+
+/// 按相对路径排序的文件列表及大小，目录无法打开时返回 None
+fn listing(dir: &Path) -> Option<Vec<(PathBuf, u64)>> {
+    fs::read_dir(dir).ok()?;
+    let mut files = Vec::new();
+    archive::list_files(dir, dir, &mut files);
+    let mut listing: Vec<(PathBuf, u64)> = files
+        .into_iter()
+        .map(|rel_path| {
+            let size = fs::metadata(dir.join(&rel_path)).map_or(0, |m| m.len());
+            (rel_path, size)
+        })
+        .collect();
+    listing.sort();
+    Some(listing)
+}
+
+/// 恢复后的检查：打开游戏目录中的每个语音文件夹（链接时即解析链接），文件列表和大小与备份一致，
+/// 复制的 .toc 文件与备份中的哈希一致；返回不一致的路径（相对游戏目录）
+pub fn check_restored(backup_path: &Path, target: &Path, info: &BackupInfo, folders: &[PathBuf], toc_files: &[PathBuf]) -> Vec<PathBuf> {
+    let mut mismatched = Vec::new();
+    for rel_path in folders {
+        let restored = listing(&target.join(rel_path));
+        if restored.is_none() || restored != listing(&backup_path.join(rel_path)) {
+            mismatched.push(rel_path.clone());
+        }
+    }
+    for rel_path in toc_files {
+        let expected = match info.toc_hashes.get(rel_path) {
+            Some(hash) => Ok(hash.clone()),
+            None => hash::file_hash(&backup_path.join(rel_path)),
+        };
+        let actual = hash::file_hash(&target.join(rel_path));
+        if !matches!((expected, actual), (Ok(expected), Ok(actual)) if expected == actual) {
+            mismatched.push(rel_path.clone());
+        }
+    }
+    mismatched
+}
+
+/// 游戏目录 target 中的 .toc 文件与备份时记录的哈希全部一致，说明游戏更新没有修改该语言的语音数据
+pub fn toc_unchanged(info: &BackupInfo, target: &Path) -> bool {
+    !info.toc_hashes.is_empty()
+        && info.toc_hashes.iter().all(|(rel_path, expected)| hash::file_hash(&target.join(rel_path)).is_ok_and(|actual| actual == *expected))
+}
+
+pub struct VerifyJob {
+    pub backup_path: PathBuf,
+    pub info: BackupInfo,
+    pub lang_name: String,
+}
+
+impl VerifyJob {
+    pub fn total_size(&self) -> u64 {
+        if self.info.compressed {
+            archive::unpacked_size(&self.backup_path)
+        } else {
+            disk::dir_size(&self.backup_path)
+        }
+    }
+
+    pub fn run(self, progress: &Progress) -> Result<String, SwitcherError> {
+        let count = if self.info.compressed { self.verify_archive(progress)? } else { self.verify_files(progress)? };
+        info!("verified {} ({} files)", self.backup_path.display(), count);
+        Ok(t!("status.verified", self.lang_name, count))
+    }
+
+    fn verify_files(&self, progress: &Progress) -> Result<usize, SwitcherError> {
+        let recorded: Vec<&PathBuf> = self.info.folders.iter().chain(&self.info.toc_files).collect();
+        if let Some(missing) = recorded.iter().find(|rel_path| !self.backup_path.join(rel_path).exists()) {
+            return Err(SwitcherError::VerifyMissing((*missing).clone()));
+        }
+
+        // 旧版本的备份没有记录文件夹，校验整个备份目录
+        let mut files = Vec::new();
+        if recorded.is_empty() {
+            archive::list_files(&self.backup_path, &self.backup_path, &mut files);
+        } else {
+            for rel_path in recorded {
+                archive::list_files(&self.backup_path, &self.backup_path.join(rel_path), &mut files);
+            }
+        }
+        for rel_path in &files {
+            progress.update(|s| s.current_file = rel_path.display().to_string());
+            File::open(self.backup_path.join(rel_path))
+                .and_then(|mut file| {
+                    hash::hash_reader(&mut file, |data| {
+                        progress.update(|s| s.done_bytes += data.len() as u64);
+                        Ok(())
+                    })
+                })
+                .map_err(|e| SwitcherError::VerifyFailed { path: rel_path.clone(), source: e })?;
+        }
+        Ok(files.len())
+    }
+
+    /// 完整解压一遍压缩包，zip 在读取结束时检查 CRC
+    fn verify_archive(&self, progress: &Progress) -> Result<usize, SwitcherError> {
+        let path = archive::archive_path(&self.backup_path);
+        let mut zip = ZipArchive::new(File::open(&path)?)?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            let name = PathBuf::from(entry.name());
+            progress.update(|s| s.current_file = name.display().to_string());
+            let size = io::copy(&mut entry, &mut io::sink()).map_err(|e| SwitcherError::VerifyFailed { path: name, source: e })?;
+            progress.update(|s| s.done_bytes += size);
+        }
+        Ok(zip.len())
+    }
+}
@@ -0,0 +1,68 @@
+//! 窗口位置和大小：关闭时保存到设置，启动时恢复，并限制在所在显示器的工作区内
+
+use eframe::egui;
+use windows_sys::Win32::Foundation::RECT;
+use windows_sys::Win32::Graphics::Gdi::{GetMonitorInfoW, MonitorFromRect, MONITORINFO, MONITOR_DEFAULTTONEAREST};
+
+use crate::settings::WindowGeometry;
+
+/// 没有保存的窗口大小时使用的大小
+const DEFAULT_SIZE: [f32; 2] = [620.0, 550.0];
+const MIN_SIZE: [f32; 2] = [480.0, 360.0];
+
+/// 窗口所在（不在任何显示器上时为最近的）显示器的工作区
+fn work_area(geometry: &WindowGeometry) -> Option<egui::Rect> {
+    let rect = RECT {
+        left: geometry.x as i32,
+        top: geometry.y as i32,
+        right: (geometry.x + geometry.width) as i32,
+        bottom: (geometry.y + geometry.height) as i32,
+    };
+    // SAFETY: rect 和 info 在调用期间有效，info.cbSize 已设置
+    let work = unsafe {
+        let monitor = MonitorFromRect(&rect, MONITOR_DEFAULTTONEAREST);
+        let mut info = MONITORINFO { cbSize: size_of::<MONITORINFO>() as u32, ..std::mem::zeroed() };
+        if GetMonitorInfoW(monitor, &mut info) == 0 {
+            return None;
+        }
+        info.rcWork
+    };
+    Some(egui::Rect::from_min_max(
+        egui::pos2(work.left as f32, work.top as f32),
+        egui::pos2(work.right as f32, work.bottom as f32),
+    ))
+}
+
+/// 设置可调整的窗口大小，并恢复保存的位置；显示器变化后超出工作区的部分移回工作区内
+pub fn restore(builder: egui::ViewportBuilder, geometry: Option<&WindowGeometry>) -> egui::ViewportBuilder {
+    let builder = builder.with_inner_size(DEFAULT_SIZE).with_min_inner_size(MIN_SIZE).with_resizable(true);
+    let Some(geometry) = geometry else {
+        return builder;
+    };
+    let Some(area) = work_area(geometry) else {
+        return builder.with_inner_size([geometry.width.max(MIN_SIZE[0]), geometry.height.max(MIN_SIZE[1])]);
+    };
+    let width = geometry.width.clamp(MIN_SIZE[0], area.width().max(MIN_SIZE[0]));
+    let height = geometry.height.clamp(MIN_SIZE[1], area.height().max(MIN_SIZE[1]));
+    let x = geometry.x.clamp(area.left(), (area.right() - width).max(area.left()));
+    let y = geometry.y.clamp(area.top(), (area.bottom() - height).max(area.top()));
+    builder.with_inner_size([width, height]).with_position([x, y]).with_maximized(geometry.maximized)
+}
+
+/// 当前窗口的位置和大小，最小化时返回 None
+pub fn current(ctx: &egui::Context) -> Option<WindowGeometry> {
+    ctx.input(|i| {
+        let viewport = i.viewport();
+        if viewport.minimized == Some(true) {
+            return None;
+        }
+        let (outer, inner) = (viewport.outer_rect?, viewport.inner_rect?);
+        Some(WindowGeometry {
+            x: outer.left(),
+            y: outer.top(),
+            width: inner.width(),
+            height: inner.height(),
+            maximized: viewport.maximized == Some(true),
+        })
+    })
+}
@@ -0,0 +1,60 @@
+//! 向导模式：把检测、选择语言、备份、切换 Steam 语言、恢复和启动选项分成逐页显示，每页只显示该步骤需要的控件
+
+use crate::i18n::t;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WizardPage {
+    #[default]
+    Detect,
+    Language,
+    Backup,
+    SteamLanguage,
+    Restore,
+    LaunchOption,
+}
+
+impl WizardPage {
+    pub const ALL: [WizardPage; 6] = [
+        WizardPage::Detect,
+        WizardPage::Language,
+        WizardPage::Backup,
+        WizardPage::SteamLanguage,
+        WizardPage::Restore,
+        WizardPage::LaunchOption,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            WizardPage::Detect => t!("ui.wizard_detect"),
+            WizardPage::Language => t!("ui.wizard_language"),
+            WizardPage::Backup => t!("ui.wizard_backup"),
+            WizardPage::SteamLanguage => t!("ui.wizard_steam_language"),
+            WizardPage::Restore => t!("ui.wizard_restore"),
+            WizardPage::LaunchOption => t!("ui.wizard_launch_option"),
+        }
+    }
+
+    /// 从 1 开始的页码
+    pub fn number(&self) -> usize {
+        Self::ALL.iter().position(|page| page == self).unwrap_or(0) + 1
+    }
+
+    pub fn previous(&self) -> Option<WizardPage> {
+        Self::ALL.get(self.number().checked_sub(2)?).copied()
+    }
+
+    pub fn next(&self) -> Option<WizardPage> {
+        Self::ALL.get(self.number()).copied()
+    }
+
+    /// 对应的经典布局中的步骤序号，用于显示完成状态
+    pub fn step(&self) -> Option<usize> {
+        match self {
+            WizardPage::Backup => Some(2),
+            WizardPage::Language => Some(1),
+            WizardPage::Detect | WizardPage::SteamLanguage => None,
+            WizardPage::Restore => Some(3),
+            WizardPage::LaunchOption => Some(4),
+        }
+    }
+}
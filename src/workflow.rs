@@ -0,0 +1,92 @@
+//! 使用流程的进度：根据备份、游戏语音和启动选项判断每个步骤的状态，并给出下一步该做什么
+
+use eframe::egui;
+
+use crate::a11y;
+use crate::i18n::t;
+use crate::theme;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepState {
+    Done,
+    /// 已完成但与所选语言不一致，或存在需要处理的问题
+    Warning,
+    Pending,
+}
+
+impl StepState {
+    /// 在步骤标题后显示的标记，未完成时不显示
+    pub fn show(&self, ui: &mut egui::Ui) {
+        match self {
+            StepState::Done => {
+                let response = ui.label(egui::RichText::new("✔").color(theme::success(ui)));
+                a11y::name(&response, egui::WidgetType::Label, t!("ui.step_done"));
+            }
+            StepState::Warning => {
+                let response = ui.label(egui::RichText::new("⚠").color(theme::warning(ui)));
+                a11y::name(&response, egui::WidgetType::Label, t!("ui.step_warning"));
+            }
+            StepState::Pending => {}
+        }
+    }
+}
+
+/// 判断步骤状态所需的信息，由界面线程根据当前状态收集
+pub struct WorkflowInput<'a> {
+    pub game_name: &'a str,
+    pub lang_name: &'a str,
+    /// 已填写语音文件夹路径
+    pub source_set: bool,
+    /// 语音文件夹路径存在
+    pub source_valid: bool,
+    /// 所选语言的语音由游戏自身安装在游戏目录中
+    pub installed: bool,
+    pub backup_exists: bool,
+    /// 游戏当前使用所选语言的语音
+    pub active: bool,
+    /// 启动选项中的语音语言：None 为未设置，Some(true) 为所选语言
+    pub launch_option: Option<bool>,
+}
+
+pub struct Workflow {
+    pub steps: [StepState; 5],
+    /// 第一个未完成步骤的提示，全部完成时为 None
+    pub next: Option<String>,
+}
+
+pub fn evaluate(input: &WorkflowInput) -> Workflow {
+    let prepared = if input.installed || input.backup_exists { StepState::Done } else { StepState::Pending };
+    // 总是有一个选中的语言
+    let chosen = StepState::Done;
+    let backed_up = if input.source_set && !input.source_valid {
+        StepState::Warning
+    } else if input.source_valid && input.backup_exists {
+        StepState::Done
+    } else {
+        StepState::Pending
+    };
+    let restored = if input.active { StepState::Done } else { StepState::Pending };
+    let launch = match input.launch_option {
+        Some(true) => StepState::Done,
+        Some(false) => StepState::Warning,
+        None => StepState::Pending,
+    };
+    let steps = [prepared, chosen, backed_up, restored, launch];
+
+    let next = if prepared != StepState::Done {
+        Some(t!("ui.next_prepare", input.game_name, input.lang_name))
+    } else if !input.source_valid {
+        Some(t!("ui.next_source").to_string())
+    } else if backed_up != StepState::Done {
+        Some(t!("ui.next_backup", input.lang_name))
+    } else if restored != StepState::Done {
+        Some(t!("ui.next_restore", input.lang_name))
+    } else if launch == StepState::Warning {
+        Some(t!("ui.next_launch_mismatch", input.lang_name))
+    } else if launch != StepState::Done {
+        Some(t!("ui.next_launch", input.lang_name))
+    } else {
+        None
+    };
+    Workflow { steps, next }
+}
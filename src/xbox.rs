@@ -0,0 +1,83 @@
+//! Xbox PC（Game Pass）安装：按各磁盘的 .GamingRoot 找到 XboxGames 目录，游戏文件位于可写的 Content 子目录
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tracing::{info, warn};
+
+use crate::disk;
+
+/// 盘符根目录下记录 Xbox 游戏目录的文件
+const GAMING_ROOT_FILE: &str = ".GamingRoot";
+
+/// .GamingRoot 不存在或无法解析时使用的默认目录
+const DEFAULT_LIBRARY: &str = "XboxGames";
+
+/// 游戏文件所在的子目录，WindowsApps 中的原始包受系统保护，只能操作这里
+const CONTENT_DIR: &str = "Content";
+
+pub struct XboxInstall {
+    /// 游戏的 Content 目录
+    pub game_path: PathBuf,
+    /// appxmanifest.xml 中的包版本，读取不到时为空
+    pub version: String,
+    pub manifest: PathBuf,
+}
+
+/// 查找 Xbox 安装的游戏，exe_name 不为空时要求 Content 目录中存在该主程序
+pub fn find_installs(install_dir: &str, exe_name: &str) -> Vec<XboxInstall> {
+    if install_dir.is_empty() {
+        return Vec::new();
+    }
+    let mut installs = Vec::new();
+    for drive in disk::fixed_drives() {
+        for library in gaming_roots(&drive) {
+            let game_path = library.join(install_dir).join(CONTENT_DIR);
+            if !game_path.is_dir() || (!exe_name.is_empty() && !game_path.join(exe_name).exists()) {
+                continue;
+            }
+            let manifest = game_path.join("appxmanifest.xml");
+            let version = fs::read_to_string(&manifest).ok().and_then(|content| identity_version(&content)).unwrap_or_default();
+            info!("found xbox install at {} (version {})", game_path.display(), version);
+            if !is_writable(&game_path) {
+                warn!("xbox content folder {} is not writable", game_path.display());
+            }
+            installs.push(XboxInstall { game_path, version, manifest });
+        }
+    }
+    installs
+}
+
+/// 读取盘符根目录的 .GamingRoot：4 字节 "RGBX"、4 字节数量，之后为以 0 结尾的 UTF-16 相对路径
+fn gaming_roots(drive: &Path) -> Vec<PathBuf> {
+    let parsed = fs::read(drive.join(GAMING_ROOT_FILE)).ok().filter(|data| data.starts_with(b"RGBX")).map(|data| {
+        let units: Vec<u16> = data[8.min(data.len())..].chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        units
+            .split(|&unit| unit == 0)
+            .map(String::from_utf16_lossy)
+            .map(|path| path.trim_start_matches(['\\', '/']).to_string())
+            .filter(|path| !path.is_empty())
+            .map(|path| drive.join(path))
+            .collect::<Vec<_>>()
+    });
+    match parsed {
+        Some(roots) if !roots.is_empty() => roots,
+        _ => vec![drive.join(DEFAULT_LIBRARY)],
+    }
+}
+
+/// <Identity ... Version="1.2.3.0"> 中的版本
+fn identity_version(content: &str) -> Option<String> {
+    let start = content.find("<Identity")?;
+    let element = &content[start..start + content[start..].find('>')?];
+    let value = &element[element.find("Version=\"")? + "Version=\"".len()..];
+    Some(value[..value.find('"')?].to_string())
+}
+
+/// 能否在目录中创建文件；Content 目录通常对当前用户可写，否则需要以管理员身份运行
+pub fn is_writable(path: &Path) -> bool {
+    let probe = path.join(".bf6-voice-switcher-probe");
+    let writable = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(&probe);
+    writable
+}